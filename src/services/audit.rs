@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+/// One-time setup of the audit log: a daily-rotating file under the
+/// platform state dir, written via `tracing` so destructive actions
+/// elsewhere in the app can emit structured events (`tracing::info!`,
+/// `tracing::warn!`) without each call site owning its own file handle.
+///
+/// Returns a guard that must be kept alive for the duration of the
+/// program - dropping it flushes and stops the background writer thread.
+/// Returns `None` if the state dir can't be resolved or created, in which
+/// case audit events are silently dropped rather than failing startup.
+pub fn init() -> Option<tracing_appender::non_blocking::WorkerGuard> {
+    let dir = audit_log_dir()?;
+    std::fs::create_dir_all(&dir).ok()?;
+
+    let file_appender = tracing_appender::rolling::daily(&dir, "audit.log");
+    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+
+    tracing_subscriber::fmt()
+        .with_writer(non_blocking)
+        .with_ansi(false)
+        .with_target(false)
+        .json()
+        .init();
+
+    Some(guard)
+}
+
+fn audit_log_dir() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|dir| dir.join("cokacdir").join("audit"))
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory under the platform config dir where persisted app state lives.
+fn app_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cokacdir"))
+}
+
+fn bookmarks_path() -> Option<PathBuf> {
+    app_config_dir().map(|dir| dir.join("bookmarks"))
+}
+
+/// Saved directory marks, keyed by a single-character label the user
+/// assigns (fm's marks / hunter's bookmark popup). Loaded once at startup
+/// and persisted back to disk on every addition or removal.
+#[derive(Debug, Clone, Default)]
+pub struct Bookmarks {
+    entries: Vec<(char, PathBuf)>,
+}
+
+impl Bookmarks {
+    /// Load saved bookmarks from the config file. Returns an empty set if
+    /// the file doesn't exist yet; a line that fails to parse is skipped.
+    pub fn load() -> Self {
+        let path = match bookmarks_path() {
+            Some(p) => p,
+            None => return Self::default(),
+        };
+
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+
+        let entries = content
+            .lines()
+            .filter_map(|line| {
+                let (letter, path) = line.split_once('\t')?;
+                let letter = letter.chars().next()?;
+                Some((letter, PathBuf::from(path)))
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// All saved marks, in insertion order.
+    pub fn entries(&self) -> &[(char, PathBuf)] {
+        &self.entries
+    }
+
+    /// The path saved under `letter`, if any.
+    pub fn get(&self, letter: char) -> Option<&Path> {
+        self.entries
+            .iter()
+            .find(|(l, _)| *l == letter)
+            .map(|(_, p)| p.as_path())
+    }
+
+    /// Save `path` under `letter`, replacing any existing mark with the
+    /// same label, then persist to disk.
+    pub fn set(&mut self, letter: char, path: PathBuf) {
+        self.entries.retain(|(l, _)| *l != letter);
+        self.entries.push((letter, path));
+        self.save();
+    }
+
+    /// Remove the mark saved under `letter`, if any, then persist to disk.
+    pub fn remove(&mut self, letter: char) {
+        self.entries.retain(|(l, _)| *l != letter);
+        self.save();
+    }
+
+    /// Persist the current marks to the config file, one
+    /// `letter<TAB>path` entry per line. Best-effort: write failures are
+    /// silently ignored, same as the other session-state writers in this
+    /// app.
+    fn save(&self) {
+        let path = match bookmarks_path() {
+            Some(p) => p,
+            None => return,
+        };
+        if let Some(parent) = path.parent() {
+            if fs::create_dir_all(parent).is_err() {
+                return;
+            }
+        }
+
+        let content: String = self
+            .entries
+            .iter()
+            .map(|(letter, path)| format!("{}\t{}\n", letter, path.display()))
+            .collect();
+
+        let _ = fs::write(path, content);
+    }
+}
@@ -0,0 +1,30 @@
+use std::io::{self, Write};
+
+use base64::Engine;
+
+/// Push `text` onto the system clipboard as plain text.
+///
+/// Writes an OSC 52 terminal escape sequence, which terminal emulators
+/// apply to the clipboard even over SSH/tmux with no display server in
+/// reach, and also tries `arboard` directly, which covers terminals that
+/// don't forward OSC 52. Succeeds if either mechanism does.
+pub fn set_clipboard_text(text: &str) -> io::Result<()> {
+    let osc52_result = set_via_osc52(text);
+    let arboard_result = set_via_arboard(text);
+    osc52_result.or(arboard_result)
+}
+
+fn set_via_osc52(text: &str) -> io::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
+
+fn set_via_arboard(text: &str) -> io::Result<()> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    clipboard
+        .set_text(text.to_string())
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))
+}
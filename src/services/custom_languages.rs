@@ -0,0 +1,170 @@
+use std::fs;
+use std::path::PathBuf;
+
+fn app_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cokacdir"))
+}
+
+fn custom_languages_path() -> Option<PathBuf> {
+    app_config_dir().map(|dir| dir.join("languages.toml"))
+}
+
+/// One user-defined language, declared by a `[[language]]` table in
+/// `languages.toml`. Owns its strings (unlike the built-in `LanguageDef`
+/// tables in `crate::ui::syntax`, which borrow `'static` string literals)
+/// since it's parsed at runtime; `SyntaxHighlighter::tokenize_custom` borrows
+/// back out of these fields to build a `LanguageDef` for `tokenize_with`.
+#[derive(Debug, Clone, Default)]
+pub struct CustomLanguageDef {
+    pub name: String,
+    pub extensions: Vec<String>,
+    pub keywords: Vec<String>,
+    pub types: Vec<String>,
+    pub line_comment: Option<String>,
+    pub block_comment: Option<(String, String)>,
+    pub nested_block_comments: bool,
+    pub string_delimiters: Vec<char>,
+}
+
+/// The registry of custom languages loaded from `languages.toml`, resolved
+/// by `Language::resolve_with_custom` and consulted by `SyntaxHighlighter`
+/// when a line belongs to `Language::Custom(idx)`. Loaded once per
+/// highlighter/resolve call the same way `Bookmarks`/`SearchHistory` load
+/// once at startup; empty (and silently so) if the file is missing,
+/// unreadable, or fails to parse.
+#[derive(Debug, Clone, Default)]
+pub struct CustomLanguages {
+    pub defs: Vec<CustomLanguageDef>,
+}
+
+impl CustomLanguages {
+    /// Load and parse `languages.toml`. Returns an empty registry if the
+    /// file doesn't exist or isn't valid — a missing/malformed config
+    /// degrades to "no custom languages", never an error the user has to
+    /// see just to open a file.
+    pub fn load() -> Self {
+        let path = match custom_languages_path() {
+            Some(p) => p,
+            None => return Self::default(),
+        };
+
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+
+        Self {
+            defs: parse_language_defs(&content),
+        }
+    }
+
+    /// Find the custom language (if any) that claims `ext` (no leading dot,
+    /// already lowercased), returning its index into `defs`.
+    pub fn resolve_extension(&self, ext: &str) -> Option<usize> {
+        self.defs
+            .iter()
+            .position(|def| def.extensions.iter().any(|e| e == ext))
+    }
+}
+
+/// Parse the `[[language]]` tables out of a `languages.toml`-shaped string.
+/// This is a hand-rolled subset of TOML, not a general parser: it
+/// understands array-of-tables headers (`[[language]]`), `key = "string"`,
+/// `key = ["a", "b"]` string arrays, `key = ["a", "b"]` single-char arrays
+/// for `string_delimiters`, `key = ["open", "close"]` two-element string
+/// pairs for `block_comment`, `key = true/false`, and `#` comments — enough for a
+/// hand-authored config file, not arbitrary TOML.
+fn parse_language_defs(content: &str) -> Vec<CustomLanguageDef> {
+    let mut defs = Vec::new();
+    let mut current: Option<CustomLanguageDef> = None;
+
+    for raw_line in content.lines() {
+        let line = strip_toml_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line == "[[language]]" {
+            if let Some(def) = current.take() {
+                defs.push(def);
+            }
+            current = Some(CustomLanguageDef::default());
+            continue;
+        }
+
+        let Some(def) = current.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+
+        match key {
+            "name" => def.name = parse_toml_string(value).unwrap_or_default(),
+            "extensions" => def.extensions = parse_toml_string_array(value),
+            "keywords" => def.keywords = parse_toml_string_array(value),
+            "types" => def.types = parse_toml_string_array(value),
+            "line_comment" => def.line_comment = parse_toml_string(value),
+            "block_comment" => {
+                let pair = parse_toml_string_array(value);
+                if pair.len() == 2 {
+                    def.block_comment = Some((pair[0].clone(), pair[1].clone()));
+                }
+            }
+            "nested_block_comments" => def.nested_block_comments = value == "true",
+            "string_delimiters" => {
+                def.string_delimiters = parse_toml_string_array(value)
+                    .iter()
+                    .filter_map(|s| s.chars().next())
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(def) = current.take() {
+        defs.push(def);
+    }
+
+    defs
+}
+
+/// Strip a trailing `#` comment, respecting `"..."` quoting so a `#` inside
+/// a string value isn't mistaken for one.
+fn strip_toml_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (idx, ch) in line.char_indices() {
+        match ch {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..idx],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parse a `"quoted string"` value, or `None` if `raw` isn't one.
+fn parse_toml_string(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.len() >= 2 && raw.starts_with('"') && raw.ends_with('"') {
+        Some(raw[1..raw.len() - 1].to_string())
+    } else {
+        None
+    }
+}
+
+/// Parse a `["a", "b", "c"]` value into its quoted elements, or an empty
+/// vec if `raw` isn't a bracketed list.
+fn parse_toml_string_array(raw: &str) -> Vec<String> {
+    let raw = raw.trim();
+    let Some(inner) = raw.strip_prefix('[').and_then(|s| s.strip_suffix(']')) else {
+        return Vec::new();
+    };
+
+    inner
+        .split(',')
+        .filter_map(|elem| parse_toml_string(elem.trim()))
+        .collect()
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// How many entries to walk between progress callbacks, so a caller
+/// streaming partial results to a spinner isn't flooded with updates.
+const PROGRESS_BATCH: usize = 200;
+
+/// Recursive byte/file/subdirectory totals for a directory tree, the way
+/// broot's `file_sum` walks a subtree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirStats {
+    pub total_bytes: u64,
+    pub file_count: usize,
+    pub dir_count: usize,
+}
+
+/// Recursively total `path`'s size, file count, and subdirectory count.
+/// Hard links are counted once (by `(dev, ino)` on Unix) so the same file
+/// linked into the tree twice doesn't double its bytes. `on_progress` is
+/// called every [`PROGRESS_BATCH`] entries with the running total, so a
+/// caller can stream partial results instead of blocking until the whole
+/// tree is walked.
+pub fn calculate_dir_stats(
+    path: &Path,
+    cancel_flag: &Arc<AtomicBool>,
+    mut on_progress: impl FnMut(DirStats),
+) -> io::Result<DirStats> {
+    let mut stats = DirStats::default();
+    let mut seen_inodes = HashSet::new();
+    walk(path, cancel_flag, &mut stats, &mut seen_inodes, &mut on_progress)?;
+    Ok(stats)
+}
+
+fn walk(
+    path: &Path,
+    cancel_flag: &Arc<AtomicBool>,
+    stats: &mut DirStats,
+    seen_inodes: &mut HashSet<(u64, u64)>,
+    on_progress: &mut impl FnMut(DirStats),
+) -> io::Result<()> {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(()),
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
+        }
+
+        let entry_path = entry.path();
+        let metadata = match fs::symlink_metadata(&entry_path) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+
+        if metadata.is_symlink() {
+            stats.file_count += 1;
+        } else if metadata.is_dir() {
+            stats.dir_count += 1;
+            walk(&entry_path, cancel_flag, stats, seen_inodes, on_progress)?;
+        } else if already_counted(&metadata, seen_inodes) {
+            stats.file_count += 1;
+        } else {
+            stats.total_bytes += metadata.len();
+            stats.file_count += 1;
+        }
+
+        if (stats.file_count + stats.dir_count) % PROGRESS_BATCH == 0 {
+            on_progress(*stats);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `metadata` is a hard link to a file already walked, recorded by
+/// `(dev, ino)`. Only meaningful on Unix -- other platforms have no portable
+/// equivalent, so every file there counts as its own link.
+#[cfg(unix)]
+fn already_counted(metadata: &fs::Metadata, seen_inodes: &mut HashSet<(u64, u64)>) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    if metadata.nlink() <= 1 {
+        return false;
+    }
+    !seen_inodes.insert((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn already_counted(_metadata: &fs::Metadata, _seen_inodes: &mut HashSet<(u64, u64)>) -> bool {
+    false
+}
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::Path;
+
+use zip::ZipArchive;
+
+/// One entry in an EPUB's table of contents: a human-readable label and the
+/// line in the flattened [`EpubBook::lines`] where that chapter begins.
+#[derive(Debug, Clone)]
+pub struct EpubChapter {
+    pub label: String,
+    pub start_line: usize,
+}
+
+/// An EPUB flattened into plain lines, ready to drop into the viewer the
+/// same way a normal text file's lines are. `bold_lines` marks the indices
+/// that came from a heading tag, since EPUBs have no syntax highlighter to
+/// lean on for emphasis.
+#[derive(Debug, Clone, Default)]
+pub struct EpubBook {
+    pub lines: Vec<String>,
+    pub chapters: Vec<EpubChapter>,
+    pub bold_lines: HashSet<usize>,
+}
+
+/// Load `path` as an EPUB: read the OPF manifest/spine to get chapters in
+/// reading order, render each chapter's XHTML to plain lines, and
+/// concatenate them with their starting line numbers recorded. Chapter
+/// labels come from the EPUB2 `.ncx` table of contents when present;
+/// EPUB3-only `nav.xhtml` documents aren't parsed, so such books fall back
+/// to "Chapter N" labels.
+pub fn load_epub(path: &Path) -> Result<EpubBook, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let container = read_zip_entry(&mut archive, "META-INF/container.xml")?;
+    let opf_path = extract_attr_from_tag(&container, "rootfile", "full-path")
+        .ok_or_else(|| "container.xml has no rootfile".to_string())?;
+
+    let opf = read_zip_entry(&mut archive, &opf_path)?;
+    let manifest = parse_manifest(&opf);
+    let spine = parse_spine(&opf);
+
+    let toc_labels = parse_toc(&mut archive, &opf_path, &manifest, &opf);
+
+    let mut book = EpubBook::default();
+    for (idx, idref) in spine.iter().enumerate() {
+        let Some(href) = manifest.iter().find(|(id, _)| id == idref).map(|(_, href)| href) else {
+            continue;
+        };
+        let chapter_path = join_opf_path(&opf_path, href);
+        let xhtml = match read_zip_entry(&mut archive, &chapter_path) {
+            Ok(x) => x,
+            Err(_) => continue,
+        };
+
+        let label = toc_labels
+            .get(href.as_str())
+            .cloned()
+            .unwrap_or_else(|| format!("Chapter {}", idx + 1));
+        book.chapters.push(EpubChapter { label, start_line: book.lines.len() });
+
+        let (mut lines, bold) = render_xhtml_to_lines(&xhtml);
+        let base = book.lines.len();
+        for line_idx in bold {
+            book.bold_lines.insert(base + line_idx);
+        }
+        book.lines.append(&mut lines);
+    }
+
+    if book.lines.is_empty() {
+        return Err("EPUB has no readable chapters".to_string());
+    }
+
+    Ok(book)
+}
+
+fn read_zip_entry(archive: &mut ZipArchive<std::fs::File>, name: &str) -> Result<String, String> {
+    let mut entry = archive
+        .by_name(name)
+        .or_else(|_| archive.by_name(name.trim_start_matches('/')))
+        .map_err(|e| format!("{}: {}", name, e))?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content).map_err(|e| e.to_string())?;
+    Ok(content)
+}
+
+/// Value of `attr="..."` on the first `<tag ...>` found in `xml`, regardless
+/// of attribute order. Good enough for the small, well-formed fragments
+/// (container.xml, OPF, NCX) this module deals with -- not a general XML
+/// parser.
+fn extract_attr_from_tag(xml: &str, tag: &str, attr: &str) -> Option<String> {
+    let tag_start = xml.find(&format!("<{}", tag))?;
+    let tag_end = xml[tag_start..].find('>')? + tag_start;
+    let tag_body = &xml[tag_start..tag_end];
+    let attr_marker = format!("{}=\"", attr);
+    let attr_start = tag_body.find(&attr_marker)? + attr_marker.len();
+    let attr_end = tag_body[attr_start..].find('"')? + attr_start;
+    Some(tag_body[attr_start..attr_end].to_string())
+}
+
+/// Every `<item id="..." href="...">` in the OPF manifest, in document
+/// order.
+fn parse_manifest(opf: &str) -> Vec<(String, String)> {
+    let mut items = Vec::new();
+    let mut rest = opf;
+    while let Some(start) = rest.find("<item ") {
+        let Some(end) = rest[start..].find('>') else { break };
+        let tag = &rest[start..start + end];
+        if let (Some(id), Some(href)) = (
+            extract_attr_from_tag(tag, "item", "id"),
+            extract_attr_from_tag(tag, "item", "href"),
+        ) {
+            items.push((id, href));
+        }
+        rest = &rest[start + end..];
+    }
+    items
+}
+
+/// Every `<itemref idref="...">` in the OPF spine, in reading order.
+fn parse_spine(opf: &str) -> Vec<String> {
+    let mut idrefs = Vec::new();
+    let mut rest = opf;
+    while let Some(start) = rest.find("<itemref ") {
+        let Some(end) = rest[start..].find('>') else { break };
+        let tag = &rest[start..start + end];
+        if let Some(idref) = extract_attr_from_tag(tag, "itemref", "idref") {
+            idrefs.push(idref);
+        }
+        rest = &rest[start + end..];
+    }
+    idrefs
+}
+
+/// Chapter labels keyed by the href they point at, read from the EPUB2
+/// `.ncx` referenced by the OPF's spine `toc` attribute. Returns an empty
+/// map (falling back to "Chapter N" labels) for EPUB3 books that only ship
+/// a `nav.xhtml` document -- parsing that format is out of scope here.
+fn parse_toc(
+    archive: &mut ZipArchive<std::fs::File>,
+    opf_path: &str,
+    manifest: &[(String, String)],
+    opf: &str,
+) -> std::collections::HashMap<String, String> {
+    let mut labels = std::collections::HashMap::new();
+
+    let Some(toc_id) = extract_attr_from_tag(opf, "spine", "toc") else {
+        return labels;
+    };
+    let Some(ncx_href) = manifest.iter().find(|(id, _)| id == &toc_id).map(|(_, href)| href) else {
+        return labels;
+    };
+    let ncx_path = join_opf_path(opf_path, ncx_href);
+    let Ok(ncx) = read_zip_entry(archive, &ncx_path) else {
+        return labels;
+    };
+
+    let mut rest = ncx.as_str();
+    while let Some(start) = rest.find("<navPoint") {
+        let Some(point_end) = rest[start..].find("</navPoint>") else { break };
+        let point = &rest[start..start + point_end];
+
+        let text = extract_tag_text(point, "text");
+        let src = extract_attr_from_tag(point, "content", "src");
+        if let (Some(text), Some(src)) = (text, src) {
+            let href = src.split('#').next().unwrap_or(&src).to_string();
+            labels.insert(href, text);
+        }
+
+        rest = &rest[start + point_end + "</navPoint>".len()..];
+    }
+
+    labels
+}
+
+/// Text content of the first `<tag>...</tag>` in `xml`.
+fn extract_tag_text(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let start = xml.find(&open)?;
+    let content_start = xml[start..].find('>')? + start + 1;
+    let content_end = xml[content_start..].find(&close)? + content_start;
+    Some(decode_entities(xml[content_start..content_end].trim()))
+}
+
+/// Resolve `href` (relative to the OPF file) against `opf_path`'s directory,
+/// since manifest/TOC hrefs are relative to the OPF, not the archive root.
+fn join_opf_path(opf_path: &str, href: &str) -> String {
+    match opf_path.rfind('/') {
+        Some(idx) => format!("{}/{}", &opf_path[..idx], href),
+        None => href.to_string(),
+    }
+}
+
+/// Render a chapter's XHTML to plain lines: strip tags, break paragraphs,
+/// prefix list items with a bullet, and record which output lines came from
+/// a heading so the viewer can render them in bold.
+fn render_xhtml_to_lines(xhtml: &str) -> (Vec<String>, HashSet<usize>) {
+    let mut lines = Vec::new();
+    let mut bold = HashSet::new();
+    let mut current = String::new();
+    let mut in_heading = false;
+    let mut in_script_or_style = false;
+
+    let bytes = xhtml.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if bytes[pos] == b'<' {
+            let Some(rel_end) = xhtml[pos..].find('>') else { break };
+            let tag_end = pos + rel_end;
+            let tag = &xhtml[pos + 1..tag_end];
+            let tag_lower = tag.to_ascii_lowercase();
+            let closing = tag_lower.starts_with('/');
+            let tag_name = tag_lower.trim_start_matches('/').split_whitespace().next().unwrap_or("");
+
+            match tag_name {
+                "script" | "style" => in_script_or_style = !closing,
+                "p" | "div" => flush_line(&mut lines, &mut bold, &mut current, in_heading),
+                "br" | "hr" => flush_line(&mut lines, &mut bold, &mut current, in_heading),
+                "li" if !closing => {
+                    flush_line(&mut lines, &mut bold, &mut current, in_heading);
+                    current.push_str("- ");
+                }
+                "li" => flush_line(&mut lines, &mut bold, &mut current, in_heading),
+                "blockquote" if !closing => {
+                    flush_line(&mut lines, &mut bold, &mut current, in_heading);
+                    current.push_str("> ");
+                }
+                "blockquote" => flush_line(&mut lines, &mut bold, &mut current, in_heading),
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    flush_line(&mut lines, &mut bold, &mut current, in_heading);
+                    in_heading = !closing;
+                }
+                _ => {}
+            }
+
+            pos = tag_end + 1;
+            continue;
+        }
+
+        let rest = &xhtml[pos..];
+        let ch = rest.chars().next().unwrap();
+        let ch_len = ch.len_utf8();
+        if !in_script_or_style {
+            current.push(ch);
+        }
+        pos += ch_len;
+    }
+
+    flush_line(&mut lines, &mut bold, &mut current, in_heading);
+
+    (lines, bold)
+}
+
+fn flush_line(lines: &mut Vec<String>, bold: &mut HashSet<usize>, current: &mut String, in_heading: bool) {
+    let text = collapse_whitespace(&decode_entities(current));
+    if !text.is_empty() {
+        if in_heading {
+            bold.insert(lines.len());
+        }
+        lines.push(text);
+    }
+    current.clear();
+}
+
+/// Decode the handful of HTML entities that show up in EPUB prose.
+/// Anything else (rare numeric/named entities) passes through unchanged
+/// rather than pulling in a full entity table for this plain-text reader.
+fn decode_entities(text: &str) -> String {
+    text.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&rsquo;", "\u{2019}")
+        .replace("&lsquo;", "\u{2018}")
+        .replace("&rdquo;", "\u{201d}")
+        .replace("&ldquo;", "\u{201c}")
+        .replace("&mdash;", "\u{2014}")
+        .replace("&ndash;", "\u{2013}")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
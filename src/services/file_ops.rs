@@ -2,15 +2,21 @@ use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::Sender;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
+use std::time::SystemTime;
 
 /// File operation type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileOperationType {
     Copy,
     Move,
+    Trash,
+    /// Create a link at the destination instead of duplicating data.
+    /// `relative: true` computes a `..`-relative target; `false` points
+    /// at the canonicalized absolute source.
+    Symlink { relative: bool },
 }
 
 /// Progress message for file operations
@@ -29,6 +35,63 @@ pub enum ProgressMessage {
     Completed(usize, usize),
     /// Error occurred (filename, error message)
     Error(String, String),
+    /// Destination already exists (filename, source info, destination
+    /// info). The worker blocks on the accompanying conflict channel until
+    /// it receives a `ConflictAction` in reply.
+    Conflict(String, ConflictMeta, ConflictMeta),
+    /// Destination was auto-renamed to avoid a conflict instead of asking
+    /// the UI (original filename, final filename actually written). Sent
+    /// when the `auto_rename_on_conflict` option is on; see
+    /// `resolve_filename_conflict`.
+    Renamed(String, String),
+    /// A symlink couldn't be created on this platform, so the file was
+    /// copied instead (filename). See `symlink_files_with_progress`'s
+    /// non-Unix fallback.
+    CopiedInsteadOfLinked(String),
+    /// An existing destination was skipped because the source wasn't
+    /// strictly newer than it (filename). See `CopyOptions::update_only`.
+    SkippedNotNewer(String),
+    /// An existing destination was moved aside before being overwritten
+    /// (original filename, backup path). See `CopyOptions::make_backup`.
+    BackedUp(String, String),
+}
+
+/// How to resolve a destination path that already exists. This is the
+/// `clipboard_paste` conflict policy: the default is to ask (`resolve_conflict`
+/// blocks on `ProgressMessage::Conflict` until the UI replies with one of
+/// these, optionally "for all" via the sticky flag on the reply channel),
+/// with `Rename` generating `name (1).ext`, `name (2).ext`, ... via
+/// `unique_destination`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictAction {
+    /// Replace the destination with the source.
+    Overwrite,
+    /// Leave the destination untouched and move on to the next file.
+    Skip,
+    /// Copy/move the source alongside the destination under an
+    /// automatically generated, non-colliding name.
+    Rename,
+    /// Overwrite only if the source is newer than the destination; skip
+    /// otherwise.
+    OverwriteIfNewer,
+}
+
+/// Size/modified-time snapshot of one side of a conflicting pair, sent to
+/// the UI so it can show the user enough to tell the two files apart.
+#[derive(Debug, Clone)]
+pub struct ConflictMeta {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl ConflictMeta {
+    fn read(path: &Path) -> Self {
+        let meta = fs::metadata(path).ok();
+        Self {
+            size: meta.as_ref().map(|m| m.len()).unwrap_or(0),
+            modified: meta.and_then(|m| m.modified().ok()),
+        }
+    }
 }
 
 /// File operation result
@@ -82,6 +145,84 @@ fn try_clonefile(_src: &Path, _dest: &Path) -> io::Result<bool> {
     Ok(false) // Not supported on non-macOS
 }
 
+/// Try to clone file using Linux's `FICLONE` ioctl, the Btrfs/XFS
+/// reflink equivalent of macOS's `clonefile`. `dest` must not already
+/// exist; it is created (and removed again on failure) by this call.
+/// Returns Ok(true) if the clone succeeded, Ok(false) if it should fall
+/// back to regular copy (cross-filesystem, or a filesystem without
+/// reflink support).
+#[cfg(target_os = "linux")]
+fn try_reflink(src: &Path, dest: &Path) -> io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    // include/uapi/linux/fs.h: #define FICLONE _IOW(0x94, 9, int)
+    const FICLONE: libc::c_ulong = 0x4004_9409;
+
+    let src_file = File::open(src)?;
+    let dest_file = File::create(dest)?;
+
+    let result = unsafe { libc::ioctl(dest_file.as_raw_fd(), FICLONE, src_file.as_raw_fd()) };
+
+    if result == 0 {
+        Ok(true)
+    } else {
+        let err = io::Error::last_os_error();
+        drop(dest_file);
+        let _ = fs::remove_file(dest);
+        match err.raw_os_error() {
+            Some(libc::EXDEV) | Some(libc::EOPNOTSUPP) | Some(libc::EINVAL) => Ok(false),
+            _ => Ok(false), // Fallback for any other error
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn try_reflink(_src: &Path, _dest: &Path) -> io::Result<bool> {
+    Ok(false) // Not supported outside Linux
+}
+
+/// Try an in-kernel `copy_file_range` copy, which avoids bouncing data
+/// through a userspace buffer and lets network filesystems do a
+/// server-side copy when they support it. Returns `Ok(Some(bytes))` on
+/// success, `Ok(None)` to fall back to the buffered read/write loop if no
+/// bytes were copied yet (e.g. `EXDEV` across filesystems).
+#[cfg(target_os = "linux")]
+fn try_copy_file_range(src_file: &File, dest_file: &File, total_size: u64) -> io::Result<Option<u64>> {
+    use std::os::unix::io::AsRawFd;
+
+    let mut copied: u64 = 0;
+    while copied < total_size {
+        let remaining = (total_size - copied) as usize;
+        let n = unsafe {
+            libc::copy_file_range(
+                src_file.as_raw_fd(),
+                std::ptr::null_mut(),
+                dest_file.as_raw_fd(),
+                std::ptr::null_mut(),
+                remaining,
+                0,
+            )
+        };
+        if n < 0 {
+            return if copied == 0 {
+                Ok(None)
+            } else {
+                Err(io::Error::last_os_error())
+            };
+        }
+        if n == 0 {
+            break;
+        }
+        copied += n as u64;
+    }
+    Ok(Some(copied))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn try_copy_file_range(_src_file: &File, _dest_file: &File, _total_size: u64) -> io::Result<Option<u64>> {
+    Ok(None)
+}
+
 /// Calculate total size of files to be copied/moved
 pub fn calculate_total_size(files: &[PathBuf], cancel_flag: &Arc<AtomicBool>) -> io::Result<(u64, usize)> {
     let mut total_size: u64 = 0;
@@ -136,12 +277,117 @@ fn calculate_dir_size(path: &Path, cancel_flag: &Arc<AtomicBool>) -> io::Result<
     Ok((total_size, total_files))
 }
 
+/// Options controlling `copy_file_with_progress`/`copy_dir_recursive_with_progress`/
+/// `copy_files_with_progress`/`move_files_with_progress`'s low-level copy
+/// behavior: whether an existing destination is truncated or silently
+/// skipped, the read/write buffer size, and whether Unix permissions are
+/// preserved. Distinct from the higher-level handling of a colliding
+/// top-level destination (asking the UI via `resolve_conflict`, or
+/// `auto_rename_on_conflict`), and from `ConflictOptions`, which is the
+/// policy for the non-progress `copy_file`/`move_file`.
+#[derive(Debug, Clone, Copy)]
+pub struct CopyOptions {
+    /// Truncate and replace an existing destination instead of failing.
+    pub overwrite: bool,
+    /// Silently count an existing destination as already done instead of
+    /// failing or overwriting it.
+    pub skip_existing: bool,
+    /// Size of the read/write buffer used by the non-clonefile copy path.
+    pub buffer_size: usize,
+    /// Copy the source file's Unix permissions onto the destination.
+    pub preserve_permissions: bool,
+    /// Copy the source's modified/accessed times onto the destination
+    /// instead of leaving it stamped with the time of the copy.
+    pub preserve_times: bool,
+    /// Before an `overwrite` replaces an existing destination, move it
+    /// aside via `backup_existing` instead of discarding it.
+    pub make_backup: bool,
+    /// Only let `overwrite` replace a destination whose modified time is
+    /// strictly older than the source's; otherwise skip it (counted as a
+    /// success, not a failure). Has no effect unless `overwrite` is set.
+    pub update_only: bool,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            overwrite: false,
+            skip_existing: false,
+            buffer_size: COPY_BUFFER_SIZE,
+            preserve_permissions: true,
+            preserve_times: true,
+            make_backup: false,
+            update_only: false,
+        }
+    }
+}
+
+/// What to do about an existing `dest` when `options.overwrite` is set,
+/// per `options.update_only`/`options.make_backup`.
+enum OverwriteDecision {
+    /// Go ahead and overwrite; carries the backup path if the old file was
+    /// moved aside first (`options.make_backup`).
+    Proceed(Option<PathBuf>),
+    /// `update_only` is set and `src` isn't strictly newer than `dest`.
+    SkipNotNewer,
+}
+
+/// Resolve `OverwriteDecision` for an overwrite of `dest` by `src`. Callers
+/// only need this when `options.overwrite` is already known to be true.
+fn decide_overwrite(src: &Path, dest: &Path, options: &CopyOptions) -> OverwriteDecision {
+    if options.update_only {
+        let src_modified = fs::metadata(src).and_then(|m| m.modified()).ok();
+        let dest_modified = fs::metadata(dest).and_then(|m| m.modified()).ok();
+        let is_newer = matches!((src_modified, dest_modified), (Some(s), Some(d)) if s > d);
+        if !is_newer {
+            return OverwriteDecision::SkipNotNewer;
+        }
+    }
+
+    let backup = if options.make_backup {
+        backup_existing(dest).ok().flatten()
+    } else {
+        None
+    };
+    OverwriteDecision::Proceed(backup)
+}
+
+/// Apply `metadata`'s modified/accessed times to an already-open `dest_file`.
+/// Best-effort: a filesystem that rejects `set_times` (e.g. some FUSE
+/// mounts) leaves the destination stamped with the copy time instead of
+/// failing the whole copy over it.
+fn apply_preserved_times(dest_file: &File, metadata: &fs::Metadata) {
+    if let (Ok(modified), Ok(accessed)) = (metadata.modified(), metadata.accessed()) {
+        let times = std::fs::FileTimes::new().set_modified(modified).set_accessed(accessed);
+        let _ = dest_file.set_times(times);
+    }
+}
+
+/// Copy `src`'s modified/accessed times onto `dest` by path, for callers
+/// that don't already hold an open `File` handle on the destination (the
+/// plain, non-progress copy functions). Symlinks are left alone -- there's
+/// no portable way to set an `lutimes`-style time on one here, and this
+/// codebase never follows them during tree-walk anyway. Best-effort, same
+/// as `apply_preserved_times`.
+fn preserve_times_by_path(src: &Path, dest: &Path) {
+    let Ok(metadata) = fs::symlink_metadata(src) else { return };
+    if metadata.is_symlink() {
+        return;
+    }
+    if let Ok(dest_file) = File::open(dest) {
+        apply_preserved_times(&dest_file, &metadata);
+    }
+}
+
 /// Copy a single file with progress callback
-/// On macOS with APFS, tries clonefile first for instant copy
+/// On macOS with APFS, tries clonefile first for instant copy. On Linux,
+/// tries a Btrfs/XFS `FICLONE` reflink first, then an in-kernel
+/// `copy_file_range` copy, before falling back to the buffered loop.
 pub fn copy_file_with_progress<F>(
     src: &Path,
     dest: &Path,
     cancel_flag: &Arc<AtomicBool>,
+    options: CopyOptions,
     mut progress_callback: F,
 ) -> io::Result<u64>
 where
@@ -155,18 +401,59 @@ where
         return Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
     }
 
-    // Try APFS clonefile first (macOS only)
+    // Try APFS clonefile first (macOS only). clonefile itself already
+    // carries over mode bits and timestamps, but re-apply them from
+    // `options` anyway for defense-in-depth, the same as every other path
+    // below.
     if try_clonefile(src, dest)? {
+        #[cfg(unix)]
+        if options.preserve_permissions {
+            fs::set_permissions(dest, metadata.permissions())?;
+        }
+        if options.preserve_times {
+            preserve_times_by_path(src, dest);
+        }
         // Clone succeeded - report 100% progress immediately
         progress_callback(total_size, total_size);
         return Ok(total_size);
     }
 
+    // Try Btrfs/XFS reflink (Linux only) - same instant copy-on-write win.
+    // Unlike clonefile, FICLONE only shares data extents - it does not
+    // carry over mode bits or mtime/atime, so those must be applied here
+    // or the destination is left with the creating process's umask and a
+    // fresh timestamp.
+    if try_reflink(src, dest)? {
+        #[cfg(unix)]
+        if options.preserve_permissions {
+            fs::set_permissions(dest, metadata.permissions())?;
+        }
+        if options.preserve_times {
+            preserve_times_by_path(src, dest);
+        }
+        progress_callback(total_size, total_size);
+        return Ok(total_size);
+    }
+
     // Fallback to regular copy with progress
     let mut src_file = File::open(src)?;
     let mut dest_file = File::create(dest)?;
 
-    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    // Try an in-kernel copy_file_range before falling back to the
+    // userspace byte loop (Linux only).
+    if let Some(copied) = try_copy_file_range(&src_file, &dest_file, total_size)? {
+        progress_callback(copied, total_size);
+        #[cfg(unix)]
+        if options.preserve_permissions {
+            fs::set_permissions(dest, metadata.permissions())?;
+        }
+        if options.preserve_times {
+            apply_preserved_times(&dest_file, &metadata);
+        }
+        return Ok(copied);
+    }
+
+    let mut buffer = vec![0u8; options.buffer_size];
     let mut copied: u64 = 0;
 
     loop {
@@ -190,31 +477,99 @@ where
         progress_callback(copied, total_size);
     }
 
-    // Preserve permissions
+    // Preserve permissions and times
     #[cfg(unix)]
-    {
+    if options.preserve_permissions {
         fs::set_permissions(dest, metadata.permissions())?;
     }
+    if options.preserve_times {
+        apply_preserved_times(&dest_file, &metadata);
+    }
 
     Ok(copied)
 }
 
+/// Maximum number of symlink directory entries `copy_dir_recursive_with_progress`
+/// will copy-as-link in a single top-level item before bailing, so a
+/// directory salted with a dense web of symlinks can't be used to hang
+/// the worker indefinitely. Distinct from `MAX_COPY_DEPTH`, which bounds
+/// how deep the real directory tree is allowed to nest.
+const MAX_SYMLINK_JUMPS: usize = 20;
+
 /// Copy directory recursively with progress reporting
+#[allow(clippy::too_many_arguments)]
 pub fn copy_dir_recursive_with_progress(
     src: &Path,
     dest: &Path,
     cancel_flag: &Arc<AtomicBool>,
     progress_tx: &Sender<ProgressMessage>,
-    completed_bytes: &mut u64,
-    completed_files: &mut usize,
+    completed_bytes: &AtomicU64,
+    completed_files: &AtomicUsize,
     total_bytes: u64,
     total_files: usize,
+    options: CopyOptions,
+) -> io::Result<()> {
+    let mut visited = HashSet::new();
+    let mut symlink_jumps = 0;
+    copy_dir_recursive_with_progress_inner(
+        src,
+        dest,
+        cancel_flag,
+        progress_tx,
+        completed_bytes,
+        completed_files,
+        total_bytes,
+        total_files,
+        options,
+        &mut visited,
+        0,
+        &mut symlink_jumps,
+    )
+}
+
+/// Internal recursion behind `copy_dir_recursive_with_progress`, carrying
+/// the same circular-symlink / max-depth guards as `copy_dir_recursive_inner`
+/// (canonicalize each directory before descending, bail if it reappears or
+/// `depth` exceeds `MAX_COPY_DEPTH`), plus a `symlink_jumps` counter capped
+/// at `MAX_SYMLINK_JUMPS` across the whole call tree.
+#[allow(clippy::too_many_arguments)]
+fn copy_dir_recursive_with_progress_inner(
+    src: &Path,
+    dest: &Path,
+    cancel_flag: &Arc<AtomicBool>,
+    progress_tx: &Sender<ProgressMessage>,
+    completed_bytes: &AtomicU64,
+    completed_files: &AtomicUsize,
+    total_bytes: u64,
+    total_files: usize,
+    options: CopyOptions,
+    visited: &mut HashSet<PathBuf>,
+    depth: usize,
+    symlink_jumps: &mut usize,
 ) -> io::Result<()> {
     // Check for cancellation
     if cancel_flag.load(Ordering::Relaxed) {
         return Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
     }
 
+    if depth > MAX_COPY_DEPTH {
+        let _ = progress_tx.send(ProgressMessage::Error(
+            src.display().to_string(),
+            format!("Maximum directory depth ({}) exceeded - possible circular symlink", MAX_COPY_DEPTH),
+        ));
+        return Err(io::Error::other(
+            format!("Maximum directory depth ({}) exceeded - possible circular symlink", MAX_COPY_DEPTH),
+        ));
+    }
+
+    let canonical_src = src.canonicalize().unwrap_or_else(|_| src.to_path_buf());
+    if visited.contains(&canonical_src) {
+        let message = format!("Circular symlink detected: {}", src.display());
+        let _ = progress_tx.send(ProgressMessage::Error(src.display().to_string(), message.clone()));
+        return Err(io::Error::other(message));
+    }
+    visited.insert(canonical_src);
+
     fs::create_dir_all(dest)?;
 
     for entry in fs::read_dir(src)? {
@@ -230,6 +585,17 @@ pub fn copy_dir_recursive_with_progress(
         let metadata = fs::symlink_metadata(&src_path)?;
 
         if metadata.is_symlink() {
+            *symlink_jumps += 1;
+            if *symlink_jumps > MAX_SYMLINK_JUMPS {
+                let message = format!(
+                    "Too many symlinks encountered while copying '{}' (limit {})",
+                    src.display(),
+                    MAX_SYMLINK_JUMPS,
+                );
+                let _ = progress_tx.send(ProgressMessage::Error(src.display().to_string(), message.clone()));
+                return Err(io::Error::other(message));
+            }
+
             // Copy symlink
             #[cfg(unix)]
             {
@@ -258,15 +624,15 @@ pub fn copy_dir_recursive_with_progress(
                 }
             }
 
-            *completed_files += 1;
+            let done_files = completed_files.fetch_add(1, Ordering::Relaxed) + 1;
             let _ = progress_tx.send(ProgressMessage::TotalProgress(
-                *completed_files,
+                done_files,
                 total_files,
-                *completed_bytes,
+                completed_bytes.load(Ordering::Relaxed),
                 total_bytes,
             ));
         } else if metadata.is_dir() {
-            copy_dir_recursive_with_progress(
+            copy_dir_recursive_with_progress_inner(
                 &src_path,
                 &dest_path,
                 cancel_flag,
@@ -275,6 +641,10 @@ pub fn copy_dir_recursive_with_progress(
                 completed_files,
                 total_bytes,
                 total_files,
+                options,
+                visited,
+                depth + 1,
+                symlink_jumps,
             )?;
         } else {
             // Regular file - copy with progress
@@ -282,19 +652,49 @@ pub fn copy_dir_recursive_with_progress(
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default();
 
+            if dest_path.exists() && options.skip_existing {
+                completed_files.fetch_add(1, Ordering::Relaxed);
+                let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
+                continue;
+            }
+            if dest_path.exists() && !options.overwrite {
+                let _ = progress_tx.send(ProgressMessage::Error(
+                    filename,
+                    "Target already exists".to_string(),
+                ));
+                continue;
+            }
+            if dest_path.exists() && options.overwrite {
+                match decide_overwrite(&src_path, &dest_path, &options) {
+                    OverwriteDecision::SkipNotNewer => {
+                        completed_files.fetch_add(1, Ordering::Relaxed);
+                        let _ = progress_tx.send(ProgressMessage::SkippedNotNewer(filename));
+                        continue;
+                    }
+                    OverwriteDecision::Proceed(Some(backup_path)) => {
+                        let _ = progress_tx.send(ProgressMessage::BackedUp(
+                            filename.clone(),
+                            backup_path.display().to_string(),
+                        ));
+                    }
+                    OverwriteDecision::Proceed(None) => {}
+                }
+            }
+
             let _ = progress_tx.send(ProgressMessage::FileStarted(filename.clone()));
 
             let file_size = metadata.len();
-            let file_completed_bytes = *completed_bytes;
+            let file_completed_bytes = completed_bytes.load(Ordering::Relaxed);
 
             let result = copy_file_with_progress(
                 &src_path,
                 &dest_path,
                 cancel_flag,
+                options,
                 |copied, total| {
                     let _ = progress_tx.send(ProgressMessage::FileProgress(copied, total));
                     let _ = progress_tx.send(ProgressMessage::TotalProgress(
-                        *completed_files,
+                        completed_files.load(Ordering::Relaxed),
                         total_files,
                         file_completed_bytes + copied,
                         total_bytes,
@@ -304,8 +704,8 @@ pub fn copy_dir_recursive_with_progress(
 
             match result {
                 Ok(_) => {
-                    *completed_bytes += file_size;
-                    *completed_files += 1;
+                    completed_bytes.fetch_add(file_size, Ordering::Relaxed);
+                    completed_files.fetch_add(1, Ordering::Relaxed);
                     let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
                 }
                 Err(e) => {
@@ -318,16 +718,412 @@ pub fn copy_dir_recursive_with_progress(
         }
     }
 
+    // Set the directory's own times (and permissions) last, since writing
+    // its children just bumped its mtime.
+    if let Ok(dest_dir) = File::open(dest) {
+        if let Ok(src_metadata) = fs::metadata(src) {
+            #[cfg(unix)]
+            if options.preserve_permissions {
+                let _ = fs::set_permissions(dest, src_metadata.permissions());
+            }
+            if options.preserve_times {
+                apply_preserved_times(&dest_dir, &src_metadata);
+            }
+        }
+    }
+
     Ok(())
 }
 
 /// Copy files with progress reporting (main entry point for progress-enabled copy)
+/// Ask the UI how to resolve `dest` already existing and wait for the
+/// answer. `sticky` remembers a previous "apply to all" choice so later
+/// conflicts in the same operation are resolved without asking again.
+/// Returns `None` if the conflict channel was dropped (dialog closed /
+/// operation abandoned), which callers treat the same as cancellation.
+fn resolve_conflict(
+    src: &Path,
+    dest: &Path,
+    filename: &str,
+    progress_tx: &Sender<ProgressMessage>,
+    conflict_rx: &Receiver<(ConflictAction, bool)>,
+    sticky: &mut Option<ConflictAction>,
+) -> Option<ConflictAction> {
+    if let Some(action) = sticky {
+        return Some(*action);
+    }
+
+    let _ = progress_tx.send(ProgressMessage::Conflict(
+        filename.to_string(),
+        ConflictMeta::read(src),
+        ConflictMeta::read(dest),
+    ));
+
+    let (action, apply_to_all) = conflict_rx.recv().ok()?;
+    if apply_to_all {
+        *sticky = Some(action);
+    }
+    Some(action)
+}
+
+/// Turn a resolved `ConflictAction` into the path that should actually be
+/// written to, or `None` if the file should be skipped entirely. When the
+/// action overwrites an existing destination and `backup_on_overwrite` is
+/// set, the existing file is moved aside via `backup_existing` first so it
+/// isn't lost.
+fn apply_conflict_action(
+    action: ConflictAction,
+    src: &Path,
+    dest: &Path,
+    backup_on_overwrite: bool,
+) -> Option<PathBuf> {
+    match action {
+        ConflictAction::Overwrite => {
+            if backup_on_overwrite {
+                let _ = backup_existing(dest);
+            }
+            Some(dest.to_path_buf())
+        }
+        ConflictAction::Skip => None,
+        ConflictAction::Rename => Some(unique_destination(dest)),
+        ConflictAction::OverwriteIfNewer => {
+            let src_modified = fs::metadata(src).and_then(|m| m.modified()).ok();
+            let dest_modified = fs::metadata(dest).and_then(|m| m.modified()).ok();
+            match (src_modified, dest_modified) {
+                (Some(s), Some(d)) if s > d => {
+                    if backup_on_overwrite {
+                        let _ = backup_existing(dest);
+                    }
+                    Some(dest.to_path_buf())
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Compare a freshly-copied `dest` against its `src` by content hash,
+/// catching silent corruption or truncation that a byte-count match alone
+/// wouldn't. Reuses `metadata::compute_hashes` (MD5 + SHA-256) rather than
+/// adding a second hashing scheme just for this check.
+pub fn verify_copy(src: &Path, dest: &Path) -> io::Result<()> {
+    let src_hashes = crate::services::metadata::compute_hashes(src)?;
+    let dest_hashes = crate::services::metadata::compute_hashes(dest)?;
+
+    if src_hashes.sha256 != dest_hashes.sha256 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Integrity check failed for '{}': copy does not match source",
+                dest.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Move `path` aside to a coreutils `mv --backup=numbered`-style backup
+/// (`path.~1~`, `path.~2~`, ...) before it gets overwritten, returning the
+/// backup path. Returns `Ok(None)` if `path` doesn't exist, so callers can
+/// use this unconditionally ahead of an overwrite.
+pub fn backup_existing(path: &Path) -> io::Result<Option<PathBuf>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let mut n = 1;
+    let backup_path = loop {
+        let candidate = path.with_file_name(format!(
+            "{}.~{}~",
+            path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            n,
+        ));
+        if !candidate.exists() {
+            break candidate;
+        }
+        n += 1;
+    };
+
+    fs::rename(path, &backup_path)?;
+    Ok(Some(backup_path))
+}
+
+/// Find a free path next to `dest` by appending " (1)", " (2)", ... before
+/// the extension until one doesn't exist.
+fn unique_destination(dest: &Path) -> PathBuf {
+    let stem = dest.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = dest.extension().map(|e| e.to_string_lossy().to_string());
+    let parent = dest.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut n = 1;
+    loop {
+        let candidate_name = match &ext {
+            Some(ext) => format!("{} ({}).{}", stem, n, ext),
+            None => format!("{} ({})", stem, n),
+        };
+        let candidate = parent.join(candidate_name);
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Auto-resolve a conflicting destination without asking the UI: if `dest`
+/// doesn't exist it's returned unchanged, otherwise `_1`, `_2`, ... is
+/// appended to the file stem (or directory name) until a free path is
+/// found. Used by the progress-tracked copy/move loops' `auto_rename_on_conflict`
+/// path; `ConflictPolicy::AutoRename` uses `unique_destination`'s " (1)",
+/// " (2)", ... style instead.
+fn resolve_filename_conflict(dest: &Path) -> PathBuf {
+    if !dest.exists() {
+        return dest.to_path_buf();
+    }
+
+    let stem = dest.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = dest.extension().map(|e| e.to_string_lossy().to_string());
+    let parent = dest.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut n = 1;
+    loop {
+        let candidate_name = match &ext {
+            Some(ext) => format!("{}_{}.{}", stem, n, ext),
+            None => format!("{}_{}", stem, n),
+        };
+        let candidate = parent.join(candidate_name);
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// What to do about a destination that already exists, for [`copy_file`],
+/// [`move_file`], [`rename_file`], and (applied per entry, so merging two
+/// directory trees works) [`copy_dir_recursive_inner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Fail with `ErrorKind::AlreadyExists` - the original hard-fail
+    /// behavior, and the default.
+    Error,
+    /// Leave the existing destination untouched and report success without
+    /// writing anything.
+    Skip,
+    /// Delete the existing destination first (refusing to touch a
+    /// `PROTECTED_PATHS` entry), then proceed as if it hadn't existed.
+    Overwrite,
+    /// Write to a nearby free name instead - `name (1).ext`, `name (2).ext`,
+    /// ... - via `unique_destination`.
+    AutoRename,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> Self {
+        ConflictPolicy::Error
+    }
+}
+
+/// Options controlling how [`copy_file`]/[`move_file`] handle a destination
+/// that already exists. `Default` (`ConflictPolicy::Error`) preserves the
+/// original hard-fail behavior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConflictOptions {
+    pub policy: ConflictPolicy,
+}
+
+/// True if canonicalizing `path` lands on one of `PROTECTED_PATHS`.
+fn is_protected_path(path: &Path) -> bool {
+    path.canonicalize()
+        .map(|canonical| {
+            let path_str = canonical.to_string_lossy();
+            PROTECTED_PATHS.iter().any(|protected| path_str == *protected)
+        })
+        .unwrap_or(false)
+}
+
+/// Resolve what to actually do about `dest` under `policy`: if it doesn't
+/// exist yet, it's returned unchanged. Otherwise `Ok(Some(path))` is the
+/// path to write to (renamed for `AutoRename`, the original `dest` again
+/// for `Overwrite` once the existing entry is gone), and `Ok(None)` means
+/// `Skip` - the caller should report success without writing anything.
+fn resolve_conflict_policy(dest: &Path, policy: ConflictPolicy) -> io::Result<Option<PathBuf>> {
+    if !dest.exists() {
+        return Ok(Some(dest.to_path_buf()));
+    }
+
+    match policy {
+        ConflictPolicy::Error => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Target already exists. Delete it first or choose a different name.",
+        )),
+        ConflictPolicy::Skip => Ok(None),
+        ConflictPolicy::AutoRename => Ok(Some(unique_destination(dest))),
+        ConflictPolicy::Overwrite => {
+            if is_protected_path(dest) {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("Cannot overwrite protected system path: {}", dest.display()),
+                ));
+            }
+            if dest.is_dir() {
+                fs::remove_dir_all(dest)?;
+            } else {
+                fs::remove_file(dest)?;
+            }
+            Ok(Some(dest.to_path_buf()))
+        }
+    }
+}
+
+/// Number of worker threads to use for `run_parallel_copy_phase`.
+/// `0` means "auto-detect": one worker per available CPU, capped to the
+/// number of items so a handful of files doesn't oversubscribe.
+fn resolve_concurrency(max_concurrency: usize, item_count: usize) -> usize {
+    let degree = if max_concurrency == 0 {
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+    } else {
+        max_concurrency
+    };
+    degree.clamp(1, item_count.max(1))
+}
+
+/// Copy a batch of already conflict-resolved `(src, dest)` items across a
+/// bounded pool of worker threads. Directory recursion within one item
+/// stays sequential (`copy_dir_recursive_with_progress`); the parallelism
+/// is across independent top-level items, which share `completed_bytes`/
+/// `completed_files` so `TotalProgress` stays coherent no matter which
+/// worker is currently ahead. `on_success` runs once an item has copied
+/// (and, if `verify_after_copy` and the item is a single file, been
+/// verified; directory items are not hash-checked) -- returning `Some(msg)`
+/// still counts the item as a success but reports `msg` as an
+/// accompanying `ProgressMessage::Error` (used by `move_files_with_progress`
+/// to surface a "copied but couldn't delete source" warning without
+/// failing the move). Returns the (success, failure) counts for this
+/// batch; the caller is responsible for the final `Completed` message.
+#[allow(clippy::too_many_arguments)]
+fn run_parallel_copy_phase(
+    items: Vec<(PathBuf, PathBuf)>,
+    cancel_flag: &Arc<AtomicBool>,
+    progress_tx: &Sender<ProgressMessage>,
+    completed_bytes: &AtomicU64,
+    completed_files: &AtomicUsize,
+    total_bytes: u64,
+    total_files: usize,
+    options: CopyOptions,
+    verify_after_copy: bool,
+    max_concurrency: usize,
+    on_success: impl Fn(&Path, &Path) -> Option<String> + Sync,
+) -> (usize, usize) {
+    if items.is_empty() {
+        return (0, 0);
+    }
+
+    let degree = resolve_concurrency(max_concurrency, items.len());
+    let next_index = AtomicUsize::new(0);
+    let success_count = AtomicUsize::new(0);
+    let failure_count = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        for _ in 0..degree {
+            let next_index = &next_index;
+            let items = &items;
+            let success_count = &success_count;
+            let failure_count = &failure_count;
+            let on_success = &on_success;
+            let cancel_flag = cancel_flag;
+            let progress_tx = progress_tx;
+
+            scope.spawn(move || loop {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                let idx = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some((src, dest)) = items.get(idx) else {
+                    break;
+                };
+
+                let filename = src.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let _ = progress_tx.send(ProgressMessage::FileStarted(filename.clone()));
+
+                let result: io::Result<()> = if src.is_dir() {
+                    copy_dir_recursive_with_progress(
+                        src,
+                        dest,
+                        cancel_flag,
+                        progress_tx,
+                        completed_bytes,
+                        completed_files,
+                        total_bytes,
+                        total_files,
+                        options,
+                    )
+                } else {
+                    let file_size = fs::metadata(src).map(|m| m.len()).unwrap_or(0);
+                    let file_completed_bytes = completed_bytes.load(Ordering::Relaxed);
+
+                    copy_file_with_progress(src, dest, cancel_flag, options, |copied, total| {
+                        let _ = progress_tx.send(ProgressMessage::FileProgress(copied, total));
+                        let _ = progress_tx.send(ProgressMessage::TotalProgress(
+                            completed_files.load(Ordering::Relaxed),
+                            total_files,
+                            file_completed_bytes + copied,
+                            total_bytes,
+                        ));
+                    })
+                    .and_then(|_| {
+                        if verify_after_copy {
+                            verify_copy(src, dest)?;
+                        }
+                        Ok(())
+                    })
+                    .map(|_| {
+                        completed_bytes.fetch_add(file_size, Ordering::Relaxed);
+                        completed_files.fetch_add(1, Ordering::Relaxed);
+                    })
+                };
+
+                match result {
+                    Ok(_) => {
+                        if let Some(warning) = on_success(src, dest) {
+                            let _ = progress_tx.send(ProgressMessage::Error(filename.clone(), warning));
+                        }
+                        success_count.fetch_add(1, Ordering::Relaxed);
+                        let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
+                    }
+                    Err(e) => {
+                        if e.kind() == io::ErrorKind::Interrupted {
+                            if dest.is_dir() {
+                                let _ = fs::remove_dir_all(dest);
+                            } else {
+                                let _ = fs::remove_file(dest);
+                            }
+                        } else {
+                            failure_count.fetch_add(1, Ordering::Relaxed);
+                            let _ = progress_tx.send(ProgressMessage::Error(filename, e.to_string()));
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    (success_count.load(Ordering::Relaxed), failure_count.load(Ordering::Relaxed))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn copy_files_with_progress(
     files: Vec<PathBuf>,
     source_dir: &Path,
     target_dir: &Path,
     cancel_flag: Arc<AtomicBool>,
     progress_tx: Sender<ProgressMessage>,
+    conflict_rx: &Receiver<(ConflictAction, bool)>,
+    backup_on_overwrite: bool,
+    verify_after_copy: bool,
+    auto_rename_on_conflict: bool,
+    options: CopyOptions,
+    max_concurrency: usize,
 ) {
     let mut success_count = 0;
     let mut failure_count = 0;
@@ -347,11 +1143,20 @@ pub fn copy_files_with_progress(
         }
     };
 
-    let mut completed_bytes: u64 = 0;
-    let mut completed_files: usize = 0;
+    let completed_bytes = AtomicU64::new(0);
+    let completed_files = AtomicUsize::new(0);
+    let mut sticky_conflict_action: Option<ConflictAction> = None;
+
+    // First resolve every conflict sequentially (interactive resolution
+    // blocks on the single `conflict_rx` reply channel, so it can't be
+    // parallelized); the result is a plain list of (src, final dest) items
+    // with no more user interaction needed, ready to copy concurrently.
+    let mut work_items: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut cancelled = false;
 
     for file_path in &files {
         if cancel_flag.load(Ordering::Relaxed) {
+            cancelled = true;
             break;
         }
 
@@ -365,90 +1170,96 @@ pub fn copy_files_with_progress(
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_default();
 
-        let dest = target_dir.join(&filename);
+        let mut dest = target_dir.join(&filename);
 
-        // Check if destination already exists
+        // Resolve an existing destination: skip/overwrite per `options`
+        // when set, otherwise auto-rename or ask the UI as before.
         if dest.exists() {
-            failure_count += 1;
-            let _ = progress_tx.send(ProgressMessage::Error(
-                filename,
-                "Target already exists".to_string(),
-            ));
-            continue;
-        }
-
-        let _ = progress_tx.send(ProgressMessage::FileStarted(filename.clone()));
-
-        if src.is_dir() {
-            match copy_dir_recursive_with_progress(
-                &src,
-                &dest,
-                &cancel_flag,
-                &progress_tx,
-                &mut completed_bytes,
-                &mut completed_files,
-                total_bytes,
-                total_files,
-            ) {
-                Ok(_) => {
-                    success_count += 1;
-                    let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
-                }
-                Err(e) => {
-                    if e.kind() == io::ErrorKind::Interrupted {
-                        // Cancelled - clean up partial copy
-                        let _ = fs::remove_dir_all(&dest);
-                        break;
+            if options.skip_existing {
+                success_count += 1;
+                let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
+                continue;
+            } else if options.overwrite {
+                match decide_overwrite(&src, &dest, &options) {
+                    OverwriteDecision::SkipNotNewer => {
+                        success_count += 1;
+                        let _ = progress_tx.send(ProgressMessage::SkippedNotNewer(filename));
+                        continue;
                     }
-                    failure_count += 1;
-                    let _ = progress_tx.send(ProgressMessage::Error(filename, e.to_string()));
-                }
-            }
-        } else {
-            let file_size = fs::metadata(&src).map(|m| m.len()).unwrap_or(0);
-            let file_completed_bytes = completed_bytes;
-
-            match copy_file_with_progress(
-                &src,
-                &dest,
-                &cancel_flag,
-                |copied, total| {
-                    let _ = progress_tx.send(ProgressMessage::FileProgress(copied, total));
-                    let _ = progress_tx.send(ProgressMessage::TotalProgress(
-                        completed_files,
-                        total_files,
-                        file_completed_bytes + copied,
-                        total_bytes,
-                    ));
-                },
-            ) {
-                Ok(_) => {
-                    completed_bytes += file_size;
-                    completed_files += 1;
-                    success_count += 1;
-                    let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
+                    OverwriteDecision::Proceed(Some(backup_path)) => {
+                        let _ = progress_tx.send(ProgressMessage::BackedUp(
+                            filename.clone(),
+                            backup_path.display().to_string(),
+                        ));
+                    }
+                    OverwriteDecision::Proceed(None) => {}
                 }
-                Err(e) => {
-                    if e.kind() == io::ErrorKind::Interrupted {
+                // Fall through and let copy_file_with_progress truncate it.
+            } else if auto_rename_on_conflict {
+                let resolved = resolve_filename_conflict(&dest);
+                let final_name = resolved.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let _ = progress_tx.send(ProgressMessage::Renamed(filename.clone(), final_name));
+                dest = resolved;
+            } else {
+                let action = match resolve_conflict(
+                    &src,
+                    &dest,
+                    &filename,
+                    &progress_tx,
+                    conflict_rx,
+                    &mut sticky_conflict_action,
+                ) {
+                    Some(action) => action,
+                    None => {
+                        cancelled = true;
                         break;
                     }
-                    failure_count += 1;
-                    let _ = progress_tx.send(ProgressMessage::Error(filename, e.to_string()));
+                };
+                match apply_conflict_action(action, &src, &dest, backup_on_overwrite) {
+                    Some(resolved) => dest = resolved,
+                    None => continue,
                 }
             }
         }
+
+        work_items.push((src, dest));
+    }
+
+    if !cancelled {
+        let (batch_success, batch_failure) = run_parallel_copy_phase(
+            work_items,
+            &cancel_flag,
+            &progress_tx,
+            &completed_bytes,
+            &completed_files,
+            total_bytes,
+            total_files,
+            options,
+            verify_after_copy,
+            max_concurrency,
+            |_src, _dest| None,
+        );
+        success_count += batch_success;
+        failure_count += batch_failure;
     }
 
     let _ = progress_tx.send(ProgressMessage::Completed(success_count, failure_count));
 }
 
 /// Move files with progress reporting
+#[allow(clippy::too_many_arguments)]
 pub fn move_files_with_progress(
     files: Vec<PathBuf>,
     source_dir: &Path,
     target_dir: &Path,
     cancel_flag: Arc<AtomicBool>,
     progress_tx: Sender<ProgressMessage>,
+    conflict_rx: &Receiver<(ConflictAction, bool)>,
+    backup_on_overwrite: bool,
+    verify_after_copy: bool,
+    auto_rename_on_conflict: bool,
+    options: CopyOptions,
+    max_concurrency: usize,
 ) {
     let mut success_count = 0;
     let mut failure_count = 0;
@@ -468,11 +1279,12 @@ pub fn move_files_with_progress(
         }
     };
 
-    let mut completed_bytes: u64 = 0;
-    let mut completed_files: usize = 0;
+    let completed_bytes = AtomicU64::new(0);
+    let completed_files = AtomicUsize::new(0);
 
     // First, try simple rename for each file (fast path for same filesystem)
-    let mut needs_copy: Vec<(PathBuf, PathBuf, u64)> = Vec::new();  // (src, dest, size)
+    let mut needs_copy: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut sticky_conflict_action: Option<ConflictAction> = None;
 
     for file_path in &files {
         if cancel_flag.load(Ordering::Relaxed) {
@@ -489,7 +1301,7 @@ pub fn move_files_with_progress(
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_default();
 
-        let dest = target_dir.join(&filename);
+        let mut dest = target_dir.join(&filename);
 
         // Get file/dir size for progress tracking
         let (item_size, item_files) = if src.is_dir() {
@@ -498,14 +1310,52 @@ pub fn move_files_with_progress(
             (fs::metadata(&src).map(|m| m.len()).unwrap_or(0), 1)
         };
 
-        // Check if destination already exists
+        // Resolve an existing destination: skip/overwrite per `options`
+        // when set, otherwise auto-rename or ask the UI as before.
         if dest.exists() {
-            failure_count += 1;
-            let _ = progress_tx.send(ProgressMessage::Error(
-                filename,
-                "Target already exists".to_string(),
-            ));
-            continue;
+            if options.skip_existing {
+                success_count += 1;
+                let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
+                continue;
+            } else if options.overwrite {
+                match decide_overwrite(&src, &dest, &options) {
+                    OverwriteDecision::SkipNotNewer => {
+                        success_count += 1;
+                        let _ = progress_tx.send(ProgressMessage::SkippedNotNewer(filename));
+                        continue;
+                    }
+                    OverwriteDecision::Proceed(Some(backup_path)) => {
+                        let _ = progress_tx.send(ProgressMessage::BackedUp(
+                            filename.clone(),
+                            backup_path.display().to_string(),
+                        ));
+                    }
+                    OverwriteDecision::Proceed(None) => {}
+                }
+                // Fall through and let fs::rename (or the copy fallback)
+                // replace it.
+            } else if auto_rename_on_conflict {
+                let resolved = resolve_filename_conflict(&dest);
+                let final_name = resolved.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let _ = progress_tx.send(ProgressMessage::Renamed(filename.clone(), final_name));
+                dest = resolved;
+            } else {
+                let action = match resolve_conflict(
+                    &src,
+                    &dest,
+                    &filename,
+                    &progress_tx,
+                    conflict_rx,
+                    &mut sticky_conflict_action,
+                ) {
+                    Some(action) => action,
+                    None => break,
+                };
+                match apply_conflict_action(action, &src, &dest, backup_on_overwrite) {
+                    Some(resolved) => dest = resolved,
+                    None => continue,
+                }
+            }
         }
 
         let _ = progress_tx.send(ProgressMessage::FileStarted(filename.clone()));
@@ -514,20 +1364,20 @@ pub fn move_files_with_progress(
         match fs::rename(&src, &dest) {
             Ok(_) => {
                 success_count += 1;
-                completed_bytes += item_size;
-                completed_files += item_files;
+                let done_bytes = completed_bytes.fetch_add(item_size, Ordering::Relaxed) + item_size;
+                let done_files = completed_files.fetch_add(item_files, Ordering::Relaxed) + item_files;
                 let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
                 let _ = progress_tx.send(ProgressMessage::TotalProgress(
-                    completed_files,
+                    done_files,
                     total_files,
-                    completed_bytes,
+                    done_bytes,
                     total_bytes,
                 ));
             }
             Err(e) => {
                 // If cross-device, we need to copy+delete
                 if e.raw_os_error() == Some(libc::EXDEV) {
-                    needs_copy.push((src, dest, item_size));
+                    needs_copy.push((src, dest));
                 } else {
                     failure_count += 1;
                     let _ = progress_tx.send(ProgressMessage::Error(filename, e.to_string()));
@@ -536,88 +1386,288 @@ pub fn move_files_with_progress(
         }
     }
 
-    // Handle cross-device moves (copy + delete)
+    // Handle cross-device moves (copy + delete) across a bounded worker
+    // pool, the same way copy_files_with_progress parallelizes its batch.
     if !needs_copy.is_empty() && !cancel_flag.load(Ordering::Relaxed) {
-        for (src, dest, _) in needs_copy {
-            if cancel_flag.load(Ordering::Relaxed) {
-                break;
+        let (batch_success, batch_failure) = run_parallel_copy_phase(
+            needs_copy,
+            &cancel_flag,
+            &progress_tx,
+            &completed_bytes,
+            &completed_files,
+            total_bytes,
+            total_files,
+            options,
+            verify_after_copy,
+            max_concurrency,
+            |src, _dest| {
+                // Delete source after successful copy (and, when enabled and
+                // the item is a single file, only after the destination has
+                // been verified to match it; directories are not verified)
+                delete_file(src).err().map(|e| format!("Copied but failed to delete source: {}", e))
+            },
+        );
+        success_count += batch_success;
+        failure_count += batch_failure;
+    }
+
+    let _ = progress_tx.send(ProgressMessage::Completed(success_count, failure_count));
+}
+
+/// Create a link at `dest` instead of duplicating `src`'s data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SymlinkOutcome {
+    Linked,
+    /// Platform has no symlink story; the file was copied instead.
+    CopiedFallback,
+}
+
+/// Create a single link (or, on non-Unix platforms, a plain copy) at
+/// `dest` pointing at `src`, for `symlink_files_with_progress`'s batch
+/// paste. Rejects linking to a handful of sensitive system paths, the same
+/// check `copy_dir_recursive_with_progress` applies when replicating an
+/// existing symlink. Distinct from the public single-item `create_symlink`
+/// below: this one also supports relative targets and copies instead of
+/// failing outright where the platform has no symlink story.
+fn create_symlink_for_paste(src: &Path, dest: &Path, relative: bool) -> io::Result<SymlinkOutcome> {
+    #[cfg(unix)]
+    {
+        let canonical_src = src.canonicalize()?;
+
+        let target_str = canonical_src.to_string_lossy();
+        let sensitive_paths = ["/etc", "/sys", "/proc", "/boot", "/root", "/var/log"];
+        for sensitive in sensitive_paths {
+            if target_str.starts_with(sensitive) {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("Cannot create symlink pointing to sensitive path: {}", target_str),
+                ));
             }
+        }
 
-            let filename = src.file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_default();
+        let link_target = if relative {
+            let dest_dir = dest.parent().unwrap_or_else(|| Path::new("."));
+            let canonical_dest_dir = dest_dir.canonicalize().unwrap_or_else(|_| dest_dir.to_path_buf());
+            relative_path_to(&canonical_dest_dir, &canonical_src)
+        } else {
+            canonical_src
+        };
 
-            let _ = progress_tx.send(ProgressMessage::FileStarted(filename.clone()));
+        std::os::unix::fs::symlink(&link_target, dest)?;
+        Ok(SymlinkOutcome::Linked)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = relative;
+        if src.is_dir() {
+            copy_dir_recursive(src, dest, ConflictPolicy::Error)?;
+        } else {
+            fs::copy(src, dest)?;
+        }
+        Ok(SymlinkOutcome::CopiedFallback)
+    }
+}
 
-            let copy_result = if src.is_dir() {
-                copy_dir_recursive_with_progress(
-                    &src,
-                    &dest,
-                    &cancel_flag,
-                    &progress_tx,
-                    &mut completed_bytes,
-                    &mut completed_files,
-                    total_bytes,
-                    total_files,
-                )
-            } else {
-                let file_size = fs::metadata(&src).map(|m| m.len()).unwrap_or(0);
-                let file_completed_bytes = completed_bytes;
+/// `link`'s name must be a valid filename and nothing may already occupy
+/// that path - shared by `create_symlink`/`create_hardlink`.
+fn validate_link_destination(link: &Path) -> io::Result<()> {
+    let name = link.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if let Err(reason) = is_valid_filename(&name) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, reason));
+    }
+    if fs::symlink_metadata(link).is_ok() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "Link destination already exists"));
+    }
+    Ok(())
+}
 
-                copy_file_with_progress(
-                    &src,
-                    &dest,
-                    &cancel_flag,
-                    |copied, total| {
-                        let _ = progress_tx.send(ProgressMessage::FileProgress(copied, total));
-                        let _ = progress_tx.send(ProgressMessage::TotalProgress(
-                            completed_files,
-                            total_files,
-                            file_completed_bytes + copied,
-                            total_bytes,
-                        ));
-                    },
-                ).map(|_| {
-                    completed_bytes += file_size;
-                    completed_files += 1;
-                })
-            };
+/// Reject `target` if it resolves into one of a handful of sensitive system
+/// paths - the same guard `copy_dir_recursive_inner` applies when
+/// replicating an existing symlink, duplicated here rather than shared.
+fn reject_sensitive_link_target(target: &Path) -> io::Result<()> {
+    if let Ok(canonical) = target.canonicalize() {
+        let target_str = canonical.to_string_lossy();
+        let sensitive_paths = ["/etc", "/sys", "/proc", "/boot", "/root", "/var/log"];
+        for sensitive in sensitive_paths {
+            if target_str.starts_with(sensitive) {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("Cannot create a link pointing to sensitive path: {}", target_str),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
 
-            match copy_result {
-                Ok(_) => {
-                    // Delete source after successful copy
-                    if let Err(e) = delete_file(&src) {
-                        // Copy succeeded but delete failed - report but count as success
-                        let _ = progress_tx.send(ProgressMessage::Error(
-                            filename.clone(),
-                            format!("Copied but failed to delete source: {}", e),
-                        ));
-                    }
-                    success_count += 1;
-                    let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
-                }
-                Err(e) => {
-                    if e.kind() == io::ErrorKind::Interrupted {
-                        // Cancelled - clean up partial copy
-                        if dest.is_dir() {
-                            let _ = fs::remove_dir_all(&dest);
-                        } else {
-                            let _ = fs::remove_file(&dest);
-                        }
-                        break;
-                    }
-                    failure_count += 1;
-                    let _ = progress_tx.send(ProgressMessage::Error(filename, e.to_string()));
+/// Create a symlink at `link` pointing at `target`, for single-item use
+/// (duplicating a large file cheaply, or laying down a shortcut) - distinct
+/// from `create_symlink_for_paste`, which also handles relative targets and
+/// a non-Unix copy fallback for `symlink_files_with_progress`'s batch paste.
+pub fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    reject_sensitive_link_target(target)?;
+    validate_link_destination(link)?;
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)
+    }
+    #[cfg(windows)]
+    {
+        // Dispatch on the target's own type, same as a real `ln -s` would.
+        // This covers what `std` exposes directly; a junction fallback for
+        // directories on editions that reject `symlink_dir` without
+        // elevation would need the external `junction` crate, which isn't
+        // vendored in this tree.
+        if target.is_dir() {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "Symlinks are not supported on this platform"))
+    }
+}
+
+/// Create a hard link at `link` sharing `target`'s data. Unlike a symlink,
+/// both paths must live on the same filesystem; that failure (`EXDEV`) is
+/// reported as a clear error instead of the raw OS message, the same way
+/// `move_file` already detects it for its rename-or-copy fallback.
+pub fn create_hardlink(target: &Path, link: &Path) -> io::Result<()> {
+    reject_sensitive_link_target(target)?;
+    validate_link_destination(link)?;
+
+    match fs::hard_link(target, link) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            #[cfg(unix)]
+            if e.raw_os_error() == Some(libc::EXDEV) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Cannot create a hard link across filesystems - source and destination must be on the same filesystem",
+                ));
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Path from directory `from` to `to`, walking up with `..` for the
+/// non-shared prefix and descending into `to`'s unique suffix. Both
+/// arguments are expected to already be absolute/canonical.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn relative_path_to(from: &Path, to: &Path) -> PathBuf {
+    let from_components: Vec<_> = from.components().collect();
+    let to_components: Vec<_> = to.components().collect();
+
+    let shared = from_components.iter()
+        .zip(to_components.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut result = PathBuf::new();
+    for _ in shared..from_components.len() {
+        result.push("..");
+    }
+    for component in &to_components[shared..] {
+        result.push(component.as_os_str());
+    }
+    result
+}
+
+/// Create links (or, where unsupported, copies) for `files` at
+/// `target_dir`, mirroring `copy_files_with_progress`'s progress/conflict
+/// handling but without moving any file data.
+pub fn symlink_files_with_progress(
+    files: Vec<PathBuf>,
+    source_dir: &Path,
+    target_dir: &Path,
+    cancel_flag: Arc<AtomicBool>,
+    progress_tx: Sender<ProgressMessage>,
+    conflict_rx: &Receiver<(ConflictAction, bool)>,
+    relative: bool,
+    auto_rename_on_conflict: bool,
+) {
+    let mut success_count = 0;
+    let mut failure_count = 0;
+    let total_files = files.len();
+    let mut completed_files: usize = 0;
+    let mut sticky_conflict_action: Option<ConflictAction> = None;
+
+    for file_path in &files {
+        if cancel_flag.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let src = if file_path.is_absolute() {
+            file_path.clone()
+        } else {
+            source_dir.join(file_path)
+        };
+
+        let filename = src.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut dest = target_dir.join(&filename);
+
+        // Resolve an existing destination: silently auto-rename when that
+        // mode is on, otherwise ask the UI the same as always.
+        if dest.exists() {
+            if auto_rename_on_conflict {
+                let resolved = resolve_filename_conflict(&dest);
+                let final_name = resolved.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let _ = progress_tx.send(ProgressMessage::Renamed(filename.clone(), final_name));
+                dest = resolved;
+            } else {
+                let action = match resolve_conflict(
+                    &src,
+                    &dest,
+                    &filename,
+                    &progress_tx,
+                    conflict_rx,
+                    &mut sticky_conflict_action,
+                ) {
+                    Some(action) => action,
+                    None => break,
+                };
+                match apply_conflict_action(action, &src, &dest, false) {
+                    Some(resolved) => dest = resolved,
+                    None => continue,
                 }
             }
         }
+
+        let _ = progress_tx.send(ProgressMessage::FileStarted(filename.clone()));
+
+        match create_symlink_for_paste(&src, &dest, relative) {
+            Ok(SymlinkOutcome::Linked) => {
+                success_count += 1;
+                completed_files += 1;
+                let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
+            }
+            Ok(SymlinkOutcome::CopiedFallback) => {
+                success_count += 1;
+                completed_files += 1;
+                let _ = progress_tx.send(ProgressMessage::CopiedInsteadOfLinked(filename.clone()));
+                let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
+            }
+            Err(e) => {
+                failure_count += 1;
+                let _ = progress_tx.send(ProgressMessage::Error(filename, e.to_string()));
+            }
+        }
+
+        let _ = progress_tx.send(ProgressMessage::TotalProgress(completed_files, total_files, 0, 0));
     }
 
     let _ = progress_tx.send(ProgressMessage::Completed(success_count, failure_count));
 }
 
 /// Copy a file or directory
-pub fn copy_file(src: &Path, dest: &Path) -> io::Result<()> {
+pub fn copy_file(src: &Path, dest: &Path, options: ConflictOptions) -> io::Result<()> {
     // Check if source and destination are the same
     let resolved_src = src.canonicalize()?;
     if dest.exists() {
@@ -630,18 +1680,23 @@ pub fn copy_file(src: &Path, dest: &Path) -> io::Result<()> {
         }
     }
 
-    // Check if destination already exists
-    if dest.exists() {
-        return Err(io::Error::new(
-            io::ErrorKind::AlreadyExists,
-            "Target already exists. Delete it first or choose a different name.",
-        ));
+    // When both source and an existing destination are directories, merge
+    // them: the policy is resolved per entry inside the recursive walk
+    // rather than deleting or renaming the whole destination tree up front.
+    if src.is_dir() && dest.is_dir() {
+        return copy_dir_recursive(src, dest, options.policy);
     }
 
+    let dest = match resolve_conflict_policy(dest, options.policy)? {
+        Some(dest) => dest,
+        None => return Ok(()),
+    };
+
     if src.is_dir() {
-        copy_dir_recursive(src, dest)
+        copy_dir_recursive(src, &dest, options.policy)
     } else {
-        fs::copy(src, dest)?;
+        fs::copy(src, &dest)?;
+        preserve_times_by_path(src, &dest);
         Ok(())
     }
 }
@@ -649,10 +1704,12 @@ pub fn copy_file(src: &Path, dest: &Path) -> io::Result<()> {
 /// Maximum recursion depth for directory copy to prevent stack overflow
 const MAX_COPY_DEPTH: usize = 256;
 
-/// Copy directory recursively with symlink loop detection
-fn copy_dir_recursive(src: &Path, dest: &Path) -> io::Result<()> {
+/// Copy directory recursively with symlink loop detection. `policy` governs
+/// what happens to each entry that already exists in `dest`, so copying
+/// onto an existing tree merges the two rather than failing outright.
+fn copy_dir_recursive(src: &Path, dest: &Path, policy: ConflictPolicy) -> io::Result<()> {
     let mut visited = HashSet::new();
-    copy_dir_recursive_inner(src, dest, &mut visited, 0)
+    copy_dir_recursive_inner(src, dest, &mut visited, 0, policy)
 }
 
 /// Internal recursive copy with visited path tracking
@@ -661,6 +1718,7 @@ fn copy_dir_recursive_inner(
     dest: &Path,
     visited: &mut HashSet<PathBuf>,
     depth: usize,
+    policy: ConflictPolicy,
 ) -> io::Result<()> {
     // Check maximum depth to prevent stack overflow
     if depth > MAX_COPY_DEPTH {
@@ -691,6 +1749,11 @@ fn copy_dir_recursive_inner(
         let metadata = fs::symlink_metadata(&src_path)?;
 
         if metadata.is_symlink() {
+            let dest_path = match resolve_conflict_policy(&dest_path, policy)? {
+                Some(dest_path) => dest_path,
+                None => continue,
+            };
+
             // Copy symlink as symlink (don't follow it)
             #[cfg(unix)]
             {
@@ -721,17 +1784,26 @@ fn copy_dir_recursive_inner(
                 }
             }
         } else if metadata.is_dir() {
-            copy_dir_recursive_inner(&src_path, &dest_path, visited, depth + 1)?;
+            copy_dir_recursive_inner(&src_path, &dest_path, visited, depth + 1, policy)?;
         } else {
+            let dest_path = match resolve_conflict_policy(&dest_path, policy)? {
+                Some(dest_path) => dest_path,
+                None => continue,
+            };
             fs::copy(&src_path, &dest_path)?;
+            preserve_times_by_path(&src_path, &dest_path);
         }
     }
 
+    // Set the directory's own times last, since writing its children just
+    // bumped its mtime.
+    preserve_times_by_path(src, dest);
+
     Ok(())
 }
 
 /// Move a file or directory
-pub fn move_file(src: &Path, dest: &Path) -> io::Result<()> {
+pub fn move_file(src: &Path, dest: &Path, options: ConflictOptions) -> io::Result<()> {
     // Check if source and destination are the same
     let resolved_src = src.canonicalize()?;
     if dest.exists() {
@@ -744,21 +1816,41 @@ pub fn move_file(src: &Path, dest: &Path) -> io::Result<()> {
         }
     }
 
-    // Check if destination already exists
-    if dest.exists() {
-        return Err(io::Error::new(
-            io::ErrorKind::AlreadyExists,
-            "Target already exists. Delete it first or choose a different name.",
-        ));
+    // When both source and an existing destination are directories, merge
+    // them: copy the tree across with the policy applied per entry, then
+    // remove the now-duplicated source. `fs::rename` can't express a merge,
+    // so this bypasses the fast rename path entirely.
+    if src.is_dir() && dest.is_dir() {
+        copy_dir_recursive(src, dest, options.policy)?;
+        return delete_file(src);
     }
 
+    let dest = match resolve_conflict_policy(dest, options.policy)? {
+        Some(dest) => dest,
+        None => return Ok(()),
+    };
+    let dest = dest.as_path();
+
     // Try rename first (fast for same filesystem)
     match fs::rename(src, dest) {
         Ok(_) => Ok(()),
         Err(e) => {
-            // If rename fails (cross-device), copy then delete
+            // If rename fails because src/dest are on different filesystems,
+            // fall back to copy-then-delete. The source is only removed once
+            // the copy has fully succeeded, so a failure here never leaves
+            // the move half-done with the source gone; a failed copy's
+            // partial destination is cleaned up rather than left behind.
+            // `dest` has already been resolved above, so the conflict is
+            // known clear here.
             if e.raw_os_error() == Some(libc::EXDEV) {
-                copy_file(src, dest)?;
+                if let Err(copy_err) = copy_file(src, dest, ConflictOptions::default()) {
+                    if dest.is_dir() {
+                        let _ = fs::remove_dir_all(dest);
+                    } else {
+                        let _ = fs::remove_file(dest);
+                    }
+                    return Err(copy_err);
+                }
                 delete_file(src)?;
                 Ok(())
             } else {
@@ -801,6 +1893,78 @@ pub fn delete_file(path: &Path) -> io::Result<()> {
     }
 }
 
+/// Move a file or directory to the OS trash/recycle bin instead of deleting
+/// it permanently. Cross-platform via the `trash` crate (Finder's Trash on
+/// macOS, the Recycle Bin on Windows, and the XDG trash spec on Linux).
+pub fn trash_file(path: &Path) -> io::Result<()> {
+    // Security: Prevent trashing protected system paths (same guard as delete_file)
+    if let Ok(canonical) = path.canonicalize() {
+        let path_str = canonical.to_string_lossy();
+        for protected in PROTECTED_PATHS {
+            if path_str == *protected {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("Cannot trash protected system path: {}", protected),
+                ));
+            }
+        }
+    }
+
+    trash::delete(path).map_err(|e| io::Error::other(e.to_string()))
+}
+
+/// Move files to the OS trash with progress reporting (main entry point for
+/// the progress-enabled trash operation). The `trash` crate moves each item
+/// in one shot, so per-byte progress isn't available - only per-file.
+pub fn trash_files_with_progress(
+    files: Vec<PathBuf>,
+    source_dir: &Path,
+    cancel_flag: Arc<AtomicBool>,
+    progress_tx: Sender<ProgressMessage>,
+) {
+    let mut success_count = 0;
+    let mut failure_count = 0;
+    let total_files = files.len();
+
+    for (completed, file_path) in files.iter().enumerate() {
+        if cancel_flag.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let src = if file_path.is_absolute() {
+            file_path.clone()
+        } else {
+            source_dir.join(file_path)
+        };
+
+        let filename = src.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let _ = progress_tx.send(ProgressMessage::FileStarted(filename.clone()));
+
+        match trash_file(&src) {
+            Ok(_) => {
+                success_count += 1;
+                let _ = progress_tx.send(ProgressMessage::FileCompleted(filename));
+            }
+            Err(e) => {
+                failure_count += 1;
+                let _ = progress_tx.send(ProgressMessage::Error(filename, e.to_string()));
+            }
+        }
+
+        let _ = progress_tx.send(ProgressMessage::TotalProgress(
+            completed + 1,
+            total_files,
+            0,
+            0,
+        ));
+    }
+
+    let _ = progress_tx.send(ProgressMessage::Completed(success_count, failure_count));
+}
+
 /// Create a new directory
 pub fn create_directory(path: &Path) -> io::Result<()> {
     if path.exists() {
@@ -813,23 +1977,57 @@ pub fn create_directory(path: &Path) -> io::Result<()> {
     fs::create_dir_all(path)
 }
 
-/// Rename a file or directory
-pub fn rename_file(old_path: &Path, new_path: &Path) -> io::Result<()> {
-    if new_path.exists() {
-        return Err(io::Error::new(
-            io::ErrorKind::AlreadyExists,
-            "Target already exists",
-        ));
-    }
+/// Rename a file or directory, resolving a colliding `new_path` per `policy`.
+pub fn rename_file(old_path: &Path, new_path: &Path, policy: ConflictPolicy) -> io::Result<()> {
+    let new_path = match resolve_conflict_policy(new_path, policy)? {
+        Some(new_path) => new_path,
+        None => return Ok(()),
+    };
 
-    fs::rename(old_path, new_path)
+    fs::rename(old_path, &new_path)
 }
 
 /// Maximum filename length (POSIX limit)
 const MAX_FILENAME_LENGTH: usize = 255;
 
-/// Validate filename for dangerous characters
+/// Which filename rules to enforce. macOS and Linux share the same POSIX
+/// rules; Windows/NTFS additionally rejects a handful of reserved device
+/// names, trailing dots/spaces, and a few more forbidden characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Posix,
+    Windows,
+}
+
+impl Platform {
+    /// The platform this binary is actually running on.
+    fn host() -> Self {
+        if cfg!(windows) {
+            Platform::Windows
+        } else {
+            Platform::Posix
+        }
+    }
+}
+
+/// Windows/NTFS device names that are reserved regardless of extension -
+/// `con.txt` is just as invalid as `con`. Compared case-insensitively.
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Validate filename for dangerous characters, using the rules of the
+/// platform this binary is running on.
 pub fn is_valid_filename(name: &str) -> Result<(), &'static str> {
+    is_valid_filename_for(name, Platform::host())
+}
+
+/// Validate filename for dangerous characters under a specific `platform`'s
+/// rules, regardless of which platform this binary is actually running on -
+/// lets tests exercise Windows' stricter rules on a POSIX build host.
+pub fn is_valid_filename_for(name: &str, platform: Platform) -> Result<(), &'static str> {
     if name.is_empty() || name.trim().is_empty() {
         return Err("Filename cannot be empty");
     }
@@ -869,6 +2067,26 @@ pub fn is_valid_filename(name: &str) -> Result<(), &'static str> {
         return Err("Filename cannot start with hyphen");
     }
 
+    if platform == Platform::Windows {
+        // Windows/NTFS forbidden characters, beyond the `/` and `\`
+        // already rejected above.
+        if name.contains(['<', '>', ':', '"', '|', '?', '*']) {
+            return Err("Filename cannot contain any of < > : \" | ? *");
+        }
+
+        // Windows silently strips a trailing dot or space, which can make
+        // two distinct-looking names collide on disk.
+        if name.ends_with('.') || name.ends_with(' ') {
+            return Err("Filename cannot end with a dot or space on Windows");
+        }
+
+        // Reserved device names are invalid with or without an extension.
+        let stem = name.split('.').next().unwrap_or(name);
+        if WINDOWS_RESERVED_NAMES.iter().any(|reserved| stem.eq_ignore_ascii_case(reserved)) {
+            return Err("Filename is a reserved Windows device name");
+        }
+    }
+
     Ok(())
 }
 
@@ -964,6 +2182,45 @@ mod tests {
         assert!(is_valid_filename("--long-option").is_err());
     }
 
+    #[test]
+    fn test_is_valid_filename_platform_rules() {
+        let cases: &[(&str, bool, bool)] = &[
+            // (name, valid on Posix, valid on Windows)
+            ("normal.txt", true, true),
+            ("CON", true, false),
+            ("con", true, false),
+            ("con.txt", true, false),
+            ("COM1", true, false),
+            ("com9.log", true, false),
+            ("LPT1", true, false),
+            ("CONSOLE", true, true),
+            ("trailing.", true, false),
+            ("trailing ", false, false),
+            ("file<name", true, false),
+            ("file>name", true, false),
+            ("file:name", true, false),
+            ("file\"name", true, false),
+            ("file|name", true, false),
+            ("file?name", true, false),
+            ("file*name", true, false),
+        ];
+
+        for (name, valid_posix, valid_windows) in cases {
+            assert_eq!(
+                is_valid_filename_for(name, Platform::Posix).is_ok(),
+                *valid_posix,
+                "Posix check for {:?}",
+                name,
+            );
+            assert_eq!(
+                is_valid_filename_for(name, Platform::Windows).is_ok(),
+                *valid_windows,
+                "Windows check for {:?}",
+                name,
+            );
+        }
+    }
+
     // ========== copy_file tests ==========
 
     #[test]
@@ -975,7 +2232,7 @@ mod tests {
         let mut file = File::create(&src).unwrap();
         writeln!(file, "test content").unwrap();
 
-        let result = copy_file(&src, &dest);
+        let result = copy_file(&src, &dest, ConflictOptions::default());
         assert!(result.is_ok());
         assert!(dest.exists());
 
@@ -992,7 +2249,7 @@ mod tests {
 
         File::create(&file_path).unwrap();
 
-        let result = copy_file(&file_path, &file_path);
+        let result = copy_file(&file_path, &file_path, ConflictOptions::default());
         assert!(result.is_err());
 
         cleanup_temp_dir(&temp_dir);
@@ -1007,13 +2264,84 @@ mod tests {
         File::create(&src).unwrap();
         File::create(&dest).unwrap();
 
-        let result = copy_file(&src, &dest);
+        let result = copy_file(&src, &dest, ConflictOptions::default());
         assert!(result.is_err());
         assert!(result.unwrap_err().kind() == std::io::ErrorKind::AlreadyExists);
 
         cleanup_temp_dir(&temp_dir);
     }
 
+    #[test]
+    fn test_copy_file_skip_leaves_destination_untouched() {
+        let temp_dir = create_temp_dir();
+        let src = temp_dir.join("src.txt");
+        let dest = temp_dir.join("dest.txt");
+
+        fs::write(&src, "new").unwrap();
+        fs::write(&dest, "original").unwrap();
+
+        let result = copy_file(&src, &dest, ConflictOptions { policy: ConflictPolicy::Skip });
+        assert!(result.is_ok());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "original");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_copy_file_overwrite_replaces_destination() {
+        let temp_dir = create_temp_dir();
+        let src = temp_dir.join("src.txt");
+        let dest = temp_dir.join("dest.txt");
+
+        fs::write(&src, "new").unwrap();
+        fs::write(&dest, "original").unwrap();
+
+        let result = copy_file(&src, &dest, ConflictOptions { policy: ConflictPolicy::Overwrite });
+        assert!(result.is_ok());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "new");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_copy_file_auto_rename_finds_free_name() {
+        let temp_dir = create_temp_dir();
+        let src = temp_dir.join("src.txt");
+        let dest = temp_dir.join("dest.txt");
+
+        fs::write(&src, "new").unwrap();
+        fs::write(&dest, "original").unwrap();
+
+        let result = copy_file(&src, &dest, ConflictOptions { policy: ConflictPolicy::AutoRename });
+        assert!(result.is_ok());
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "original");
+        assert_eq!(fs::read_to_string(temp_dir.join("dest (1).txt")).unwrap(), "new");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_copy_dir_recursive_merges_with_per_entry_policy() {
+        let temp_dir = create_temp_dir();
+        let src_dir = temp_dir.join("src_dir");
+        let dest_dir = temp_dir.join("dest_dir");
+
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::create_dir_all(&dest_dir).unwrap();
+        fs::write(src_dir.join("only_in_src.txt"), "src").unwrap();
+        fs::write(src_dir.join("conflict.txt"), "new").unwrap();
+        fs::write(dest_dir.join("only_in_dest.txt"), "dest").unwrap();
+        fs::write(dest_dir.join("conflict.txt"), "original").unwrap();
+
+        let result = copy_file(&src_dir, &dest_dir, ConflictOptions { policy: ConflictPolicy::Overwrite });
+        assert!(result.is_ok());
+        assert_eq!(fs::read_to_string(dest_dir.join("only_in_src.txt")).unwrap(), "src");
+        assert_eq!(fs::read_to_string(dest_dir.join("only_in_dest.txt")).unwrap(), "dest");
+        assert_eq!(fs::read_to_string(dest_dir.join("conflict.txt")).unwrap(), "new");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
     #[test]
     fn test_copy_dir_recursive() {
         let temp_dir = create_temp_dir();
@@ -1024,7 +2352,7 @@ mod tests {
         File::create(src_dir.join("file1.txt")).unwrap();
         File::create(src_dir.join("subdir/file2.txt")).unwrap();
 
-        let result = copy_file(&src_dir, &dest_dir);
+        let result = copy_file(&src_dir, &dest_dir, ConflictOptions::default());
         assert!(result.is_ok());
         assert!(dest_dir.exists());
         assert!(dest_dir.join("file1.txt").exists());
@@ -1050,7 +2378,7 @@ mod tests {
         std::os::unix::fs::symlink(&dir_a, dir_b.join("link_to_a")).unwrap();
 
         // This should detect the circular symlink
-        let result = copy_file(&dir_a, &dest);
+        let result = copy_file(&dir_a, &dest, ConflictOptions::default());
         // The copy should succeed since we don't follow symlinks into loops
         // (symlinks are copied as symlinks, not followed)
         assert!(result.is_ok());
@@ -1070,7 +2398,7 @@ mod tests {
         // Create symlink pointing to /etc (sensitive path)
         std::os::unix::fs::symlink("/etc", src_dir.join("sensitive_link")).unwrap();
 
-        let result = copy_file(&src_dir, &dest_dir);
+        let result = copy_file(&src_dir, &dest_dir, ConflictOptions::default());
         assert!(result.is_err());
 
         cleanup_temp_dir(&temp_dir);
@@ -1088,7 +2416,7 @@ mod tests {
         writeln!(file, "move content").unwrap();
         drop(file);
 
-        let result = move_file(&src, &dest);
+        let result = move_file(&src, &dest, ConflictOptions::default());
         assert!(result.is_ok());
         assert!(!src.exists());
         assert!(dest.exists());
@@ -1103,7 +2431,7 @@ mod tests {
 
         File::create(&file_path).unwrap();
 
-        let result = move_file(&file_path, &file_path);
+        let result = move_file(&file_path, &file_path, ConflictOptions::default());
         assert!(result.is_err());
 
         cleanup_temp_dir(&temp_dir);
@@ -1220,7 +2548,7 @@ mod tests {
 
         File::create(&old_path).unwrap();
 
-        let result = rename_file(&old_path, &new_path);
+        let result = rename_file(&old_path, &new_path, ConflictPolicy::Error);
         assert!(result.is_ok());
         assert!(!old_path.exists());
         assert!(new_path.exists());
@@ -1237,10 +2565,142 @@ mod tests {
         File::create(&old_path).unwrap();
         File::create(&new_path).unwrap();
 
-        let result = rename_file(&old_path, &new_path);
+        let result = rename_file(&old_path, &new_path, ConflictPolicy::Error);
         assert!(result.is_err());
         assert!(result.unwrap_err().kind() == std::io::ErrorKind::AlreadyExists);
 
         cleanup_temp_dir(&temp_dir);
     }
+
+    #[test]
+    fn test_unique_destination_appends_counter() {
+        let temp_dir = create_temp_dir();
+        let dest = temp_dir.join("file.txt");
+        File::create(&dest).unwrap();
+        File::create(temp_dir.join("file (1).txt")).unwrap();
+
+        let result = unique_destination(&dest);
+        assert_eq!(result, temp_dir.join("file (2).txt"));
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_unique_destination_no_extension() {
+        let temp_dir = create_temp_dir();
+        let dest = temp_dir.join("notes");
+        File::create(&dest).unwrap();
+
+        let result = unique_destination(&dest);
+        assert_eq!(result, temp_dir.join("notes (1)"));
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_apply_conflict_action_rename_uses_unique_destination() {
+        let temp_dir = create_temp_dir();
+        let src = temp_dir.join("src.txt");
+        let dest = temp_dir.join("dest.txt");
+        File::create(&src).unwrap();
+        File::create(&dest).unwrap();
+
+        let resolved = apply_conflict_action(ConflictAction::Rename, &src, &dest, false);
+        assert_eq!(resolved, Some(temp_dir.join("dest (1).txt")));
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_apply_conflict_action_overwrite_if_newer() {
+        let temp_dir = create_temp_dir();
+        let src = temp_dir.join("src.txt");
+        let dest = temp_dir.join("dest.txt");
+        File::create(&dest).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        File::create(&src).unwrap();
+
+        // src is newer than dest, so it should be allowed to overwrite.
+        let resolved = apply_conflict_action(ConflictAction::OverwriteIfNewer, &src, &dest, false);
+        assert_eq!(resolved, Some(dest.clone()));
+
+        // Reversed: dest is newer, so the conflict should resolve to skip.
+        let resolved = apply_conflict_action(ConflictAction::OverwriteIfNewer, &dest, &src, false);
+        assert_eq!(resolved, None);
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_backup_existing_numbers_sequentially() {
+        let temp_dir = create_temp_dir();
+        let path = temp_dir.join("file.txt");
+        fs::write(&path, "v1").unwrap();
+
+        let backup1 = backup_existing(&path).unwrap().unwrap();
+        assert_eq!(backup1, temp_dir.join("file.txt.~1~"));
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(&backup1).unwrap(), "v1");
+
+        fs::write(&path, "v2").unwrap();
+        let backup2 = backup_existing(&path).unwrap().unwrap();
+        assert_eq!(backup2, temp_dir.join("file.txt.~2~"));
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_backup_existing_missing_path_is_noop() {
+        let temp_dir = create_temp_dir();
+        let path = temp_dir.join("does_not_exist.txt");
+
+        assert!(backup_existing(&path).unwrap().is_none());
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_apply_conflict_action_overwrite_backs_up_destination() {
+        let temp_dir = create_temp_dir();
+        let src = temp_dir.join("src.txt");
+        let dest = temp_dir.join("dest.txt");
+        File::create(&src).unwrap();
+        fs::write(&dest, "old content").unwrap();
+
+        let resolved = apply_conflict_action(ConflictAction::Overwrite, &src, &dest, true);
+        assert_eq!(resolved, Some(dest.clone()));
+        assert_eq!(
+            fs::read_to_string(temp_dir.join("dest.txt.~1~")).unwrap(),
+            "old content"
+        );
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_verify_copy_matching_content_ok() {
+        let temp_dir = create_temp_dir();
+        let src = temp_dir.join("src.txt");
+        let dest = temp_dir.join("dest.txt");
+        fs::write(&src, "identical content").unwrap();
+        fs::write(&dest, "identical content").unwrap();
+
+        assert!(verify_copy(&src, &dest).is_ok());
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_verify_copy_mismatched_content_errors() {
+        let temp_dir = create_temp_dir();
+        let src = temp_dir.join("src.txt");
+        let dest = temp_dir.join("dest.txt");
+        fs::write(&src, "original content").unwrap();
+        fs::write(&dest, "corrupted content").unwrap();
+
+        let err = verify_copy(&src, &dest).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        cleanup_temp_dir(&temp_dir);
+    }
 }
@@ -0,0 +1,232 @@
+use std::path::PathBuf;
+
+/// A single mounted volume with its capacity snapshot.
+#[derive(Debug, Clone)]
+pub struct MountInfo {
+    pub device: String,
+    pub mountpoint: PathBuf,
+    pub fs_type: String,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    pub free_bytes: u64,
+    pub read_only: bool,
+}
+
+impl MountInfo {
+    /// Fraction of the volume currently used, in `0.0..=1.0`.
+    pub fn usage_ratio(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            self.used_bytes as f64 / self.total_bytes as f64
+        }
+    }
+}
+
+/// Pseudo/virtual filesystems that clutter `/proc/mounts` but aren't real
+/// storage volumes a user would want to browse or switch panels into.
+const IGNORED_FS_TYPES: &[&str] = &[
+    "proc", "sysfs", "devtmpfs", "devpts", "tmpfs", "cgroup", "cgroup2",
+    "pstore", "bpf", "tracefs", "debugfs", "mqueue", "hugetlbfs",
+    "binfmt_misc", "securityfs", "configfs", "autofs", "fusectl",
+];
+
+/// List currently mounted filesystems with live usage figures, falling back
+/// to an empty list if the mount table can't be read. A mount whose
+/// capacity lookup fails is left out of the list.
+pub fn list_mounts() -> Vec<MountInfo> {
+    get_mount_list_result().unwrap_or_default()
+}
+
+/// List currently mounted filesystems with error handling, reading
+/// `/proc/mounts` for the mount table and `statvfs` for capacity.
+#[cfg(target_os = "linux")]
+pub fn get_mount_list_result() -> Result<Vec<MountInfo>, String> {
+    let content = std::fs::read_to_string("/proc/mounts")
+        .map_err(|e| format!("Failed to read /proc/mounts: {}", e))?;
+
+    Ok(content
+        .lines()
+        .filter_map(parse_mount_line)
+        .filter(|m| !IGNORED_FS_TYPES.contains(&m.fs_type.as_str()))
+        .filter_map(fill_usage)
+        .collect())
+}
+
+/// List mounted volumes on Windows by enumerating logical drives and
+/// querying each with `GetDiskFreeSpaceExW`/`GetVolumeInformationW`, since
+/// there's no `/proc/mounts` equivalent to parse.
+#[cfg(windows)]
+pub fn get_mount_list_result() -> Result<Vec<MountInfo>, String> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+
+    extern "system" {
+        fn GetLogicalDrives() -> u32;
+        fn GetDiskFreeSpaceExW(
+            lp_directory_name: *const u16,
+            lp_free_bytes_available: *mut u64,
+            lp_total_number_of_bytes: *mut u64,
+            lp_total_number_of_free_bytes: *mut u64,
+        ) -> i32;
+        fn GetVolumeInformationW(
+            lp_root_path_name: *const u16,
+            lp_volume_name_buffer: *mut u16,
+            n_volume_name_size: u32,
+            lp_volume_serial_number: *mut u32,
+            lp_maximum_component_length: *mut u32,
+            lp_file_system_flags: *mut u32,
+            lp_file_system_name_buffer: *mut u16,
+            n_file_system_name_size: u32,
+        ) -> i32;
+        fn GetDriveTypeW(lp_root_path_name: *const u16) -> u32;
+    }
+
+    const DRIVE_REMOTE: u32 = 4;
+
+    fn to_wide(s: &str) -> Vec<u16> {
+        OsStr::new(s).encode_wide().chain(std::iter::once(0)).collect()
+    }
+
+    let mut mounts = Vec::new();
+    let bitmask = unsafe { GetLogicalDrives() };
+
+    for letter in 0..26u32 {
+        if bitmask & (1 << letter) == 0 {
+            continue;
+        }
+        let root = format!("{}:\\", (b'A' + letter as u8) as char);
+        let wide_root = to_wide(&root);
+
+        let mut free_available = 0u64;
+        let mut total_bytes = 0u64;
+        let mut total_free = 0u64;
+        let ok = unsafe {
+            GetDiskFreeSpaceExW(
+                wide_root.as_ptr(),
+                &mut free_available,
+                &mut total_bytes,
+                &mut total_free,
+            )
+        };
+        if ok == 0 {
+            // Usually an empty card reader or disconnected network drive letter.
+            continue;
+        }
+
+        let mut fs_name_buf = [0u16; 32];
+        let volume_ok = unsafe {
+            GetVolumeInformationW(
+                wide_root.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                fs_name_buf.as_mut_ptr(),
+                fs_name_buf.len() as u32,
+            )
+        };
+        let fs_type = if volume_ok != 0 {
+            let len = fs_name_buf
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(fs_name_buf.len());
+            String::from_utf16_lossy(&fs_name_buf[..len])
+        } else {
+            String::new()
+        };
+
+        let drive_type = unsafe { GetDriveTypeW(wide_root.as_ptr()) };
+        let device = if drive_type == DRIVE_REMOTE {
+            format!("{} (network)", root)
+        } else {
+            root.clone()
+        };
+
+        mounts.push(MountInfo {
+            device,
+            mountpoint: PathBuf::from(&root),
+            fs_type,
+            total_bytes,
+            used_bytes: total_bytes.saturating_sub(total_free),
+            free_bytes: free_available,
+            read_only: false,
+        });
+    }
+
+    Ok(mounts)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn get_mount_list_result() -> Result<Vec<MountInfo>, String> {
+    // No portable mount-table source on this platform yet.
+    Ok(Vec::new())
+}
+
+#[cfg(target_os = "linux")]
+fn parse_mount_line(line: &str) -> Option<MountInfo> {
+    let mut fields = line.split_whitespace();
+    let device = unescape_octal(fields.next()?);
+    let mountpoint = PathBuf::from(unescape_octal(fields.next()?));
+    let fs_type = fields.next()?.to_string();
+    let options = fields.next()?;
+    let read_only = options.split(',').any(|opt| opt == "ro");
+
+    Some(MountInfo {
+        device,
+        mountpoint,
+        fs_type,
+        total_bytes: 0,
+        used_bytes: 0,
+        free_bytes: 0,
+        read_only,
+    })
+}
+
+/// `/proc/mounts` escapes spaces, tabs, backslashes and newlines as octal
+/// `\xxx` sequences so paths embedding them parse as a single field.
+#[cfg(target_os = "linux")]
+fn unescape_octal(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = String::with_capacity(s.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() {
+            if let Ok(code) = u8::from_str_radix(&s[i + 1..i + 4], 8) {
+                out.push(code as char);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i] as char);
+        i += 1;
+    }
+    out
+}
+
+#[cfg(target_os = "linux")]
+fn fill_usage(mut mount: MountInfo) -> Option<MountInfo> {
+    use std::ffi::CString;
+    use std::mem::MaybeUninit;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(mount.mountpoint.as_os_str().as_bytes()).ok()?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    let ret = unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) };
+    if ret != 0 {
+        return None;
+    }
+    let stat = unsafe { stat.assume_init() };
+
+    let block_size = stat.f_frsize as u64;
+    let total_bytes = stat.f_blocks as u64 * block_size;
+    let free_bytes = stat.f_bavail as u64 * block_size;
+    let reserved_free_bytes = stat.f_bfree as u64 * block_size;
+
+    mount.total_bytes = total_bytes;
+    mount.free_bytes = free_bytes;
+    mount.used_bytes = total_bytes.saturating_sub(reserved_free_bytes);
+
+    Some(mount)
+}
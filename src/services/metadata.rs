@@ -0,0 +1,143 @@
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+/// EXIF fields surfaced in the file-info screen. Every field is optional
+/// since cameras/editors populate EXIF tags inconsistently.
+#[derive(Debug, Clone, Default)]
+pub struct ExifInfo {
+    pub camera_model: Option<String>,
+    pub captured_at: Option<String>,
+    pub dimensions: Option<(u32, u32)>,
+    pub orientation: Option<u32>,
+    /// (latitude, longitude) in decimal degrees.
+    pub gps: Option<(f64, f64)>,
+}
+
+impl ExifInfo {
+    fn is_empty(&self) -> bool {
+        self.camera_model.is_none()
+            && self.captured_at.is_none()
+            && self.dimensions.is_none()
+            && self.orientation.is_none()
+            && self.gps.is_none()
+    }
+}
+
+/// Extensions EXIF extraction is attempted for. Other image formats (PNG,
+/// GIF, WebP, ...) don't carry EXIF and fall back to basic metadata.
+const EXIF_EXTENSIONS: &[&str] = &["jpg", "jpeg", "tiff", "tif", "heic", "heif"];
+
+pub fn supports_exif(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| EXIF_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Read EXIF metadata from `path`. Returns `None` if the file has no EXIF
+/// segment or isn't a format we attempt to parse - the caller should fall
+/// back to basic filesystem metadata in that case.
+pub fn read_exif(path: &Path) -> Option<ExifInfo> {
+    if !supports_exif(path) {
+        return None;
+    }
+
+    let file = File::open(path).ok()?;
+    let mut reader = BufReader::new(file);
+    let exif = exif::Reader::new()
+        .read_from_container(&mut reader)
+        .ok()?;
+
+    let info = ExifInfo {
+        camera_model: field_string(&exif, exif::Tag::Model),
+        captured_at: field_string(&exif, exif::Tag::DateTimeOriginal)
+            .or_else(|| field_string(&exif, exif::Tag::DateTime)),
+        dimensions: field_u32(&exif, exif::Tag::PixelXDimension)
+            .zip(field_u32(&exif, exif::Tag::PixelYDimension)),
+        orientation: field_u32(&exif, exif::Tag::Orientation),
+        gps: read_gps(&exif),
+    };
+
+    if info.is_empty() {
+        None
+    } else {
+        Some(info)
+    }
+}
+
+fn field_string(exif: &exif::Exif, tag: exif::Tag) -> Option<String> {
+    exif.get_field(tag, exif::In::PRIMARY)
+        .map(|f| f.display_value().to_string())
+}
+
+fn field_u32(exif: &exif::Exif, tag: exif::Tag) -> Option<u32> {
+    exif.get_field(tag, exif::In::PRIMARY)
+        .and_then(|f| f.value.get_uint(0))
+}
+
+fn read_gps(exif: &exif::Exif) -> Option<(f64, f64)> {
+    let lat = gps_coord(exif, exif::Tag::GPSLatitude, exif::Tag::GPSLatitudeRef, "S")?;
+    let lon = gps_coord(exif, exif::Tag::GPSLongitude, exif::Tag::GPSLongitudeRef, "W")?;
+    Some((lat, lon))
+}
+
+fn gps_coord(
+    exif: &exif::Exif,
+    value_tag: exif::Tag,
+    ref_tag: exif::Tag,
+    negative_ref: &str,
+) -> Option<f64> {
+    use exif::Value;
+
+    let field = exif.get_field(value_tag, exif::In::PRIMARY)?;
+    let rationals = match &field.value {
+        Value::Rational(v) if v.len() == 3 => v,
+        _ => return None,
+    };
+
+    let degrees = rationals[0].to_f64();
+    let minutes = rationals[1].to_f64();
+    let seconds = rationals[2].to_f64();
+    let mut decimal = degrees + minutes / 60.0 + seconds / 3600.0;
+
+    if let Some(reference) = exif.get_field(ref_tag, exif::In::PRIMARY) {
+        if reference.display_value().to_string().trim() == negative_ref {
+            decimal = -decimal;
+        }
+    }
+
+    Some(decimal)
+}
+
+/// Content hashes computed for a file, used to compare or verify copies.
+#[derive(Debug, Clone)]
+pub struct FileHashes {
+    pub md5: String,
+    pub sha256: String,
+}
+
+const HASH_BUFFER_SIZE: usize = 256 * 1024;
+
+/// Stream `path` through MD5 and SHA-256 in a single pass.
+pub fn compute_hashes(path: &Path) -> io::Result<FileHashes> {
+    let mut file = File::open(path)?;
+    let mut md5_ctx = md5::Context::new();
+    let mut sha256_ctx = Sha256::new();
+
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        md5_ctx.consume(&buffer[..read]);
+        sha256_ctx.update(&buffer[..read]);
+    }
+
+    Ok(FileHashes {
+        md5: format!("{:x}", md5_ctx.compute()),
+        sha256: format!("{:x}", sha256_ctx.finalize()),
+    })
+}
@@ -0,0 +1,125 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of directories kept in the ring before the least recently
+/// visited is dropped.
+const MAX_ENTRIES: usize = 50;
+
+fn app_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cokacdir"))
+}
+
+fn path_history_file() -> Option<PathBuf> {
+    app_config_dir().map(|dir| dir.join("path_history"))
+}
+
+/// One remembered directory and how many times it's been visited. Position
+/// in `PathHistory::entries` (front = most recent) carries the recency
+/// half of the ranking in `ranked`; `visits` carries the frequency half.
+#[derive(Debug, Clone)]
+struct PathVisit {
+    path: String,
+    visits: u32,
+}
+
+/// A per-session, optionally persisted ring of recently visited
+/// directories, offered by the Goto dialog (see `dialogs::history_suggestions`
+/// and the ghost-suggestion logic in `dialogs::compute_history_ghost`) the same
+/// way `SearchHistory` backs the Find File dialog's recent queries. Loaded
+/// once at startup and persisted back to disk on every `record`.
+#[derive(Debug, Clone, Default)]
+pub struct PathHistory {
+    entries: Vec<PathVisit>,
+}
+
+impl PathHistory {
+    /// Load the ring from the config file. Returns an empty history if the
+    /// file doesn't exist yet or fails to parse.
+    pub fn load() -> Self {
+        let path = match path_history_file() {
+            Some(p) => p,
+            None => return Self::default(),
+        };
+
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+
+        let mut entries = Vec::new();
+        for line in content.lines() {
+            let mut parts = line.splitn(2, '\t');
+            let Some(visits_str) = parts.next() else { continue };
+            let Some(path_str) = parts.next() else { continue };
+            if let Ok(visits) = visits_str.parse::<u32>() {
+                entries.push(PathVisit { path: path_str.to_string(), visits });
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Record a visit to `dir`: dedupe against any existing entry (bumping
+    /// its visit count) and move it to the front, or insert it fresh with
+    /// a count of 1. Trims to `MAX_ENTRIES` and persists.
+    pub fn record(&mut self, dir: &Path) {
+        let path_str = dir.display().to_string();
+        if path_str.is_empty() {
+            return;
+        }
+
+        let visits = match self.entries.iter().position(|e| e.path == path_str) {
+            Some(idx) => self.entries.remove(idx).visits.saturating_add(1),
+            None => 1,
+        };
+        self.entries.insert(0, PathVisit { path: path_str, visits });
+        self.entries.truncate(MAX_ENTRIES);
+        self.save();
+    }
+
+    /// Directories ranked by recency (position in the ring) plus visit
+    /// frequency, most relevant first. Recency decays linearly from the
+    /// front of the ring; each visit adds a flat bonus so a frequently
+    /// revisited directory can outrank one visited only slightly more
+    /// recently.
+    pub fn ranked(&self) -> Vec<&str> {
+        let len = self.entries.len();
+        let mut scored: Vec<(i64, &str)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let recency_score = (len - i) as i64;
+                let frequency_score = e.visits as i64 * 2;
+                (recency_score + frequency_score, e.path.as_str())
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, path)| path).collect()
+    }
+
+    /// Persist the ring to the config file, one `visits<TAB>path` entry
+    /// per line, most-recent-first. Best-effort: write failures are
+    /// silently ignored, same as `Bookmarks`/`SearchHistory`.
+    fn save(&self) {
+        let path = match path_history_file() {
+            Some(p) => p,
+            None => return,
+        };
+        if let Some(parent) = path.parent() {
+            if fs::create_dir_all(parent).is_err() {
+                return;
+            }
+        }
+
+        let mut content = String::new();
+        for entry in &self.entries {
+            content.push_str(&entry.visits.to_string());
+            content.push('\t');
+            content.push_str(&entry.path);
+            content.push('\n');
+        }
+
+        let _ = fs::write(path, content);
+    }
+}
@@ -1,4 +1,7 @@
 use std::process::Command;
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortField {
@@ -126,7 +129,7 @@ fn parse_process_line(line: &str) -> Option<ProcessInfo> {
 
 /// Get process start time from /proc/[pid]/stat for additional PID validation
 #[cfg(target_os = "linux")]
-fn get_process_starttime(pid: i32) -> Option<u64> {
+pub fn get_process_starttime(pid: i32) -> Option<u64> {
     let stat_path = format!("/proc/{}/stat", pid);
     let content = std::fs::read_to_string(stat_path).ok()?;
 
@@ -162,6 +165,51 @@ fn verify_process_identity(_pid: i32, _saved_starttime: Option<u64>) -> Result<(
     Ok(())
 }
 
+/// Get process start time for additional PID validation. Always `None` on
+/// platforms without `/proc`.
+#[cfg(not(target_os = "linux"))]
+pub fn get_process_starttime(_pid: i32) -> Option<u64> {
+    None
+}
+
+/// A POSIX signal that can be dispatched to a process, restricted to the
+/// subset that's useful from the process view (not e.g. `SIGSEGV`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// Hangup - conventionally asks a daemon to reload its config.
+    Hup,
+    Int,
+    Quit,
+    /// User-defined signal 1 - app-specific handler.
+    Usr1,
+    /// User-defined signal 2 - app-specific handler.
+    Usr2,
+    /// Graceful termination (the default for `kill_process`).
+    Term,
+    /// Immediate, unblockable termination (the default for `force_kill_process`).
+    Kill,
+    /// Suspend the process until `Cont` is sent.
+    Stop,
+    /// Resume a process suspended with `Stop`.
+    Cont,
+}
+
+impl Signal {
+    fn as_raw(self) -> libc::c_int {
+        match self {
+            Signal::Hup => libc::SIGHUP,
+            Signal::Int => libc::SIGINT,
+            Signal::Quit => libc::SIGQUIT,
+            Signal::Usr1 => libc::SIGUSR1,
+            Signal::Usr2 => libc::SIGUSR2,
+            Signal::Term => libc::SIGTERM,
+            Signal::Kill => libc::SIGKILL,
+            Signal::Stop => libc::SIGSTOP,
+            Signal::Cont => libc::SIGCONT,
+        }
+    }
+}
+
 /// Kill a process by PID
 pub fn kill_process(pid: i32) -> Result<(), String> {
     kill_process_with_verification(pid, None)
@@ -169,32 +217,7 @@ pub fn kill_process(pid: i32) -> Result<(), String> {
 
 /// Kill a process by PID with optional starttime verification
 pub fn kill_process_with_verification(pid: i32, starttime: Option<u64>) -> Result<(), String> {
-    if !is_valid_pid(pid) {
-        return Err("Invalid PID".to_string());
-    }
-
-    // Get process info to check if it's a kernel thread
-    let command = get_process_command(pid);
-    is_protected_pid(pid, command.as_deref())?;
-
-    // Verify process identity if starttime is provided (Linux only)
-    #[cfg(target_os = "linux")]
-    verify_process_identity(pid, starttime)?;
-    #[cfg(not(target_os = "linux"))]
-    let _ = starttime; // Suppress unused warning
-
-    // Use libc kill for safety
-    let result = unsafe { libc::kill(pid, libc::SIGTERM) };
-    if result == 0 {
-        Ok(())
-    } else {
-        let errno = std::io::Error::last_os_error();
-        match errno.raw_os_error() {
-            Some(libc::ESRCH) => Err("Process not found".to_string()),
-            Some(libc::EPERM) => Err("Permission denied".to_string()),
-            _ => Err(errno.to_string()),
-        }
-    }
+    send_signal(pid, Signal::Term, starttime)
 }
 
 /// Force kill a process by PID (SIGKILL)
@@ -204,29 +227,54 @@ pub fn force_kill_process(pid: i32) -> Result<(), String> {
 
 /// Force kill a process by PID (SIGKILL) with optional starttime verification
 pub fn force_kill_process_with_verification(pid: i32, starttime: Option<u64>) -> Result<(), String> {
+    send_signal(pid, Signal::Kill, starttime)
+}
+
+/// Send an arbitrary POSIX signal to a process, running it through the same
+/// guards as `kill_process`/`force_kill_process`: PID sanity, the protected
+/// PID list, and (on Linux) starttime re-verification to defeat PID reuse.
+///
+/// Every outcome - refused, failed, or successful - is recorded as a
+/// structured `tracing` event so there's an after-the-fact record of which
+/// processes were signaled and why a refusal happened, even though the TUI
+/// only ever shows the caller a short message.
+pub fn send_signal(pid: i32, signal: Signal, starttime: Option<u64>) -> Result<(), String> {
     if !is_valid_pid(pid) {
+        warn!(pid, ?signal, reason = "invalid_pid", "signal refused");
         return Err("Invalid PID".to_string());
     }
 
+    // Get process info to check if it's a kernel thread
     let command = get_process_command(pid);
-    is_protected_pid(pid, command.as_deref())?;
+    if let Err(reason) = is_protected_pid(pid, command.as_deref()) {
+        warn!(pid, ?signal, command = ?command, %reason, "signal refused: protected pid");
+        return Err(reason);
+    }
 
     // Verify process identity if starttime is provided (Linux only)
     #[cfg(target_os = "linux")]
-    verify_process_identity(pid, starttime)?;
+    if let Err(reason) = verify_process_identity(pid, starttime) {
+        warn!(pid, ?signal, saved_starttime = ?starttime, %reason, "signal refused: pid reuse");
+        return Err(reason);
+    }
     #[cfg(not(target_os = "linux"))]
     let _ = starttime; // Suppress unused warning
 
-    let result = unsafe { libc::kill(pid, libc::SIGKILL) };
+    // Use libc kill for safety
+    let result = unsafe { libc::kill(pid, signal.as_raw()) };
     if result == 0 {
+        info!(pid, ?signal, command = ?command, "process signaled");
         Ok(())
     } else {
         let errno = std::io::Error::last_os_error();
-        match errno.raw_os_error() {
-            Some(libc::ESRCH) => Err("Process not found".to_string()),
-            Some(libc::EPERM) => Err("Permission denied".to_string()),
-            _ => Err(errno.to_string()),
-        }
+        let raw = errno.raw_os_error();
+        let message = match raw {
+            Some(libc::ESRCH) => "Process not found".to_string(),
+            Some(libc::EPERM) => "Permission denied".to_string(),
+            _ => errno.to_string(),
+        };
+        warn!(pid, ?signal, errno = ?raw, %message, "signal failed");
+        Err(message)
     }
 }
 
@@ -245,3 +293,146 @@ fn get_process_command(pid: i32) -> Option<String> {
         Some(command.to_string())
     }
 }
+
+/// CPU usage (percent) at or above which a row is highlighted as a runaway
+/// process in the monitor screen.
+pub const HIGH_CPU_THRESHOLD: f32 = 80.0;
+
+/// Memory usage (percent of total RAM) at or above which a row is
+/// highlighted in the monitor screen.
+pub const HIGH_MEM_THRESHOLD: f32 = 50.0;
+
+/// Whether a process's CPU or memory usage has crossed the highlight
+/// threshold.
+pub fn is_high_usage(proc: &ProcessInfo) -> bool {
+    proc.cpu >= HIGH_CPU_THRESHOLD || proc.mem >= HIGH_MEM_THRESHOLD
+}
+
+/// Live view over the process list for the process monitor screen: holds
+/// the current snapshot plus sort/filter/refresh state so the screen can
+/// auto-refresh on a timer instead of only on a manual keypress, mirroring
+/// how `Bookmarks` wraps what used to be scattered fields.
+#[derive(Debug, Clone)]
+pub struct ProcessMonitorState {
+    pub processes: Vec<ProcessInfo>,
+    pub selected_index: usize,
+    pub sort_field: SortField,
+    pub sort_ascending: bool,
+    pub refresh_interval: Duration,
+    /// Command substring filter; empty shows every process.
+    pub filter: String,
+    /// Whether the filter text box is currently accepting input.
+    pub filter_active: bool,
+    /// PID and captured starttime of the process pending a kill
+    /// confirmation. The starttime is re-checked at kill time to defeat PID
+    /// reuse (see `kill_process_with_verification`).
+    pub confirm_kill: Option<(i32, Option<u64>)>,
+    pub force_kill: bool,
+    last_refresh: Instant,
+}
+
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+const REFRESH_STEP: Duration = Duration::from_millis(500);
+
+impl ProcessMonitorState {
+    /// An empty, unrefreshed state - no `ps` invocation happens until
+    /// `refresh`/`tick` is called, so constructing this has no side effect.
+    pub fn new() -> Self {
+        Self {
+            processes: Vec::new(),
+            selected_index: 0,
+            sort_field: SortField::Cpu,
+            sort_ascending: false,
+            refresh_interval: Duration::from_secs(2),
+            filter: String::new(),
+            filter_active: false,
+            confirm_kill: None,
+            force_kill: false,
+            last_refresh: Instant::now(),
+        }
+    }
+
+    /// Re-fetch the process list if `refresh_interval` has elapsed since
+    /// the last refresh. No-op otherwise; call this once per UI tick.
+    pub fn tick(&mut self) {
+        if self.last_refresh.elapsed() >= self.refresh_interval {
+            self.refresh();
+        }
+    }
+
+    /// Unconditionally re-fetch the process list and re-apply the current
+    /// sort.
+    pub fn refresh(&mut self) {
+        self.processes = get_process_list_result().unwrap_or_default();
+        self.last_refresh = Instant::now();
+        self.apply_sort();
+    }
+
+    /// Processes matching `filter` as a case-insensitive command substring,
+    /// in the current sort order. Returns every process when the filter is
+    /// empty.
+    pub fn visible(&self) -> Vec<&ProcessInfo> {
+        if self.filter.is_empty() {
+            self.processes.iter().collect()
+        } else {
+            let needle = self.filter.to_lowercase();
+            self.processes
+                .iter()
+                .filter(|p| p.command.to_lowercase().contains(&needle))
+                .collect()
+        }
+    }
+
+    /// Change the active sort field, reversing order if it's already the
+    /// active field (same toggle-then-reverse behavior as before).
+    pub fn set_sort(&mut self, field: SortField) {
+        if self.sort_field == field {
+            self.sort_ascending = !self.sort_ascending;
+        } else {
+            self.sort_field = field;
+            self.sort_ascending = field == SortField::Pid || field == SortField::Command;
+        }
+        self.apply_sort();
+    }
+
+    fn apply_sort(&mut self) {
+        let field = self.sort_field;
+        let ascending = self.sort_ascending;
+        self.processes.sort_by(|a, b| {
+            let cmp = match field {
+                SortField::Pid => a.pid.cmp(&b.pid),
+                SortField::Cpu => a.cpu.partial_cmp(&b.cpu).unwrap_or(std::cmp::Ordering::Equal),
+                SortField::Mem => a.mem.partial_cmp(&b.mem).unwrap_or(std::cmp::Ordering::Equal),
+                SortField::Command => a.command.cmp(&b.command),
+            };
+            if ascending { cmp } else { cmp.reverse() }
+        });
+    }
+
+    /// Speed up or slow down the refresh timer by one step, clamped to a
+    /// sane range so the monitor can't be set to hammer `ps` every tick.
+    pub fn adjust_refresh_interval(&mut self, faster: bool) {
+        self.refresh_interval = if faster {
+            self.refresh_interval.saturating_sub(REFRESH_STEP).max(MIN_REFRESH_INTERVAL)
+        } else {
+            (self.refresh_interval + REFRESH_STEP).min(MAX_REFRESH_INTERVAL)
+        };
+    }
+
+    /// Arm the kill confirmation for the currently selected process,
+    /// capturing its starttime so the eventual kill can detect PID reuse.
+    pub fn request_kill(&mut self, force: bool) {
+        if let Some(proc) = self.visible().get(self.selected_index).copied() {
+            let starttime = get_process_starttime(proc.pid);
+            self.confirm_kill = Some((proc.pid, starttime));
+            self.force_kill = force;
+        }
+    }
+}
+
+impl Default for ProcessMonitorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
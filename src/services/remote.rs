@@ -0,0 +1,305 @@
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Protocol used to reach a mounted remote panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteProtocol {
+    Sftp,
+    Ftp,
+}
+
+impl RemoteProtocol {
+    fn default_port(self) -> u16 {
+        match self {
+            RemoteProtocol::Sftp => 22,
+            RemoteProtocol::Ftp => 21,
+        }
+    }
+}
+
+/// A parsed `sftp://` or `ftp://` connection target, as typed into the
+/// Connect dialog.
+#[derive(Debug, Clone)]
+pub struct RemoteTarget {
+    pub protocol: RemoteProtocol,
+    pub user: Option<String>,
+    pub host: String,
+    pub port: u16,
+    pub path: PathBuf,
+}
+
+/// Parse a `sftp://[user@]host[:port][/path]` or equivalent `ftp://` URL.
+/// Returns `None` if `url` doesn't start with a recognized scheme or the
+/// host portion is empty.
+pub fn parse_remote_url(url: &str) -> Option<RemoteTarget> {
+    let (protocol, rest) = if let Some(rest) = url.strip_prefix("sftp://") {
+        (RemoteProtocol::Sftp, rest)
+    } else if let Some(rest) = url.strip_prefix("ftp://") {
+        (RemoteProtocol::Ftp, rest)
+    } else {
+        return None;
+    };
+
+    let (authority, path) = match rest.split_once('/') {
+        Some((authority, path)) => (authority, format!("/{path}")),
+        None => (rest, "/".to_string()),
+    };
+
+    let (user, host_port) = match authority.split_once('@') {
+        Some((user, host_port)) => (Some(user.to_string()), host_port),
+        None => (None, authority),
+    };
+
+    let (host, port) = match host_port.split_once(':') {
+        Some((host, port)) => (host.to_string(), port.parse().ok()?),
+        None => (host_port.to_string(), protocol.default_port()),
+    };
+
+    if host.is_empty() {
+        return None;
+    }
+
+    Some(RemoteTarget { protocol, user, host, port, path: PathBuf::from(path) })
+}
+
+/// One entry in a remote directory listing.
+#[derive(Debug, Clone)]
+pub struct RemoteEntry {
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// A live connection to a remote host, reached over SFTP or FTP. Wraps
+/// whichever backend matches the target's protocol behind the small set
+/// of operations a panel needs (list/read/write/remove/mkdir).
+pub enum RemoteSession {
+    Sftp(SftpSession),
+    Ftp(FtpSession),
+}
+
+impl RemoteSession {
+    /// Connect to `target` and authenticate with `password` (used as the
+    /// SFTP password fallback when no agent/key is configured, and as the
+    /// FTP password unconditionally).
+    pub fn connect(target: &RemoteTarget, password: &str) -> io::Result<Self> {
+        match target.protocol {
+            RemoteProtocol::Sftp => Ok(Self::Sftp(SftpSession::connect(target, password)?)),
+            RemoteProtocol::Ftp => Ok(Self::Ftp(FtpSession::connect(target, password)?)),
+        }
+    }
+
+    pub fn list_dir(&mut self, path: &Path) -> io::Result<Vec<RemoteEntry>> {
+        match self {
+            Self::Sftp(s) => s.list_dir(path),
+            Self::Ftp(s) => s.list_dir(path),
+        }
+    }
+
+    pub fn read_file(&mut self, path: &Path) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Sftp(s) => s.read_file(path),
+            Self::Ftp(s) => s.read_file(path),
+        }
+    }
+
+    pub fn write_file(&mut self, path: &Path, data: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Sftp(s) => s.write_file(path, data),
+            Self::Ftp(s) => s.write_file(path, data),
+        }
+    }
+
+    pub fn remove_file(&mut self, path: &Path) -> io::Result<()> {
+        match self {
+            Self::Sftp(s) => s.remove_file(path),
+            Self::Ftp(s) => s.remove_file(path),
+        }
+    }
+
+    pub fn mkdir(&mut self, path: &Path) -> io::Result<()> {
+        match self {
+            Self::Sftp(s) => s.mkdir(path),
+            Self::Ftp(s) => s.mkdir(path),
+        }
+    }
+}
+
+// `ssh2::Session`/`suppaftp::FtpStream` don't implement `Debug`; the panel
+// that owns a session only ever needs to know that one is mounted.
+impl std::fmt::Debug for RemoteSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sftp(_) => write!(f, "RemoteSession::Sftp"),
+            Self::Ftp(_) => write!(f, "RemoteSession::Ftp"),
+        }
+    }
+}
+
+/// SFTP backend, built on an authenticated `ssh2` session.
+pub struct SftpSession {
+    sess: ssh2::Session,
+}
+
+impl SftpSession {
+    fn connect(target: &RemoteTarget, password: &str) -> io::Result<Self> {
+        let tcp = std::net::TcpStream::connect((target.host.as_str(), target.port))?;
+        let mut sess = ssh2::Session::new().map_err(to_io_err)?;
+        sess.set_tcp_stream(tcp);
+        sess.handshake().map_err(to_io_err)?;
+
+        let user = target.user.as_deref().unwrap_or("anonymous");
+        // Try the keys offered by the running user's SSH agent first and
+        // fall back to the password, mirroring how most SFTP clients probe
+        // auth methods.
+        if sess.userauth_agent(user).is_err() {
+            sess.userauth_password(user, password).map_err(to_io_err)?;
+        }
+        if !sess.authenticated() {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, "Authentication failed"));
+        }
+
+        Ok(Self { sess })
+    }
+
+    fn sftp(&self) -> io::Result<ssh2::Sftp> {
+        self.sess.sftp().map_err(to_io_err)
+    }
+
+    fn list_dir(&mut self, path: &Path) -> io::Result<Vec<RemoteEntry>> {
+        let sftp = self.sftp()?;
+        let entries = sftp.readdir(path).map_err(to_io_err)?;
+        Ok(entries
+            .into_iter()
+            .map(|(entry_path, stat)| RemoteEntry {
+                name: entry_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                is_dir: stat.is_dir(),
+                size: stat.size.unwrap_or(0),
+                modified: stat.mtime.map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+            })
+            .collect())
+    }
+
+    fn read_file(&mut self, path: &Path) -> io::Result<Vec<u8>> {
+        let sftp = self.sftp()?;
+        let mut file = sftp.open(path).map_err(to_io_err)?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let sftp = self.sftp()?;
+        let mut file = sftp.create(path).map_err(to_io_err)?;
+        file.write_all(data)
+    }
+
+    fn remove_file(&mut self, path: &Path) -> io::Result<()> {
+        self.sftp()?.unlink(path).map_err(to_io_err)
+    }
+
+    fn mkdir(&mut self, path: &Path) -> io::Result<()> {
+        self.sftp()?.mkdir(path, 0o755).map_err(to_io_err)
+    }
+}
+
+fn to_io_err(e: ssh2::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e.to_string())
+}
+
+/// FTP backend, built on a logged-in `suppaftp` stream.
+pub struct FtpSession {
+    stream: suppaftp::FtpStream,
+}
+
+impl FtpSession {
+    fn connect(target: &RemoteTarget, password: &str) -> io::Result<Self> {
+        let mut stream = suppaftp::FtpStream::connect((target.host.as_str(), target.port))
+            .map_err(to_ftp_err)?;
+        let user = target.user.as_deref().unwrap_or("anonymous");
+        stream.login(user, password).map_err(to_ftp_err)?;
+        Ok(Self { stream })
+    }
+
+    fn list_dir(&mut self, path: &Path) -> io::Result<Vec<RemoteEntry>> {
+        let lines = self
+            .stream
+            .list(Some(&path.to_string_lossy()))
+            .map_err(to_ftp_err)?;
+        Ok(lines.iter().filter_map(|line| parse_list_line(line)).collect())
+    }
+
+    fn read_file(&mut self, path: &Path) -> io::Result<Vec<u8>> {
+        self.stream
+            .retr_as_buffer(&path.to_string_lossy())
+            .map(|cursor| cursor.into_inner())
+            .map_err(to_ftp_err)
+    }
+
+    fn write_file(&mut self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let mut cursor = io::Cursor::new(data.to_vec());
+        self.stream
+            .put_file(&path.to_string_lossy(), &mut cursor)
+            .map(|_| ())
+            .map_err(to_ftp_err)
+    }
+
+    fn remove_file(&mut self, path: &Path) -> io::Result<()> {
+        self.stream.rm(&path.to_string_lossy()).map_err(to_ftp_err)
+    }
+
+    fn mkdir(&mut self, path: &Path) -> io::Result<()> {
+        self.stream.mkdir(&path.to_string_lossy()).map_err(to_ftp_err)
+    }
+}
+
+fn to_ftp_err(e: suppaftp::FtpError) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e.to_string())
+}
+
+/// Parse one line of a Unix `ls -l`-style `LIST` response. FTP has no
+/// universally supported machine-readable listing format, so this covers
+/// the common case most servers emit and skips anything else.
+fn parse_list_line(line: &str) -> Option<RemoteEntry> {
+    let mut fields = line.split_whitespace();
+    let perms = fields.next()?;
+    let is_dir = perms.starts_with('d');
+    let size: u64 = fields.nth(3)?.parse().ok()?;
+    let name = line.rsplit(' ').next()?.to_string();
+    Some(RemoteEntry { name, is_dir, size, modified: None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sftp_url_full() {
+        let target = parse_remote_url("sftp://alice@example.com:2222/home/alice").unwrap();
+        assert_eq!(target.protocol, RemoteProtocol::Sftp);
+        assert_eq!(target.user.as_deref(), Some("alice"));
+        assert_eq!(target.host, "example.com");
+        assert_eq!(target.port, 2222);
+        assert_eq!(target.path, PathBuf::from("/home/alice"));
+    }
+
+    #[test]
+    fn test_parse_ftp_url_default_port_and_path() {
+        let target = parse_remote_url("ftp://example.com").unwrap();
+        assert_eq!(target.protocol, RemoteProtocol::Ftp);
+        assert_eq!(target.user, None);
+        assert_eq!(target.port, 21);
+        assert_eq!(target.path, PathBuf::from("/"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_scheme() {
+        assert!(parse_remote_url("http://example.com").is_none());
+        assert!(parse_remote_url("not a url").is_none());
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Number of raw queries kept in the recent ring before the oldest is
+/// dropped.
+const MAX_RECENT: usize = 20;
+
+fn app_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cokacdir"))
+}
+
+fn search_history_path() -> Option<PathBuf> {
+    app_config_dir().map(|dir| dir.join("search_history"))
+}
+
+/// Saved advanced-search filters: named presets the user explicitly saves,
+/// plus an automatically maintained ring of the raw input fields for the
+/// last `MAX_RECENT` executed queries. Loaded once at startup and persisted
+/// back to disk on every change, same as `Bookmarks`.
+///
+/// Each entry is the dialog's six raw field strings (`AdvancedSearchState
+/// ::values`) rather than a parsed `SearchCriteria`, so recalling one just
+/// repopulates the fields verbatim with no re-formatting needed.
+#[derive(Debug, Clone, Default)]
+pub struct SearchHistory {
+    presets: Vec<(String, [String; 6])>,
+    recent: Vec<[String; 6]>,
+}
+
+impl SearchHistory {
+    /// Load saved presets and recent queries from the config file. Returns
+    /// an empty history if the file doesn't exist yet or fails to parse.
+    pub fn load() -> Self {
+        let path = match search_history_path() {
+            Some(p) => p,
+            None => return Self::default(),
+        };
+
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+
+        let mut presets = Vec::new();
+        let mut recent = Vec::new();
+
+        for line in content.lines() {
+            let mut parts = line.split('\t');
+            match parts.next() {
+                Some("P") => {
+                    let Some(name) = parts.next() else { continue };
+                    if let Some(values) = parse_values(parts) {
+                        presets.push((name.to_string(), values));
+                    }
+                }
+                Some("R") => {
+                    if let Some(values) = parse_values(parts) {
+                        recent.push(values);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self { presets, recent }
+    }
+
+    /// Saved presets, in insertion order.
+    pub fn presets(&self) -> &[(String, [String; 6])] {
+        &self.presets
+    }
+
+    /// Recent queries, most-recently-executed first.
+    pub fn recent(&self) -> &[[String; 6]] {
+        &self.recent
+    }
+
+    /// Save `values` under `name`, replacing any existing preset with the
+    /// same name, then persist to disk.
+    pub fn save_preset(&mut self, name: String, values: [String; 6]) {
+        self.presets.retain(|(n, _)| *n != name);
+        self.presets.push((name, values));
+        self.save();
+    }
+
+    /// Remove the preset saved under `name`, if any, then persist to disk.
+    pub fn remove_preset(&mut self, name: &str) {
+        self.presets.retain(|(n, _)| n != name);
+        self.save();
+    }
+
+    /// Record an executed query at the front of the recent ring, dropping
+    /// a duplicate of the same query if present and trimming the ring to
+    /// `MAX_RECENT`, then persist to disk.
+    pub fn push_recent(&mut self, values: [String; 6]) {
+        self.recent.retain(|v| v != &values);
+        self.recent.insert(0, values);
+        self.recent.truncate(MAX_RECENT);
+        self.save();
+    }
+
+    /// Persist presets and recent queries to the config file, one entry
+    /// per line (`P<TAB>name<TAB>f0..f5` or `R<TAB>f0..f5`). Best-effort:
+    /// write failures are silently ignored, same as `Bookmarks`.
+    fn save(&self) {
+        let path = match search_history_path() {
+            Some(p) => p,
+            None => return,
+        };
+        if let Some(parent) = path.parent() {
+            if fs::create_dir_all(parent).is_err() {
+                return;
+            }
+        }
+
+        let mut content = String::new();
+        for (name, values) in &self.presets {
+            content.push_str("P\t");
+            content.push_str(name);
+            for value in values {
+                content.push('\t');
+                content.push_str(value);
+            }
+            content.push('\n');
+        }
+        for values in &self.recent {
+            content.push('R');
+            for value in values {
+                content.push('\t');
+                content.push_str(value);
+            }
+            content.push('\n');
+        }
+
+        let _ = fs::write(path, content);
+    }
+}
+
+/// Collect exactly six tab-separated fields from `parts` into the raw
+/// dialog values, or `None` if the line doesn't have exactly six.
+fn parse_values<'a>(parts: impl Iterator<Item = &'a str>) -> Option<[String; 6]> {
+    let fields: Vec<String> = parts.map(|s| s.to_string()).collect();
+    fields.try_into().ok()
+}
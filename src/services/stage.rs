@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+/// Cross-directory batch selection (broot calls this "staging"): unlike a
+/// panel's `selected_files`, which only ever holds names from the
+/// currently listed directory, a `Stage` accumulates absolute paths as the
+/// user navigates around, so a copy/move can span several source
+/// directories in one action.
+#[derive(Debug, Clone, Default)]
+pub struct Stage {
+    paths: Vec<PathBuf>,
+    /// Bumped on every mutation so callers holding a cached view (e.g. a
+    /// rendered footer count) can cheaply tell it needs refreshing.
+    version: usize,
+}
+
+impl Stage {
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.paths.iter().any(|p| p == path)
+    }
+
+    /// Add `path` to the stage. Returns `false` without mutating anything
+    /// if it's already present.
+    pub fn add(&mut self, path: PathBuf) -> bool {
+        if self.contains(&path) {
+            return false;
+        }
+        self.paths.push(path);
+        self.version += 1;
+        true
+    }
+
+    pub fn remove(&mut self, path: &Path) {
+        let before = self.paths.len();
+        self.paths.retain(|p| p != path);
+        if self.paths.len() != before {
+            self.version += 1;
+        }
+    }
+
+    /// Add `path` if absent, remove it if present.
+    pub fn toggle(&mut self, path: PathBuf) {
+        if self.contains(&path) {
+            self.remove(&path);
+        } else {
+            self.add(path);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        if !self.paths.is_empty() {
+            self.paths.clear();
+            self.version += 1;
+        }
+    }
+}
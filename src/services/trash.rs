@@ -0,0 +1,204 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Local};
+
+use crate::services::file_ops::{self, ConflictOptions};
+
+/// Same protected-path guard as `file_ops::delete_file`/`trash_file`; kept
+/// as its own copy rather than a shared helper, matching how those two
+/// already each carry their own copy of the loop.
+const PROTECTED_PATHS: &[&str] = &[
+    "/", "/bin", "/boot", "/dev", "/etc", "/home", "/lib", "/lib64",
+    "/opt", "/proc", "/root", "/sbin", "/sys", "/tmp", "/usr", "/var",
+];
+
+/// Root directory under the platform data dir (`XDG_DATA_HOME` on Linux)
+/// where this crate's own trash lives. Distinct from `file_ops::trash_file`,
+/// which hands items to the OS's native Trash/Recycle Bin via the `trash`
+/// crate - that crate has no API to list, restore, or empty what it moved,
+/// so undo support needs a trash this app manages itself.
+fn trash_root() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("cokacdir").join("trash"))
+}
+
+fn trashed_files_dir() -> Option<PathBuf> {
+    trash_root().map(|dir| dir.join("files"))
+}
+
+fn trash_info_dir() -> Option<PathBuf> {
+    trash_root().map(|dir| dir.join("info"))
+}
+
+/// One item currently sitting in the trash, recovered from its sidecar
+/// `.trashinfo` file.
+#[derive(Debug, Clone)]
+pub struct TrashEntry {
+    /// Name of the moved item under the trash's `files/` directory; the
+    /// handle `restore_from_trash` takes to identify it.
+    pub id: String,
+    pub original_path: PathBuf,
+    pub deleted_at: DateTime<Local>,
+    /// Unix permission bits the item had before being trashed, if known.
+    pub mode: Option<u32>,
+}
+
+/// Move `path` into this crate's own trash directory instead of deleting it,
+/// recording enough in a sidecar metadata file to restore it later with
+/// `restore_from_trash`. Uses `file_ops::move_file`'s rename-first,
+/// copy-then-delete-on-`EXDEV` fallback, so this works even when the trash
+/// directory lives on a different filesystem than `path`.
+pub fn trash_file(path: &Path) -> io::Result<String> {
+    if let Ok(canonical) = path.canonicalize() {
+        let path_str = canonical.to_string_lossy();
+        for protected in PROTECTED_PATHS {
+            if path_str == *protected {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("Cannot trash protected system path: {}", protected),
+                ));
+            }
+        }
+    }
+
+    let files_dir = trash_files_dir_or_err()?;
+    let info_dir = trash_info_dir_or_err()?;
+    fs::create_dir_all(&files_dir)?;
+    fs::create_dir_all(&info_dir)?;
+
+    let metadata = fs::symlink_metadata(path)?;
+    #[cfg(unix)]
+    let mode = Some(std::os::unix::fs::PermissionsExt::mode(&metadata.permissions()));
+    #[cfg(not(unix))]
+    let mode: Option<u32> = None;
+
+    let id = unique_trash_id(&files_dir, path);
+    let trashed_path = files_dir.join(&id);
+
+    file_ops::move_file(path, &trashed_path, ConflictOptions::default())?;
+
+    let info = format!(
+        "{}\t{}\t{}\t{}\n",
+        path.display(),
+        Local::now().to_rfc3339(),
+        mode.map(|m| m.to_string()).unwrap_or_default(),
+        metadata.is_dir() as u8,
+    );
+    fs::write(info_dir.join(format!("{}.trashinfo", id)), info)?;
+
+    Ok(id)
+}
+
+/// Every item currently in the trash, newest first. Entries whose sidecar
+/// file fails to parse are silently skipped.
+pub fn list_trash() -> Vec<TrashEntry> {
+    let Some(info_dir) = trash_info_dir() else { return Vec::new() };
+    let Ok(read_dir) = fs::read_dir(&info_dir) else { return Vec::new() };
+
+    let mut entries: Vec<TrashEntry> = read_dir
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let id = file_name.to_string_lossy().strip_suffix(".trashinfo")?.to_string();
+            parse_trash_info(&id, &entry.path())
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    entries
+}
+
+fn parse_trash_info(id: &str, info_path: &Path) -> Option<TrashEntry> {
+    let content = fs::read_to_string(info_path).ok()?;
+    let mut parts = content.trim_end().splitn(4, '\t');
+    let original_path = PathBuf::from(parts.next()?);
+    let deleted_at = DateTime::parse_from_rfc3339(parts.next()?).ok()?.with_timezone(&Local);
+    let mode = parts.next().filter(|s| !s.is_empty()).and_then(|s| s.parse().ok());
+
+    Some(TrashEntry {
+        id: id.to_string(),
+        original_path,
+        deleted_at,
+        mode,
+    })
+}
+
+/// Move the trashed item `id` back to where it was trashed from, restoring
+/// its original Unix permissions, then drop its sidecar metadata file.
+/// Fails (without removing anything from the trash) if something already
+/// occupies the original path.
+pub fn restore_from_trash(id: &str) -> io::Result<PathBuf> {
+    let files_dir = trash_files_dir_or_err()?;
+    let info_dir = trash_info_dir_or_err()?;
+    let info_path = info_dir.join(format!("{}.trashinfo", id));
+
+    let entry = parse_trash_info(id, &info_path)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No such trash entry"))?;
+
+    // `move_file` merges a directory into an existing directory, so check
+    // for an occupied original path up front.
+    if fs::symlink_metadata(&entry.original_path).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "Original path is already occupied",
+        ));
+    }
+
+    let trashed_path = files_dir.join(id);
+    file_ops::move_file(&trashed_path, &entry.original_path, ConflictOptions::default())?;
+
+    #[cfg(unix)]
+    if let Some(mode) = entry.mode {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&entry.original_path, fs::Permissions::from_mode(mode));
+    }
+
+    let _ = fs::remove_file(&info_path);
+    Ok(entry.original_path)
+}
+
+/// Permanently delete every item currently in the trash. Returns the number
+/// of items removed; a single item failing to delete doesn't stop the rest
+/// from being tried.
+pub fn empty_trash() -> io::Result<usize> {
+    let files_dir = trash_files_dir_or_err()?;
+    let info_dir = trash_info_dir_or_err()?;
+
+    let mut removed = 0;
+    for entry in list_trash() {
+        let trashed_path = files_dir.join(&entry.id);
+        if file_ops::delete_file(&trashed_path).is_ok() {
+            let _ = fs::remove_file(info_dir.join(format!("{}.trashinfo", entry.id)));
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+fn trash_files_dir_or_err() -> io::Result<PathBuf> {
+    trashed_files_dir().ok_or_else(|| io::Error::other("Could not resolve the trash directory"))
+}
+
+fn trash_info_dir_or_err() -> io::Result<PathBuf> {
+    trash_info_dir().ok_or_else(|| io::Error::other("Could not resolve the trash directory"))
+}
+
+/// Pick a name for `path` inside the trash's `files/` directory that won't
+/// collide with anything already there, seeded with a nanosecond timestamp
+/// so unrelated trashings never clash and the original filename stays
+/// visible to anyone browsing the trash directly.
+fn unique_trash_id(files_dir: &Path, path: &Path) -> String {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+
+    let mut candidate = format!("{}-{}", nanos, name);
+    let mut suffix = 1;
+    while files_dir.join(&candidate).exists() {
+        candidate = format!("{}-{}-{}", nanos, suffix, name);
+        suffix += 1;
+    }
+    candidate
+}
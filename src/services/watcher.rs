@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Debounce window for collapsing bursts of filesystem events (e.g. a build
+/// writing many files in quick succession) into a single reload.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches a single directory for changes and forwards debounced paths.
+///
+/// Mirrors the `FileOperationProgress` receiver pattern: the notify callback
+/// and a debounce thread hold the `Sender`s, while callers poll the paired
+/// `Receiver` once per frame. The OS watch handle itself is owned by this
+/// struct.
+pub struct DirWatcher {
+    path: PathBuf,
+    receiver: Receiver<PathBuf>,
+    // Keep the watcher alive for as long as this struct lives; dropping it
+    // tears down the underlying inotify (or platform equivalent) handle.
+    _watcher: RecommendedWatcher,
+}
+
+impl DirWatcher {
+    /// Start watching `path` (non-recursively - subdirectories get their own
+    /// watcher when the user navigates into them).
+    pub fn new(path: &Path) -> notify::Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let (debounce_tx, debounce_rx) = mpsc::channel();
+
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            if let Ok(event) = res {
+                for p in event.paths {
+                    let _ = tx.send(p);
+                }
+            }
+        })?;
+        watcher.watch(path, RecursiveMode::NonRecursive)?;
+
+        // Debounce on a background thread so bursts of events collapse into
+        // a single notification per quiet period.
+        thread_debounce(rx, debounce_tx);
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            receiver: debounce_rx,
+            _watcher: watcher,
+        })
+    }
+
+    /// The directory this watcher is watching.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Drain any pending change notifications. Returns true if at least one
+    /// change was observed since the last poll.
+    pub fn poll(&self) -> bool {
+        let mut changed = false;
+        while self.receiver.try_recv().is_ok() {
+            changed = true;
+        }
+        changed
+    }
+}
+
+/// Spawn a thread that coalesces raw filesystem events into a single
+/// "something changed" ping per `DEBOUNCE` window.
+fn thread_debounce(raw_rx: Receiver<PathBuf>, out_tx: mpsc::Sender<PathBuf>) {
+    std::thread::spawn(move || loop {
+        let first = match raw_rx.recv() {
+            Ok(p) => p,
+            Err(_) => return, // watcher dropped
+        };
+
+        // Drain any further events that arrive within the debounce window.
+        let deadline = std::time::Instant::now() + DEBOUNCE;
+        let mut last = first;
+        loop {
+            let now = std::time::Instant::now();
+            if now >= deadline {
+                break;
+            }
+            match raw_rx.recv_timeout(deadline - now) {
+                Ok(p) => last = p,
+                Err(mpsc::RecvTimeoutError::Timeout) => break,
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    let _ = out_tx.send(last);
+                    return;
+                }
+            }
+        }
+
+        if out_tx.send(last).is_err() {
+            return; // receiver dropped
+        }
+    });
+}
@@ -1,3 +1,11 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
 use crossterm::event::KeyCode;
 use ratatui::{
     layout::Rect,
@@ -8,14 +16,59 @@ use ratatui::{
 };
 
 use super::theme::Theme;
+use super::search_result::walk;
+
+/// How the Name field's pattern is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameMatchKind {
+    /// Case-insensitive substring match (the default).
+    #[default]
+    Substring,
+    /// Ranked, in-order (not necessarily contiguous) character match scored by `fuzzy_match`.
+    Fuzzy,
+    /// Shell-style glob (`*`, `**`, `?`, `{a,b}`, `[...]`), matched against
+    /// the path relative to the search root so `**` can span directories.
+    Glob,
+    /// Regular expression, matched against the bare file name.
+    Regex,
+}
+
+impl NameMatchKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NameMatchKind::Substring => "exact",
+            NameMatchKind::Fuzzy => "fuzzy",
+            NameMatchKind::Glob => "glob",
+            NameMatchKind::Regex => "regex",
+        }
+    }
+
+    /// Next mode in the cycle the dialog steps through on each key press.
+    pub fn next(self) -> NameMatchKind {
+        match self {
+            NameMatchKind::Substring => NameMatchKind::Fuzzy,
+            NameMatchKind::Fuzzy => NameMatchKind::Glob,
+            NameMatchKind::Glob => NameMatchKind::Regex,
+            NameMatchKind::Regex => NameMatchKind::Substring,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct SearchCriteria {
     pub name: String,
+    pub name_kind: NameMatchKind,
+    /// Precompiled once per search for `Glob`/`Regex` kinds so
+    /// `matches_criteria*` never re-compiles a pattern per file. `None` for
+    /// `Substring`/`Fuzzy`, or when the pattern fails to compile.
+    pub name_pattern: Option<regex::Regex>,
     pub min_size: Option<u64>,
     pub max_size: Option<u64>,
     pub modified_after: Option<chrono::NaiveDate>,
     pub modified_before: Option<chrono::NaiveDate>,
+    /// Grep-style text to look for inside the file body; empty skips
+    /// content scanning entirely.
+    pub contents: String,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,16 +78,18 @@ pub enum SearchField {
     MaxSize,
     ModifiedAfter,
     ModifiedBefore,
+    Contents,
 }
 
 impl SearchField {
-    pub fn all() -> [SearchField; 5] {
+    pub fn all() -> [SearchField; 6] {
         [
             SearchField::Name,
             SearchField::MinSize,
             SearchField::MaxSize,
             SearchField::ModifiedAfter,
             SearchField::ModifiedBefore,
+            SearchField::Contents,
         ]
     }
 
@@ -45,6 +100,7 @@ impl SearchField {
             SearchField::MaxSize => "Max Size",
             SearchField::ModifiedAfter => "After",
             SearchField::ModifiedBefore => "Before",
+            SearchField::Contents => "Contents",
         }
     }
 
@@ -55,15 +111,87 @@ impl SearchField {
             SearchField::MaxSize => "e.g., 1K, 1M",
             SearchField::ModifiedAfter => "YYYY-MM-DD",
             SearchField::ModifiedBefore => "YYYY-MM-DD",
+            SearchField::Contents => "Text to find inside files",
         }
     }
 }
 
-#[derive(Default)]
+/// A single search hit: either a file whose name matched, or a specific
+/// line inside a file whose contents matched. Both kinds can coexist in one
+/// result set when both the Name and Contents fields are set.
+#[derive(Debug, Clone)]
+pub enum SearchResult {
+    FileName {
+        name: String,
+    },
+    LineInFile {
+        name: String,
+        line_number: usize,
+        line_text: String,
+        match_indices: Vec<usize>,
+    },
+}
+
+/// A message streamed from the background search worker to the UI thread.
+enum SearchMessage {
+    Result(SearchResult),
+    Progress { processed: usize, total: usize },
+    Done,
+}
+
 pub struct AdvancedSearchState {
     pub active_field: usize,
-    pub values: [String; 5],
+    pub values: [String; 6],
     pub active: bool,
+    /// How the Name field's pattern is interpreted; cycled by the dialog's
+    /// mode key.
+    pub name_kind: NameMatchKind,
+    /// Compile error for the current Name field, set whenever `name_kind` is
+    /// `Glob` or `Regex` and `values[0]` fails to compile. `draw` renders
+    /// the field in an error style with this as the hint, and Enter is
+    /// disabled while it's `Some`.
+    pub name_pattern_error: Option<String>,
+
+    /// Results streamed back so far from the in-flight (or last completed)
+    /// background search.
+    pub results: Vec<SearchResult>,
+    /// Whether a background search is currently running.
+    pub loading: bool,
+    pub processed: usize,
+    pub total: usize,
+    receiver: Option<mpsc::Receiver<SearchMessage>>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+
+    /// Saved presets and the recent-query ring, loaded from disk on
+    /// construction and persisted back whenever a preset is saved or a
+    /// search is executed.
+    pub history: crate::services::search_history::SearchHistory,
+    /// Index into `history.presets()` last shown by `cycle_preset`, so
+    /// repeated presses step through every saved preset in turn.
+    preset_cursor: usize,
+    /// Index into `history.recent()` last shown by `cycle_recent`.
+    recent_cursor: usize,
+}
+
+impl Default for AdvancedSearchState {
+    fn default() -> Self {
+        Self {
+            active_field: 0,
+            values: Default::default(),
+            active: false,
+            name_kind: NameMatchKind::default(),
+            name_pattern_error: None,
+            results: Vec::new(),
+            loading: false,
+            processed: 0,
+            total: 0,
+            receiver: None,
+            cancel_flag: None,
+            history: crate::services::search_history::SearchHistory::load(),
+            preset_cursor: 0,
+            recent_cursor: 0,
+        }
+    }
 }
 
 impl AdvancedSearchState {
@@ -80,18 +208,446 @@ impl AdvancedSearchState {
             String::new(),
             String::new(),
             String::new(),
+            String::new(),
         ];
+        self.name_pattern_error = None;
+    }
+
+    /// Step the Name field to the next match mode (Substring -> Fuzzy ->
+    /// Glob -> Regex -> Substring), re-validating the current pattern.
+    pub fn cycle_name_kind(&mut self) {
+        self.name_kind = self.name_kind.next();
+        self.update_name_pattern_error();
+    }
+
+    /// Re-check `values[0]` against the `regex` crate when `name_kind` is
+    /// `Glob` or `Regex`, so `draw` and `handle_input` can surface a compile
+    /// error inline instead of silently matching nothing. Call after every
+    /// edit to the Name field or to `name_kind` itself.
+    pub fn update_name_pattern_error(&mut self) {
+        self.name_pattern_error = match self.name_kind {
+            NameMatchKind::Glob if !self.values[0].is_empty() => {
+                regex::Regex::new(&glob_to_regex(&self.values[0])).err().map(|e| e.to_string())
+            }
+            NameMatchKind::Regex if !self.values[0].is_empty() => {
+                regex::Regex::new(&self.values[0]).err().map(|e| e.to_string())
+            }
+            _ => None,
+        };
+    }
+
+    /// Whether the Enter action should be accepted: always true unless the
+    /// Name field is an invalid glob/regex pattern.
+    pub fn can_submit(&self) -> bool {
+        self.name_pattern_error.is_none()
+    }
+
+    /// Spawn a worker thread that walks `base_path` and streams matches back
+    /// over a channel, cancelling any search already in flight. Call
+    /// `poll_search` once per frame to drain it and `cancel_search` to stop
+    /// it early (e.g. on Esc or before starting a new one).
+    pub fn start_search(&mut self, base_path: PathBuf, criteria: SearchCriteria) {
+        self.cancel_search();
+
+        self.results.clear();
+        self.processed = 0;
+        self.total = 0;
+        self.loading = true;
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        self.receiver = Some(rx);
+        self.cancel_flag = Some(cancel_flag.clone());
+
+        thread::spawn(move || run_search(&base_path, &criteria, &tx, &cancel_flag));
+    }
+
+    /// Signal the in-flight worker (if any) to stop at its next checkpoint.
+    pub fn cancel_search(&mut self) {
+        if let Some(flag) = &self.cancel_flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+        self.loading = false;
+    }
+
+    /// Drain whatever the worker has produced since the last poll. Cheap to
+    /// call unconditionally; a no-op once the search is no longer loading
+    /// and the channel has been fully drained.
+    pub fn poll_search(&mut self) {
+        let Some(rx) = self.receiver.as_ref() else {
+            return;
+        };
+
+        loop {
+            match rx.try_recv() {
+                Ok(SearchMessage::Result(result)) => self.results.push(result),
+                Ok(SearchMessage::Progress { processed, total }) => {
+                    self.processed = processed;
+                    self.total = total;
+                }
+                Ok(SearchMessage::Done) => {
+                    self.loading = false;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.loading = false;
+                    break;
+                }
+            }
+        }
     }
 
     pub fn get_criteria(&self) -> SearchCriteria {
+        let name_pattern = match self.name_kind {
+            NameMatchKind::Glob => regex::Regex::new(&glob_to_regex(&self.values[0])).ok(),
+            NameMatchKind::Regex => regex::Regex::new(&self.values[0]).ok(),
+            NameMatchKind::Substring | NameMatchKind::Fuzzy => None,
+        };
+
         SearchCriteria {
             name: self.values[0].clone(),
+            name_kind: self.name_kind,
+            name_pattern,
             min_size: parse_size(&self.values[1]),
             max_size: parse_size(&self.values[2]),
             modified_after: parse_date(&self.values[3]),
             modified_before: parse_date(&self.values[4]),
+            contents: self.values[5].clone(),
+        }
+    }
+
+    /// Save the current field values as a named preset, keyed by the Name
+    /// field's pattern (or `"unnamed"` if it's empty) so presets don't
+    /// require a separate naming prompt.
+    pub fn save_current_preset(&mut self) {
+        let name = if self.values[0].is_empty() {
+            "unnamed".to_string()
+        } else {
+            self.values[0].clone()
+        };
+        self.history.save_preset(name, self.values.clone());
+    }
+
+    /// Load the next saved preset's values into the dialog, wrapping
+    /// around. No-op if there are no saved presets.
+    pub fn cycle_preset(&mut self) {
+        let presets = self.history.presets();
+        if presets.is_empty() {
+            return;
+        }
+        let (_, values) = &presets[self.preset_cursor % presets.len()];
+        self.values = values.clone();
+        self.preset_cursor = (self.preset_cursor + 1) % presets.len();
+        self.update_name_pattern_error();
+    }
+
+    /// Load the next entry from the recent-query ring into the dialog,
+    /// wrapping around. No-op if nothing has been searched yet.
+    pub fn cycle_recent(&mut self) {
+        let recent = self.history.recent();
+        if recent.is_empty() {
+            return;
+        }
+        self.values = recent[self.recent_cursor % recent.len()].clone();
+        self.recent_cursor = (self.recent_cursor + 1) % recent.len();
+        self.update_name_pattern_error();
+    }
+}
+
+/// Skim/Smith-Waterman-style fuzzy matcher. Returns `None` unless every
+/// character of `pattern` appears in `name`, in order (case-insensitive);
+/// otherwise returns a relevance score - higher is a better match - and the
+/// matched character indices into `name`, for the UI to highlight later.
+pub fn fuzzy_match(name: &str, pattern: &str) -> Option<(i64, Vec<usize>)> {
+    if pattern.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let name_chars: Vec<char> = name.chars().collect();
+    let name_lower: Vec<char> = name.to_lowercase().chars().collect();
+    let pattern_lower: Vec<char> = pattern.to_lowercase().chars().collect();
+
+    const BASE_SCORE: i64 = 16;
+    const CONSECUTIVE_BONUS: i64 = 8;
+    const BOUNDARY_BONUS: i64 = 12;
+    const LEADING_GAP_PENALTY: i64 = 1;
+    const GAP_PENALTY: i64 = 2;
+
+    let mut indices = Vec::with_capacity(pattern_lower.len());
+    let mut score: i64 = 0;
+    let mut search_from = 0usize;
+    let mut prev_matched: Option<usize> = None;
+
+    for &pc in &pattern_lower {
+        let idx = (search_from..name_lower.len()).find(|&i| name_lower[i] == pc)?;
+
+        let mut char_score = BASE_SCORE;
+
+        let is_boundary = idx == 0
+            || matches!(name_chars[idx - 1], '_' | '-' | '.' | ' ' | '/')
+            || (name_chars[idx - 1].is_lowercase() && name_chars[idx].is_uppercase());
+        if is_boundary {
+            char_score += BOUNDARY_BONUS;
+        }
+
+        match prev_matched {
+            Some(prev) if idx == prev + 1 => char_score += CONSECUTIVE_BONUS,
+            Some(prev) => char_score -= GAP_PENALTY * (idx - prev - 1) as i64,
+            None => char_score -= LEADING_GAP_PENALTY * idx as i64,
+        }
+
+        score += char_score;
+        indices.push(idx);
+        prev_matched = Some(idx);
+        search_from = idx + 1;
+    }
+
+    Some((score, indices))
+}
+
+/// First chunk read when sniffing a file for binary content.
+const BINARY_SNIFF_LEN: usize = 8192;
+/// Per-file byte cap for content search, so one huge log doesn't stall the
+/// dialog while scanning.
+const CONTENT_SEARCH_BYTE_CAP: u64 = 1024 * 1024;
+
+/// Grep-style content search: scan `path` line by line for a case-
+/// insensitive match of `query`, returning `(line_number, line_text,
+/// match_indices)` for every matching line. Skips binary files (detected by
+/// a NUL byte in the first block) and stops after `CONTENT_SEARCH_BYTE_CAP`
+/// bytes. Returns an empty vec for an empty query, an unreadable file, or a
+/// file that looks binary.
+pub fn matches_contents(path: &Path, query: &str) -> Vec<(usize, String, Vec<usize>)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+    let mut reader = BufReader::new(file);
+
+    let mut sniff = vec![0u8; BINARY_SNIFF_LEN];
+    let read = reader.read(&mut sniff).unwrap_or(0);
+    if sniff[..read].contains(&0) {
+        return Vec::new();
+    }
+
+    let capped = Cursor::new(sniff[..read].to_vec())
+        .chain(reader)
+        .take(CONTENT_SEARCH_BYTE_CAP);
+    let mut lines = BufReader::new(capped);
+
+    let query_lower = query.to_lowercase();
+    let mut results = Vec::new();
+    let mut line_number = 0;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        match lines.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {
+                line_number += 1;
+                let text = line.trim_end_matches(['\n', '\r']);
+                if let Some(pos) = text.to_lowercase().find(&query_lower) {
+                    let indices = (pos..pos + query.len()).collect();
+                    results.push((line_number, text.to_string(), indices));
+                }
+            }
+            Err(_) => break,
+        }
+    }
+
+    results
+}
+
+/// Collect every `SearchResult` for one file against `criteria`: a
+/// `FileName` hit if the Name field matches, plus one `LineInFile` hit per
+/// matching line if the Contents field is set. Both kinds can coexist for
+/// the same file.
+/// `file_name` (the bare file name) is what the Name field matches against
+/// for every mode except `Glob`, which matches `display_path` (typically
+/// the path relative to the search root) instead so a pattern like
+/// `src/**/*.rs` can span directories. `display_path` is also what gets
+/// embedded into the returned `SearchResult`s, so recursive results stay
+/// disambiguated even when two matches share a file name.
+pub fn matches_criteria_results(
+    path: &Path,
+    file_name: &str,
+    display_path: &str,
+    size: u64,
+    modified: chrono::DateTime<chrono::Local>,
+    criteria: &SearchCriteria,
+) -> Vec<SearchResult> {
+    let mut results = Vec::new();
+
+    let size_and_date_ok = size_and_date_match(size, modified, criteria);
+    if !size_and_date_ok {
+        return results;
+    }
+
+    if !criteria.name.is_empty() && name_score(file_name, display_path, criteria).is_some() {
+        results.push(SearchResult::FileName {
+            name: display_path.to_string(),
+        });
+    }
+
+    if !criteria.contents.is_empty() {
+        for (line_number, line_text, match_indices) in matches_contents(path, &criteria.contents) {
+            results.push(SearchResult::LineInFile {
+                name: display_path.to_string(),
+                line_number,
+                line_text,
+                match_indices,
+            });
+        }
+    }
+
+    results
+}
+
+/// Walk `base_path`, matching every regular file against `criteria` and
+/// streaming each hit back over `tx`, along with periodic progress updates
+/// and a final `Done`. Checks `cancel_flag` between files so a cancelled
+/// search stops promptly instead of running to completion.
+fn run_search(
+    base_path: &Path,
+    criteria: &SearchCriteria,
+    tx: &mpsc::Sender<SearchMessage>,
+    cancel_flag: &Arc<AtomicBool>,
+) {
+    let mut processed = 0usize;
+    let mut stopped = false;
+
+    walk(base_path, &mut |path, is_dir| {
+        if cancel_flag.load(Ordering::Relaxed) {
+            stopped = true;
+            return false;
         }
+        if is_dir {
+            return true;
+        }
+
+        processed += 1;
+
+        let file_name = match path.file_name().and_then(|n| n.to_str()) {
+            Some(n) => n,
+            None => return true,
+        };
+        let display_path = path
+            .strip_prefix(base_path)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned();
+        let metadata = match std::fs::metadata(path) {
+            Ok(m) => m,
+            Err(_) => return true,
+        };
+        let modified = metadata
+            .modified()
+            .map(chrono::DateTime::<chrono::Local>::from)
+            .unwrap_or_else(|_| chrono::Local::now());
+
+        for result in
+            matches_criteria_results(path, file_name, &display_path, metadata.len(), modified, criteria)
+        {
+            if tx.send(SearchMessage::Result(result)).is_err() {
+                stopped = true;
+                return false;
+            }
+        }
+
+        if processed % 64 == 0
+            && tx
+                .send(SearchMessage::Progress {
+                    processed,
+                    total: processed,
+                })
+                .is_err()
+        {
+            stopped = true;
+            return false;
+        }
+
+        true
+    });
+
+    if !stopped {
+        let _ = tx.send(SearchMessage::Progress {
+            processed,
+            total: processed,
+        });
     }
+    let _ = tx.send(SearchMessage::Done);
+}
+
+/// Translate a shell-style glob into an anchored, case-insensitive regex
+/// matched against a path relative to the search root: `*` matches within
+/// one path segment, `**` crosses separators, `?` matches a single
+/// non-separator character, `{a,b}` becomes an alternation, and `[...]`
+/// character classes are passed through to the regex engine unchanged.
+/// Other metacharacters are escaped so they match literally.
+fn glob_to_regex(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::from("(?i)^");
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                out.push_str(".*");
+                i += 2;
+            }
+            '*' => {
+                out.push_str("[^/]*");
+                i += 1;
+            }
+            '?' => {
+                out.push_str("[^/]");
+                i += 1;
+            }
+            '{' => match chars[i..].iter().position(|&c| c == '}') {
+                Some(rel_end) => {
+                    let end = i + rel_end;
+                    let alternatives = chars[i + 1..end]
+                        .iter()
+                        .collect::<String>()
+                        .split(',')
+                        .map(regex::escape)
+                        .collect::<Vec<_>>()
+                        .join("|");
+                    out.push_str("(?:");
+                    out.push_str(&alternatives);
+                    out.push(')');
+                    i = end + 1;
+                }
+                None => {
+                    out.push_str(&regex::escape("{"));
+                    i += 1;
+                }
+            },
+            '[' => match chars[i..].iter().position(|&c| c == ']') {
+                Some(rel_end) => {
+                    let end = i + rel_end;
+                    out.extend(chars[i..=end].iter().copied());
+                    i = end + 1;
+                }
+                None => {
+                    out.push_str(&regex::escape("["));
+                    i += 1;
+                }
+            },
+            c => {
+                out.push_str(&regex::escape(&c.to_string()));
+                i += 1;
+            }
+        }
+    }
+
+    out.push('$');
+    out
 }
 
 fn parse_size(s: &str) -> Option<u64> {
@@ -126,9 +682,48 @@ fn parse_date(s: &str) -> Option<chrono::NaiveDate> {
     chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
 }
 
+/// Inverse of `parse_size`: the largest `K`/`M`/`G`/`T` unit that divides
+/// `bytes` evenly, e.g. `1048576` -> `"1M"`, or the plain byte count when no
+/// unit divides it evenly.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [(u64, &str); 4] = [
+        (1024 * 1024 * 1024 * 1024, "T"),
+        (1024 * 1024 * 1024, "G"),
+        (1024 * 1024, "M"),
+        (1024, "K"),
+    ];
+
+    for (multiplier, suffix) in UNITS {
+        if bytes >= multiplier && bytes % multiplier == 0 {
+            return format!("{}{}", bytes / multiplier, suffix);
+        }
+    }
+
+    bytes.to_string()
+}
+
+fn format_date(date: chrono::NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Inverse of `get_criteria`: repopulate the dialog's raw input fields from
+/// an already-executed `SearchCriteria`, so a recalled preset or recent
+/// query reads back exactly as the user would have typed it (sizes
+/// formatted `1M`-style, dates as `YYYY-MM-DD`).
+pub fn criteria_to_values(criteria: &SearchCriteria) -> [String; 6] {
+    [
+        criteria.name.clone(),
+        criteria.min_size.map(format_size).unwrap_or_default(),
+        criteria.max_size.map(format_size).unwrap_or_default(),
+        criteria.modified_after.map(format_date).unwrap_or_default(),
+        criteria.modified_before.map(format_date).unwrap_or_default(),
+        criteria.contents.clone(),
+    ]
+}
+
 pub fn draw(frame: &mut Frame, state: &AdvancedSearchState, area: Rect, theme: &Theme) {
     let width = 50u16;
-    let height = 12u16;
+    let height = 15u16;
     let x = area.x + (area.width.saturating_sub(width)) / 2;
     let y = area.y + (area.height.saturating_sub(height)) / 2;
     let dialog_area = Rect::new(x, y, width, height);
@@ -150,21 +745,32 @@ pub fn draw(frame: &mut Frame, state: &AdvancedSearchState, area: Rect, theme: &
 
     for (i, field) in fields.iter().enumerate() {
         let is_active = i == state.active_field;
+        let is_name_field = *field == SearchField::Name;
+        let is_invalid_pattern = is_name_field && state.name_pattern_error.is_some();
         let prefix = if is_active { "> " } else { "  " };
         let value = &state.values[i];
+        let label = if is_name_field {
+            format!("Name ({})", state.name_kind.label())
+        } else {
+            field.label().to_string()
+        };
 
         let mut spans = vec![
             Span::styled(
                 prefix,
-                if is_active {
+                if is_invalid_pattern {
+                    theme.error_style()
+                } else if is_active {
                     Style::default().fg(theme.border_active)
                 } else {
                     theme.normal_style()
                 },
             ),
             Span::styled(
-                format!("{:10}", field.label()),
-                if is_active {
+                format!("{:16}", label),
+                if is_invalid_pattern {
+                    theme.error_style()
+                } else if is_active {
                     Style::default().fg(theme.border_active)
                 } else {
                     theme.normal_style()
@@ -173,7 +779,9 @@ pub fn draw(frame: &mut Frame, state: &AdvancedSearchState, area: Rect, theme: &
             Span::styled("[", Style::default().fg(theme.info)),
             Span::styled(
                 format!("{:12}", value),
-                if is_active {
+                if is_invalid_pattern {
+                    theme.error_style()
+                } else if is_active {
                     theme.selected_style()
                 } else {
                     theme.normal_style()
@@ -182,7 +790,14 @@ pub fn draw(frame: &mut Frame, state: &AdvancedSearchState, area: Rect, theme: &
             Span::styled("]", Style::default().fg(theme.info)),
         ];
 
-        if is_active {
+        if let Some(err) = state.name_pattern_error.as_ref().filter(|_| is_invalid_pattern) {
+            spans.push(Span::styled(format!(" {err}"), theme.error_style()));
+        } else if is_active && is_name_field {
+            spans.push(Span::styled(
+                " [F2] cycle match mode",
+                theme.dim_style(),
+            ));
+        } else if is_active {
             spans.push(Span::styled(
                 format!(" {}", field.hint()),
                 theme.dim_style(),
@@ -191,12 +806,18 @@ pub fn draw(frame: &mut Frame, state: &AdvancedSearchState, area: Rect, theme: &
 
         lines.push(Line::from(spans));
     }
-
-    lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "[↑↓/Tab] Navigate  [Enter] Search  [Esc] Cancel",
         theme.dim_style(),
     )));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "[F4] Save preset  [F5] Presets ({})  [F6] Recent ({})",
+            state.history.presets().len(),
+            state.history.recent().len(),
+        ),
+        theme.dim_style(),
+    )));
 
     frame.render_widget(
         Paragraph::new(lines),
@@ -208,12 +829,17 @@ pub fn handle_input(state: &mut AdvancedSearchState, code: KeyCode) -> Option<Se
     match code {
         KeyCode::Esc => {
             state.active = false;
+            state.cancel_search();
             state.reset();
             None
         }
         KeyCode::Enter => {
+            if !state.can_submit() {
+                return None;
+            }
             state.active = false;
             let criteria = state.get_criteria();
+            state.history.push_recent(criteria_to_values(&criteria));
             state.reset();
             Some(criteria)
         }
@@ -222,17 +848,39 @@ pub fn handle_input(state: &mut AdvancedSearchState, code: KeyCode) -> Option<Se
             None
         }
         KeyCode::Down | KeyCode::Tab => {
-            if state.active_field < 4 {
+            if state.active_field < 5 {
                 state.active_field += 1;
             }
             None
         }
         KeyCode::Backspace => {
             state.values[state.active_field].pop();
+            if state.active_field == 0 {
+                state.update_name_pattern_error();
+            }
+            None
+        }
+        KeyCode::F(2) => {
+            state.cycle_name_kind();
+            None
+        }
+        KeyCode::F(4) => {
+            state.save_current_preset();
+            None
+        }
+        KeyCode::F(5) => {
+            state.cycle_preset();
+            None
+        }
+        KeyCode::F(6) => {
+            state.cycle_recent();
             None
         }
         KeyCode::Char(c) => {
             state.values[state.active_field].push(c);
+            if state.active_field == 0 {
+                state.update_name_pattern_error();
+            }
             None
         }
         _ => None,
@@ -246,16 +894,21 @@ pub fn matches_criteria(
     modified: chrono::DateTime<chrono::Local>,
     criteria: &SearchCriteria,
 ) -> bool {
-    // Name match (case-insensitive substring match)
-    if !criteria.name.is_empty() {
-        let name_lower = name.to_lowercase();
-        let pattern_lower = criteria.name.to_lowercase();
-        if !name_lower.contains(&pattern_lower) {
-            return false;
-        }
+    if !criteria.name.is_empty() && name_score(name, name, criteria).is_none() {
+        return false;
     }
 
-    // Size range
+    size_and_date_match(size, modified, criteria)
+}
+
+/// Size and modified-date bounds from `criteria`, independent of the Name
+/// or Contents fields. Shared by `matches_criteria` and
+/// `matches_criteria_results`.
+fn size_and_date_match(
+    size: u64,
+    modified: chrono::DateTime<chrono::Local>,
+    criteria: &SearchCriteria,
+) -> bool {
     if let Some(min) = criteria.min_size {
         if size < min {
             return false;
@@ -268,7 +921,6 @@ pub fn matches_criteria(
         }
     }
 
-    // Date range
     let file_date = modified.date_naive();
 
     if let Some(after) = criteria.modified_after {
@@ -285,3 +937,235 @@ pub fn matches_criteria(
 
     true
 }
+
+/// Name-field relevance score for ranking matches best-first, dispatched on
+/// `criteria.name_kind`: the fuzzy score for `Fuzzy`, a flat `0` for a
+/// `Substring`/`Glob`/`Regex` hit. `None` means the name doesn't match at
+/// all. An empty pattern always scores `0`. `Glob` matches `relative_path`;
+/// every other kind matches the bare `name`.
+fn name_score(name: &str, relative_path: &str, criteria: &SearchCriteria) -> Option<i64> {
+    if criteria.name.is_empty() {
+        return Some(0);
+    }
+
+    match criteria.name_kind {
+        NameMatchKind::Glob => criteria
+            .name_pattern
+            .as_ref()
+            .and_then(|re| re.is_match(relative_path).then_some(0)),
+        NameMatchKind::Regex => criteria
+            .name_pattern
+            .as_ref()
+            .and_then(|re| re.is_match(name).then_some(0)),
+        NameMatchKind::Fuzzy => fuzzy_match(name, &criteria.name).map(|(score, _)| score),
+        NameMatchKind::Substring => {
+            let name_lower = name.to_lowercase();
+            let pattern_lower = criteria.name.to_lowercase();
+            name_lower.contains(&pattern_lower).then_some(0)
+        }
+    }
+}
+
+/// Relevance score for a file against `criteria`'s Name field, for sorting
+/// matches best-first. Returns `None` if the file doesn't match at all
+/// (including the size/date bounds, same as `matches_criteria`).
+pub fn matches_criteria_scored(
+    name: &str,
+    size: u64,
+    modified: chrono::DateTime<chrono::Local>,
+    criteria: &SearchCriteria,
+) -> Option<i64> {
+    let score = name_score(name, name, criteria)?;
+    if !matches_criteria(name, size, modified, criteria) {
+        return None;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_criteria_to_values_round_trips_size_and_date() {
+        let criteria = SearchCriteria {
+            name: "needle".to_string(),
+            name_kind: NameMatchKind::Substring,
+            name_pattern: None,
+            min_size: Some(1024 * 1024),
+            max_size: Some(512),
+            modified_after: chrono::NaiveDate::from_ymd_opt(2026, 1, 15),
+            modified_before: None,
+            contents: "body".to_string(),
+        };
+
+        let values = criteria_to_values(&criteria);
+
+        assert_eq!(values[0], "needle");
+        assert_eq!(values[1], "1M");
+        assert_eq!(values[2], "512");
+        assert_eq!(values[3], "2026-01-15");
+        assert_eq!(values[4], "");
+        assert_eq!(values[5], "body");
+    }
+
+    #[test]
+    fn test_fuzzy_match_requires_in_order_chars() {
+        assert!(fuzzy_match("search_mod.rs", "srchmd").is_some());
+        assert!(fuzzy_match("search_mod.rs", "dmhcrs").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_match_ranks_consecutive_and_boundary_hits_higher() {
+        let (consecutive, _) = fuzzy_match("search_mod.rs", "search").unwrap();
+        let (scattered, _) = fuzzy_match("search_mod.rs", "sd").unwrap();
+        let (boundary, _) = fuzzy_match("search_mod.rs", "sm").unwrap();
+        let (mid_word, _) = fuzzy_match("search_mod.rs", "am").unwrap();
+        assert!(consecutive > scattered);
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn test_matches_criteria_fuzzy_toggle() {
+        let modified = chrono::Local::now();
+        let mut criteria = SearchCriteria {
+            name: "srchmd".to_string(),
+            name_kind: NameMatchKind::Substring,
+            name_pattern: None,
+            min_size: None,
+            max_size: None,
+            modified_after: None,
+            modified_before: None,
+            contents: String::new(),
+        };
+        assert!(!matches_criteria("search_mod.rs", 0, modified, &criteria));
+
+        criteria.name_kind = NameMatchKind::Fuzzy;
+        assert!(matches_criteria("search_mod.rs", 0, modified, &criteria));
+    }
+
+    #[test]
+    fn test_matches_criteria_regex_mode() {
+        let modified = chrono::Local::now();
+        let criteria = SearchCriteria {
+            name: r"^search_.*\.rs$".to_string(),
+            name_kind: NameMatchKind::Regex,
+            name_pattern: regex::Regex::new(r"^search_.*\.rs$").ok(),
+            min_size: None,
+            max_size: None,
+            modified_after: None,
+            modified_before: None,
+            contents: String::new(),
+        };
+        assert!(matches_criteria("search_mod.rs", 0, modified, &criteria));
+        assert!(!matches_criteria("other.rs", 0, modified, &criteria));
+    }
+
+    #[test]
+    fn test_matches_criteria_results_glob_matches_relative_path() {
+        let path = temp_file(b"");
+        let criteria = SearchCriteria {
+            name: "src/**/*.rs".to_string(),
+            name_kind: NameMatchKind::Glob,
+            name_pattern: regex::Regex::new(&glob_to_regex("src/**/*.rs")).ok(),
+            min_size: None,
+            max_size: None,
+            modified_after: None,
+            modified_before: None,
+            contents: String::new(),
+        };
+
+        let results = matches_criteria_results(
+            &path,
+            "lib.rs",
+            "src/ui/lib.rs",
+            path.metadata().unwrap().len(),
+            chrono::Local::now(),
+            &criteria,
+        );
+        let _ = std::fs::remove_file(&path);
+
+        assert!(results
+            .iter()
+            .any(|r| matches!(r, SearchResult::FileName { name } if name == "src/ui/lib.rs")));
+    }
+
+    #[test]
+    fn test_glob_to_regex_braces_and_classes() {
+        let re = regex::Regex::new(&glob_to_regex("*.{toml,lock}")).unwrap();
+        assert!(re.is_match("Cargo.toml"));
+        assert!(re.is_match("Cargo.lock"));
+        assert!(!re.is_match("Cargo.rs"));
+
+        let re = regex::Regex::new(&glob_to_regex("file[0-9].txt")).unwrap();
+        assert!(re.is_match("file3.txt"));
+        assert!(!re.is_match("fileA.txt"));
+    }
+
+    fn temp_file(contents: &[u8]) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir().join(format!(
+            "cokacdir_adv_search_test_{}_{}",
+            std::process::id(),
+            id
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_matches_contents_finds_line_and_indices() {
+        let path = temp_file(b"one\ntwo needle three\nfour\n");
+        let hits = matches_contents(&path, "needle");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(hits.len(), 1);
+        let (line_number, line_text, indices) = &hits[0];
+        assert_eq!(*line_number, 2);
+        assert_eq!(line_text, "two needle three");
+        assert_eq!(indices, &vec![4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_matches_contents_skips_binary_files() {
+        let path = temp_file(&[0u8, 1, 2, b'n', b'e', b'e', b'd', b'l', b'e']);
+        let hits = matches_contents(&path, "needle");
+        let _ = std::fs::remove_file(&path);
+
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_matches_criteria_results_combines_name_and_content_hits() {
+        let path = temp_file(b"contains needle here\n");
+        let criteria = SearchCriteria {
+            name: "needle".to_string(),
+            name_kind: NameMatchKind::Substring,
+            name_pattern: None,
+            min_size: None,
+            max_size: None,
+            modified_after: None,
+            modified_before: None,
+            contents: "needle".to_string(),
+        };
+
+        let results = matches_criteria_results(
+            &path,
+            "needle_file.txt",
+            "needle_file.txt",
+            path.metadata().unwrap().len(),
+            chrono::Local::now(),
+            &criteria,
+        );
+        let _ = std::fs::remove_file(&path);
+
+        assert!(results
+            .iter()
+            .any(|r| matches!(r, SearchResult::FileName { name } if name == "needle_file.txt")));
+        assert!(results
+            .iter()
+            .any(|r| matches!(r, SearchResult::LineInFile { .. })));
+    }
+}
@@ -1,13 +1,20 @@
-use std::collections::HashSet;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 use chrono::{DateTime, Local};
 
-use crate::services::file_ops::{self, FileOperationType, ProgressMessage, FileOperationResult};
+use crate::services::bookmarks::Bookmarks;
+use crate::services::dir_stats;
+use crate::services::file_ops::{self, FileOperationType, ProgressMessage, FileOperationResult, ConflictAction, ConflictMeta};
+use crate::services::remote::{self, RemoteSession, RemoteTarget};
+use crate::services::stage::Stage;
+use crate::services::watcher::DirWatcher;
 use crate::ui::file_viewer::ViewerState;
 use crate::ui::file_editor::EditorState;
 use crate::ui::file_info::FileInfoState;
@@ -45,6 +52,46 @@ pub fn get_valid_path(target_path: &Path, fallback: &Path) -> PathBuf {
     PathBuf::from("/")
 }
 
+/// Shared directories-first comparator used both for the top-level listing
+/// and for sorting newly expanded tree-view children.
+fn compare_file_items(a: &FileItem, b: &FileItem, sort_by: SortBy, sort_order: SortOrder) -> std::cmp::Ordering {
+    if a.is_directory && !b.is_directory {
+        return std::cmp::Ordering::Less;
+    }
+    if !a.is_directory && b.is_directory {
+        return std::cmp::Ordering::Greater;
+    }
+
+    let cmp = match sort_by {
+        SortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        SortBy::Size => a.size.cmp(&b.size),
+        SortBy::Modified => a.modified.cmp(&b.modified),
+    };
+
+    match sort_order {
+        SortOrder::Asc => cmp,
+        SortOrder::Desc => cmp.reverse(),
+    }
+}
+
+/// Reload a panel's directory listing while keeping the cursor on the same
+/// file (matched by name) and the same scroll position, so an external
+/// change doesn't yank the user's place in the list.
+fn reload_preserving_cursor(panel: &mut PanelState) {
+    let focused_name = panel.current_file().map(|f| f.name.clone());
+    let scroll_offset = panel.scroll_offset;
+
+    panel.load_files();
+
+    if let Some(name) = focused_name {
+        if let Some(idx) = panel.files.iter().position(|f| f.name == name) {
+            panel.selected_index = idx;
+        }
+    }
+    panel.selected_files.retain(|name| panel.files.iter().any(|f| &f.name == name));
+    panel.scroll_offset = scroll_offset.min(panel.files.len().saturating_sub(1));
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PanelSide {
     Left,
@@ -77,6 +124,8 @@ pub enum Screen {
     SystemInfo,
     ImageViewer,
     SearchResult,
+    Filesystems,
+    Trash,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -91,6 +140,11 @@ pub enum DialogType {
     LargeImageConfirm,
     TrueColorWarning,
     Progress,
+    Bookmarks,
+    Conflict,
+    Connect,
+    /// Comma-separated extension list, applied to `extension_filter.allowed`.
+    Filter,
 }
 
 /// Clipboard operation type for Ctrl+C/X/V operations
@@ -108,12 +162,24 @@ pub struct Clipboard {
     pub operation: ClipboardOperation,
 }
 
+/// A copy/move conflict awaiting a user decision, surfaced by the
+/// background worker over the progress channel. The worker thread is
+/// blocked on its conflict receiver until `FileOperationProgress::resolve_conflict`
+/// sends a reply.
+#[derive(Debug, Clone)]
+pub struct PendingConflict {
+    pub name: String,
+    pub source: ConflictMeta,
+    pub destination: ConflictMeta,
+}
+
 /// File operation progress state for progress dialog
 pub struct FileOperationProgress {
     pub operation_type: FileOperationType,
     pub is_active: bool,
     pub cancel_flag: Arc<AtomicBool>,
     receiver: Option<Receiver<ProgressMessage>>,
+    conflict_sender: Option<mpsc::Sender<(ConflictAction, bool)>>,
 
     // Progress state
     pub current_file: String,
@@ -123,12 +189,33 @@ pub struct FileOperationProgress {
     pub total_bytes: u64,
     pub completed_bytes: u64,
 
+    /// (timestamp, completed_bytes) samples from roughly the last
+    /// `RATE_WINDOW`, used to compute a moving-average transfer rate.
+    rate_samples: VecDeque<(Instant, u64)>,
+
+    /// Set while the worker thread is blocked waiting for a conflict
+    /// decision; cleared by `resolve_conflict`.
+    pub pending_conflict: Option<PendingConflict>,
+
     pub result: Option<FileOperationResult>,
 
     // Store last error before result is created
     last_error: Option<String>,
+
+    /// Items the worker silently renamed to avoid a conflict (original
+    /// name, final name), accumulated so the dialog can list them once the
+    /// operation completes. See `auto_rename_on_conflict`.
+    pub renamed_files: Vec<(String, String)>,
+
+    /// Filenames the worker copied instead of linking because the
+    /// platform has no symlink support. See
+    /// `file_ops::symlink_files_with_progress`.
+    pub copied_instead_of_linked: Vec<String>,
 }
 
+/// Width of the rolling window used to average the transfer rate.
+const RATE_WINDOW: Duration = Duration::from_millis(500);
+
 impl FileOperationProgress {
     pub fn new(operation_type: FileOperationType) -> Self {
         Self {
@@ -136,15 +223,30 @@ impl FileOperationProgress {
             is_active: false,
             cancel_flag: Arc::new(AtomicBool::new(false)),
             receiver: None,
+            conflict_sender: None,
             current_file: String::new(),
             current_file_progress: 0.0,
             total_files: 0,
             completed_files: 0,
             total_bytes: 0,
             completed_bytes: 0,
+            rate_samples: VecDeque::new(),
+            pending_conflict: None,
             result: None,
             last_error: None,
+            renamed_files: Vec::new(),
+            copied_instead_of_linked: Vec::new(),
+        }
+    }
+
+    /// Reply to the currently pending conflict, letting the worker thread
+    /// resume. `apply_to_all` makes the worker reuse `action` for any
+    /// further conflicts in this operation without asking again.
+    pub fn resolve_conflict(&mut self, action: ConflictAction, apply_to_all: bool) {
+        if let Some(sender) = &self.conflict_sender {
+            let _ = sender.send((action, apply_to_all));
         }
+        self.pending_conflict = None;
     }
 
     /// Cancel the ongoing operation
@@ -181,6 +283,12 @@ impl FileOperationProgress {
                                 self.total_files = total_files;
                                 self.completed_bytes = completed_bytes;
                                 self.total_bytes = total_bytes;
+
+                                let now = Instant::now();
+                                self.rate_samples.push_back((now, completed_bytes));
+                                while self.rate_samples.front().is_some_and(|&(t, _)| now.duration_since(t) > RATE_WINDOW) {
+                                    self.rate_samples.pop_front();
+                                }
                             }
                             ProgressMessage::Completed(success, failure) => {
                                 self.result = Some(FileOperationResult {
@@ -195,6 +303,18 @@ impl FileOperationProgress {
                                 // Store error for later (result is created on Completed)
                                 self.last_error = Some(err);
                             }
+                            ProgressMessage::Conflict(name, source, destination) => {
+                                self.pending_conflict = Some(PendingConflict { name, source, destination });
+                                // The worker is now blocked waiting for resolve_conflict();
+                                // stop draining until the caller answers.
+                                break;
+                            }
+                            ProgressMessage::Renamed(original_name, final_name) => {
+                                self.renamed_files.push((original_name, final_name));
+                            }
+                            ProgressMessage::CopiedInsteadOfLinked(name) => {
+                                self.copied_instead_of_linked.push(name);
+                            }
                         }
                     }
                     Err(mpsc::TryRecvError::Empty) => {
@@ -221,13 +341,139 @@ impl FileOperationProgress {
             0.0
         }
     }
+
+    /// Moving-average transfer rate in bytes/sec, averaged over the samples
+    /// collected in roughly the last `RATE_WINDOW`. `None` until at least two
+    /// samples have landed.
+    pub fn transfer_rate(&self) -> Option<f64> {
+        let (&(oldest_t, oldest_bytes), &(newest_t, newest_bytes)) =
+            self.rate_samples.front().zip(self.rate_samples.back())?;
+        let elapsed = newest_t.duration_since(oldest_t).as_secs_f64();
+        if elapsed <= 0.0 || newest_bytes <= oldest_bytes {
+            return None;
+        }
+        Some((newest_bytes - oldest_bytes) as f64 / elapsed)
+    }
+
+    /// Estimated time remaining based on the current transfer rate, or
+    /// `None` if the rate isn't known yet or there's nothing left to copy.
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.total_bytes.saturating_sub(self.completed_bytes);
+        if remaining == 0 {
+            return None;
+        }
+        let rate = self.transfer_rate()?;
+        Some(Duration::from_secs_f64(remaining as f64 / rate))
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct PathCompletion {
-    pub suggestions: Vec<String>,  // 자동완성 후보 목록
+    pub suggestions: Vec<CompletionEntry>,  // 자동완성 후보 목록
     pub selected_index: usize,     // 선택된 후보 인덱스
     pub visible: bool,             // 목록 표시 여부
+    /// 현재 선택된 항목의 미리보기. 렌더링은 `&Dialog`만 받기 때문에 내부
+    /// 가변성으로 경로별로 캐시해서, 같은 항목에 머물러 있는 동안은 매
+    /// 프레임 디스크를 다시 읽지 않는다.
+    pub preview_cache: RefCell<Option<(PathBuf, PreviewContent)>>,
+    /// Shift+Up/Down으로 표시한, `selected_index`와 별개인 범위 선택.
+    /// 비어 있으면 "단일 선택" 모드로 동작한다 (기존 동작 그대로).
+    pub marked_indices: HashSet<usize>,
+    /// Goto 다이얼로그 전용 zsh 스타일 인라인 자동완성: 현재 입력으로
+    /// 시작하는 방문 기록 중 recency+frequency 순위가 가장 높은 항목의
+    /// 나머지 부분. 드롭다운(`visible`)이 떠 있지 않을 때만 입력 줄
+    /// 바로 뒤에 흐리게 표시되고, Right/End로 그대로 입력에 붙는다.
+    pub history_ghost: Option<String>,
+}
+
+/// 완성 목록의 한 후보. 이름 외에 메타데이터 컬럼(크기/수정 시각)과,
+/// 퍼지/접두어 매칭된 문자 인덱스(목록에서 굵게 표시할 위치)를 들고 있다.
+#[derive(Debug, Clone, Default)]
+pub struct CompletionEntry {
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+    /// `%m-%d %H:%M`로 포맷된 수정 시각, 읽지 못했으면 빈 문자열.
+    pub modified: String,
+    pub match_positions: Vec<usize>,
+}
+
+impl CompletionEntry {
+    /// 디렉토리면 `/`를 붙인 이름 — 입력 줄에 적용하거나 미리보기 경로를
+    /// 만들 때 여전히 문자열 하나로 다루는 게 편한 기존 호출부를 위해.
+    pub fn display_name(&self) -> String {
+        if self.is_dir {
+            format!("{}/", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// czkawka 스타일의 허용/제외 확장자 필터. 허용 목록이 비어 있으면 모든
+/// 확장자를 통과시키고, 비어 있지 않으면 그 목록에 있는 것만 통과시킨다.
+/// 제외 목록은 항상 이긴다 (허용 목록에 있어도 제외되면 걸러진다).
+/// 디렉토리는 탐색 대상이므로 필터 대상이 아니다.
+#[derive(Debug, Clone, Default)]
+pub struct ExtensionFilter {
+    pub allowed: HashSet<String>,
+    pub excluded: HashSet<String>,
+}
+
+impl ExtensionFilter {
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.excluded.is_empty()
+    }
+
+    /// `name`이 필터를 통과하면 true. `is_dir`인 항목은 항상 통과한다.
+    pub fn passes(&self, name: &str, is_dir: bool) -> bool {
+        if is_dir || self.is_empty() {
+            return true;
+        }
+        let ext = Path::new(name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if self.excluded.contains(&ext) {
+            return false;
+        }
+        self.allowed.is_empty() || self.allowed.contains(&ext)
+    }
+
+    /// 쉼표로 구분된 확장자 목록(`"jpg,png,mp4"`)을 허용 목록으로 파싱해
+    /// 교체한다. 점(`.`)이 붙어 있어도, 공백이 섞여 있어도 무시한다.
+    pub fn set_allowed_from_str(&mut self, input: &str) {
+        self.allowed = Self::parse_extension_list(input);
+    }
+
+    pub fn set_excluded_from_str(&mut self, input: &str) {
+        self.excluded = Self::parse_extension_list(input);
+    }
+
+    fn parse_extension_list(input: &str) -> HashSet<String> {
+        input
+            .split(',')
+            .map(|s| s.trim().trim_start_matches('.').to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+}
+
+/// `draw_goto_dialog`/`draw_copy_move_dialog`의 완성 목록 옆에 표시되는,
+/// 현재 선택된 항목의 미리보기 내용.
+#[derive(Debug, Clone)]
+pub enum PreviewContent {
+    Directory {
+        entries: Vec<String>,
+        total: usize,
+    },
+    File {
+        lines: Vec<String>,
+        size: u64,
+        modified: String,
+        binary: bool,
+    },
+    Unavailable,
 }
 
 #[derive(Debug, Clone)]
@@ -245,20 +491,143 @@ pub struct FileItem {
     pub is_directory: bool,
     pub size: u64,
     pub modified: DateTime<Local>,
-    #[allow(dead_code)]
     pub permissions: String,
+    /// Indentation level in tree mode; 0 for top-level entries.
+    pub depth: u16,
+    /// Whether a directory's children are currently inlined below it.
+    /// Meaningless outside tree mode.
+    pub expanded: bool,
+    /// Whether this entry is a symlink, for `LsColors` resolution.
+    pub is_symlink: bool,
+    /// Whether this entry is a symlink whose target doesn't resolve.
+    /// Meaningless when `is_symlink` is false.
+    pub is_broken_symlink: bool,
+}
+
+impl FileItem {
+    /// Whether any of the Unix permission bits' executable positions is
+    /// set, from the `rwxr-xr-x`-style string `permissions` already holds.
+    pub fn is_executable(&self) -> bool {
+        self.permissions
+            .char_indices()
+            .any(|(i, c)| c == 'x' && matches!(i, 2 | 5 | 8))
+    }
+}
+
+/// What a panel's `path` is actually rooted in. Local is the default;
+/// mounting a `DialogType::Connect` URL switches a panel to Remote so its
+/// directory listing comes from the mounted session instead of `fs::`.
+#[derive(Debug, Clone)]
+pub enum PanelBackend {
+    Local,
+    Remote {
+        session: Arc<Mutex<RemoteSession>>,
+        target: RemoteTarget,
+    },
+}
+
+/// A scroll request against a panel's file list, modeled on broot: move by a
+/// signed number of lines, or by whole pages (a page being the list's
+/// `last_visible_height`). `PanelState::scroll` resolves either into a new
+/// `scroll_offset`/`selected_index` pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollCommand {
+    Lines(i32),
+    Pages(i32),
+}
+
+impl ScrollCommand {
+    /// Number of rows this command moves the view by, given `page_size`
+    /// (normally `panel.last_visible_height`).
+    fn delta(self, page_size: usize) -> i32 {
+        match self {
+            ScrollCommand::Lines(n) => n,
+            ScrollCommand::Pages(n) => n * page_size.max(1) as i32,
+        }
+    }
+}
+
+/// Background recursive size/file/dir-count walk of the directory currently
+/// highlighted in a panel, started by `PanelState::start_dir_size_calc` and
+/// drained once per frame by `PanelState::poll_dir_size_calc` so
+/// `panel::draw` can show a running total in the footer instead of the
+/// plain folder/file counts.
+#[derive(Debug)]
+pub struct DirSizeCalc {
+    pub path: PathBuf,
+    pub stats: dir_stats::DirStats,
+    pub done: bool,
+    receiver: Receiver<(dir_stats::DirStats, bool)>,
+}
+
+/// How a panel's live `quick_filter` query narrows `files`, chosen by the
+/// prefix `PanelState::quick_filter_mode` strips off: `/…/` for regex,
+/// `c/…` for a content search, `s/…` for a plain substring match, and
+/// anything else (the default, no prefix needed) for broot-style fuzzy
+/// subsequence matching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickFilterMode {
+    Substring,
+    Fuzzy,
+    Regex,
+    Content,
 }
 
 #[derive(Debug)]
 pub struct PanelState {
     pub path: PathBuf,
+    pub backend: PanelBackend,
     pub files: Vec<FileItem>,
     pub selected_index: usize,
     pub selected_files: HashSet<String>,
+    /// Cursor index Shift+Up/Shift+Down range-selection started from. Set by
+    /// the first shift-move, recomputed against on every subsequent one, and
+    /// cleared by a plain arrow move.
+    pub selection_anchor: Option<usize>,
     pub sort_by: SortBy,
     pub sort_order: SortOrder,
     pub scroll_offset: usize,
+    /// The file list's visible row count as of the last `draw`, cached so
+    /// `ScrollCommand::Pages` knows a page size outside of `draw` itself.
+    /// `0` until the first frame renders this panel.
+    pub last_visible_height: usize,
     pub pending_focus: Option<String>,
+
+    /// Remembers (selected_index, scroll_offset) for each directory visited,
+    /// so returning to it later restores exactly where the cursor was.
+    pub cursor_hist: HashMap<PathBuf, (usize, usize)>,
+    /// Back/forward navigation stack. `history[history_pos]` is the current
+    /// directory; entries after `history_pos` are the "forward" stack.
+    pub history: Vec<PathBuf>,
+    pub history_pos: usize,
+
+    /// When true, `files` holds a tree projection (parent directories
+    /// interleaved with their expanded children) instead of a flat listing.
+    pub tree_mode: bool,
+    /// Absolute paths of directories currently expanded in tree mode, kept
+    /// so a `load_files` refresh can re-expand them instead of collapsing.
+    pub expanded_dirs: HashSet<PathBuf>,
+
+    /// Live-narrowing query typed directly into the panel (broot-style
+    /// quick filter); empty means no filter is applied. `load_files` and
+    /// the quick-filter keystroke handlers keep `files` consistent with
+    /// this -- see `apply_quick_filter`/`refilter_quick_filter`. Doesn't
+    /// currently re-narrow entries spliced in by tree-mode expand/collapse,
+    /// since those operate on `files` directly.
+    pub quick_filter: String,
+    /// Whether the quick filter is capturing keystrokes right now; Esc
+    /// clears both this and `quick_filter`, Enter leaves typing mode but
+    /// keeps the filter applied.
+    pub quick_filter_active: bool,
+    /// Snapshot of `files` from the last `load_files`/`load_remote_files`,
+    /// before `quick_filter` narrowed it. Backspacing needs to restore
+    /// entries the filter hid, so each keystroke re-filters from here
+    /// rather than narrowing an already-narrowed list.
+    unfiltered_files: Vec<FileItem>,
+
+    /// Recursive size walk for a directory the user asked to total up, shown
+    /// in the footer while it runs. `None` once cleared by navigating away.
+    pub dir_size_calc: Option<DirSizeCalc>,
 }
 
 impl PanelState {
@@ -268,20 +637,107 @@ impl PanelState {
         let valid_path = get_valid_path(&path, &fallback);
 
         let mut state = Self {
-            path: valid_path,
+            path: valid_path.clone(),
+            backend: PanelBackend::Local,
             files: Vec::new(),
             selected_index: 0,
             selected_files: HashSet::new(),
+            selection_anchor: None,
             sort_by: SortBy::Name,
             sort_order: SortOrder::Asc,
             scroll_offset: 0,
+            last_visible_height: 0,
             pending_focus: None,
+            cursor_hist: HashMap::new(),
+            history: vec![valid_path],
+            history_pos: 0,
+            tree_mode: false,
+            expanded_dirs: HashSet::new(),
+            quick_filter: String::new(),
+            quick_filter_active: false,
+            unfiltered_files: Vec::new(),
+            dir_size_calc: None,
         };
         state.load_files();
         state
     }
 
+    /// Save the current cursor position for `self.path` so it can be
+    /// restored the next time this directory is visited.
+    fn remember_cursor(&mut self) {
+        self.cursor_hist.insert(self.path.clone(), (self.selected_index, self.scroll_offset));
+    }
+
+    /// Restore a previously remembered cursor position for `self.path`, if any.
+    fn restore_cursor(&mut self) {
+        if let Some(&(selected_index, scroll_offset)) = self.cursor_hist.get(&self.path) {
+            if selected_index < self.files.len() {
+                self.selected_index = selected_index;
+                self.scroll_offset = scroll_offset;
+            }
+        }
+    }
+
+    /// Navigate to `new_path`, remembering the cursor for the directory
+    /// being left, restoring it for `new_path` if previously visited, and
+    /// recording the move on the back/forward history stack (truncating any
+    /// forward entries, mirroring normal browser history semantics).
+    pub fn navigate_to(&mut self, new_path: PathBuf) {
+        self.remember_cursor();
+
+        self.history.truncate(self.history_pos + 1);
+        self.history.push(new_path.clone());
+        self.history_pos = self.history.len() - 1;
+
+        self.path = new_path;
+        self.selected_index = 0;
+        self.selected_files.clear();
+        self.selection_anchor = None;
+        self.load_files();
+        self.restore_cursor();
+    }
+
+    /// Move back one entry in the navigation history, if possible.
+    pub fn history_back(&mut self) -> bool {
+        if self.history_pos == 0 {
+            return false;
+        }
+        self.remember_cursor();
+        self.history_pos -= 1;
+        self.path = self.history[self.history_pos].clone();
+        self.selected_files.clear();
+        self.selection_anchor = None;
+        self.load_files();
+        self.restore_cursor();
+        true
+    }
+
+    /// Move forward one entry in the navigation history, if possible.
+    pub fn history_forward(&mut self) -> bool {
+        if self.history_pos + 1 >= self.history.len() {
+            return false;
+        }
+        self.remember_cursor();
+        self.history_pos += 1;
+        self.path = self.history[self.history_pos].clone();
+        self.selected_files.clear();
+        self.selection_anchor = None;
+        self.load_files();
+        self.restore_cursor();
+        true
+    }
+
     pub fn load_files(&mut self) {
+        // A size walk in progress belongs to whatever directory it was
+        // started for; reloading (navigation, refresh, sort) leaves it
+        // pointed at an entry that's no longer listed, so drop it.
+        self.dir_size_calc = None;
+
+        if matches!(self.backend, PanelBackend::Remote { .. }) {
+            self.load_remote_files();
+            return;
+        }
+
         self.files.clear();
 
         // Add parent directory entry if not at root
@@ -292,6 +748,10 @@ impl PanelState {
                 size: 0,
                 modified: Local::now(),
                 permissions: String::new(),
+                depth: 0,
+                expanded: false,
+                is_symlink: false,
+                is_broken_symlink: false,
             });
         }
 
@@ -303,6 +763,8 @@ impl PanelState {
             items.extend(entries.into_iter().filter_map(|entry| {
                     let name = entry.file_name().to_string_lossy().to_string();
                     let metadata = entry.metadata().ok()?;
+                    let is_symlink = metadata.file_type().is_symlink();
+                    let is_broken_symlink = is_symlink && fs::metadata(entry.path()).is_err();
                     let is_directory = metadata.is_dir();
                     let size = if is_directory { 0 } else { metadata.len() };
                     let modified = metadata.modified().ok()
@@ -324,30 +786,14 @@ impl PanelState {
                         size,
                         modified,
                         permissions,
+                        depth: 0,
+                        expanded: false,
+                        is_symlink,
+                        is_broken_symlink,
                     })
                 }));
 
-            // Sort files
-            items.sort_by(|a, b| {
-                // Directories always first
-                if a.is_directory && !b.is_directory {
-                    return std::cmp::Ordering::Less;
-                }
-                if !a.is_directory && b.is_directory {
-                    return std::cmp::Ordering::Greater;
-                }
-
-                let cmp = match self.sort_by {
-                    SortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-                    SortBy::Size => a.size.cmp(&b.size),
-                    SortBy::Modified => a.modified.cmp(&b.modified),
-                };
-
-                match self.sort_order {
-                    SortOrder::Asc => cmp,
-                    SortOrder::Desc => cmp.reverse(),
-                }
-            });
+            items.sort_by(|a, b| compare_file_items(a, b, self.sort_by, self.sort_order));
 
             self.files.reserve(items.len());
             self.files.extend(items);
@@ -364,6 +810,352 @@ impl PanelState {
         if self.selected_index >= self.files.len() && !self.files.is_empty() {
             self.selected_index = self.files.len() - 1;
         }
+
+        // Re-expand directories that were expanded before the refresh, so a
+        // background reload doesn't silently collapse the tree.
+        if self.tree_mode && !self.expanded_dirs.is_empty() {
+            let to_expand: Vec<PathBuf> = self.expanded_dirs.iter().cloned().collect();
+            for dir in to_expand {
+                if let Some(idx) = self.files.iter().position(|f| self.path.join(&f.name) == dir && f.is_directory) {
+                    self.expand_node(idx);
+                }
+            }
+        }
+
+        self.apply_quick_filter();
+    }
+
+    /// `load_files`'s counterpart for a mounted remote panel: lists
+    /// `self.path` through the session instead of `fs::read_dir`. Tree mode
+    /// and cursor history aren't supported on remote panels yet, so this
+    /// always produces a flat listing.
+    fn load_remote_files(&mut self) {
+        let PanelBackend::Remote { session, .. } = &self.backend else { return };
+
+        self.files.clear();
+        if self.path.parent().is_some() {
+            self.files.push(FileItem {
+                name: "..".to_string(),
+                is_directory: true,
+                size: 0,
+                modified: Local::now(),
+                permissions: String::new(),
+                depth: 0,
+                expanded: false,
+                is_symlink: false,
+                is_broken_symlink: false,
+            });
+        }
+
+        let entries = match session.lock() {
+            Ok(mut session) => session.list_dir(&self.path).unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+
+        let mut items: Vec<FileItem> = entries
+            .into_iter()
+            .map(|entry| FileItem {
+                name: entry.name,
+                is_directory: entry.is_dir,
+                size: entry.size,
+                modified: entry.modified.map(DateTime::<Local>::from).unwrap_or_else(Local::now),
+                permissions: String::new(),
+                depth: 0,
+                expanded: false,
+                is_symlink: false,
+                is_broken_symlink: false,
+            })
+            .collect();
+
+        items.sort_by(|a, b| compare_file_items(a, b, self.sort_by, self.sort_order));
+        self.files.extend(items);
+
+        if self.selected_index >= self.files.len() && !self.files.is_empty() {
+            self.selected_index = self.files.len() - 1;
+        }
+
+        self.apply_quick_filter();
+    }
+
+    /// Read the immediate children of the directory at `self.files[index]`
+    /// and splice them in directly below it at `parent.depth + 1`, sorted
+    /// with the same directories-first comparator used at the top level.
+    fn expand_node(&mut self, index: usize) {
+        let Some(parent) = self.files.get(index) else { return };
+        if !parent.is_directory || parent.name == ".." || parent.expanded {
+            return;
+        }
+        let child_depth = parent.depth + 1;
+        let dir_path = self.path.join(&parent.name);
+
+        let mut children: Vec<FileItem> = match fs::read_dir(&dir_path) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let metadata = entry.metadata().ok()?;
+                    let is_symlink = metadata.file_type().is_symlink();
+                    let is_broken_symlink = is_symlink && fs::metadata(entry.path()).is_err();
+                    let is_directory = metadata.is_dir();
+                    let size = if is_directory { 0 } else { metadata.len() };
+                    let modified = metadata.modified().ok()
+                        .map(DateTime::<Local>::from)
+                        .unwrap_or_else(Local::now);
+
+                    #[cfg(unix)]
+                    let permissions = {
+                        use std::os::unix::fs::PermissionsExt;
+                        let mode = metadata.permissions().mode();
+                        crate::utils::format::format_permissions_short(mode)
+                    };
+                    #[cfg(not(unix))]
+                    let permissions = String::new();
+
+                    Some(FileItem {
+                        name,
+                        is_directory,
+                        size,
+                        modified,
+                        permissions,
+                        depth: child_depth,
+                        expanded: false,
+                        is_symlink,
+                        is_broken_symlink,
+                    })
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        children.sort_by(|a, b| compare_file_items(a, b, self.sort_by, self.sort_order));
+
+        self.files[index].expanded = true;
+        self.expanded_dirs.insert(dir_path);
+        self.files.splice(index + 1..index + 1, children);
+    }
+
+    /// Remove all contiguous entries below `index` whose depth exceeds the
+    /// parent's, collapsing it back to a single row.
+    fn collapse_node(&mut self, index: usize) {
+        let Some(parent) = self.files.get(index) else { return };
+        if !parent.expanded {
+            return;
+        }
+        let parent_depth = parent.depth;
+        let dir_path = self.path.join(&parent.name);
+
+        let end = self.files[index + 1..]
+            .iter()
+            .position(|f| f.depth <= parent_depth)
+            .map(|offset| index + 1 + offset)
+            .unwrap_or(self.files.len());
+
+        self.files.drain(index + 1..end);
+        self.files[index].expanded = false;
+        self.expanded_dirs.remove(&dir_path);
+    }
+
+    /// Toggle expand/collapse of the directory under the cursor (tree mode
+    /// only). No-op for files or the ".." entry.
+    pub fn toggle_tree_node(&mut self) {
+        if !self.tree_mode {
+            return;
+        }
+        let index = self.selected_index;
+        match self.files.get(index) {
+            Some(f) if f.is_directory && f.name != ".." && f.expanded => self.collapse_node(index),
+            Some(f) if f.is_directory && f.name != ".." => self.expand_node(index),
+            _ => {}
+        }
+    }
+
+    /// Switch between flat listing and tree view. Entering tree mode starts
+    /// fully collapsed; leaving it forgets which directories were expanded.
+    pub fn toggle_tree_mode(&mut self) {
+        self.tree_mode = !self.tree_mode;
+        if !self.tree_mode {
+            self.expanded_dirs.clear();
+            self.load_files();
+        }
+    }
+
+    /// Resolve a `ScrollCommand` against this panel's current
+    /// `scroll_offset`, clamping the new offset to
+    /// `0..=total.saturating_sub(last_visible_height)`. Mirrors broot's edge
+    /// behavior: if the view is already pinned at the top/bottom and the
+    /// command pushes further the blocked way, move the cursor to the
+    /// first/last entry instead of leaving it in place.
+    pub fn scroll(&mut self, command: ScrollCommand) {
+        if self.files.is_empty() {
+            return;
+        }
+
+        let total = self.files.len();
+        let page_size = self.last_visible_height.max(1);
+        let max_offset = total.saturating_sub(page_size) as i32;
+        let target = self.scroll_offset as i32 + command.delta(page_size);
+
+        if target < 0 && self.scroll_offset == 0 {
+            self.selected_index = 0;
+            self.selection_anchor = None;
+            return;
+        }
+        if target > max_offset && self.scroll_offset as i32 == max_offset {
+            self.selected_index = total - 1;
+            self.selection_anchor = None;
+            return;
+        }
+
+        let new_offset = target.clamp(0, max_offset) as usize;
+        self.scroll_offset = new_offset;
+        self.selected_index = self
+            .selected_index
+            .clamp(new_offset, new_offset + page_size.saturating_sub(1).min(total - 1));
+        self.selection_anchor = None;
+    }
+
+    /// Start a recursive size/file/dir-count walk of the currently
+    /// highlighted directory on a background thread, so the footer can show
+    /// a running total. A no-op on `..` or a regular file.
+    pub fn start_dir_size_calc(&mut self) {
+        let Some(file) = self.current_file() else { return; };
+        if !file.is_directory || file.name == ".." {
+            return;
+        }
+
+        let path = self.path.join(&file.name);
+        let walk_path = path.clone();
+        let (tx, rx) = mpsc::channel();
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+
+        thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let result = dir_stats::calculate_dir_stats(&walk_path, &cancel_flag, |stats| {
+                let _ = progress_tx.send((stats, false));
+            });
+            if let Ok(stats) = result {
+                let _ = tx.send((stats, true));
+            }
+        });
+
+        self.dir_size_calc = Some(DirSizeCalc {
+            path,
+            stats: dir_stats::DirStats::default(),
+            done: false,
+            receiver: rx,
+        });
+    }
+
+    /// Drain any pending progress from `start_dir_size_calc`'s background
+    /// walk. Called once per frame from `panel::draw`.
+    pub fn poll_dir_size_calc(&mut self) {
+        if let Some(calc) = self.dir_size_calc.as_mut() {
+            while let Ok((stats, done)) = calc.receiver.try_recv() {
+                calc.stats = stats;
+                calc.done = done;
+            }
+        }
+    }
+
+    /// Split `quick_filter` into its mode and the query the mode matches
+    /// against -- see `QuickFilterMode` for what each prefix selects.
+    pub fn quick_filter_mode(&self) -> (QuickFilterMode, &str) {
+        let q = self.quick_filter.as_str();
+        if q.len() >= 2 && q.starts_with('/') && q.ends_with('/') {
+            (QuickFilterMode::Regex, &q[1..q.len() - 1])
+        } else if let Some(rest) = q.strip_prefix("c/") {
+            (QuickFilterMode::Content, rest)
+        } else if let Some(rest) = q.strip_prefix("s/") {
+            (QuickFilterMode::Substring, rest)
+        } else {
+            (QuickFilterMode::Fuzzy, q)
+        }
+    }
+
+    /// Start capturing keystrokes into `quick_filter`.
+    pub fn start_quick_filter(&mut self) {
+        self.quick_filter_active = true;
+    }
+
+    /// Stop capturing keystrokes but leave the current filter applied.
+    pub fn stop_quick_filter_typing(&mut self) {
+        self.quick_filter_active = false;
+    }
+
+    /// Clear the filter entirely and restore the unfiltered listing.
+    pub fn clear_quick_filter(&mut self) {
+        self.quick_filter.clear();
+        self.quick_filter_active = false;
+        self.refilter_quick_filter();
+    }
+
+    pub fn push_quick_filter_char(&mut self, c: char) {
+        self.quick_filter.push(c);
+        self.refilter_quick_filter();
+    }
+
+    pub fn pop_quick_filter_char(&mut self) {
+        self.quick_filter.pop();
+        self.refilter_quick_filter();
+    }
+
+    /// Snapshot `files` as the unfiltered listing and apply whatever quick
+    /// filter is already set -- called after `load_files`/
+    /// `load_remote_files` rebuild `files` from scratch.
+    fn apply_quick_filter(&mut self) {
+        self.unfiltered_files = self.files.clone();
+        self.refilter_quick_filter();
+    }
+
+    /// Recompute `files` from `unfiltered_files` under the current
+    /// `quick_filter`, without touching the filesystem. Used for every
+    /// filter keystroke so backspacing restores entries a narrower query
+    /// had hidden.
+    fn refilter_quick_filter(&mut self) {
+        self.files = self.unfiltered_files.clone();
+        let (mode, query) = self.quick_filter_mode();
+        if query.is_empty() {
+            return;
+        }
+        let query = query.to_string();
+
+        match mode {
+            QuickFilterMode::Fuzzy => {
+                let mut scored: Vec<(i64, FileItem)> = self
+                    .files
+                    .iter()
+                    .filter(|f| f.name != "..")
+                    .filter_map(|f| {
+                        crate::ui::advanced_search::fuzzy_match(&f.name, &query)
+                            .map(|(score, _)| (score, f.clone()))
+                    })
+                    .collect();
+                scored.sort_by(|a, b| b.0.cmp(&a.0));
+                self.files = scored.into_iter().map(|(_, f)| f).collect();
+            }
+            QuickFilterMode::Substring => {
+                let needle = query.to_lowercase();
+                self.files
+                    .retain(|f| f.name != ".." && f.name.to_lowercase().contains(&needle));
+            }
+            QuickFilterMode::Regex => match regex::Regex::new(&query) {
+                Ok(re) => self.files.retain(|f| f.name != ".." && re.is_match(&f.name)),
+                Err(_) => self.files.clear(),
+            },
+            QuickFilterMode::Content => {
+                let base = self.path.clone();
+                self.files.retain(|f| {
+                    f.name != ".."
+                        && !f.is_directory
+                        && !crate::ui::advanced_search::matches_contents(&base.join(&f.name), &query)
+                            .is_empty()
+                });
+            }
+        }
+
+        if self.selected_index >= self.files.len() {
+            self.selected_index = self.files.len().saturating_sub(1);
+        }
     }
 
     pub fn current_file(&self) -> Option<&FileItem> {
@@ -415,6 +1207,9 @@ pub struct App {
 
     // File editor state (새로운 고급 상태)
     pub editor_state: Option<EditorState>,
+    /// Original filenames being bulk-renamed, set while `editor_state` holds
+    /// the one-name-per-line buffer opened by `show_bulk_rename`.
+    pub bulk_rename_files: Option<Vec<String>>,
 
     // File editor state (레거시 호환용 - 제거 예정)
     #[allow(dead_code)]
@@ -433,14 +1228,21 @@ pub struct App {
     // File info state
     pub info_file_path: PathBuf,
     pub file_info_state: Option<FileInfoState>,
+    /// Recursive directory stats keyed by path, valid as long as the
+    /// directory's mtime hasn't changed since the walk. Lets reopening the
+    /// info dialog on the same directory skip the background walk entirely.
+    pub dir_stats_cache: HashMap<PathBuf, (std::time::SystemTime, crate::services::dir_stats::DirStats)>,
 
     // Process manager state
-    pub processes: Vec<crate::services::process::ProcessInfo>,
-    pub process_selected_index: usize,
-    pub process_sort_field: crate::services::process::SortField,
-    pub process_sort_asc: bool,
-    pub process_confirm_kill: Option<i32>,
-    pub process_force_kill: bool,
+    pub process_monitor: crate::services::process::ProcessMonitorState,
+
+    // Filesystems screen state
+    pub mounts: Vec<crate::services::filesystems::MountInfo>,
+    pub mounts_selected_index: usize,
+
+    // Trash screen state
+    pub trash_entries: Vec<crate::services::trash::TrashEntry>,
+    pub trash_selected_index: usize,
 
     // AI screen state
     pub ai_state: Option<crate::ui::ai_screen::AIScreenState>,
@@ -472,13 +1274,65 @@ pub struct App {
 
     // File operation progress state
     pub file_operation_progress: Option<FileOperationProgress>,
+
+    // Filesystem watchers, one per panel, re-armed whenever the panel's path changes
+    pub left_watcher: Option<DirWatcher>,
+    pub right_watcher: Option<DirWatcher>,
+
+    // Bookmarked directories, persisted under the platform config dir
+    pub bookmarks: Bookmarks,
+    /// True while the bookmarks popup is in "press a letter to save here"
+    /// mode; false while it's in "press a letter to jump" mode.
+    pub bookmark_add_mode: bool,
+
+    /// Cross-directory batch selection for copy/move. Unlike a panel's
+    /// `selected_files`, entries survive navigating to a different
+    /// directory, so the user can collect files from several places before
+    /// acting on all of them at once.
+    pub stage: Stage,
+
+    /// Allowed/excluded extension set applied to the Goto/Copy/Move path
+    /// completion dropdown (directories always pass through).
+    pub extension_filter: ExtensionFilter,
+
+    /// When true, a file about to be overwritten by rename or paste is
+    /// first moved aside to a numbered backup (`name.~1~`, `name.~2~`, ...)
+    /// via `file_ops::backup_existing`, mirroring `mv --backup=numbered`.
+    /// Off by default, matching `mv`'s own default.
+    pub backup_on_overwrite: bool,
+
+    /// When true, a copied/moved file is re-read and hash-compared against
+    /// its source once the copy finishes (`file_ops::verify_copy`), and a
+    /// mismatch is surfaced as an error instead of silently trusting the
+    /// byte count. For cut operations this also blocks deleting the source.
+    /// Only items that are themselves single files are checked; the files
+    /// inside a copied or moved directory are not.
+    pub verify_after_copy: bool,
+
+    /// When true, a copy/move destination that already exists is silently
+    /// renamed (`file_ops::resolve_filename_conflict`) instead of failing
+    /// outright (`copy_file`/`move_file`) or prompting the user
+    /// (`copy_files_with_progress`/`move_files_with_progress`). Off by
+    /// default so existing conflict-handling behavior is unchanged.
+    pub auto_rename_on_conflict: bool,
+
+    /// Recency+frequency ring of directories visited via the Goto dialog
+    /// (`execute_goto`/`goto_directory_with_focus`), persisted under the
+    /// platform config dir. Backs both the dialog's empty-input suggestion
+    /// list and its inline ghost-suffix autosuggestion.
+    pub path_history: crate::services::path_history::PathHistory,
 }
 
 impl App {
     pub fn new(left_path: PathBuf, right_path: PathBuf) -> Self {
+        let left_panel = PanelState::new(left_path);
+        let right_panel = PanelState::new(right_path);
+        let left_watcher = DirWatcher::new(&left_panel.path).ok();
+        let right_watcher = DirWatcher::new(&right_panel.path).ok();
+
         Self {
-            left_panel: PanelState::new(left_path),
-            right_panel: PanelState::new(right_path),
+            left_panel,
+            right_panel,
             active_panel: PanelSide::Left,
             current_screen: Screen::DualPanel,
             dialog: None,
@@ -488,6 +1342,7 @@ impl App {
             // 새로운 고급 상태
             viewer_state: None,
             editor_state: None,
+            bulk_rename_files: None,
 
             // 레거시 호환용
             viewer_lines: Vec::new(),
@@ -507,13 +1362,15 @@ impl App {
 
             info_file_path: PathBuf::new(),
             file_info_state: None,
+            dir_stats_cache: HashMap::new(),
+
+            process_monitor: crate::services::process::ProcessMonitorState::new(),
+
+            mounts: Vec::new(),
+            mounts_selected_index: 0,
 
-            processes: Vec::new(),
-            process_selected_index: 0,
-            process_sort_field: crate::services::process::SortField::Cpu,
-            process_sort_asc: false,
-            process_confirm_kill: None,
-            process_force_kill: false,
+            trash_entries: Vec::new(),
+            trash_selected_index: 0,
 
             ai_state: None,
             saved_ai_history: Vec::new(),
@@ -526,6 +1383,84 @@ impl App {
             previous_screen: None,
             clipboard: None,
             file_operation_progress: None,
+            left_watcher,
+            right_watcher,
+
+            bookmarks: Bookmarks::load(),
+            bookmark_add_mode: false,
+
+            stage: Stage::default(),
+            extension_filter: ExtensionFilter::default(),
+
+            backup_on_overwrite: false,
+            verify_after_copy: false,
+            auto_rename_on_conflict: false,
+
+            path_history: crate::services::path_history::PathHistory::load(),
+        }
+    }
+
+    /// Toggle whether rename/paste overwrites back up the existing file
+    /// first (see `backup_on_overwrite`).
+    pub fn toggle_backup_on_overwrite(&mut self) {
+        self.backup_on_overwrite = !self.backup_on_overwrite;
+        let state = if self.backup_on_overwrite { "on" } else { "off" };
+        self.show_message(&format!("Backup on overwrite: {}", state));
+    }
+
+    /// Toggle post-copy content-hash verification (see `verify_after_copy`).
+    pub fn toggle_verify_after_copy(&mut self) {
+        self.verify_after_copy = !self.verify_after_copy;
+        let state = if self.verify_after_copy { "on" } else { "off" };
+        self.show_message(&format!("Verify after copy: {}", state));
+    }
+
+    /// Toggle silently auto-renaming conflicting copy/move destinations
+    /// instead of failing or prompting (see `auto_rename_on_conflict`).
+    pub fn toggle_auto_rename_on_conflict(&mut self) {
+        self.auto_rename_on_conflict = !self.auto_rename_on_conflict;
+        let state = if self.auto_rename_on_conflict { "on" } else { "off" };
+        self.show_message(&format!("Auto-rename on conflict: {}", state));
+    }
+
+    /// Re-arm the watcher for one side after its panel navigates to a new
+    /// directory. Dropping the old `DirWatcher` tears down its inotify (or
+    /// platform equivalent) handle before the new one is installed.
+    fn rearm_watcher(&mut self, side: PanelSide) {
+        let path = match side {
+            PanelSide::Left => self.left_panel.path.clone(),
+            PanelSide::Right => self.right_panel.path.clone(),
+        };
+        let watcher = DirWatcher::new(&path).ok();
+        match side {
+            PanelSide::Left => self.left_watcher = watcher,
+            PanelSide::Right => self.right_watcher = watcher,
+        }
+    }
+
+    /// Poll both panel watchers and reload any panel whose directory changed
+    /// on disk. Call once per frame. Returns true if either panel reloaded.
+    pub fn poll_watchers(&mut self) -> bool {
+        let left_changed = self.left_watcher.as_ref().map(DirWatcher::poll).unwrap_or(false);
+        let right_changed = self.right_watcher.as_ref().map(DirWatcher::poll).unwrap_or(false);
+
+        if left_changed {
+            reload_preserving_cursor(&mut self.left_panel);
+        }
+        if right_changed {
+            reload_preserving_cursor(&mut self.right_panel);
+        }
+
+        left_changed || right_changed
+    }
+
+    /// Auto-refresh the process monitor's snapshot once its refresh
+    /// interval has elapsed. Call once per frame while `ProcessManager` is
+    /// the active screen; a no-op otherwise keeps this cheap to call
+    /// unconditionally.
+    pub fn poll_process_monitor(&mut self) {
+        if self.current_screen == Screen::ProcessManager {
+            self.process_monitor.tick();
         }
     }
 
@@ -588,6 +1523,41 @@ impl App {
             .max(0)
             .min(panel.files.len().saturating_sub(1) as i32) as usize;
         panel.selected_index = new_index;
+        panel.selection_anchor = None;
+    }
+
+    /// Extend the range selection upward by one entry (Shift+Up). The first
+    /// call anchors the range at the current cursor; every call recomputes
+    /// `selected_files` as every filename between the anchor and the new
+    /// cursor position, inclusive, skipping `".."`.
+    pub fn select_range_up(&mut self) {
+        self.select_range(-1);
+    }
+
+    /// Extend the range selection downward by one entry (Shift+Down). See
+    /// [`select_range_up`] for the anchor semantics.
+    pub fn select_range_down(&mut self) {
+        self.select_range(1);
+    }
+
+    fn select_range(&mut self, delta: i32) {
+        let panel = self.active_panel_mut();
+        if panel.files.is_empty() {
+            return;
+        }
+        let anchor = *panel.selection_anchor.get_or_insert(panel.selected_index);
+        let new_index = (panel.selected_index as i32 + delta)
+            .max(0)
+            .min(panel.files.len().saturating_sub(1) as i32) as usize;
+        panel.selected_index = new_index;
+
+        let (start, end) = if anchor <= new_index { (anchor, new_index) } else { (new_index, anchor) };
+        panel.selected_files.clear();
+        for file in &panel.files[start..=end] {
+            if file.name != ".." {
+                panel.selected_files.insert(file.name.clone());
+            }
+        }
     }
 
     pub fn cursor_to_start(&mut self) {
@@ -605,22 +1575,29 @@ impl App {
         let panel = self.active_panel_mut();
         if let Some(file) = panel.current_file().cloned() {
             if file.is_directory {
+                if panel.tree_mode && file.name != ".." {
+                    panel.toggle_tree_node();
+                    return;
+                }
+                let mut navigated = false;
                 if file.name == ".." {
-                    // Go to parent - remember current directory name
+                    // Go to parent - remember current directory name as a
+                    // fallback focus target for directories visited for the
+                    // first time (no cursor_hist entry yet)
                     if let Some(current_name) = panel.path.file_name() {
                         panel.pending_focus = Some(current_name.to_string_lossy().to_string());
                     }
-                    if let Some(parent) = panel.path.parent() {
-                        panel.path = parent.to_path_buf();
-                        panel.selected_index = 0;
-                        panel.selected_files.clear();
-                        panel.load_files();
+                    if let Some(parent) = panel.path.parent().map(|p| p.to_path_buf()) {
+                        panel.navigate_to(parent);
+                        navigated = true;
                     }
                 } else {
-                    panel.path = panel.path.join(&file.name);
-                    panel.selected_index = 0;
-                    panel.selected_files.clear();
-                    panel.load_files();
+                    let target = panel.path.join(&file.name);
+                    panel.navigate_to(target);
+                    navigated = true;
+                }
+                if navigated {
+                    self.rearm_watcher(self.active_panel);
                 }
             } else {
                 // It's a file - open viewer (text or image)
@@ -634,11 +1611,9 @@ impl App {
         if let Some(current_name) = panel.path.file_name() {
             panel.pending_focus = Some(current_name.to_string_lossy().to_string());
         }
-        if let Some(parent) = panel.path.parent() {
-            panel.path = parent.to_path_buf();
-            panel.selected_index = 0;
-            panel.selected_files.clear();
-            panel.load_files();
+        if let Some(parent) = panel.path.parent().map(|p| p.to_path_buf()) {
+            panel.navigate_to(parent);
+            self.rearm_watcher(self.active_panel);
         }
     }
 
@@ -674,6 +1649,17 @@ impl App {
         }
     }
 
+    /// Toggle the current file in and out of the cross-directory stage.
+    pub fn toggle_stage_current(&mut self) {
+        let panel = self.active_panel();
+        if let Some(file) = panel.current_file() {
+            if file.name != ".." {
+                let path = panel.path.join(&file.name);
+                self.stage.toggle(path);
+            }
+        }
+    }
+
     pub fn toggle_sort_by_name(&mut self) {
         self.active_panel_mut().toggle_sort(SortBy::Name);
     }
@@ -740,14 +1726,28 @@ impl App {
 
         self.info_file_path = file_path.clone();
 
-        // For directories, start async size calculation
+        // Directories get an async recursive size calculation; files get
+        // async EXIF extraction and content hashing. Both run on a
+        // background thread so the UI stays responsive on large trees/files.
+        // A directory whose mtime matches a cached walk skips the thread
+        // entirely and shows the cached totals immediately.
+        let mut state = FileInfoState::new();
         if is_directory {
-            let mut state = FileInfoState::new();
-            state.start_calculation(&file_path);
-            self.file_info_state = Some(state);
+            let mtime = fs::metadata(&file_path).and_then(|m| m.modified()).ok();
+            let cached = mtime.and_then(|mtime| {
+                self.dir_stats_cache
+                    .get(&file_path)
+                    .filter(|(cached_mtime, _)| *cached_mtime == mtime)
+                    .map(|(_, stats)| *stats)
+            });
+            match cached {
+                Some(stats) => state.set_cached_stats(stats),
+                None => state.start_calculation(&file_path),
+            }
         } else {
-            self.file_info_state = None;
+            state.start_file_analysis(&file_path);
         }
+        self.file_info_state = Some(state);
 
         self.current_screen = Screen::FileInfo;
     }
@@ -896,7 +1896,7 @@ impl App {
         self.dialog = Some(Dialog {
             dialog_type: DialogType::Delete,
             input: String::new(),
-            message: format!("Delete {}?", file_list),
+            message: format!("Move {} to trash? (Shift+Enter: delete permanently)", file_list),
             completion: None,
             selected_button: 1,  // 기본값: No (안전을 위해)
         });
@@ -913,6 +1913,11 @@ impl App {
     }
 
     pub fn show_rename_dialog(&mut self) {
+        if self.active_panel().selected_files.len() > 1 {
+            self.show_bulk_rename();
+            return;
+        }
+
         let panel = self.active_panel();
         if let Some(file) = panel.current_file() {
             if file.name != ".." {
@@ -929,6 +1934,87 @@ impl App {
         }
     }
 
+    /// Open the selected filenames, one per line, in the advanced editor so
+    /// the user can bulk-rename them by editing the buffer and saving.
+    fn show_bulk_rename(&mut self) {
+        let files = self.get_operation_files();
+        if files.len() < 2 {
+            self.show_message("Select multiple files to bulk rename");
+            return;
+        }
+
+        let mut editor = EditorState::new();
+        editor.set_lines(files.clone());
+        self.bulk_rename_files = Some(files);
+        self.editor_state = Some(editor);
+        self.current_screen = Screen::FileEditor;
+    }
+
+    /// Read the bulk-rename buffer back, pair lines positionally with the
+    /// original selection, and rename every file whose line changed.
+    /// Rejects the whole batch on a line-count mismatch, a duplicate target
+    /// name, or a collision with an existing file.
+    pub fn execute_bulk_rename(&mut self) {
+        let originals = match self.bulk_rename_files.take() {
+            Some(v) => v,
+            None => return,
+        };
+        let new_names = match &self.editor_state {
+            Some(state) => state.lines(),
+            None => return,
+        };
+
+        if new_names.len() != originals.len() {
+            self.show_message("Bulk rename cancelled: line count no longer matches selection");
+            return;
+        }
+
+        for name in &new_names {
+            if let Err(e) = file_ops::is_valid_filename(name) {
+                self.show_message(&format!("Bulk rename cancelled: {}", e));
+                return;
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for name in &new_names {
+            if !seen.insert(name) {
+                self.show_message(&format!("Bulk rename cancelled: duplicate name '{}'", name));
+                return;
+            }
+        }
+
+        let dir = self.active_panel().path.clone();
+        let originals_set: HashSet<&String> = originals.iter().collect();
+        for name in &new_names {
+            if !originals_set.contains(name) && dir.join(name).exists() {
+                self.show_message(&format!("Bulk rename cancelled: '{}' already exists", name));
+                return;
+            }
+        }
+
+        let mut renamed = 0;
+        let mut last_error = String::new();
+        for (old, new) in originals.iter().zip(new_names.iter()) {
+            if old == new {
+                continue;
+            }
+            match file_ops::rename_file(&dir.join(old), &dir.join(new), file_ops::ConflictPolicy::Error) {
+                Ok(_) => renamed += 1,
+                Err(e) => last_error = e.to_string(),
+            }
+        }
+
+        if last_error.is_empty() {
+            self.show_message(&format!("Renamed {} file(s)", renamed));
+        } else {
+            self.show_message(&format!("Renamed {}/{} file(s). Error: {}", renamed, originals.len(), last_error));
+        }
+
+        self.editor_state = None;
+        self.current_screen = Screen::DualPanel;
+    }
+
     pub fn show_search_dialog(&mut self) {
         self.dialog = Some(Dialog {
             dialog_type: DialogType::Search,
@@ -951,9 +2037,9 @@ impl App {
     }
 
     pub fn show_process_manager(&mut self) {
-        self.processes = crate::services::process::get_process_list();
-        self.process_selected_index = 0;
-        self.process_confirm_kill = None;
+        self.process_monitor.selected_index = 0;
+        self.process_monitor.confirm_kill = None;
+        self.process_monitor.refresh();
         self.current_screen = Screen::ProcessManager;
     }
 
@@ -986,6 +2072,56 @@ impl App {
         self.current_screen = Screen::SystemInfo;
     }
 
+    pub fn show_filesystems(&mut self) {
+        self.mounts = crate::services::filesystems::list_mounts();
+        self.mounts_selected_index = 0;
+        self.current_screen = Screen::Filesystems;
+    }
+
+    /// Open the trash browser, listing everything `crate::services::trash`
+    /// currently holds for this app (separate from the OS trash/recycle bin
+    /// `execute_delete` uses by default).
+    pub fn show_trash(&mut self) {
+        self.trash_entries = crate::services::trash::list_trash();
+        self.trash_selected_index = 0;
+        self.current_screen = Screen::Trash;
+    }
+
+    /// Restore the entry under the cursor back to where it was trashed
+    /// from, then refresh the list.
+    pub fn restore_selected_trash_entry(&mut self) {
+        let Some(entry) = self.trash_entries.get(self.trash_selected_index) else {
+            return;
+        };
+        match crate::services::trash::restore_from_trash(&entry.id) {
+            Ok(path) => self.show_message(&format!("Restored to {}", path.display())),
+            Err(e) => self.show_message(&format!("Restore failed: {}", e)),
+        }
+        self.trash_entries = crate::services::trash::list_trash();
+        self.trash_selected_index = self.trash_selected_index.min(self.trash_entries.len().saturating_sub(1));
+    }
+
+    /// Permanently delete everything currently in the trash.
+    pub fn empty_trash_now(&mut self) {
+        match crate::services::trash::empty_trash() {
+            Ok(count) => self.show_message(&format!("Emptied {} item(s) from trash", count)),
+            Err(e) => self.show_message(&format!("Empty trash failed: {}", e)),
+        }
+        self.trash_entries = crate::services::trash::list_trash();
+        self.trash_selected_index = 0;
+    }
+
+    /// Switch the active panel to the currently selected mountpoint and
+    /// return to the dual-panel view, mirroring `enter_selected`'s
+    /// navigate-then-rearm-watcher sequence.
+    pub fn enter_selected_mount(&mut self) {
+        if let Some(mount) = self.mounts.get(self.mounts_selected_index).cloned() {
+            self.active_panel_mut().navigate_to(mount.mountpoint);
+            self.rearm_watcher(self.active_panel);
+            self.current_screen = Screen::DualPanel;
+        }
+    }
+
     #[allow(dead_code)]
     pub fn show_advanced_search_dialog(&mut self) {
         self.advanced_search_state.active = true;
@@ -994,28 +2130,33 @@ impl App {
 
     pub fn execute_advanced_search(&mut self, criteria: &crate::ui::advanced_search::SearchCriteria) {
         let panel = self.active_panel_mut();
-        let mut matched_count = 0;
 
-        panel.selected_files.clear();
+        let mut matches: Vec<(i64, &str)> = panel
+            .files
+            .iter()
+            .filter(|file| file.name != "..")
+            .filter_map(|file| {
+                crate::ui::advanced_search::matches_criteria_scored(
+                    &file.name,
+                    file.size,
+                    file.modified,
+                    criteria,
+                )
+                .map(|score| (score, file.name.as_str()))
+            })
+            .collect();
 
-        for file in &panel.files {
-            if file.name == ".." {
-                continue;
-            }
+        // Best matches first, so the highest-relevance fuzzy hits are the
+        // ones a future ranked result view would show at the top.
+        matches.sort_by(|a, b| b.0.cmp(&a.0));
 
-            if crate::ui::advanced_search::matches_criteria(
-                &file.name,
-                file.size,
-                file.modified,
-                criteria,
-            ) {
-                panel.selected_files.insert(file.name.clone());
-                matched_count += 1;
-            }
+        panel.selected_files.clear();
+        for (_, name) in &matches {
+            panel.selected_files.insert(name.to_string());
         }
 
-        if matched_count > 0 {
-            self.show_message(&format!("Found {} matching file(s)", matched_count));
+        if !matches.is_empty() {
+            self.show_message(&format!("Found {} matching file(s)", matches.len()));
         } else {
             self.show_message("No files match the criteria");
         }
@@ -1029,6 +2170,34 @@ impl App {
     }
 
     pub fn execute_copy_to(&mut self, target_path: &Path) {
+        if !self.stage.is_empty() {
+            let srcs: Vec<PathBuf> = self.stage.paths().to_vec();
+            let mut success_count = 0;
+            let mut last_error = String::new();
+
+            for src in &srcs {
+                let dest = target_path.join(src.file_name().unwrap_or_default());
+                let conflict_policy = if self.auto_rename_on_conflict {
+                    file_ops::ConflictPolicy::AutoRename
+                } else {
+                    file_ops::ConflictPolicy::Error
+                };
+                match file_ops::copy_file(src, &dest, file_ops::ConflictOptions { policy: conflict_policy }) {
+                    Ok(_) => success_count += 1,
+                    Err(e) => last_error = e.to_string(),
+                }
+            }
+
+            if success_count == srcs.len() {
+                self.show_message(&format!("Copied {} staged file(s)", success_count));
+                self.stage.clear();
+            } else {
+                self.show_message(&format!("Copied {}/{}. Error: {}", success_count, srcs.len(), last_error));
+            }
+            // Both panels' directory watchers pick up the change on the next poll.
+            return;
+        }
+
         let files = self.get_operation_files();
         let source_path = self.active_panel().path.clone();
 
@@ -1038,7 +2207,12 @@ impl App {
         for file_name in &files {
             let src = source_path.join(file_name);
             let dest = target_path.join(file_name);
-            match file_ops::copy_file(&src, &dest) {
+            let conflict_policy = if self.auto_rename_on_conflict {
+                file_ops::ConflictPolicy::AutoRename
+            } else {
+                file_ops::ConflictPolicy::Error
+            };
+            match file_ops::copy_file(&src, &dest, file_ops::ConflictOptions { policy: conflict_policy }) {
                 Ok(_) => success_count += 1,
                 Err(e) => last_error = e.to_string(),
             }
@@ -1049,13 +2223,34 @@ impl App {
         } else {
             self.show_message(&format!("Copied {}/{}. Error: {}", success_count, files.len(), last_error));
         }
-        self.refresh_panels();
+        // Both panels' directory watchers pick up the change on the next poll.
     }
 
-    /// Execute copy with progress dialog
+    /// Execute copy with progress dialog. Like `execute_copy_to`, staged
+    /// files take priority over the active panel's selection - but unlike
+    /// that synchronous version, the stage is cleared up front rather than
+    /// on success, since success isn't known until the background worker
+    /// finishes (staging is single-use either way).
     pub fn execute_copy_to_with_progress(&mut self, target_path: &Path) {
-        let files = self.get_operation_files();
-        if files.is_empty() {
+        let file_paths: Vec<PathBuf> = if !self.stage.is_empty() {
+            let paths = self.stage.paths().to_vec();
+            self.stage.clear();
+            paths
+        } else {
+            self.get_operation_files().iter().map(PathBuf::from).collect()
+        };
+        self.execute_copy_files_to_with_progress(file_paths, target_path);
+    }
+
+    /// Same as `execute_copy_to_with_progress`, but takes the file list
+    /// explicitly instead of reading (and clearing) the stage. Callers that
+    /// copy the same staged set to several targets in a row — e.g. the
+    /// multi-mark copy dialog in `dialogs.rs` — must snapshot the stage
+    /// once and pass it to every target through this method, since reading
+    /// `self.stage` again after the first target would find it already
+    /// cleared.
+    pub fn execute_copy_files_to_with_progress(&mut self, file_paths: Vec<PathBuf>, target_path: &Path) {
+        if file_paths.is_empty() {
             self.show_message("No files selected");
             return;
         }
@@ -1072,8 +2267,13 @@ impl App {
         let (tx, rx) = mpsc::channel();
         progress.receiver = Some(rx);
 
-        // Convert files to PathBuf
-        let file_paths: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+        // Create channel for conflict resolution replies
+        let (conflict_tx, conflict_rx) = mpsc::channel();
+        progress.conflict_sender = Some(conflict_tx);
+
+        let backup_on_overwrite = self.backup_on_overwrite;
+        let verify_after_copy = self.verify_after_copy;
+        let auto_rename_on_conflict = self.auto_rename_on_conflict;
 
         // Start copy in background thread
         thread::spawn(move || {
@@ -1083,6 +2283,12 @@ impl App {
                 &target_path,
                 cancel_flag,
                 tx,
+                &conflict_rx,
+                backup_on_overwrite,
+                verify_after_copy,
+                auto_rename_on_conflict,
+                file_ops::CopyOptions::default(),
+                0,
             );
         });
 
@@ -1104,6 +2310,34 @@ impl App {
     }
 
     pub fn execute_move_to(&mut self, target_path: &Path) {
+        if !self.stage.is_empty() {
+            let srcs: Vec<PathBuf> = self.stage.paths().to_vec();
+            let mut success_count = 0;
+            let mut last_error = String::new();
+
+            for src in &srcs {
+                let dest = target_path.join(src.file_name().unwrap_or_default());
+                let conflict_policy = if self.auto_rename_on_conflict {
+                    file_ops::ConflictPolicy::AutoRename
+                } else {
+                    file_ops::ConflictPolicy::Error
+                };
+                match file_ops::move_file(src, &dest, file_ops::ConflictOptions { policy: conflict_policy }) {
+                    Ok(_) => success_count += 1,
+                    Err(e) => last_error = e.to_string(),
+                }
+            }
+
+            if success_count == srcs.len() {
+                self.show_message(&format!("Moved {} staged file(s)", success_count));
+                self.stage.clear();
+            } else {
+                self.show_message(&format!("Moved {}/{}. Error: {}", success_count, srcs.len(), last_error));
+            }
+            // Both panels' directory watchers pick up the change on the next poll.
+            return;
+        }
+
         let files = self.get_operation_files();
         let source_path = self.active_panel().path.clone();
 
@@ -1113,7 +2347,12 @@ impl App {
         for file_name in &files {
             let src = source_path.join(file_name);
             let dest = target_path.join(file_name);
-            match file_ops::move_file(&src, &dest) {
+            let conflict_policy = if self.auto_rename_on_conflict {
+                file_ops::ConflictPolicy::AutoRename
+            } else {
+                file_ops::ConflictPolicy::Error
+            };
+            match file_ops::move_file(&src, &dest, file_ops::ConflictOptions { policy: conflict_policy }) {
                 Ok(_) => success_count += 1,
                 Err(e) => last_error = e.to_string(),
             }
@@ -1124,13 +2363,28 @@ impl App {
         } else {
             self.show_message(&format!("Moved {}/{}. Error: {}", success_count, files.len(), last_error));
         }
-        self.refresh_panels();
+        // Both panels' directory watchers pick up the change on the next poll.
     }
 
-    /// Execute move with progress dialog
+    /// Execute move with progress dialog. See `execute_copy_to_with_progress`
+    /// for why the stage is cleared up front instead of on success.
     pub fn execute_move_to_with_progress(&mut self, target_path: &Path) {
-        let files = self.get_operation_files();
-        if files.is_empty() {
+        let file_paths: Vec<PathBuf> = if !self.stage.is_empty() {
+            let paths = self.stage.paths().to_vec();
+            self.stage.clear();
+            paths
+        } else {
+            self.get_operation_files().iter().map(PathBuf::from).collect()
+        };
+        self.execute_move_files_to_with_progress(file_paths, target_path);
+    }
+
+    /// Same as `execute_move_to_with_progress`, but takes the file list
+    /// explicitly instead of reading (and clearing) the stage. See
+    /// `execute_copy_files_to_with_progress` for why multi-target callers
+    /// need this.
+    pub fn execute_move_files_to_with_progress(&mut self, file_paths: Vec<PathBuf>, target_path: &Path) {
+        if file_paths.is_empty() {
             self.show_message("No files selected");
             return;
         }
@@ -1147,8 +2401,13 @@ impl App {
         let (tx, rx) = mpsc::channel();
         progress.receiver = Some(rx);
 
-        // Convert files to PathBuf
-        let file_paths: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+        // Create channel for conflict resolution replies
+        let (conflict_tx, conflict_rx) = mpsc::channel();
+        progress.conflict_sender = Some(conflict_tx);
+
+        let backup_on_overwrite = self.backup_on_overwrite;
+        let verify_after_copy = self.verify_after_copy;
+        let auto_rename_on_conflict = self.auto_rename_on_conflict;
 
         // Start move in background thread
         thread::spawn(move || {
@@ -1158,6 +2417,12 @@ impl App {
                 &target_path,
                 cancel_flag,
                 tx,
+                &conflict_rx,
+                backup_on_overwrite,
+                verify_after_copy,
+                auto_rename_on_conflict,
+                file_ops::CopyOptions::default(),
+                0,
             );
         });
 
@@ -1172,7 +2437,18 @@ impl App {
         });
     }
 
-    pub fn execute_delete(&mut self) {
+    /// Answer a pending copy/move conflict (see `PendingConflict`), letting
+    /// the background worker resume. No-op if no operation is in progress.
+    pub fn resolve_paste_conflict(&mut self, action: ConflictAction, apply_to_all: bool) {
+        if let Some(progress) = &mut self.file_operation_progress {
+            progress.resolve_conflict(action, apply_to_all);
+        }
+    }
+
+    /// Delete the selected files. By default items are moved to the OS
+    /// trash; pass `permanent = true` (Shift+Enter / Shift+Y on the confirm
+    /// dialog) to bypass the trash and remove them for good.
+    pub fn execute_delete(&mut self, permanent: bool) {
         let files = self.get_operation_files();
         let source_path = self.active_panel().path.clone();
 
@@ -1181,22 +2457,63 @@ impl App {
 
         for file_name in &files {
             let path = source_path.join(file_name);
-            match file_ops::delete_file(&path) {
+            let result = if permanent {
+                file_ops::delete_file(&path)
+            } else {
+                file_ops::trash_file(&path)
+            };
+            match result {
                 Ok(_) => success_count += 1,
                 Err(e) => last_error = e.to_string(),
             }
         }
 
+        let verb = if permanent { "Deleted" } else { "Trashed" };
         if success_count == files.len() {
-            self.show_message(&format!("Deleted {} file(s)", success_count));
+            self.show_message(&format!("{} {} file(s)", verb, success_count));
         } else {
-            self.show_message(&format!("Deleted {}/{}. Error: {}", success_count, files.len(), last_error));
+            self.show_message(&format!("{} {}/{}. Error: {}", verb, success_count, files.len(), last_error));
         }
-        self.refresh_panels();
+        // The active panel's directory watcher picks up the removal on the next poll.
     }
 
     // ========== Clipboard operations (Ctrl+C/X/V) ==========
 
+    /// Copy the current file's bare name to the system clipboard as text,
+    /// independent of the internal copy/move `Clipboard` buffer.
+    pub fn copy_filename_to_clipboard(&mut self) {
+        let name = match self.active_panel().current_file() {
+            Some(file) => file.name.clone(),
+            None => {
+                self.show_message("No file selected");
+                return;
+            }
+        };
+
+        match crate::services::clipboard::set_clipboard_text(&name) {
+            Ok(_) => self.show_message(&format!("Copied name: {}", name)),
+            Err(e) => self.show_message(&format!("Failed to copy name: {}", e)),
+        }
+    }
+
+    /// Copy the current file's absolute path to the system clipboard as
+    /// text, independent of the internal copy/move `Clipboard` buffer.
+    pub fn copy_filepath_to_clipboard(&mut self) {
+        let path = match self.active_panel().current_file() {
+            Some(file) => self.active_panel().path.join(&file.name),
+            None => {
+                self.show_message("No file selected");
+                return;
+            }
+        };
+        let path_str = path.display().to_string();
+
+        match crate::services::clipboard::set_clipboard_text(&path_str) {
+            Ok(_) => self.show_message(&format!("Copied path: {}", path_str)),
+            Err(e) => self.show_message(&format!("Failed to copy path: {}", e)),
+        }
+    }
+
     /// Copy selected files to clipboard (Ctrl+C)
     pub fn clipboard_copy(&mut self) {
         let files = self.get_operation_files();
@@ -1312,9 +2629,16 @@ impl App {
         let (tx, rx) = mpsc::channel();
         progress.receiver = Some(rx);
 
+        // Create channel for conflict resolution replies
+        let (conflict_tx, conflict_rx) = mpsc::channel();
+        progress.conflict_sender = Some(conflict_tx);
+
         // Convert files to PathBuf
         let file_paths: Vec<PathBuf> = valid_files.iter().map(PathBuf::from).collect();
         let source_path = clipboard.source_path.clone();
+        let backup_on_overwrite = self.backup_on_overwrite;
+        let verify_after_copy = self.verify_after_copy;
+        let auto_rename_on_conflict = self.auto_rename_on_conflict;
 
         // Start operation in background thread
         let clipboard_operation = clipboard.operation;
@@ -1327,6 +2651,12 @@ impl App {
                         &target_path,
                         cancel_flag,
                         tx,
+                        &conflict_rx,
+                        backup_on_overwrite,
+                        verify_after_copy,
+                        auto_rename_on_conflict,
+                        file_ops::CopyOptions::default(),
+                        0,
                     );
                 }
                 ClipboardOperation::Cut => {
@@ -1336,6 +2666,12 @@ impl App {
                         &target_path,
                         cancel_flag,
                         tx,
+                        &conflict_rx,
+                        backup_on_overwrite,
+                        verify_after_copy,
+                        auto_rename_on_conflict,
+                        file_ops::CopyOptions::default(),
+                        0,
                     );
                 }
             }
@@ -1383,6 +2719,18 @@ impl App {
         }
     }
 
+    /// Replace the allowed-extension set from a comma-separated list typed
+    /// into the Filter dialog (e.g. `"jpg,png,mp4"`). An empty input clears
+    /// the filter back to "show everything".
+    pub fn execute_set_extension_filter(&mut self, input: &str) {
+        self.extension_filter.set_allowed_from_str(input);
+        if self.extension_filter.allowed.is_empty() {
+            self.show_message("Extension filter cleared");
+        } else {
+            self.show_message(&format!("Showing only: {}", input.trim()));
+        }
+    }
+
     pub fn execute_mkdir(&mut self, name: &str) {
         // Validate filename to prevent path traversal attacks
         if let Err(e) = file_ops::is_valid_filename(name) {
@@ -1412,7 +2760,7 @@ impl App {
             Ok(_) => self.show_message(&format!("Created directory: {}", name)),
             Err(e) => self.show_message(&format!("Error: {}", e)),
         }
-        self.refresh_panels();
+        // The active panel's directory watcher picks up the new entry on the next poll.
     }
 
     pub fn execute_rename(&mut self, new_name: &str) {
@@ -1439,15 +2787,38 @@ impl App {
                 }
             }
 
-            match file_ops::rename_file(&old_path, &new_path) {
-                Ok(_) => self.show_message(&format!("Renamed to: {}", new_name)),
+            let mut backed_up = None;
+            if self.backup_on_overwrite && new_path.exists() {
+                match file_ops::backup_existing(&new_path) {
+                    Ok(path) => backed_up = path,
+                    Err(e) => {
+                        self.show_message(&format!("Error backing up '{}': {}", new_name, e));
+                        return;
+                    }
+                }
+            }
+
+            match file_ops::rename_file(&old_path, &new_path, file_ops::ConflictPolicy::Error) {
+                Ok(_) => match backed_up {
+                    Some(backup) => self.show_message(&format!(
+                        "Renamed to: {} (old file backed up to {})",
+                        new_name,
+                        backup.display()
+                    )),
+                    None => self.show_message(&format!("Renamed to: {}", new_name)),
+                },
                 Err(e) => self.show_message(&format!("Error: {}", e)),
             }
             self.refresh_panels();
         }
     }
 
+    #[allow(dead_code)]
     pub fn execute_search(&mut self, term: &str) {
+        self.execute_search_with_mode(term, crate::ui::search_result::SearchMode::Substring);
+    }
+
+    pub fn execute_search_with_mode(&mut self, term: &str, mode: crate::ui::search_result::SearchMode) {
         if term.trim().is_empty() {
             self.show_message("Please enter a search term");
             return;
@@ -1455,12 +2826,31 @@ impl App {
 
         // 재귀 검색 수행
         let base_path = self.active_panel().path.clone();
-        let results = crate::ui::search_result::execute_recursive_search(
+        let results = crate::ui::search_result::execute_search_with_mode(
             &base_path,
             term,
             1000,  // 최대 결과 수
+            mode,
         );
 
+        self.finish_search(term, base_path, results);
+    }
+
+    /// Find File 입력을 broot 스타일 패턴 언어(`/regex/`, `c/regex/`, 그 외는
+    /// 이름 퍼지 매치)로 해석해 검색한다. `Tab`으로 수동 선택한 모드보다
+    /// 입력에 적힌 패턴 문법이 우선한다.
+    pub fn execute_search_with_pattern(&mut self, input: &str) {
+        if input.trim().is_empty() {
+            self.show_message("Please enter a search term");
+            return;
+        }
+
+        let base_path = self.active_panel().path.clone();
+        let results = crate::ui::search_result::execute_search_with_pattern(&base_path, input, 1000);
+        self.finish_search(input, base_path, results);
+    }
+
+    fn finish_search(&mut self, term: &str, base_path: std::path::PathBuf, results: Vec<crate::ui::search_result::SearchResultItem>) {
         if results.is_empty() {
             self.show_message(&format!("No files found matching \"{}\"", term));
             return;
@@ -1499,10 +2889,9 @@ impl App {
                     let valid_path = get_valid_path(&canonical, &fallback);
                     if valid_path != fallback {
                         let panel = self.active_panel_mut();
-                        panel.path = valid_path.clone();
-                        panel.selected_index = 0;
-                        panel.selected_files.clear();
-                        panel.load_files();
+                        panel.navigate_to(valid_path.clone());
+                        self.rearm_watcher(self.active_panel);
+                        self.path_history.record(&valid_path);
                         self.show_message(&format!("Moved to: {}", valid_path.display()));
                     } else {
                         self.show_message("Error: Path not found or not accessible");
@@ -1535,10 +2924,9 @@ impl App {
 
         if valid_path != fallback {
             let panel = self.active_panel_mut();
-            panel.path = valid_path.clone();
-            panel.selected_index = 0;
-            panel.selected_files.clear();
-            panel.load_files();
+            panel.navigate_to(valid_path.clone());
+            self.rearm_watcher(self.active_panel);
+            self.path_history.record(&valid_path);
 
             if valid_path == path {
                 self.show_message(&format!("Moved to: {}", valid_path.display()));
@@ -1553,11 +2941,174 @@ impl App {
     /// 디렉토리로 이동하고 특정 파일에 커서를 위치시킴
     pub fn goto_directory_with_focus(&mut self, dir: &Path, filename: Option<String>) {
         let panel = self.active_panel_mut();
-        panel.path = dir.to_path_buf();
-        panel.selected_index = 0;
-        panel.selected_files.clear();
         panel.pending_focus = filename;
+        panel.navigate_to(dir.to_path_buf());
+        self.rearm_watcher(self.active_panel);
+        self.path_history.record(dir);
+    }
+
+    /// Move the active panel back one entry in its navigation history.
+    pub fn history_back(&mut self) {
+        if self.active_panel_mut().history_back() {
+            self.rearm_watcher(self.active_panel);
+        }
+    }
+
+    /// Move the active panel forward one entry in its navigation history.
+    pub fn history_forward(&mut self) {
+        if self.active_panel_mut().history_forward() {
+            self.rearm_watcher(self.active_panel);
+        }
+    }
+
+    /// Toggle the active panel between flat listing and tree view.
+    pub fn toggle_tree_mode(&mut self) {
+        self.active_panel_mut().toggle_tree_mode();
+    }
+
+    /// Scroll the active panel by `lines` rows (negative scrolls up) -- the
+    /// mouse-wheel hook.
+    pub fn scroll_lines(&mut self, lines: i32) {
+        self.active_panel_mut().scroll(ScrollCommand::Lines(lines));
+    }
+
+    /// Scroll the active panel by `pages` whole pages (negative scrolls
+    /// up) -- the PgUp/PgDn hook.
+    pub fn scroll_pages(&mut self, pages: i32) {
+        self.active_panel_mut().scroll(ScrollCommand::Pages(pages));
+    }
+
+    /// Total up the highlighted directory's size recursively in the
+    /// background, for display in the active panel's footer -- the
+    /// `calculate-dir-size` footer keybind's hook.
+    pub fn calculate_selected_dir_size(&mut self) {
+        self.active_panel_mut().start_dir_size_calc();
+    }
+
+    /// Start typing a live quick filter into the active panel.
+    pub fn start_quick_filter(&mut self) {
+        self.active_panel_mut().start_quick_filter();
+    }
+
+    /// Append a character to the active panel's quick filter.
+    pub fn push_quick_filter_char(&mut self, c: char) {
+        self.active_panel_mut().push_quick_filter_char(c);
+    }
+
+    /// Backspace the active panel's quick filter by one character.
+    pub fn pop_quick_filter_char(&mut self) {
+        self.active_panel_mut().pop_quick_filter_char();
+    }
+
+    /// Stop capturing quick-filter keystrokes, keeping the filter applied.
+    pub fn stop_quick_filter_typing(&mut self) {
+        self.active_panel_mut().stop_quick_filter_typing();
+    }
+
+    /// Clear the active panel's quick filter entirely.
+    pub fn clear_quick_filter(&mut self) {
+        self.active_panel_mut().clear_quick_filter();
+    }
+
+    /// Open the bookmarks popup to jump to a saved directory.
+    pub fn show_bookmarks_dialog(&mut self) {
+        self.bookmark_add_mode = false;
+        self.dialog = Some(Dialog {
+            dialog_type: DialogType::Bookmarks,
+            input: String::new(),
+            message: "Jump to bookmark:".to_string(),
+            completion: None,
+            selected_button: 0,
+        });
+    }
+
+    /// Open the bookmarks popup to save the active panel's current path
+    /// under a letter the user is about to press.
+    pub fn show_add_bookmark_dialog(&mut self) {
+        self.bookmark_add_mode = true;
+        self.dialog = Some(Dialog {
+            dialog_type: DialogType::Bookmarks,
+            input: String::new(),
+            message: "Bookmark this directory as:".to_string(),
+            completion: None,
+            selected_button: 0,
+        });
+    }
+
+    /// Save the active panel's current path under `letter`, rejecting it if
+    /// the path doesn't pass the same validation used for navigation.
+    pub fn add_bookmark(&mut self, letter: char) {
+        let path = self.active_panel().path.clone();
+        let fallback = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        if get_valid_path(&path, &fallback) != path {
+            self.show_message("Cannot bookmark an invalid path");
+            return;
+        }
+
+        self.bookmarks.set(letter, path);
+        self.show_message(&format!("Bookmarked as '{}'", letter));
+    }
+
+    /// Remove the mark saved under `letter`, if any.
+    pub fn remove_bookmark(&mut self, letter: char) {
+        self.bookmarks.remove(letter);
+        self.show_message(&format!("Removed bookmark '{}'", letter));
+    }
+
+    /// Jump the active panel to the directory saved under `letter`, if any.
+    pub fn jump_to_bookmark(&mut self, letter: char) {
+        if let Some(path) = self.bookmarks.get(letter).map(|p| p.to_path_buf()) {
+            let panel = self.active_panel_mut();
+            panel.selected_files.clear();
+            panel.navigate_to(path);
+            self.rearm_watcher(self.active_panel);
+        }
+    }
+
+    /// Open the Connect popup, which accepts a `sftp://` or `ftp://` URL to
+    /// mount onto the active panel.
+    pub fn show_connect_dialog(&mut self) {
+        self.dialog = Some(Dialog {
+            dialog_type: DialogType::Connect,
+            input: String::new(),
+            message: "Connect to (sftp://user@host/path):".to_string(),
+            completion: None,
+            selected_button: 0,
+        });
+    }
+
+    /// Parse `url`, connect and authenticate with `password`, and mount the
+    /// result as the active panel's backend. Shows an error message and
+    /// leaves the panel untouched on any failure.
+    pub fn execute_connect(&mut self, url: &str, password: &str) {
+        let target = match remote::parse_remote_url(url) {
+            Some(target) => target,
+            None => {
+                self.show_message("Invalid remote URL");
+                return;
+            }
+        };
+
+        let session = match RemoteSession::connect(&target, password) {
+            Ok(session) => session,
+            Err(e) => {
+                self.show_message(&format!("Connection failed: {}", e));
+                return;
+            }
+        };
+
+        let path = target.path.clone();
+        let panel = self.active_panel_mut();
+        panel.backend = PanelBackend::Remote {
+            session: Arc::new(Mutex::new(session)),
+            target,
+        };
+        panel.path = path;
+        panel.selected_files.clear();
+        panel.selected_index = 0;
         panel.load_files();
+
+        self.show_message("Connected");
     }
 
     /// 검색 결과에서 선택한 항목의 경로로 이동
@@ -2059,6 +3610,54 @@ mod tests {
         cleanup_temp_dir(&temp_dir);
     }
 
+    #[test]
+    fn test_clipboard_paste_conflict_skip_keeps_destination() {
+        let temp_dir = create_temp_dir();
+        let src_dir = temp_dir.join("src");
+        let dest_dir = temp_dir.join("dest");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::create_dir_all(&dest_dir).unwrap();
+        fs::write(src_dir.join("file.txt"), "new content").unwrap();
+        fs::write(dest_dir.join("file.txt"), "old content").unwrap();
+
+        let mut app = App::new(src_dir.clone(), dest_dir.clone());
+
+        if app.active_panel().files.first().map(|f| f.name.as_str()) == Some("..") {
+            app.move_cursor(1);
+        }
+
+        app.clipboard_copy();
+        app.switch_panel();
+        app.clipboard_paste();
+
+        // Drive the worker until it surfaces the conflict, then skip it.
+        loop {
+            let pending = app.file_operation_progress.as_mut()
+                .and_then(|p| { p.poll(); p.pending_conflict.take() });
+            if pending.is_some() {
+                app.resolve_paste_conflict(ConflictAction::Skip, false);
+                break;
+            }
+            if !app.file_operation_progress.as_ref().map(|p| p.is_active).unwrap_or(false) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        // Let the operation finish after the reply.
+        while app.file_operation_progress.as_ref().map(|p| p.is_active).unwrap_or(false) {
+            if let Some(ref mut progress) = app.file_operation_progress {
+                progress.poll();
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        // Skipped: destination keeps its original content.
+        assert_eq!(fs::read_to_string(dest_dir.join("file.txt")).unwrap(), "old content");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
     #[test]
     fn test_clipboard_paste_same_folder_rejected() {
         let temp_dir = create_temp_dir();
@@ -2150,4 +3749,120 @@ mod tests {
         assert_eq!(ClipboardOperation::Cut, ClipboardOperation::Cut);
         assert_ne!(ClipboardOperation::Copy, ClipboardOperation::Cut);
     }
+
+    // ========== Range selection tests ==========
+
+    #[test]
+    fn test_select_range_down_grows_from_anchor() {
+        let temp_dir = create_temp_dir();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(temp_dir.join(name), "content").unwrap();
+        }
+
+        let mut app = App::new(temp_dir.clone(), temp_dir.clone());
+        if app.active_panel().files.first().map(|f| f.name.as_str()) == Some("..") {
+            app.move_cursor(1);
+        }
+
+        app.select_range_down();
+        app.select_range_down();
+
+        assert_eq!(app.active_panel().selected_files.len(), 3);
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_select_range_shrinks_back_past_anchor() {
+        let temp_dir = create_temp_dir();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(temp_dir.join(name), "content").unwrap();
+        }
+
+        let mut app = App::new(temp_dir.clone(), temp_dir.clone());
+        if app.active_panel().files.first().map(|f| f.name.as_str()) == Some("..") {
+            app.move_cursor(1);
+        }
+
+        app.move_cursor(1);
+
+        // Anchor at the middle file, extend down then back up past the anchor.
+        app.select_range_down();
+        app.select_range_down();
+        app.select_range_up();
+        app.select_range_up();
+
+        assert_eq!(app.active_panel().selected_files.len(), 2);
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_plain_move_clears_selection_anchor() {
+        let temp_dir = create_temp_dir();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(temp_dir.join(name), "content").unwrap();
+        }
+
+        let mut app = App::new(temp_dir.clone(), temp_dir.clone());
+        if app.active_panel().files.first().map(|f| f.name.as_str()) == Some("..") {
+            app.move_cursor(1);
+        }
+
+        app.select_range_down();
+        assert!(app.active_panel().selection_anchor.is_some());
+
+        app.move_cursor(1);
+        assert!(app.active_panel().selection_anchor.is_none());
+    }
+
+    // ========== File operation progress tests ==========
+
+    #[test]
+    fn test_transfer_rate_none_before_two_samples() {
+        let mut progress = FileOperationProgress::new(FileOperationType::Copy);
+        let (tx, rx) = mpsc::channel();
+        progress.receiver = Some(rx);
+        progress.is_active = true;
+
+        tx.send(ProgressMessage::TotalProgress(0, 1, 1000, 10_000)).unwrap();
+        progress.poll();
+
+        assert!(progress.transfer_rate().is_none());
+    }
+
+    #[test]
+    fn test_transfer_rate_and_eta_from_samples() {
+        let mut progress = FileOperationProgress::new(FileOperationType::Copy);
+        let (tx, rx) = mpsc::channel();
+        progress.receiver = Some(rx);
+        progress.is_active = true;
+
+        tx.send(ProgressMessage::TotalProgress(0, 1, 0, 10_000)).unwrap();
+        progress.poll();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        tx.send(ProgressMessage::TotalProgress(0, 1, 1_000, 10_000)).unwrap();
+        progress.poll();
+
+        let rate = progress.transfer_rate().expect("rate after two samples");
+        // ~1000 bytes over ~100ms is roughly 10_000 bytes/sec; allow slack for
+        // scheduling jitter.
+        assert!(rate > 2_000.0, "rate was {rate}");
+
+        let eta = progress.eta().expect("eta while bytes remain");
+        assert!(eta.as_secs_f64() > 0.0);
+    }
+
+    #[test]
+    fn test_eta_none_when_complete() {
+        let mut progress = FileOperationProgress::new(FileOperationType::Copy);
+        let (tx, rx) = mpsc::channel();
+        progress.receiver = Some(rx);
+        progress.is_active = true;
+
+        tx.send(ProgressMessage::TotalProgress(1, 1, 10_000, 10_000)).unwrap();
+        progress.poll();
+
+        assert!(progress.eta().is_none());
+    }
 }
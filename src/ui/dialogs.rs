@@ -11,9 +11,10 @@ use ratatui::{
 };
 
 use super::{
-    app::{App, Dialog, DialogType, PathCompletion},
+    app::{App, CompletionEntry, Dialog, DialogType, ExtensionFilter, PathCompletion, PreviewContent},
     theme::Theme,
 };
+use crate::utils::format::format_size;
 
 /// 경로 문자열을 확장 (~ 홈 경로 확장)
 fn expand_path_string(input: &str) -> PathBuf {
@@ -74,48 +75,465 @@ fn parse_path_for_completion(input: &str) -> (PathBuf, String) {
     }
 }
 
-/// 디렉토리 읽기 및 접두어 매칭
-/// 대소문자 무시 검색, 디렉토리 우선 정렬
-fn get_path_suggestions(base_dir: &PathBuf, prefix: &str) -> Vec<String> {
-    let mut suggestions: Vec<(String, bool)> = Vec::new();
-    let lower_prefix = prefix.to_lowercase();
-
-    if let Ok(entries) = fs::read_dir(base_dir) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            let name = entry.file_name().to_string_lossy().to_string();
-            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
-
-            // 접두어 매칭 (대소문자 무시)
-            if prefix.is_empty() || name.to_lowercase().starts_with(&lower_prefix) {
-                let display_name = if is_dir {
-                    format!("{}/", name)
-                } else {
-                    name
-                };
-                suggestions.push((display_name, is_dir));
+/// Goto 다이얼로그가 `:verb args` 문법으로 인식하는 명령 (broot의 verb
+/// 시스템에서 영감을 받음). `arg_hint`는 도움말/자동완성용 플레이스홀더
+/// 이름일 뿐이고, 실제 실행 시점에는 입력의 나머지 부분이 그 자리를
+/// 채우며 비어 있으면 활성 패널에서 현재 선택된 항목의 경로로 대체된다
+/// (`:mkdir`만 예외로, 인자가 비어 있으면 사용법만 보여준다).
+struct GotoVerb {
+    name: &'static str,
+    arg_hint: &'static str,
+}
+
+const GOTO_VERBS: &[GotoVerb] = &[
+    GotoVerb { name: "mkdir", arg_hint: "{name}" },
+    GotoVerb { name: "cp", arg_hint: "{dir}" },
+    GotoVerb { name: "mv", arg_hint: "{dir}" },
+    GotoVerb { name: "rm", arg_hint: "" },
+    GotoVerb { name: "focus", arg_hint: "{path}" },
+];
+
+/// `:verb rest` 형태의 입력을 (verb 이름, 나머지 인자)로 분리한다.
+/// 맨 앞이 `:`가 아니면 일반 경로 입력이므로 `None`.
+fn parse_goto_command(input: &str) -> Option<(&str, &str)> {
+    let rest = input.strip_prefix(':')?;
+    match rest.find(char::is_whitespace) {
+        Some(idx) => Some((&rest[..idx], rest[idx..].trim_start())),
+        None => Some((rest, "")),
+    }
+}
+
+/// `:verb` 중 인자를 받는 것(`arg_hint`가 비어있지 않은 것)이면, 그
+/// 자리를 자동완성 대상으로 넘길 수 있도록 (`:verb ` 접두어, 인자 부분)을
+/// 돌려준다. 인자를 받지 않는 verb(`:rm`)나 일반 경로 입력은 `None`.
+fn split_goto_verb_arg(input: &str) -> Option<(String, String)> {
+    let (verb, arg) = parse_goto_command(input)?;
+    GOTO_VERBS.iter().find(|v| v.name == verb && !v.arg_hint.is_empty())?;
+    let prefix_len = input.len() - arg.len();
+    Some((input[..prefix_len].to_string(), arg.to_string()))
+}
+
+/// `split_goto_verb_arg`로 인자 부분만 떼어 기존 경로 자동완성 로직에
+/// 넘기고, 결과를 다시 `:verb ` 접두어와 합친다. 일반 경로 입력이면
+/// `f`를 그대로 한 번 호출한다.
+fn with_goto_completion_arg<F: FnOnce(&mut Dialog)>(dialog: &mut Dialog, f: F) {
+    match split_goto_verb_arg(&dialog.input) {
+        Some((prefix, arg)) => {
+            dialog.input = arg;
+            f(dialog);
+            dialog.input = format!("{}{}", prefix, dialog.input);
+        }
+        None => f(dialog),
+    }
+}
+
+/// 완성 목록에서 선택된 항목을 현재 입력에 적용한다. 일반 경로 입력과
+/// `:verb {arg}` 입력 모두 처리하며, verb 모드에서는 인자 부분에만
+/// 적용한 뒤 `:verb ` 접두어를 되돌려 붙인다.
+fn apply_selected_goto_completion(dialog: &mut Dialog) {
+    with_goto_completion_arg(dialog, |dialog| {
+        let (base_dir, _) = parse_path_for_completion(&dialog.input);
+        let suggestion = dialog
+            .completion
+            .as_ref()
+            .and_then(|c| c.suggestions.get(c.selected_index).map(|e| e.display_name()));
+        if let Some(suggestion) = suggestion {
+            apply_completion(dialog, &base_dir, &suggestion);
+        }
+    });
+}
+
+/// 이 글자 수 미만으로 입력됐을 때는 파일시스템 대신 최근 방문 기록
+/// (`PathHistory`)에서 후보를 채운다 - 디렉토리를 고르려는데 아직 경로를
+/// 쓰지 않은 시점에 파일시스템 전체 목록보다 이게 더 쓸모 있다.
+const GOTO_HISTORY_MIN_QUERY_LEN: usize = 2;
+
+/// `query`에 맞는 방문 기록 후보 목록을 만든다. 빈 입력이면
+/// `PathHistory::ranked`가 이미 매긴 recency+frequency 순서 그대로,
+/// 아니면 전체 경로 문자열에 `fuzzy_match`를 적용해 점수순으로 정렬한다.
+/// `CompletionEntry::name`에 경로 전체를 담아두면, `apply_completion`이
+/// 쓰는 `Path::join`이 절대 경로를 통째로 치환해주는 덕에 선택 시 입력
+/// 전체가 그 경로로 바뀐다.
+fn history_suggestions(history: &[String], query: &str) -> Vec<CompletionEntry> {
+    if query.is_empty() {
+        return history
+            .iter()
+            .map(|path| CompletionEntry {
+                name: path.clone(),
+                is_dir: true,
+                size: 0,
+                modified: String::new(),
+                match_positions: Vec::new(),
+            })
+            .collect();
+    }
+
+    let mut scored: Vec<(i32, CompletionEntry)> = history
+        .iter()
+        .filter_map(|path| {
+            let (score, positions) = fuzzy_match(path, query)?;
+            Some((
+                score,
+                CompletionEntry {
+                    name: path.clone(),
+                    is_dir: true,
+                    size: 0,
+                    modified: String::new(),
+                    match_positions: positions,
+                },
+            ))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// 방문 기록이나 파일시스템 둘 중 하나로 `completion.suggestions`를
+/// 채우는 공통 틀. `current_input`이 `GOTO_HISTORY_MIN_QUERY_LEN`보다
+/// 짧으면 `history`에서, 아니면 `fallback`(기존 파일시스템 완성 로직)에
+/// 위임한다.
+fn goto_suggestions_for(dialog: &mut Dialog, history: &[String], fallback: impl FnOnce(&mut Dialog)) {
+    if dialog.input.chars().count() < GOTO_HISTORY_MIN_QUERY_LEN {
+        let suggestions = history_suggestions(history, &dialog.input);
+        if let Some(ref mut completion) = dialog.completion {
+            completion.marked_indices.clear();
+            if suggestions.is_empty() {
+                completion.suggestions.clear();
+                completion.visible = false;
+            } else {
+                completion.suggestions = suggestions;
+                completion.selected_index = 0;
+                completion.visible = true;
             }
         }
+    } else {
+        fallback(dialog);
     }
+}
+
+/// zsh 스타일 인라인 "고스트" 제안: 현재 입력으로 시작하는(대소문자 무시)
+/// 기록 중 recency+frequency 순위가 가장 높은 것의 나머지 부분을 돌려준다.
+/// 빈 입력이면 (드롭다운이 따로 전체 목록을 보여주므로) 제안하지 않는다.
+fn compute_history_ghost(history: &[String], current_input: &str) -> Option<String> {
+    if current_input.is_empty() {
+        return None;
+    }
+    let needle = current_input.to_lowercase();
+    history
+        .iter()
+        .find(|candidate| candidate.len() > current_input.len() && candidate.to_lowercase().starts_with(&needle))
+        .map(|candidate| candidate[current_input.len()..].to_string())
+}
+
+/// Goto 입력이 바뀔 때마다 호출: 경로 완성 후보 목록과(`goto_suggestions_for`
+/// 경유), 드롭다운이 떠 있지 않을 때 보여줄 인라인 고스트 제안
+/// (`completion.history_ghost`)을 함께 갱신한다. `:verb {arg}` 입력이면
+/// 둘 다 인자 부분에 대해서만 계산한다.
+fn update_goto_suggestions(dialog: &mut Dialog, filter: &ExtensionFilter, history: &[String]) {
+    with_goto_completion_arg(dialog, |dialog| {
+        goto_suggestions_for(dialog, history, |dialog| update_path_suggestions(dialog, filter));
+        let ghost = compute_history_ghost(history, &dialog.input);
+        if let Some(ref mut completion) = dialog.completion {
+            completion.history_ghost = ghost;
+        }
+    });
+}
+
+/// Tab으로 자동완성을 트리거할 때의 `update_goto_suggestions` 대응판 -
+/// 짧은 입력이면 방문 기록을, 아니면 기존 `trigger_path_completion`을
+/// 쓴다.
+fn trigger_goto_completion(dialog: &mut Dialog, filter: &ExtensionFilter, history: &[String]) {
+    with_goto_completion_arg(dialog, |dialog| {
+        goto_suggestions_for(dialog, history, |dialog| trigger_path_completion(dialog, filter));
+        let ghost = compute_history_ghost(history, &dialog.input);
+        if let Some(ref mut completion) = dialog.completion {
+            completion.history_ghost = ghost;
+        }
+    });
+}
+
+/// Right/End가 눌렸을 때 대기 중인 인라인 고스트 제안을 그대로 입력에
+/// 붙인다 (`:verb {arg}`면 인자 부분에). 드롭다운이 떠 있으면 그쪽 선택을
+/// 우선해야 하므로 호출하지 않는다.
+fn accept_history_ghost(dialog: &mut Dialog, filter: &ExtensionFilter, history: &[String]) {
+    with_goto_completion_arg(dialog, |dialog| {
+        let ghost = dialog.completion.as_ref().and_then(|c| c.history_ghost.clone());
+        if let Some(ghost) = ghost {
+            dialog.input.push_str(&ghost);
+        }
+    });
+    update_goto_suggestions(dialog, filter, history);
+}
+
+/// 후보 문자열(`candidate`)에 대해 `query`를 부분열(subsequence)로 매칭한다.
+/// 왼쪽부터 순서대로 각 query 문자를 다음에 나오는 일치 문자에 매칭하며,
+/// 중간에 매칭 못하는 문자가 있으면 `None`을 반환한다. 연속 매칭(run)일수록
+/// 가중치가 커지고, `/`·`_`·`-`·`.` 구분자 또는 대소문자 전환(단어 경계)
+/// 직후의 매칭과 0번 인덱스 매칭에 보너스를 준다. 선행 갭과 누적 갭 거리는
+/// 감점한다. 점수와 함께, 자동완성 목록이 굵게 표시할 수 있도록 매칭된 문자
+/// 인덱스들도 돌려준다.
+fn fuzzy_match(candidate: &str, query: &str) -> Option<(i32, Vec<usize>)> {
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let cand_chars: Vec<char> = candidate.chars().collect();
+    let query_chars: Vec<char> = query.chars().collect();
+
+    let mut positions: Vec<usize> = Vec::with_capacity(query_chars.len());
+    let mut score: i32 = 0;
+    let mut run = 0i32;
+    let mut cand_idx = 0;
+
+    for &qc in &query_chars {
+        let qc = qc.to_lowercase().next().unwrap_or(qc);
+        let found = (cand_idx..cand_chars.len())
+            .find(|&i| cand_chars[i].to_lowercase().next().unwrap_or(cand_chars[i]) == qc)?;
+
+        let gap = match positions.last() {
+            Some(&last) => found - last - 1,
+            None => found,
+        };
+
+        let is_boundary = found == 0
+            || matches!(cand_chars[found - 1], '/' | '_' | '-' | '.')
+            || (cand_chars[found - 1].is_lowercase() && cand_chars[found].is_uppercase());
+
+        if gap == 0 && !positions.is_empty() {
+            run += 1;
+            score += 4 + run;
+        } else {
+            run = 0;
+        }
+        if is_boundary {
+            score += 10;
+        }
+        if found == 0 {
+            score += 15;
+        }
+        score -= gap as i32;
+        if positions.is_empty() {
+            score -= found as i32;
+        }
+
+        positions.push(found);
+        cand_idx = found + 1;
+    }
+
+    Some((score, positions))
+}
 
-    // 디렉토리 우선, 그 다음 이름순 정렬
-    suggestions.sort_by(|a, b| {
-        match (a.1, b.1) {
+/// 디렉토리 읽기 및 이름 매칭. 모든 후보를 `fuzzy_match`로 채점해 내림차순
+/// 점수로 정렬한다 (동점이면 디렉토리 우선, 그다음 이름순) — 접두어는 그저
+/// 점수가 가장 높은 부분열 질의의 특수한 경우일 뿐이므로 별도 취급하지
+/// 않는다. 입력이 비어 있으면 모든 후보가 동점(0점)이 되어 디렉토리 우선·
+/// 이름순 정렬만 남는다. 각 항목의 이름·크기·수정 시각과 함께, 목록이
+/// 굵게 표시할 매칭 문자 인덱스도 돌려준다.
+fn get_path_suggestions(
+    base_dir: &PathBuf,
+    prefix: &str,
+    filter: &ExtensionFilter,
+) -> Vec<CompletionEntry> {
+    let entries: Vec<(String, bool, u64, String)> = match fs::read_dir(base_dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                let metadata = e.metadata().ok();
+                let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+                let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+                let modified = metadata
+                    .as_ref()
+                    .and_then(|m| m.modified().ok())
+                    .map(|t| {
+                        let dt: chrono::DateTime<chrono::Local> = t.into();
+                        dt.format("%m-%d %H:%M").to_string()
+                    })
+                    .unwrap_or_default();
+                (name, is_dir, size, modified)
+            })
+            .filter(|(name, is_dir, ..)| filter.passes(name, *is_dir))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let mut scored: Vec<(String, bool, u64, String, i32, Vec<usize>)> = entries
+        .into_iter()
+        .filter_map(|(name, is_dir, size, modified)| {
+            let (score, positions) = fuzzy_match(&name, prefix)?;
+            Some((name, is_dir, size, modified, score, positions))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| {
+        b.4.cmp(&a.4)
+            .then_with(|| b.1.cmp(&a.1))
+            .then_with(|| a.0.to_lowercase().cmp(&b.0.to_lowercase()))
+    });
+
+    scored
+        .into_iter()
+        .map(|(name, is_dir, size, modified, _, match_positions)| CompletionEntry {
+            name,
+            is_dir,
+            size,
+            modified,
+            match_positions,
+        })
+        .collect()
+}
+
+fn display_name(name: &str, is_dir: bool) -> String {
+    if is_dir {
+        format!("{}/", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// 미리보기 창에 표시할 디렉토리 자식 수 / 파일 줄 수 상한
+const PREVIEW_MAX_CHILDREN: usize = 20;
+const PREVIEW_MAX_LINES: usize = 40;
+
+/// 미리보기 창을 그릴 최소 다이얼로그 너비. 더 좁으면 패널이 완성 목록을
+/// 가려버리므로, 그 아래에서는 조용히 생략한다.
+const PREVIEW_MIN_DIALOG_WIDTH: u16 = 90;
+
+/// 현재 선택된 완성 항목의 미리보기. `completion.preview_cache`에 경로가
+/// 그대로 남아 있으면 디스크를 다시 읽지 않고 재사용한다.
+fn selected_preview(completion: &PathCompletion, base_dir: &Path) -> Option<PreviewContent> {
+    let entry = completion.suggestions.get(completion.selected_index)?;
+    let full_path = base_dir.join(&entry.name);
+
+    if let Some((cached_path, content)) = completion.preview_cache.borrow().as_ref() {
+        if *cached_path == full_path {
+            return Some(content.clone());
+        }
+    }
+
+    let content = build_preview(&full_path);
+    *completion.preview_cache.borrow_mut() = Some((full_path, content.clone()));
+    Some(content)
+}
+
+/// 디렉토리는 (get_path_suggestions와 같은 디렉토리 우선 정렬로) 자식 이름
+/// 일부를, 파일은 UTF-8로 읽을 수 있으면 앞부분 몇 줄을, 아니면 "binary /
+/// NNN bytes" 요약을 만든다.
+fn build_preview(path: &Path) -> PreviewContent {
+    let metadata = match fs::metadata(path) {
+        Ok(m) => m,
+        Err(_) => return PreviewContent::Unavailable,
+    };
+
+    if metadata.is_dir() {
+        let mut children: Vec<(String, bool)> = fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| {
+                        let name = e.file_name().to_string_lossy().to_string();
+                        let is_dir = e.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                        (name, is_dir)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        children.sort_by(|a, b| match (a.1, b.1) {
             (true, false) => std::cmp::Ordering::Less,
             (false, true) => std::cmp::Ordering::Greater,
             _ => a.0.to_lowercase().cmp(&b.0.to_lowercase()),
+        });
+
+        let total = children.len();
+        let entries = children
+            .into_iter()
+            .take(PREVIEW_MAX_CHILDREN)
+            .map(|(name, is_dir)| display_name(&name, is_dir))
+            .collect();
+
+        return PreviewContent::Directory { entries, total };
+    }
+
+    let size = metadata.len();
+    let modified = metadata
+        .modified()
+        .ok()
+        .map(|t| {
+            let datetime: chrono::DateTime<chrono::Local> = t.into();
+            datetime.format("%Y-%m-%d %H:%M").to_string()
+        })
+        .unwrap_or_default();
+
+    match fs::read(path) {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(text) => {
+                let lines = text.lines().take(PREVIEW_MAX_LINES).map(String::from).collect();
+                PreviewContent::File { lines, size, modified, binary: false }
+            }
+            Err(_) => PreviewContent::File { lines: Vec::new(), size, modified, binary: true },
+        },
+        Err(_) => PreviewContent::Unavailable,
+    }
+}
+
+/// 미리보기 창 렌더링 (디렉토리 목록 아래/완성 목록 옆)
+fn draw_preview_pane(frame: &mut Frame, content: &PreviewContent, area: Rect, theme: &Theme) {
+    let block = Block::default()
+        .title(" Preview ")
+        .title_style(theme.dim_style())
+        .borders(Borders::ALL)
+        .border_style(theme.border_style(false));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines: Vec<Line> = match content {
+        PreviewContent::Directory { entries, total } => {
+            let mut lines: Vec<Line> = entries
+                .iter()
+                .map(|name| Line::from(Span::styled(name.clone(), theme.normal_style())))
+                .collect();
+            if *total > entries.len() {
+                lines.push(Line::from(Span::styled(
+                    format!("... and {} more", total - entries.len()),
+                    theme.dim_style(),
+                )));
+            }
+            lines
         }
-    });
+        PreviewContent::File { lines, size, modified, binary } => {
+            let mut out = vec![Line::from(Span::styled(
+                format!("{}  {}", format_size(*size), modified),
+                theme.dim_style(),
+            ))];
+            if *binary {
+                out.push(Line::from(Span::styled("binary file", theme.dim_style())));
+            } else {
+                out.extend(
+                    lines
+                        .iter()
+                        .map(|l| Line::from(Span::styled(l.clone(), theme.normal_style()))),
+                );
+            }
+            out
+        }
+        PreviewContent::Unavailable => {
+            vec![Line::from(Span::styled("(unavailable)", theme.dim_style()))]
+        }
+    };
 
-    suggestions.into_iter().map(|(name, _)| name).collect()
+    let visible = lines.into_iter().take(inner.height as usize).collect::<Vec<_>>();
+    frame.render_widget(Paragraph::new(visible), inner);
 }
 
 /// 자동완성 목록 업데이트 (입력할 때마다 호출)
 /// 매칭되는 항목들을 목록에 표시
-fn update_path_suggestions(dialog: &mut Dialog) {
+fn update_path_suggestions(dialog: &mut Dialog, filter: &ExtensionFilter) {
     let (base_dir, prefix) = parse_path_for_completion(&dialog.input);
-    let suggestions = get_path_suggestions(&base_dir, &prefix);
+    let suggestions = get_path_suggestions(&base_dir, &prefix, filter);
 
     if let Some(ref mut completion) = dialog.completion {
+        completion.marked_indices.clear();
         if suggestions.is_empty() {
             completion.suggestions.clear();
             completion.visible = false;
@@ -129,28 +547,29 @@ fn update_path_suggestions(dialog: &mut Dialog) {
 
 /// Tab 키로 자동완성 트리거
 /// 유일 매칭: 바로 적용, 복수 매칭: 공통 접두어 적용
-fn trigger_path_completion(dialog: &mut Dialog) {
+fn trigger_path_completion(dialog: &mut Dialog, filter: &ExtensionFilter) {
     let (base_dir, prefix) = parse_path_for_completion(&dialog.input);
-    let suggestions = get_path_suggestions(&base_dir, &prefix);
+    let suggestions = get_path_suggestions(&base_dir, &prefix, filter);
+    let names: Vec<String> = suggestions.iter().map(|e| e.display_name()).collect();
 
     if let Some(ref mut completion) = dialog.completion {
-        if suggestions.is_empty() {
+        if names.is_empty() {
             completion.suggestions.clear();
             completion.visible = false;
-        } else if suggestions.len() == 1 {
+        } else if names.len() == 1 {
             // 유일 매칭 - 바로 적용
-            apply_completion(dialog, &base_dir, &suggestions[0]);
+            apply_completion(dialog, &base_dir, &names[0]);
             // 적용 후 새로운 suggestions 업데이트
-            update_path_suggestions(dialog);
+            update_path_suggestions(dialog, filter);
         } else {
             // 복수 매칭 - 공통 접두어 적용 후 목록 표시
-            let common = find_common_prefix(&suggestions);
+            let common = find_common_prefix(&names);
             if common.len() > prefix.len() {
                 let new_path = base_dir.join(&common);
                 dialog.input = new_path.display().to_string();
             }
             // 적용 후 새로운 suggestions 업데이트
-            update_path_suggestions(dialog);
+            update_path_suggestions(dialog, filter);
         }
     }
 }
@@ -218,7 +637,8 @@ pub fn draw_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Theme
             let w = area.width.saturating_sub(6).max(60);
             (w, 6 + completion_height)
         }
-        DialogType::Search | DialogType::Mkdir | DialogType::Rename => (50u16, 5u16),  // 간결한 입력창
+        DialogType::Search => (50u16, 6u16),  // 검색 모드 표시줄 포함
+        DialogType::Mkdir | DialogType::Rename | DialogType::Filter => (50u16, 5u16),  // 간결한 입력창
     };
 
     let x = area.x + (area.width.saturating_sub(width)) / 2;
@@ -238,7 +658,7 @@ pub fn draw_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Theme
         DialogType::Goto => {
             draw_goto_dialog(frame, dialog, dialog_area, theme);
         }
-        DialogType::Search | DialogType::Mkdir | DialogType::Rename => {
+        DialogType::Search | DialogType::Mkdir | DialogType::Rename | DialogType::Filter => {
             draw_simple_input_dialog(frame, dialog, dialog_area, theme);
         }
     }
@@ -250,6 +670,7 @@ fn draw_simple_input_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, them
         DialogType::Search => " Find File ",
         DialogType::Mkdir => " Create Directory ",
         DialogType::Rename => " Rename ",
+        DialogType::Filter => " Extension Filter (comma-separated, e.g. jpg,png) ",
         _ => " Input ",
     };
 
@@ -283,6 +704,30 @@ fn draw_simple_input_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, them
     let y_pos = inner.y + inner.height / 2;
     let input_area = Rect::new(inner.x + 1, y_pos, inner.width - 2, 1);
     frame.render_widget(Paragraph::new(input_line), input_area);
+
+    if dialog.dialog_type == DialogType::Search {
+        let pattern = crate::ui::search_result::SearchPattern::parse(&dialog.input);
+        let mode_line = match pattern {
+            crate::ui::search_result::SearchPattern::NameFuzzy(_) => {
+                let mode = crate::ui::search_result::SearchMode::from_index(dialog.selected_button);
+                Line::from(vec![
+                    Span::styled("Mode: ", theme.dim_style()),
+                    Span::styled(mode.label(), Style::default().fg(theme.info)),
+                    Span::styled("  [Tab] cycle, or /regex/ c/content/", theme.dim_style()),
+                ])
+            }
+            crate::ui::search_result::SearchPattern::NameRegex(_) => Line::from(vec![
+                Span::styled("Mode: ", theme.dim_style()),
+                Span::styled("Regex (name)", Style::default().fg(theme.info)),
+            ]),
+            crate::ui::search_result::SearchPattern::ContentRegex(_) => Line::from(vec![
+                Span::styled("Mode: ", theme.dim_style()),
+                Span::styled("Regex (content)", Style::default().fg(theme.info)),
+            ]),
+        };
+        let mode_area = Rect::new(inner.x + 1, y_pos + 1, inner.width - 2, 1);
+        frame.render_widget(Paragraph::new(mode_line), mode_area);
+    }
 }
 
 fn draw_confirm_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Theme) {
@@ -365,11 +810,11 @@ fn draw_copy_move_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme:
 
     let preview_suffix = if let Some(ref completion) = dialog.completion {
         if completion.visible && !completion.suggestions.is_empty() {
-            if let Some(selected) = completion.suggestions.get(completion.selected_index) {
-                let selected_name = selected.trim_end_matches('/');
+            if let Some(entry) = completion.suggestions.get(completion.selected_index) {
+                let selected_name = entry.name.as_str();
                 if selected_name.to_lowercase().starts_with(&current_prefix.to_lowercase()) {
                     let suffix = &selected_name[current_prefix.len()..];
-                    if selected.ends_with('/') {
+                    if entry.is_dir {
                         format!("{}/", suffix)
                     } else {
                         suffix.to_string()
@@ -435,13 +880,34 @@ fn draw_copy_move_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme:
 
     if let Some(ref completion) = dialog.completion {
         if completion.visible && !completion.suggestions.is_empty() {
+            let list_height = inner.height.saturating_sub(6);
+            let show_preview = inner.width >= PREVIEW_MIN_DIALOG_WIDTH;
+            let preview_width = if show_preview { (inner.width / 3).min(50) } else { 0 };
+            let list_width = if show_preview {
+                list_width.saturating_sub(preview_width + 1)
+            } else {
+                list_width
+            };
+
             draw_completion_list(
                 frame,
                 completion,
-                Rect::new(list_x, list_start_y, list_width, inner.height.saturating_sub(6)),
+                Rect::new(list_x, list_start_y, list_width, list_height),
                 theme,
                 is_root_path,
             );
+
+            if show_preview {
+                if let Some(content) = selected_preview(completion, &base_dir) {
+                    let preview_area = Rect::new(
+                        list_x + list_width + 1,
+                        list_start_y,
+                        preview_width,
+                        list_height,
+                    );
+                    draw_preview_pane(frame, &content, preview_area, theme);
+                }
+            }
         }
     }
 
@@ -522,31 +988,43 @@ fn draw_goto_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Them
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    // `:verb {arg}` 입력이면 경로 완성 대상은 인자 부분뿐이므로, verb
+    // 접두어 길이만큼 오프셋을 두고 그 나머지만 기존 경로 로직에 넘긴다.
+    let (verb_prefix, completion_input) = match split_goto_verb_arg(&dialog.input) {
+        Some((prefix, arg)) => (prefix, arg),
+        None => (String::new(), dialog.input.clone()),
+    };
+    let verb_prefix_chars = verb_prefix.chars().count();
+
     // 입력에서 완성할 이름(prefix)의 시작 위치 계산 (char 인덱스)
-    let input_chars: Vec<char> = dialog.input.chars().collect();
-    let prefix_char_start = if dialog.input.ends_with('/') {
-        input_chars.len()
+    let full_input_chars: Vec<char> = dialog.input.chars().collect();
+    let target_chars: Vec<char> = completion_input.chars().collect();
+    let target_prefix_start = if completion_input.ends_with('/') {
+        target_chars.len()
     } else {
         // 마지막 '/' 위치 찾기
-        input_chars.iter().rposition(|&c| c == '/').map(|i| i + 1).unwrap_or(0)
+        target_chars.iter().rposition(|&c| c == '/').map(|i| i + 1).unwrap_or(0)
     };
+    let prefix_char_start = verb_prefix_chars + target_prefix_start;
 
     // 현재 입력된 prefix 추출
-    let current_prefix: String = input_chars[prefix_char_start..].iter().collect();
+    let current_prefix: String = target_chars[target_prefix_start..].iter().collect();
 
     // base_dir 계산하여 루트 경로 여부 확인
-    let (base_dir, _) = parse_path_for_completion(&dialog.input);
+    let (base_dir, _) = parse_path_for_completion(&completion_input);
     let is_root_path = base_dir == Path::new("/");
 
-    // 선택된 항목에서 미리보기 부분 계산 (입력된 prefix 이후 부분)
+    // 선택된 항목에서 미리보기 부분 계산 (입력된 prefix 이후 부분). 완성
+    // 드롭다운이 떠 있지 않으면, 대신 방문 기록에서 온 인라인 고스트
+    // 제안(`history_ghost`)을 같은 자리에 흐리게 보여준다.
     let preview_suffix = if let Some(ref completion) = dialog.completion {
         if completion.visible && !completion.suggestions.is_empty() {
-            if let Some(selected) = completion.suggestions.get(completion.selected_index) {
-                let selected_name = selected.trim_end_matches('/');
+            if let Some(entry) = completion.suggestions.get(completion.selected_index) {
+                let selected_name = entry.name.as_str();
                 // 대소문자 무시하여 prefix 매칭 후 나머지 부분 추출
                 if selected_name.to_lowercase().starts_with(&current_prefix.to_lowercase()) {
                     let suffix = &selected_name[current_prefix.len()..];
-                    if selected.ends_with('/') {
+                    if entry.is_dir {
                         format!("{}/", suffix)
                     } else {
                         suffix.to_string()
@@ -558,7 +1036,7 @@ fn draw_goto_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Them
                 String::new()
             }
         } else {
-            String::new()
+            completion.history_ghost.clone().unwrap_or_default()
         }
     } else {
         String::new()
@@ -568,7 +1046,7 @@ fn draw_goto_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Them
     // 미리보기를 포함한 전체 길이 고려
     let max_input_width = (inner.width - 4) as usize;
     let preview_chars: Vec<char> = preview_suffix.chars().collect();
-    let total_len = input_chars.len() + preview_chars.len();
+    let total_len = full_input_chars.len() + preview_chars.len();
 
     let (display_input, display_preview, display_prefix_start) = if total_len > max_input_width {
         // 앞부분을 ...로 생략하고 뒷부분(미리보기 포함) 표시
@@ -581,8 +1059,8 @@ fn draw_goto_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Them
         } else {
             // 입력 일부 + 미리보기 전체 표시
             let input_available = available - preview_chars.len();
-            let skip = input_chars.len().saturating_sub(input_available);
-            let input_display: String = input_chars[skip..].iter().collect();
+            let skip = full_input_chars.len().saturating_sub(input_available);
+            let input_display: String = full_input_chars[skip..].iter().collect();
             let prefix_pos = if prefix_char_start >= skip {
                 3 + (prefix_char_start - skip)
             } else {
@@ -621,13 +1099,34 @@ fn draw_goto_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Them
 
     if let Some(ref completion) = dialog.completion {
         if completion.visible && !completion.suggestions.is_empty() {
+            let list_height = inner.height.saturating_sub(3);
+            let show_preview = inner.width >= PREVIEW_MIN_DIALOG_WIDTH;
+            let preview_width = if show_preview { (inner.width / 3).min(50) } else { 0 };
+            let list_width = if show_preview {
+                list_width.saturating_sub(preview_width + 1)
+            } else {
+                list_width
+            };
+
             draw_completion_list(
                 frame,
                 completion,
-                Rect::new(list_x, list_start_y, list_width, inner.height.saturating_sub(3)),
+                Rect::new(list_x, list_start_y, list_width, list_height),
                 theme,
                 is_root_path,
             );
+
+            if show_preview {
+                if let Some(content) = selected_preview(completion, &base_dir) {
+                    let preview_area = Rect::new(
+                        list_x + list_width + 1,
+                        list_start_y,
+                        preview_width,
+                        list_height,
+                    );
+                    draw_preview_pane(frame, &content, preview_area, theme);
+                }
+            }
         }
     }
 
@@ -654,6 +1153,15 @@ fn draw_goto_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Them
                 Span::styled(":cancel", theme.dim_style()),
             ])
         }
+    } else if dialog.input.is_empty() {
+        // 빈 입력일 때만 verb 문법 힌트를 보여준다 - 경로를 치기 시작하면
+        // 자동완성 도움말로 대체되어 자리를 두고 경쟁하지 않는다.
+        let hints = GOTO_VERBS
+            .iter()
+            .map(|v| format!(":{} {}", v.name, v.arg_hint))
+            .collect::<Vec<_>>()
+            .join("  ");
+        Line::from(Span::styled(hints.trim().to_string(), theme.dim_style()))
     } else {
         Line::from(vec![
             Span::styled("Enter", theme.header_style()),
@@ -667,7 +1175,21 @@ fn draw_goto_dialog(frame: &mut Frame, dialog: &Dialog, area: Rect, theme: &Them
     frame.render_widget(Paragraph::new(help_line), help_area);
 }
 
-/// 자동완성 목록 렌더링
+/// 이름 컬럼 너비에 맞춰 자르고, 잘렸으면 "..."을 붙인다.
+fn truncate_name(name: &str, max_width: usize) -> String {
+    let char_count = name.chars().count();
+    if char_count <= max_width {
+        name.to_string()
+    } else if max_width <= 3 {
+        ".".repeat(max_width)
+    } else {
+        let kept: String = name.chars().take(max_width - 3).collect();
+        format!("{}...", kept)
+    }
+}
+
+/// 자동완성 목록 렌더링. 이름 뒤에 크기·수정 시각 컬럼을 오른쪽 정렬로
+/// 붙이고, 퍼지 매칭된 문자는 굵게 표시한다.
 fn draw_completion_list(
     frame: &mut Frame,
     completion: &PathCompletion,
@@ -687,7 +1209,7 @@ fn draw_completion_list(
         completion.selected_index - max_visible / 2
     };
 
-    let visible_items: Vec<&String> = completion
+    let visible_items: Vec<&CompletionEntry> = completion
         .suggestions
         .iter()
         .skip(scroll_offset)
@@ -701,29 +1223,87 @@ fn draw_completion_list(
     let dir_style = Style::default().fg(theme.text_directory);
     let file_style = theme.normal_style();
 
-    for (i, suggestion) in visible_items.iter().enumerate() {
+    // 너비가 좁으면(예: 미리보기 창과 나란히) 메타데이터 컬럼은 조용히 생략
+    const SIZE_WIDTH: usize = 8;
+    const DATE_WIDTH: usize = 11;
+    const MARK_WIDTH: usize = 2;
+    let show_columns = area.width as usize >= 30;
+    let meta_width = if show_columns { SIZE_WIDTH + 1 + DATE_WIDTH + 1 } else { 0 };
+    let name_width = (area.width as usize)
+        .saturating_sub(meta_width)
+        .saturating_sub(MARK_WIDTH)
+        .max(1);
+
+    let marked_style = Style::default().bg(theme.bg_status_bar).fg(theme.warning);
+
+    for (i, entry) in visible_items.iter().enumerate() {
         let actual_index = scroll_offset + i;
         let is_selected = actual_index == completion.selected_index;
-        let is_dir = suggestion.ends_with('/');
+        let is_marked = completion.marked_indices.contains(&actual_index);
 
-        let style = if is_selected {
+        let base_style = if is_selected {
             selected_style
-        } else if is_dir {
+        } else if is_marked {
+            marked_style
+        } else if entry.is_dir {
             dir_style
         } else {
             file_style
         };
 
+        let mark_col = if is_marked { "\u{2713} " } else { "  " };
+
+        let glyph = if entry.is_dir { theme.chars.folder } else { theme.chars.file };
         // 루트 경로일 때 "/" 추가
-        let display_name = if is_root {
-            format!("/{}", suggestion)
+        let raw_name = if is_root {
+            format!("/{}{}", glyph, entry.name)
         } else {
-            suggestion.to_string()
+            format!("{}{}", glyph, entry.name)
         };
+        let prefix_chars = if is_root { 2 } else { 1 };
+        let display_name = truncate_name(&raw_name, name_width);
+
+        let mut spans: Vec<Span<'static>> = vec![Span::styled(mark_col, base_style)];
+        if entry.match_positions.is_empty() {
+            spans.push(Span::styled(
+                format!("{:<width$}", display_name, width = name_width),
+                base_style,
+            ));
+        } else {
+            let bold_style = base_style.add_modifier(Modifier::BOLD);
+            let mut rendered_width = 0usize;
+            for (idx, ch) in display_name.chars().enumerate() {
+                let matched = idx >= prefix_chars
+                    && entry.match_positions.contains(&(idx - prefix_chars));
+                let style = if matched { bold_style } else { base_style };
+                spans.push(Span::styled(ch.to_string(), style));
+                rendered_width += 1;
+            }
+            if rendered_width < name_width {
+                spans.push(Span::styled(
+                    " ".repeat(name_width - rendered_width),
+                    base_style,
+                ));
+            }
+        }
 
-        // 전체 라인을 선택 스타일로 채우기
-        let padded = format!("{:<width$}", display_name, width = area.width as usize);
-        let line = Line::from(Span::styled(padded, style));
+        if show_columns {
+            let size_str = if entry.is_dir {
+                "<DIR>".to_string()
+            } else {
+                format_size(entry.size)
+            };
+            spans.push(Span::styled(
+                format!(" {:>width$}", size_str, width = SIZE_WIDTH - 1),
+                base_style,
+            ));
+            spans.push(Span::styled(
+                format!(" {:>width$}", entry.modified, width = DATE_WIDTH - 1),
+                base_style,
+            ));
+        }
+
+        let line = Line::from(spans);
 
         let y = area.y + i as u16;
         if y < area.y + area.height {
@@ -752,7 +1332,7 @@ pub fn handle_dialog_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers
                 match code {
                     KeyCode::Char('y') | KeyCode::Char('Y') => {
                         app.dialog = None;
-                        app.execute_delete();
+                        app.execute_delete(modifiers.contains(KeyModifiers::SHIFT));
                     }
                     KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                         app.dialog = None;
@@ -764,7 +1344,7 @@ pub fn handle_dialog_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers
                     KeyCode::Enter => {
                         if dialog.selected_button == 0 {
                             app.dialog = None;
-                            app.execute_delete();
+                            app.execute_delete(modifiers.contains(KeyModifiers::SHIFT));
                         } else {
                             app.dialog = None;
                         }
@@ -778,17 +1358,57 @@ pub fn handle_dialog_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers
             DialogType::Goto => {
                 return handle_goto_dialog_input(app, code, modifiers);
             }
+            DialogType::Search => {
+                match code {
+                    KeyCode::Enter => {
+                        let input = dialog.input.clone();
+                        let selected_button = dialog.selected_button;
+                        app.dialog = None;
+                        if !input.trim().is_empty() {
+                            // `/regex/`, `c/regex/` 패턴 문법이 있으면 그걸 쓰고,
+                            // 그냥 단어면 Tab으로 고른 모드를 그대로 쓴다 (Glob 등
+                            // 패턴 문법이 표현하지 못하는 모드를 위해).
+                            match crate::ui::search_result::SearchPattern::parse(&input) {
+                                crate::ui::search_result::SearchPattern::NameFuzzy(_) => {
+                                    let mode = crate::ui::search_result::SearchMode::from_index(selected_button);
+                                    app.execute_search_with_mode(&input, mode);
+                                }
+                                _ => {
+                                    app.execute_search_with_pattern(&input);
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Esc => {
+                        app.dialog = None;
+                    }
+                    KeyCode::Tab => {
+                        // 검색 모드 순환 (Substring -> Glob -> Regex -> Content)
+                        dialog.selected_button = (dialog.selected_button + 1)
+                            % crate::ui::search_result::SearchMode::ALL.len();
+                    }
+                    KeyCode::Backspace => {
+                        dialog.input.pop();
+                    }
+                    KeyCode::Char(c) => {
+                        dialog.input.push(c);
+                    }
+                    _ => {}
+                }
+            }
             _ => {
                 match code {
                     KeyCode::Enter => {
                         let input = dialog.input.clone();
                         let dialog_type = dialog.dialog_type;
                         app.dialog = None;
-                        if !input.trim().is_empty() {
+                        if dialog_type == DialogType::Filter {
+                            // 빈 입력도 유효 — 필터를 지우는 의미이므로 trim 검사를 건너뛴다.
+                            app.execute_set_extension_filter(&input);
+                        } else if !input.trim().is_empty() {
                             match dialog_type {
                                 DialogType::Mkdir => app.execute_mkdir(&input),
                                 DialogType::Rename => app.execute_rename(&input),
-                                DialogType::Search => app.execute_search(&input),
                                 DialogType::Goto => app.execute_goto(&input),
                                 _ => {}
                             }
@@ -813,6 +1433,8 @@ pub fn handle_dialog_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers
 
 /// Go to Path 대화상자 키 입력 처리
 fn handle_goto_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyModifiers) -> bool {
+    let filter = app.extension_filter.clone();
+    let history: Vec<String> = app.path_history.ranked().iter().map(|s| s.to_string()).collect();
     if let Some(ref mut dialog) = app.dialog {
         let completion_visible = dialog
             .completion
@@ -823,21 +1445,19 @@ fn handle_goto_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyModifie
         match code {
             KeyCode::Tab => {
                 if completion_visible {
-                    // 목록에서 선택된 항목으로 완성
-                    let (base_dir, _) = parse_path_for_completion(&dialog.input);
-                    let suggestion = dialog
-                        .completion
-                        .as_ref()
-                        .and_then(|c| c.suggestions.get(c.selected_index).cloned());
-
-                    if let Some(suggestion) = suggestion {
-                        apply_completion(dialog, &base_dir, &suggestion);
-                    }
+                    // 목록에서 선택된 항목으로 완성 (`:verb {arg}`면 인자 부분만)
+                    apply_selected_goto_completion(dialog);
                     // 완성 후 새로운 suggestions 업데이트
-                    update_path_suggestions(dialog);
+                    update_goto_suggestions(dialog, &filter, &history);
                 } else {
                     // 목록이 없으면 자동완성 트리거
-                    trigger_path_completion(dialog);
+                    trigger_goto_completion(dialog, &filter, &history);
+                }
+            }
+            KeyCode::Right | KeyCode::End => {
+                // 드롭다운이 떠 있으면 그쪽 선택이 우선이므로 건드리지 않는다
+                if !completion_visible {
+                    accept_history_ghost(dialog, &filter, &history);
                 }
             }
             KeyCode::BackTab => {
@@ -879,24 +1499,22 @@ fn handle_goto_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyModifie
             }
             KeyCode::Enter => {
                 if completion_visible {
-                    // 선택된 항목으로 완성
-                    let (base_dir, _) = parse_path_for_completion(&dialog.input);
-                    let suggestion = dialog
-                        .completion
-                        .as_ref()
-                        .and_then(|c| c.suggestions.get(c.selected_index).cloned());
-
-                    if let Some(suggestion) = suggestion {
-                        apply_completion(dialog, &base_dir, &suggestion);
-                    }
+                    // 선택된 항목으로 완성 (`:verb {arg}`면 인자 부분만)
+                    apply_selected_goto_completion(dialog);
                 }
 
-                // 경로 검증
                 let input = dialog.input.clone();
                 if input.trim().is_empty() {
                     return false;
                 }
 
+                // `:verb args` 문법이면 일반 경로 처리 대신 verb 테이블로 위임
+                if let Some((verb, arg)) = parse_goto_command(&input) {
+                    app.dialog = None;
+                    execute_goto_command(app, verb, arg);
+                    return false;
+                }
+
                 let path = expand_path_string(&input);
 
                 if !path.exists() {
@@ -943,32 +1561,24 @@ fn handle_goto_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyModifie
             KeyCode::Backspace => {
                 dialog.input.pop();
                 // 입력 변경 후 자동완성 목록 업데이트
-                update_path_suggestions(dialog);
+                update_goto_suggestions(dialog, &filter, &history);
             }
             KeyCode::Char(c) => {
                 if c == '/' && completion_visible {
                     // '/' 입력 시 선택된 항목으로 완성 (Tab과 동일)
-                    let (base_dir, _) = parse_path_for_completion(&dialog.input);
-                    let suggestion = dialog
-                        .completion
-                        .as_ref()
-                        .and_then(|comp| comp.suggestions.get(comp.selected_index).cloned());
-
-                    if let Some(suggestion) = suggestion {
-                        apply_completion(dialog, &base_dir, &suggestion);
-                    }
+                    apply_selected_goto_completion(dialog);
                     // 완성 후 새로운 suggestions 업데이트
-                    update_path_suggestions(dialog);
-                } else if c == '~' {
-                    // '~' 입력 시 홈 폴더 경로로 설정
+                    update_goto_suggestions(dialog, &filter, &history);
+                } else if c == '~' && parse_goto_command(&dialog.input).is_none() {
+                    // '~' 입력 시 홈 폴더 경로로 설정 (verb 인자 중에는 그냥 입력값으로 받는다)
                     if let Some(home) = dirs::home_dir() {
                         dialog.input = format!("{}/", home.display());
-                        update_path_suggestions(dialog);
+                        update_goto_suggestions(dialog, &filter, &history);
                     }
                 } else {
                     dialog.input.push(c);
                     // 입력 변경 후 자동완성 목록 업데이트
-                    update_path_suggestions(dialog);
+                    update_goto_suggestions(dialog, &filter, &history);
                 }
             }
             _ => {}
@@ -977,8 +1587,59 @@ fn handle_goto_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyModifie
     false
 }
 
+/// Goto 다이얼로그에서 `:verb args` 입력을 실행한다. `arg`가 비어 있으면
+/// `:cp`/`:mv`/`:focus`는 활성 패널에서 현재 선택된 항목의 경로로
+/// 대체한다 (broot에서 선택 항목이 현재 타겟이 되는 것과 같은 맥락).
+/// `:mkdir`은 대체하지 않고 사용법 메시지만 보여준다. `Copy`/`Move`/`Mkdir`/`Delete`/`Goto` 각각의 기존
+/// `execute_*`에 그대로 위임하므로, 단축키로 같은 대화상자를 열어 쓰는
+/// 경로와 동작이 갈리지 않는다.
+fn execute_goto_command(app: &mut App, verb: &str, arg: &str) {
+    if !GOTO_VERBS.iter().any(|v| v.name == verb) {
+        app.show_message(&format!("Unknown command: :{}", verb));
+        return;
+    }
+
+    let selected_path = app
+        .active_panel()
+        .current_file()
+        .map(|f| app.active_panel().path.join(&f.name));
+    let target_path = if arg.trim().is_empty() {
+        selected_path
+    } else {
+        Some(expand_path_string(arg.trim()))
+    };
+
+    match verb {
+        "mkdir" => match arg.trim() {
+            "" => app.show_message("Usage: :mkdir {name}"),
+            name => app.execute_mkdir(name),
+        },
+        "cp" => match target_path {
+            Some(path) if path.is_dir() => app.execute_copy_to_with_progress(&path),
+            _ => app.show_message("Usage: :cp {dir} (target must be an existing directory)"),
+        },
+        "mv" => match target_path {
+            Some(path) if path.is_dir() => app.execute_move_to_with_progress(&path),
+            _ => app.show_message("Usage: :mv {dir} (target must be an existing directory)"),
+        },
+        "rm" => app.execute_delete(false),
+        "focus" => match target_path {
+            Some(path) if path.is_file() => {
+                let filename = path.file_name().map(|n| n.to_string_lossy().to_string());
+                if let Some(parent) = path.parent() {
+                    app.goto_directory_with_focus(parent, filename);
+                }
+            }
+            Some(path) if path.is_dir() => app.execute_goto(&path.display().to_string()),
+            _ => app.show_message("Usage: :focus {path}"),
+        },
+        _ => unreachable!("checked by GOTO_VERBS lookup above"),
+    }
+}
+
 /// Copy/Move 다이얼로그 키 입력 처리
-fn handle_copy_move_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyModifiers) -> bool {
+fn handle_copy_move_dialog_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> bool {
+    let filter = app.extension_filter.clone();
     if let Some(ref mut dialog) = app.dialog {
         let completion_visible = dialog
             .completion
@@ -993,20 +1654,21 @@ fn handle_copy_move_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyMo
                     let suggestion = dialog
                         .completion
                         .as_ref()
-                        .and_then(|c| c.suggestions.get(c.selected_index).cloned());
+                        .and_then(|c| c.suggestions.get(c.selected_index).map(|e| e.display_name()));
 
                     if let Some(suggestion) = suggestion {
                         apply_completion(dialog, &base_dir, &suggestion);
                     }
-                    update_path_suggestions(dialog);
+                    update_path_suggestions(dialog, &filter);
                 } else {
-                    trigger_path_completion(dialog);
+                    trigger_path_completion(dialog, &filter);
                 }
             }
-            KeyCode::BackTab | KeyCode::Up => {
+            KeyCode::BackTab => {
                 if completion_visible {
                     if let Some(ref mut completion) = dialog.completion {
                         if !completion.suggestions.is_empty() {
+                            completion.marked_indices.clear();
                             if completion.selected_index == 0 {
                                 completion.selected_index = completion.suggestions.len() - 1;
                             } else {
@@ -1016,12 +1678,45 @@ fn handle_copy_move_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyMo
                     }
                 }
             }
+            KeyCode::Up => {
+                if completion_visible {
+                    if let Some(ref mut completion) = dialog.completion {
+                        if !completion.suggestions.is_empty() {
+                            if modifiers.contains(KeyModifiers::SHIFT) {
+                                // Shift+Up: 현재 선택을 범위에 포함시키고 위로 확장
+                                completion.marked_indices.insert(completion.selected_index);
+                                if completion.selected_index > 0 {
+                                    completion.selected_index -= 1;
+                                }
+                                completion.marked_indices.insert(completion.selected_index);
+                            } else {
+                                completion.marked_indices.clear();
+                                if completion.selected_index == 0 {
+                                    completion.selected_index = completion.suggestions.len() - 1;
+                                } else {
+                                    completion.selected_index -= 1;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
             KeyCode::Down => {
                 if completion_visible {
                     if let Some(ref mut completion) = dialog.completion {
                         if !completion.suggestions.is_empty() {
-                            completion.selected_index =
-                                (completion.selected_index + 1) % completion.suggestions.len();
+                            if modifiers.contains(KeyModifiers::SHIFT) {
+                                // Shift+Down: 현재 선택을 범위에 포함시키고 아래로 확장
+                                completion.marked_indices.insert(completion.selected_index);
+                                if completion.selected_index + 1 < completion.suggestions.len() {
+                                    completion.selected_index += 1;
+                                }
+                                completion.marked_indices.insert(completion.selected_index);
+                            } else {
+                                completion.marked_indices.clear();
+                                completion.selected_index =
+                                    (completion.selected_index + 1) % completion.suggestions.len();
+                            }
                         }
                     }
                 }
@@ -1029,15 +1724,60 @@ fn handle_copy_move_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyMo
             KeyCode::Enter => {
                 if completion_visible {
                     let (base_dir, _) = parse_path_for_completion(&dialog.input);
+
+                    // Shift+Up/Down으로 여러 디렉토리를 표시해 둔 상태라면, 목록에서
+                    // 하나를 골라 입력줄에 적용하는 대신 표시된 모든 디렉토리에 대해
+                    // 바로 복사/이동을 실행한다.
+                    let marked_dirs: Vec<PathBuf> = dialog
+                        .completion
+                        .as_ref()
+                        .map(|c| {
+                            c.marked_indices
+                                .iter()
+                                .filter_map(|&i| c.suggestions.get(i))
+                                .filter(|e| e.is_dir)
+                                .map(|e| base_dir.join(&e.name))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
+                    if marked_dirs.len() > 1 {
+                        let dialog_type = dialog.dialog_type;
+                        app.dialog = None;
+
+                        // Snapshot the file list once: the underlying
+                        // `execute_*_to_with_progress` clears the stage
+                        // after reading it, so calling it once per target
+                        // would only copy/move the full set to the first
+                        // target and fall back to the cursor selection for
+                        // every target after that.
+                        let file_paths: Vec<PathBuf> = if !app.stage.is_empty() {
+                            let paths = app.stage.paths().to_vec();
+                            app.stage.clear();
+                            paths
+                        } else {
+                            app.get_operation_files().iter().map(PathBuf::from).collect()
+                        };
+
+                        for target in &marked_dirs {
+                            match dialog_type {
+                                DialogType::Copy => app.execute_copy_files_to_with_progress(file_paths.clone(), target),
+                                DialogType::Move => app.execute_move_files_to_with_progress(file_paths.clone(), target),
+                                _ => {}
+                            }
+                        }
+                        return false;
+                    }
+
                     let suggestion = dialog
                         .completion
                         .as_ref()
-                        .and_then(|c| c.suggestions.get(c.selected_index).cloned());
+                        .and_then(|c| c.suggestions.get(c.selected_index).map(|e| e.display_name()));
 
                     if let Some(suggestion) = suggestion {
                         apply_completion(dialog, &base_dir, &suggestion);
                     }
-                    update_path_suggestions(dialog);
+                    update_path_suggestions(dialog, &filter);
                     return false;
                 }
 
@@ -1065,8 +1805,8 @@ fn handle_copy_move_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyMo
                 app.dialog = None;
 
                 match dialog_type {
-                    DialogType::Copy => app.execute_copy_to(&target_path),
-                    DialogType::Move => app.execute_move_to(&target_path),
+                    DialogType::Copy => app.execute_copy_to_with_progress(&target_path),
+                    DialogType::Move => app.execute_move_to_with_progress(&target_path),
                     _ => {}
                 }
                 return false;
@@ -1083,7 +1823,7 @@ fn handle_copy_move_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyMo
             }
             KeyCode::Backspace => {
                 dialog.input.pop();
-                update_path_suggestions(dialog);
+                update_path_suggestions(dialog, &filter);
             }
             KeyCode::Char(c) => {
                 if c == '/' && completion_visible {
@@ -1091,20 +1831,20 @@ fn handle_copy_move_dialog_input(app: &mut App, code: KeyCode, _modifiers: KeyMo
                     let suggestion = dialog
                         .completion
                         .as_ref()
-                        .and_then(|comp| comp.suggestions.get(comp.selected_index).cloned());
+                        .and_then(|comp| comp.suggestions.get(comp.selected_index).map(|e| e.display_name()));
 
                     if let Some(suggestion) = suggestion {
                         apply_completion(dialog, &base_dir, &suggestion);
                     }
-                    update_path_suggestions(dialog);
+                    update_path_suggestions(dialog, &filter);
                 } else if c == '~' {
                     if let Some(home) = dirs::home_dir() {
                         dialog.input = format!("{}/", home.display());
-                        update_path_suggestions(dialog);
+                        update_path_suggestions(dialog, &filter);
                     }
                 } else {
                     dialog.input.push(c);
-                    update_path_suggestions(dialog);
+                    update_path_suggestions(dialog, &filter);
                 }
             }
             _ => {}
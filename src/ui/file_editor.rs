@@ -1,6 +1,8 @@
 use std::fs;
+use std::io::Write;
 use std::path::PathBuf;
 use std::collections::VecDeque;
+use std::time::Instant;
 use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
     layout::Rect,
@@ -13,49 +15,45 @@ use regex::Regex;
 
 use super::{
     app::{App, Screen},
-    syntax::{Language, SyntaxHighlighter},
+    syntax::{LexerState, Language, SyntaxHighlighter, Token, TokenType},
     theme::Theme,
 };
+use crate::utils::rope::Rope;
 
 /// Undo/Redo 액션 유형
+///
+/// Every edit is addressed by an absolute char offset into the document
+/// rather than by `(line, col)`, so a newline is just another char and no
+/// separate line-merge/line-split/line-insert variant is needed. `undo`
+/// reverses an action by swapping `Insert`/`Delete`; `redo` replays it
+/// verbatim. `Batch` reverses by reversing both the action list and each
+/// action within it, which exactly retraces a composite edit's forward
+/// char offsets in reverse.
 #[derive(Debug, Clone)]
 pub enum EditAction {
     Insert {
-        line: usize,
-        col: usize,
+        char_idx: usize,
         text: String,
     },
     Delete {
-        line: usize,
-        col: usize,
+        char_idx: usize,
         text: String,
     },
-    InsertLine {
-        line: usize,
-        content: String,
-    },
-    DeleteLine {
-        line: usize,
-        content: String,
-    },
-    MergeLine {
-        line: usize,
-        col: usize,
-    },
-    SplitLine {
-        line: usize,
-        col: usize,
-    },
-    Replace {
-        line: usize,
-        old_content: String,
-        new_content: String,
-    },
     Batch {
         actions: Vec<EditAction>,
     },
 }
 
+/// Which side of a coalescing keystroke an `EditAction` came from. Two
+/// consecutive pushes of the same kind that are spatially contiguous and
+/// land within `undo_coalesce_ms` of each other merge into one undo entry
+/// instead of stacking a separate entry per character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UndoKind {
+    Insert,
+    Delete,
+}
+
 /// 선택 영역
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Selection {
@@ -91,6 +89,134 @@ impl Selection {
     }
 }
 
+/// One cursor/selection pair in a multi-caret edit. `EditorState`'s own
+/// `cursor_line`/`cursor_col`/`selection` fields remain the primary
+/// caret; `secondary_carets` holds any additional ones added on top of
+/// it. Every editing primitive applies to all of them at once.
+#[derive(Debug, Clone, Copy)]
+pub struct Caret {
+    pub line: usize,
+    pub col: usize,
+    pub selection: Option<Selection>,
+}
+
+/// Dominant line-ending style detected from a file's raw bytes on load and
+/// reused verbatim on save, so opening a CRLF or `\r`-only file and saving
+/// it back doesn't silently rewrite every line ending to `\n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl LineEnding {
+    fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+
+    /// Scan raw bytes and pick whichever ending occurs most often,
+    /// defaulting to `Lf` for a file with no line breaks at all.
+    fn detect(bytes: &[u8]) -> Self {
+        let (mut crlf, mut lf, mut cr) = (0usize, 0usize, 0usize);
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'\r' {
+                if i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
+                    crlf += 1;
+                    i += 2;
+                    continue;
+                }
+                cr += 1;
+            } else if bytes[i] == b'\n' {
+                lf += 1;
+            }
+            i += 1;
+        }
+        if crlf >= lf && crlf >= cr && crlf > 0 {
+            LineEnding::CrLf
+        } else if cr > lf {
+            LineEnding::Cr
+        } else {
+            LineEnding::Lf
+        }
+    }
+}
+
+/// Text encoding detected from a file's leading bytes (BOM) or inferred
+/// when there's no BOM and the bytes aren't valid UTF-8. Stored so
+/// `save_file` can re-encode losslessly instead of always writing UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl TextEncoding {
+    fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&[0xFF, 0xFE]) {
+            TextEncoding::Utf16Le
+        } else if bytes.starts_with(&[0xFE, 0xFF]) {
+            TextEncoding::Utf16Be
+        } else if std::str::from_utf8(bytes).is_ok() {
+            TextEncoding::Utf8
+        } else {
+            // Not valid UTF-8 and no BOM: assume Latin-1 (ISO-8859-1), whose
+            // code points map 1:1 onto the first 256 Unicode scalars, so
+            // every byte decodes unambiguously.
+            TextEncoding::Latin1
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            TextEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            TextEncoding::Utf16Le => {
+                let units: Vec<u16> = bytes[2..]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            TextEncoding::Utf16Be => {
+                let units: Vec<u16> = bytes[2..]
+                    .chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            TextEncoding::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+        }
+    }
+
+    fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            TextEncoding::Utf8 => text.as_bytes().to_vec(),
+            TextEncoding::Utf16Le => {
+                let mut out = vec![0xFF, 0xFE];
+                for unit in text.encode_utf16() {
+                    out.extend_from_slice(&unit.to_le_bytes());
+                }
+                out
+            }
+            TextEncoding::Utf16Be => {
+                let mut out = vec![0xFE, 0xFF];
+                for unit in text.encode_utf16() {
+                    out.extend_from_slice(&unit.to_be_bytes());
+                }
+                out
+            }
+            TextEncoding::Latin1 => text.chars().map(|c| c as u8).collect(),
+        }
+    }
+}
+
 /// 찾기/바꾸기 모드
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FindReplaceMode {
@@ -99,19 +225,348 @@ pub enum FindReplaceMode {
     Replace,
 }
 
+/// Vim-style modal editing mode. Only consulted when `vim_mode` is on;
+/// non-Vim users are pinned to `Insert` forever and `handle_input` behaves
+/// exactly as it did before this existed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditMode {
+    Normal,
+    Insert,
+    Visual,
+    VisualLine,
+}
+
+/// An operator awaiting its motion: pressing `d` sets
+/// `Operator::Delete`, and the motion that follows (`w`, `$`, a repeated
+/// `d` for linewise, ...) resolves it into a range fed to
+/// `delete_selection`/`get_selected_text`/`copy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
 /// 찾기/바꾸기 옵션
 #[derive(Debug, Clone, Default)]
 pub struct FindReplaceOptions {
     pub case_sensitive: bool,
     pub use_regex: bool,
     pub whole_word: bool,
+    pub fuzzy: bool,
+    /// When on, `find_term` is parsed as a `&`/`|`/`!` expression tree of
+    /// leaf patterns instead of a single pattern, so literal `&`/`|` still
+    /// work as plain text with this off.
+    pub composite: bool,
+}
+
+/// A parsed (but not yet compiled) composite find query: leaf search
+/// terms joined by `&`/`|`/`!`, matching `&` binding tighter than `|` and
+/// `!` binding to the single term that follows it.
+#[derive(Debug, Clone)]
+enum QueryExpr {
+    Leaf(String),
+    Not(Box<QueryExpr>),
+    And(Box<QueryExpr>, Box<QueryExpr>),
+    Or(Box<QueryExpr>, Box<QueryExpr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum QueryToken {
+    Leaf(String),
+    And,
+    Or,
+    Not,
+}
+
+fn tokenize_query(input: &str) -> Vec<QueryToken> {
+    let mut tokens = Vec::new();
+    let mut leaf = String::new();
+    let flush = |leaf: &mut String, tokens: &mut Vec<QueryToken>| {
+        let trimmed = leaf.trim();
+        if !trimmed.is_empty() {
+            tokens.push(QueryToken::Leaf(trimmed.to_string()));
+        }
+        leaf.clear();
+    };
+    for c in input.chars() {
+        match c {
+            '&' => {
+                flush(&mut leaf, &mut tokens);
+                tokens.push(QueryToken::And);
+            }
+            '|' => {
+                flush(&mut leaf, &mut tokens);
+                tokens.push(QueryToken::Or);
+            }
+            '!' => {
+                flush(&mut leaf, &mut tokens);
+                tokens.push(QueryToken::Not);
+            }
+            _ => leaf.push(c),
+        }
+    }
+    flush(&mut leaf, &mut tokens);
+    tokens
+}
+
+/// Recursive-descent parser over `&`/`|`/`!` tokens: `or` is lowest
+/// precedence, then `and`, then unary `not`, matching the binding order
+/// of the boolean operators of most languages that have them.
+struct QueryParser<'a> {
+    tokens: &'a [QueryToken],
+    pos: usize,
+}
+
+impl<'a> QueryParser<'a> {
+    fn parse_or(&mut self) -> Option<QueryExpr> {
+        let mut left = self.parse_and()?;
+        while self.tokens.get(self.pos) == Some(&QueryToken::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = QueryExpr::Or(Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    fn parse_and(&mut self) -> Option<QueryExpr> {
+        let mut left = self.parse_unary()?;
+        while self.tokens.get(self.pos) == Some(&QueryToken::And) {
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            left = QueryExpr::And(Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    fn parse_unary(&mut self) -> Option<QueryExpr> {
+        if self.tokens.get(self.pos) == Some(&QueryToken::Not) {
+            self.pos += 1;
+            return Some(QueryExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        match self.tokens.get(self.pos) {
+            Some(QueryToken::Leaf(term)) => {
+                self.pos += 1;
+                Some(QueryExpr::Leaf(term.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse a composite find query, or `None` if it's empty or malformed
+/// (e.g. a dangling operator) — callers fall back to treating `find_term`
+/// as a single literal/regex pattern in that case.
+fn parse_query(input: &str) -> Option<QueryExpr> {
+    let tokens = tokenize_query(input);
+    if tokens.is_empty() {
+        return None;
+    }
+    let mut parser = QueryParser { tokens: &tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    (parser.pos == tokens.len()).then_some(expr)
+}
+
+/// A composite query with every leaf pattern already compiled to a
+/// `Regex`, ready to evaluate per line.
+enum CompiledQuery {
+    Leaf(Regex),
+    Not(Box<CompiledQuery>),
+    And(Box<CompiledQuery>, Box<CompiledQuery>),
+    Or(Box<CompiledQuery>, Box<CompiledQuery>),
+}
+
+impl CompiledQuery {
+    /// Whether `line` satisfies this query, plus the match spans
+    /// contributed by every *positively* matched leaf (negated leaves
+    /// gate the line but never contribute a highlight).
+    fn eval(&self, line: &str) -> (bool, Vec<(usize, usize)>) {
+        match self {
+            CompiledQuery::Leaf(re) => {
+                let spans: Vec<(usize, usize)> =
+                    re.find_iter(line).map(|m| (m.start(), m.end())).collect();
+                let matched = !spans.is_empty();
+                (matched, spans)
+            }
+            CompiledQuery::Not(inner) => {
+                let (matched, _) = inner.eval(line);
+                (!matched, Vec::new())
+            }
+            CompiledQuery::And(lhs, rhs) => {
+                let (lm, mut spans) = lhs.eval(line);
+                let (rm, rhs_spans) = rhs.eval(line);
+                spans.extend(rhs_spans);
+                (lm && rm, spans)
+            }
+            CompiledQuery::Or(lhs, rhs) => {
+                let (lm, mut spans) = lhs.eval(line);
+                let (rm, rhs_spans) = rhs.eval(line);
+                spans.extend(rhs_spans);
+                (lm || rm, spans)
+            }
+        }
+    }
+}
+
+/// One cached line of tokenization: the tokens themselves, plus the
+/// lexer state the highlighter was left in afterwards. A downstream
+/// line's cache entry is only trustworthy as long as its predecessor's
+/// `end_state` still matches what it was computed against.
+#[derive(Debug, Clone)]
+struct CachedLine {
+    tokens: Vec<Token>,
+    end_state: LexerState,
+}
+
+/// How one line of the buffer compares to the on-disk baseline, for the
+/// gutter markers gitui's diff component uses (`+`/`~`/`-`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineTag {
+    Added,
+    Modified,
+    Removed,
+}
+
+/// Line-level LCS diff between `old` and `new`, returned as `(line_idx,
+/// tag)` pairs keyed by position in `new`. A deletion with no matching
+/// current line is attributed to the line it now sits in front of (or the
+/// last line, if the deletion was at the end) so it still has somewhere
+/// to draw a gutter marker.
+fn diff_lines(old: &[String], new: &[String]) -> Vec<(usize, DiffLineTag)> {
+    let n = old.len();
+    let m = new.len();
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut tags: Vec<Option<DiffLineTag>> = vec![None; m];
+    let mut removed_before = vec![0usize; m + 1];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            removed_before[j] += 1;
+            i += 1;
+        } else {
+            tags[j] = Some(DiffLineTag::Added);
+            j += 1;
+        }
+    }
+    removed_before[j] += n - i;
+    while j < m {
+        tags[j] = Some(DiffLineTag::Added);
+        j += 1;
+    }
+
+    // A deletion immediately followed by an addition at the same spot
+    // reads as one changed line rather than a delete/insert pair.
+    for idx in 0..m {
+        if removed_before[idx] > 0 && tags[idx] == Some(DiffLineTag::Added) {
+            tags[idx] = Some(DiffLineTag::Modified);
+            removed_before[idx] -= 1;
+        }
+    }
+
+    let mut result = Vec::new();
+    for (idx, tag) in tags.iter().enumerate() {
+        if tag.is_none() && removed_before[idx] > 0 {
+            result.push((idx, DiffLineTag::Removed));
+        }
+        if let Some(tag) = tag {
+            result.push((idx, *tag));
+        }
+    }
+    if removed_before[m] > 0 {
+        result.push((m.saturating_sub(1), DiffLineTag::Removed));
+    }
+    result.sort_by_key(|&(idx, _)| idx);
+    result
+}
+
+/// Word/whitespace/punctuation bucket for a char, used to stop undo
+/// coalescing at word boundaries: typing `hello world` should undo as two
+/// words, not one run of 11 keystrokes. `None` (no char, i.e. an empty
+/// accumulated run) never equals the class of an actual char, so an empty
+/// run never merges with a typed one.
+fn char_class(c: Option<char>) -> Option<u8> {
+    c.map(|c| {
+        if c.is_whitespace() {
+            0
+        } else if c.is_alphanumeric() || c == '_' {
+            1
+        } else {
+            2
+        }
+    })
+}
+
+/// Subsequence fuzzy match of `query` against `line`: every query char
+/// must appear in `line` in order, but not necessarily contiguously.
+/// Returns the matched span (from the first matched char through the
+/// last, inclusive of any gaps) and a score that rewards consecutive
+/// runs and word-boundary starts and penalizes skipped characters, so
+/// callers can rank lines the way an fzf-style picker would.
+fn fuzzy_score_line(line: &str, query: &str, case_sensitive: bool) -> Option<(usize, usize, i64)> {
+    if query.is_empty() {
+        return None;
+    }
+
+    let hay: Vec<char> = line.chars().collect();
+    let needle: Vec<char> = query.chars().collect();
+    let eq = |a: char, b: char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_ascii_lowercase() == b.to_ascii_lowercase()
+        }
+    };
+
+    let mut positions = Vec::with_capacity(needle.len());
+    let mut cursor = 0;
+    for &nc in &needle {
+        let pos = (cursor..hay.len()).find(|&i| eq(hay[i], nc))?;
+        positions.push(pos);
+        cursor = pos + 1;
+    }
+
+    let start = positions[0];
+    let end = positions[positions.len() - 1] + 1;
+
+    let mut score: i64 = 0;
+    for pair in positions.windows(2) {
+        let gap = pair[1] - pair[0] - 1;
+        if gap == 0 {
+            score += 5; // 연속 매치 보너스
+        } else {
+            score -= gap as i64; // 건너뛴 글자 수만큼 페널티
+        }
+    }
+
+    let is_word_boundary_start = start == 0
+        || hay[start - 1].is_whitespace()
+        || hay[start - 1] == '_'
+        || (hay[start - 1].is_lowercase() && hay[start].is_uppercase());
+    if is_word_boundary_start {
+        score += 10;
+    }
+
+    Some((start, end, score))
 }
 
 /// 편집기 상태
 #[derive(Debug)]
 pub struct EditorState {
     pub file_path: PathBuf,
-    pub lines: Vec<String>,
+    buffer: Rope,
     pub cursor_line: usize,
     pub cursor_col: usize,
     pub scroll: usize,
@@ -127,6 +582,15 @@ pub struct EditorState {
     pub selection: Option<Selection>,
     pub clipboard: String,
 
+    // 멀티 커서: 위 selection/cursor_*가 primary caret이고, 나머지는 여기에
+    pub secondary_carets: Vec<Caret>,
+
+    // 열(컬럼/블록) 선택: Alt+Shift+화살표로 드래그하는 동안만 쓰는 상태.
+    // `secondary_carets`에 한 줄당 하나씩 풀어내므로 드래그가 끝나면(편집,
+    // 일반 커서 이동, Esc) 그냥 일반 멀티 커서와 동일하게 취급된다.
+    block_selection: bool,
+    block_anchor: Option<(usize, usize)>,
+
     // 찾기/바꾸기
     pub find_mode: FindReplaceMode,
     pub find_input: String,
@@ -141,6 +605,11 @@ pub struct EditorState {
     pub goto_mode: bool,
     pub goto_input: String,
 
+    // Save As
+    pub save_as_mode: bool,
+    pub save_as_input: String,
+    pub keep_backup: bool,
+
     // 문법 강조
     pub language: Language,
     pub highlighter: Option<SyntaxHighlighter>,
@@ -154,13 +623,42 @@ pub struct EditorState {
 
     // 괄호 매칭
     pub matching_bracket: Option<(usize, usize)>,
+
+    // Vim 모드
+    pub vim_mode: bool,
+    pub mode: EditMode,
+    pub pending_operator: Option<Operator>,
+    pub count: usize,
+
+    // 줄바꿈 문자 / 인코딩
+    pub line_ending: LineEnding,
+    pub final_newline: bool,
+    pub encoding: TextEncoding,
+
+    // Undo coalescing
+    pub undo_coalesce_ms: u64,
+    last_edit_kind: Option<UndoKind>,
+    last_edit_at: Option<Instant>,
+
+    // 디스크 대비 diff 표시
+    pub diff_mode: bool,
+    disk_baseline: Vec<String>,
+
+    // 라인별 토큰 캐시: 스크롤할 때마다 처음부터 다시 강조하지 않도록.
+    // 이 프로젝트는 tree-sitter 같은 전체 파싱 트리 대신 `SyntaxHighlighter`의
+    // 줄 단위 렉서 상태(`LexerState`)를 이어 붙이는 방식을 쓴다 — 증분 재강조가
+    // 필요로 하는 것(편집 지점부터만 무효화, 상태가 안정될 때까지만 재토큰화,
+    // 확장자 기반 문법 감지, Plain으로의 폴백)은 동일하게 얻으면서 별도 파서
+    // 바인딩이나 문법 크레이트 없이 기존 아키텍처 위에서 해결한다.
+    token_cache: Vec<Option<CachedLine>>,
+    dirty_from: Option<usize>,
 }
 
 impl EditorState {
     pub fn new() -> Self {
         Self {
             file_path: PathBuf::new(),
-            lines: vec![String::new()],
+            buffer: Rope::from_str(""),
             cursor_line: 0,
             cursor_col: 0,
             scroll: 0,
@@ -171,6 +669,9 @@ impl EditorState {
             max_undo_size: 1000,
             selection: None,
             clipboard: String::new(),
+            secondary_carets: Vec::new(),
+            block_selection: false,
+            block_anchor: None,
             find_mode: FindReplaceMode::None,
             find_input: String::new(),
             replace_input: String::new(),
@@ -181,6 +682,9 @@ impl EditorState {
             input_focus: 0,
             goto_mode: false,
             goto_input: String::new(),
+            save_as_mode: false,
+            save_as_input: String::new(),
+            keep_backup: false,
             language: Language::Plain,
             highlighter: None,
             auto_indent: true,
@@ -188,9 +692,53 @@ impl EditorState {
             use_tabs: false,
             show_whitespace: false,
             matching_bracket: None,
+            vim_mode: false,
+            mode: EditMode::Insert,
+            pending_operator: None,
+            count: 0,
+            line_ending: LineEnding::Lf,
+            final_newline: true,
+            encoding: TextEncoding::Utf8,
+            undo_coalesce_ms: 300,
+            last_edit_kind: None,
+            last_edit_at: None,
+            diff_mode: false,
+            disk_baseline: Vec::new(),
+            token_cache: Vec::new(),
+            dirty_from: Some(0),
         }
     }
 
+    /// Every line of the document, materialized fresh from the rope.
+    pub fn lines(&self) -> Vec<String> {
+        self.buffer.lines()
+    }
+
+    /// Replace the whole document with `lines`, joined by `\n`. Used by
+    /// callers (like bulk rename) that populate the editor without going
+    /// through `load_file`.
+    pub fn set_lines(&mut self, lines: Vec<String>) {
+        self.buffer = Rope::from_str(&lines.join("\n"));
+        self.token_cache.clear();
+        self.dirty_from = Some(0);
+    }
+
+    fn line_count(&self) -> usize {
+        self.buffer.len_lines()
+    }
+
+    fn line(&self, idx: usize) -> String {
+        self.buffer.line(idx)
+    }
+
+    fn line_len(&self, idx: usize) -> usize {
+        self.buffer.line_len_chars(idx)
+    }
+
+    fn char_idx(&self, line: usize, col: usize) -> usize {
+        self.buffer.line_col_to_char(line, col)
+    }
+
     /// 파일 로드
     pub fn load_file(&mut self, path: &PathBuf) -> Result<(), String> {
         self.file_path = path.clone();
@@ -204,50 +752,276 @@ impl EditorState {
         self.selection = None;
         self.find_mode = FindReplaceMode::None;
 
-        // 파일 읽기
-        match fs::read_to_string(path) {
-            Ok(content) => {
-                self.lines = content.lines().map(String::from).collect();
-                if self.lines.is_empty() {
-                    self.lines.push(String::new());
+        // 파일 읽기: 줄바꿈 스타일/최종 개행/인코딩을 원본 바이트에서 감지한다
+        match fs::read(path) {
+            Ok(bytes) => {
+                self.encoding = TextEncoding::detect(&bytes);
+                self.line_ending = LineEnding::detect(&bytes);
+                self.final_newline = bytes.last().is_some_and(|&b| b == b'\n' || b == b'\r');
+
+                let content = self.encoding.decode(&bytes);
+                let mut parts: Vec<&str> = content.split(self.line_ending.as_str()).collect();
+                if self.final_newline && parts.last().is_some_and(|s| s.is_empty()) {
+                    parts.pop();
                 }
+                self.buffer = Rope::from_str(&parts.join("\n"));
             }
             Err(_) => {
                 // 새 파일
-                self.lines = vec![String::new()];
+                self.encoding = TextEncoding::Utf8;
+                self.line_ending = LineEnding::Lf;
+                self.final_newline = true;
+                self.buffer = Rope::from_str("");
             }
         }
 
-        // 언어 감지
-        self.language = Language::from_extension(path);
+        // 언어 감지 (languages.toml의 커스텀 언어 포함)
+        self.language = Language::resolve_with_custom(
+            path,
+            &crate::services::custom_languages::CustomLanguages::load(),
+        );
         self.highlighter = Some(SyntaxHighlighter::new(self.language));
+        self.token_cache.clear();
+        self.dirty_from = Some(0);
+
+        self.disk_baseline = self.lines();
 
         Ok(())
     }
 
-    /// 파일 저장
+    /// 파일 저장: 감지된 줄바꿈 스타일/최종 개행/인코딩으로 되돌려 쓴다
     pub fn save_file(&mut self) -> Result<(), String> {
-        let content = self.lines.join("\n");
-        fs::write(&self.file_path, content).map_err(|e| e.to_string())?;
+        let path = self.file_path.clone();
+        self.write_to_disk(&path)
+    }
+
+    /// 다른 이름으로 저장: 쓰기에 성공하면 이후의 저장은 새 경로를 대상으로 한다
+    pub fn save_file_as(&mut self, path: PathBuf) -> Result<(), String> {
+        self.write_to_disk(&path)?;
+        self.file_path = path;
+        self.language = Language::resolve_with_custom(
+            &self.file_path,
+            &crate::services::custom_languages::CustomLanguages::load(),
+        );
+        self.highlighter = Some(SyntaxHighlighter::new(self.language));
+        self.token_cache.clear();
+        self.dirty_from = Some(0);
+        Ok(())
+    }
+
+    /// `path`에 원자적으로 기록한다: 같은 디렉터리의 임시 파일에 쓰고 fsync한
+    /// 뒤 그 임시 파일을 목표 경로 위로 rename한다. rename은 같은 파일시스템
+    /// 안에서 원자적이므로, 쓰는 도중에 크래시가 나도 `path`는 이전 내용 그대
+    /// 로이거나 새 내용 그대로인 둘 중 하나만 관찰되고 절반만 쓰인 상태가 되지
+    /// 않는다. `keep_backup`이 켜져 있으면 덮어쓰기 전에 기존 내용을 `~` 접미사
+    /// 파일로 남긴다.
+    fn write_to_disk(&mut self, path: &PathBuf) -> Result<(), String> {
+        let mut content = self.buffer.as_str().to_string();
+        if self.line_ending != LineEnding::Lf {
+            content = content.replace('\n', self.line_ending.as_str());
+        }
+        if self.final_newline {
+            content.push_str(self.line_ending.as_str());
+        }
+        let bytes = self.encoding.encode(&content);
+
+        let mut tmp_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "untitled".to_string());
+        tmp_name.push_str(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+
+        {
+            let mut tmp_file = fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+            tmp_file.write_all(&bytes).map_err(|e| e.to_string())?;
+            tmp_file.sync_all().map_err(|e| e.to_string())?;
+        }
+
+        if self.keep_backup && path.exists() {
+            let mut backup_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "untitled".to_string());
+            backup_name.push('~');
+            let backup_path = path.with_file_name(backup_name);
+            let _ = fs::copy(path, backup_path);
+        }
+
+        if let Err(e) = fs::rename(&tmp_path, path) {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(e.to_string());
+        }
+
         self.modified = false;
+        self.last_edit_kind = None;
+        self.disk_baseline = self.lines();
         Ok(())
     }
 
+    /// Per-line diff of the buffer against `disk_baseline`, keyed by the
+    /// line's index in the *current* buffer. Recomputed from scratch on
+    /// every call rather than maintained incrementally — like `Rope`,
+    /// that's the right trade until profiling says otherwise: files stay
+    /// small enough that an O(n*m) LCS every render is unnoticeable.
+    pub fn diff_against_disk(&self) -> Vec<(usize, DiffLineTag)> {
+        diff_lines(&self.disk_baseline, &self.lines())
+    }
+
+    /// Move the cursor to the first changed line after the current one
+    /// (wrapping to the first hunk in the document if none remain below).
+    pub fn goto_next_diff_hunk(&mut self) {
+        let hunks = self.diff_against_disk();
+        if hunks.is_empty() {
+            return;
+        }
+        let target = hunks
+            .iter()
+            .find(|&&(line, _)| line > self.cursor_line)
+            .or_else(|| hunks.first());
+        if let Some(&(line, _)) = target {
+            self.cursor_line = line;
+            self.cursor_col = 0;
+            self.selection = None;
+            self.update_scroll();
+        }
+    }
+
+    /// Move the cursor to the first changed line before the current one
+    /// (wrapping to the last hunk in the document if none remain above).
+    pub fn goto_prev_diff_hunk(&mut self) {
+        let hunks = self.diff_against_disk();
+        if hunks.is_empty() {
+            return;
+        }
+        let target = hunks
+            .iter()
+            .rev()
+            .find(|&&(line, _)| line < self.cursor_line)
+            .or_else(|| hunks.last());
+        if let Some(&(line, _)) = target {
+            self.cursor_line = line;
+            self.cursor_col = 0;
+            self.selection = None;
+            self.update_scroll();
+        }
+    }
+
     /// Undo 액션 추가
     pub fn push_undo(&mut self, action: EditAction) {
+        self.invalidate_tokens_from(self.action_start_line(&action));
         self.redo_stack.clear();
         self.undo_stack.push_back(action);
         while self.undo_stack.len() > self.max_undo_size {
             self.undo_stack.pop_front();
         }
         self.modified = true;
+        // Every non-coalesced push is itself an undo boundary: whatever
+        // keystroke streak was coalescing before it can't extend across it.
+        self.last_edit_kind = None;
+    }
+
+    /// The earliest line touched by `action` (the minimum across a
+    /// `Batch`), used to scope token-cache invalidation to what actually
+    /// changed instead of the whole document.
+    fn action_start_line(&self, action: &EditAction) -> usize {
+        match action {
+            EditAction::Insert { char_idx, .. } | EditAction::Delete { char_idx, .. } => {
+                self.buffer.char_to_line(*char_idx)
+            }
+            EditAction::Batch { actions } => actions
+                .iter()
+                .map(|a| self.action_start_line(a))
+                .min()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Mark `line_idx` onward as needing re-tokenization. If the line
+    /// count itself changed since the cache was built, every cached
+    /// index downstream of an edit could now refer to the wrong line, so
+    /// this drops the whole cache rather than risk stale highlighting;
+    /// that only happens on edits that add/remove lines, not on ordinary
+    /// typing.
+    fn invalidate_tokens_from(&mut self, line_idx: usize) {
+        if self.token_cache.len() != self.line_count() {
+            self.token_cache = vec![None; self.line_count()];
+            self.dirty_from = Some(0);
+        } else {
+            self.dirty_from = Some(self.dirty_from.map_or(line_idx, |d| d.min(line_idx)));
+        }
+    }
+
+    /// Push a single-keystroke `Insert`/`Delete`, merging it into the top
+    /// of the undo stack when it's the same kind as, spatially contiguous
+    /// with, and within `undo_coalesce_ms` of the previous push — modeled
+    /// on Helix's `UndoKind` coalescing, so one Ctrl-Z reverts a whole
+    /// typing burst instead of a single glyph.
+    fn push_undo_coalesced(&mut self, kind: UndoKind, char_idx: usize, text: String) {
+        self.invalidate_tokens_from(self.buffer.char_to_line(char_idx));
+        let now = Instant::now();
+        let within_interval = self.last_edit_at.is_some_and(|t| {
+            now.duration_since(t).as_millis() <= self.undo_coalesce_ms as u128
+        });
+
+        if self.last_edit_kind == Some(kind)
+            && within_interval
+            && self.try_merge_undo_top(kind, char_idx, &text).is_some()
+        {
+            self.redo_stack.clear();
+            self.modified = true;
+        } else {
+            self.push_undo(match kind {
+                UndoKind::Insert => EditAction::Insert { char_idx, text },
+                UndoKind::Delete => EditAction::Delete { char_idx, text },
+            });
+        }
+        self.last_edit_kind = Some(kind);
+        self.last_edit_at = Some(now);
+    }
+
+    /// Try to extend the top-of-stack action in place with a new
+    /// contiguous `Insert`/`Delete`. Returns `Some(())` on success (the
+    /// stack was mutated); `None` if the new edit isn't contiguous with
+    /// the top entry, leaving the stack untouched.
+    fn try_merge_undo_top(&mut self, kind: UndoKind, char_idx: usize, text: &str) -> Option<()> {
+        let top = self.undo_stack.back_mut()?;
+        match (kind, top) {
+            (UndoKind::Insert, EditAction::Insert { char_idx: prev_idx, text: prev_text })
+                if *prev_idx + prev_text.chars().count() == char_idx
+                    && char_class(prev_text.chars().last()) == char_class(text.chars().next()) =>
+            {
+                prev_text.push_str(text);
+                Some(())
+            }
+            (UndoKind::Delete, EditAction::Delete { char_idx: prev_idx, text: prev_text })
+                if char_idx + text.chars().count() == *prev_idx =>
+            {
+                // Backspace grows backward: the new deleted text sits in
+                // front of what's already recorded, and the anchor moves
+                // back to meet it.
+                let mut merged = text.to_string();
+                merged.push_str(prev_text);
+                *prev_text = merged;
+                *prev_idx = char_idx;
+                Some(())
+            }
+            (UndoKind::Delete, EditAction::Delete { char_idx: prev_idx, text: prev_text })
+                if *prev_idx == char_idx =>
+            {
+                // Forward-delete grows forward at a fixed anchor.
+                prev_text.push_str(text);
+                Some(())
+            }
+            _ => None,
+        }
     }
 
     /// Undo 실행
     pub fn undo(&mut self) {
         if let Some(action) = self.undo_stack.pop_back() {
             let reverse = self.reverse_action(&action);
-            self.apply_action(&reverse, false);
+            self.apply_action(&reverse);
             self.redo_stack.push_back(action);
         }
     }
@@ -255,7 +1029,7 @@ impl EditorState {
     /// Redo 실행
     pub fn redo(&mut self) {
         if let Some(action) = self.redo_stack.pop_back() {
-            self.apply_action(&action, false);
+            self.apply_action(&action);
             self.undo_stack.push_back(action);
         }
     }
@@ -263,41 +1037,14 @@ impl EditorState {
     /// 액션 역순 생성
     fn reverse_action(&self, action: &EditAction) -> EditAction {
         match action {
-            EditAction::Insert { line, col, text } => EditAction::Delete {
-                line: *line,
-                col: *col,
+            EditAction::Insert { char_idx, text } => EditAction::Delete {
+                char_idx: *char_idx,
                 text: text.clone(),
             },
-            EditAction::Delete { line, col, text } => EditAction::Insert {
-                line: *line,
-                col: *col,
+            EditAction::Delete { char_idx, text } => EditAction::Insert {
+                char_idx: *char_idx,
                 text: text.clone(),
             },
-            EditAction::InsertLine { line, content } => EditAction::DeleteLine {
-                line: *line,
-                content: content.clone(),
-            },
-            EditAction::DeleteLine { line, content } => EditAction::InsertLine {
-                line: *line,
-                content: content.clone(),
-            },
-            EditAction::MergeLine { line, col } => EditAction::SplitLine {
-                line: *line,
-                col: *col,
-            },
-            EditAction::SplitLine { line, col } => EditAction::MergeLine {
-                line: *line,
-                col: *col,
-            },
-            EditAction::Replace {
-                line,
-                old_content,
-                new_content,
-            } => EditAction::Replace {
-                line: *line,
-                old_content: new_content.clone(),
-                new_content: old_content.clone(),
-            },
             EditAction::Batch { actions } => EditAction::Batch {
                 actions: actions.iter().rev().map(|a| self.reverse_action(a)).collect(),
             },
@@ -305,92 +1052,106 @@ impl EditorState {
     }
 
     /// 액션 적용
-    fn apply_action(&mut self, action: &EditAction, _record: bool) {
+    fn apply_action(&mut self, action: &EditAction) {
+        self.invalidate_tokens_from(self.action_start_line(action));
         match action {
-            EditAction::Insert { line, col, text } => {
-                if *line < self.lines.len() {
-                    let line_content = &mut self.lines[*line];
-                    let mut chars: Vec<char> = line_content.chars().collect();
-                    for (i, c) in text.chars().enumerate() {
-                        if *col + i <= chars.len() {
-                            chars.insert(*col + i, c);
-                        }
-                    }
-                    *line_content = chars.into_iter().collect();
-                }
-            }
-            EditAction::Delete { line, col, text } => {
-                if *line < self.lines.len() {
-                    let line_content = &mut self.lines[*line];
-                    let mut chars: Vec<char> = line_content.chars().collect();
-                    for _ in 0..text.chars().count() {
-                        if *col < chars.len() {
-                            chars.remove(*col);
-                        }
-                    }
-                    *line_content = chars.into_iter().collect();
-                }
-            }
-            EditAction::InsertLine { line, content } => {
-                if *line <= self.lines.len() {
-                    self.lines.insert(*line, content.clone());
-                }
-            }
-            EditAction::DeleteLine { line, .. } => {
-                if *line < self.lines.len() && self.lines.len() > 1 {
-                    self.lines.remove(*line);
-                }
-            }
-            EditAction::MergeLine { line, .. } => {
-                if *line + 1 < self.lines.len() {
-                    let next_line = self.lines.remove(*line + 1);
-                    self.lines[*line].push_str(&next_line);
-                }
-            }
-            EditAction::SplitLine { line, col } => {
-                if *line < self.lines.len() {
-                    let content = &self.lines[*line];
-                    let chars: Vec<char> = content.chars().collect();
-                    let before: String = chars[..*col.min(&chars.len())].iter().collect();
-                    let after: String = chars[*col.min(&chars.len())..].iter().collect();
-                    self.lines[*line] = before;
-                    self.lines.insert(*line + 1, after);
-                }
+            EditAction::Insert { char_idx, text } => {
+                self.buffer.insert(*char_idx, text);
             }
-            EditAction::Replace {
-                line,
-                new_content,
-                ..
-            } => {
-                if *line < self.lines.len() {
-                    self.lines[*line] = new_content.clone();
-                }
+            EditAction::Delete { char_idx, text } => {
+                let len = text.chars().count();
+                self.buffer.remove(*char_idx..*char_idx + len);
             }
             EditAction::Batch { actions } => {
                 for a in actions {
-                    self.apply_action(a, false);
+                    self.apply_action(a);
                 }
             }
         }
     }
 
+    /// Tokens for lines `start..start+count`, recomputing only whatever
+    /// dirty lines stand between the nearest clean line and a stabilized
+    /// lexer state — not the whole prefix up to `start` the way a naive
+    /// per-frame re-tokenize would. See `CachedLine` for what "stabilized"
+    /// means.
+    pub fn visible_tokens(&mut self, start: usize, count: usize) -> Vec<Vec<Token>> {
+        let line_count = self.line_count();
+        if self.token_cache.len() != line_count {
+            self.token_cache = vec![None; line_count];
+            self.dirty_from = Some(0);
+        }
+        let end = (start + count).min(line_count);
+
+        if let Some(dirty) = self.dirty_from {
+            let dirty = dirty.min(line_count.saturating_sub(1));
+            match self.highlighter.clone() {
+                Some(mut highlighter) => {
+                    let mut entering: LexerState = if dirty == 0 {
+                        LexerState::default()
+                    } else {
+                        self.token_cache[dirty - 1]
+                            .as_ref()
+                            .map(|c| c.end_state.clone())
+                            .unwrap_or_default()
+                    };
+
+                    let mut line_idx = dirty;
+                    while line_idx < line_count {
+                        let old_end_state = self.token_cache[line_idx].as_ref().map(|c| c.end_state.clone());
+
+                        highlighter.set_lexer_state(entering.clone());
+                        let line_text = self.line(line_idx);
+                        let tokens = highlighter.tokenize_line(&line_text);
+                        let new_end_state = highlighter.lexer_state();
+                        self.token_cache[line_idx] = Some(CachedLine {
+                            tokens,
+                            end_state: new_end_state.clone(),
+                        });
+
+                        let reached_needed_range = line_idx + 1 >= end;
+                        let stabilized = old_end_state == Some(new_end_state.clone());
+                        entering = new_end_state;
+                        line_idx += 1;
+
+                        if reached_needed_range && stabilized {
+                            break;
+                        }
+                    }
+
+                    self.dirty_from = if line_idx >= line_count { None } else { Some(line_idx) };
+                }
+                None => self.dirty_from = None,
+            }
+        }
+
+        (start..end)
+            .map(|i| {
+                self.token_cache[i]
+                    .as_ref()
+                    .map(|c| c.tokens.clone())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
     /// 문자 삽입
     pub fn insert_char(&mut self, c: char) {
-        self.delete_selection();
+        self.block_selection = false;
+        self.block_anchor = None;
+        if !self.secondary_carets.is_empty() {
+            self.insert_char_multi(c);
+            return;
+        }
 
-        let action = EditAction::Insert {
-            line: self.cursor_line,
-            col: self.cursor_col,
-            text: c.to_string(),
-        };
+        self.delete_selection();
 
-        let line = &mut self.lines[self.cursor_line];
-        let mut chars: Vec<char> = line.chars().collect();
-        chars.insert(self.cursor_col, c);
-        *line = chars.into_iter().collect();
+        let char_idx = self.char_idx(self.cursor_line, self.cursor_col);
+        let text = c.to_string();
+        self.buffer.insert(char_idx, &text);
         self.cursor_col += 1;
 
-        self.push_undo(action);
+        self.push_undo_coalesced(UndoKind::Insert, char_idx, text);
         self.update_scroll();
     }
 
@@ -417,203 +1178,498 @@ impl EditorState {
 
     /// 새 줄 삽입
     pub fn insert_newline(&mut self) {
+        self.block_selection = false;
+        self.block_anchor = None;
+        if !self.secondary_carets.is_empty() {
+            self.insert_newline_multi();
+            return;
+        }
+
         self.delete_selection();
 
-        let line = &self.lines[self.cursor_line];
+        let line = self.line(self.cursor_line);
         let chars: Vec<char> = line.chars().collect();
         let before: String = chars[..self.cursor_col.min(chars.len())].iter().collect();
-        let after: String = chars[self.cursor_col.min(chars.len())..].iter().collect();
 
         // 자동 들여쓰기
         let indent = if self.auto_indent {
-            let leading_ws: String = before.chars().take_while(|c| c.is_whitespace()).collect();
-            leading_ws
+            before.chars().take_while(|c| c.is_whitespace()).collect::<String>()
         } else {
             String::new()
         };
 
-        let action = EditAction::SplitLine {
-            line: self.cursor_line,
-            col: self.cursor_col,
-        };
+        let char_idx = self.char_idx(self.cursor_line, self.cursor_col);
+        let text = format!("\n{}", indent);
+        self.buffer.insert(char_idx, &text);
 
-        self.lines[self.cursor_line] = before;
-        self.lines.insert(self.cursor_line + 1, format!("{}{}", indent, after));
         self.cursor_line += 1;
         self.cursor_col = indent.len();
 
-        self.push_undo(action);
+        self.push_undo(EditAction::Insert { char_idx, text });
         self.update_scroll();
     }
 
     /// 뒤로 삭제 (Backspace)
     pub fn delete_backward(&mut self) {
+        self.block_selection = false;
+        self.block_anchor = None;
+        if !self.secondary_carets.is_empty() {
+            self.delete_backward_multi();
+            return;
+        }
+
         if self.selection.is_some() {
             self.delete_selection();
             return;
         }
 
         if self.cursor_col > 0 {
-            let line = &mut self.lines[self.cursor_line];
-            let mut chars: Vec<char> = line.chars().collect();
-            let deleted = chars.remove(self.cursor_col - 1);
-            *line = chars.into_iter().collect();
-
-            let action = EditAction::Delete {
-                line: self.cursor_line,
-                col: self.cursor_col - 1,
-                text: deleted.to_string(),
-            };
-
+            let char_idx = self.char_idx(self.cursor_line, self.cursor_col) - 1;
+            let deleted = self.buffer.slice(char_idx..char_idx + 1);
+            self.buffer.remove(char_idx..char_idx + 1);
             self.cursor_col -= 1;
-            self.push_undo(action);
+
+            self.push_undo_coalesced(UndoKind::Delete, char_idx, deleted);
         } else if self.cursor_line > 0 {
-            // 이전 줄과 병합
-            let current_line = self.lines.remove(self.cursor_line);
+            // 이전 줄과 병합 (줄 사이 개행 제거)
+            let char_idx = self.char_idx(self.cursor_line, 0) - 1;
+            self.buffer.remove(char_idx..char_idx + 1);
+
             self.cursor_line -= 1;
-            self.cursor_col = self.lines[self.cursor_line].chars().count();
-            self.lines[self.cursor_line].push_str(&current_line);
+            self.cursor_col = self.line_len(self.cursor_line);
+
+            self.push_undo(EditAction::Delete {
+                char_idx,
+                text: "\n".to_string(),
+            });
+        }
+        self.update_scroll();
+    }
+
+    /// 앞으로 삭제 (Delete)
+    pub fn delete_forward(&mut self) {
+        self.block_selection = false;
+        self.block_anchor = None;
+        if !self.secondary_carets.is_empty() {
+            self.delete_forward_multi();
+            return;
+        }
+
+        if self.selection.is_some() {
+            self.delete_selection();
+            return;
+        }
+
+        let line_len = self.line_len(self.cursor_line);
+        if self.cursor_col < line_len {
+            let char_idx = self.char_idx(self.cursor_line, self.cursor_col);
+            let deleted = self.buffer.slice(char_idx..char_idx + 1);
+            self.buffer.remove(char_idx..char_idx + 1);
+
+            self.push_undo_coalesced(UndoKind::Delete, char_idx, deleted);
+        } else if self.cursor_line + 1 < self.line_count() {
+            // 다음 줄과 병합 (줄 사이 개행 제거)
+            let char_idx = self.char_idx(self.cursor_line, self.cursor_col);
+            self.buffer.remove(char_idx..char_idx + 1);
+
+            self.push_undo(EditAction::Delete {
+                char_idx,
+                text: "\n".to_string(),
+            });
+        }
+    }
+
+    /// 선택 영역 삭제
+    pub fn delete_selection(&mut self) {
+        self.block_selection = false;
+        self.block_anchor = None;
+        if !self.secondary_carets.is_empty() {
+            self.delete_selection_multi();
+            return;
+        }
+
+        let sel = match self.selection.take() {
+            Some(s) if !s.is_empty() => s,
+            _ => return,
+        };
+
+        let (start_line, start_col, end_line, end_col) = sel.normalized();
+        let start_char = self.char_idx(start_line, start_col);
+        let end_char = self.char_idx(end_line, end_col);
+
+        let deleted = self.buffer.slice(start_char..end_char);
+        self.buffer.remove(start_char..end_char);
+
+        self.push_undo(EditAction::Delete {
+            char_idx: start_char,
+            text: deleted,
+        });
+
+        self.cursor_line = start_line;
+        self.cursor_col = start_col;
+        self.update_scroll();
+    }
+
+    /// Every active caret (the primary one plus `secondary_carets`),
+    /// ordered by descending document position. Editing from back to
+    /// front means an earlier edit never invalidates a later caret's
+    /// `(line, col)`.
+    fn all_carets_desc(&self) -> Vec<Caret> {
+        let mut carets: Vec<Caret> = std::iter::once(Caret {
+            line: self.cursor_line,
+            col: self.cursor_col,
+            selection: self.selection,
+        })
+        .chain(self.secondary_carets.iter().copied())
+        .collect();
+        carets.sort_by_key(|c| std::cmp::Reverse(self.char_idx(c.line, c.col)));
+        carets
+    }
+
+    /// Write a post-edit caret list back onto the state, merging any that
+    /// collided into the same position and promoting the lowest-offset
+    /// one to primary.
+    fn set_carets(&mut self, mut carets: Vec<Caret>) {
+        carets.sort_by_key(|c| (c.line, c.col));
+        carets.dedup_by_key(|c| (c.line, c.col));
+        let primary = carets.remove(0);
+        self.cursor_line = primary.line;
+        self.cursor_col = primary.col;
+        self.selection = primary.selection;
+        self.secondary_carets = carets;
+    }
+
+    /// `insert_char` applied to every caret, descending, as one `Batch`.
+    fn insert_char_multi(&mut self, c: char) {
+        let text = c.to_string();
+        let mut carets = self.all_carets_desc();
+        let mut actions = Vec::with_capacity(carets.len());
+
+        for caret in &mut carets {
+            if let Some(sel) = caret.selection.take() {
+                if !sel.is_empty() {
+                    let (sl, sc, el, ec) = sel.normalized();
+                    let start = self.char_idx(sl, sc);
+                    let end = self.char_idx(el, ec);
+                    let deleted = self.buffer.slice(start..end);
+                    self.buffer.remove(start..end);
+                    actions.push(EditAction::Delete { char_idx: start, text: deleted });
+                    caret.line = sl;
+                    caret.col = sc;
+                }
+            }
+            let char_idx = self.char_idx(caret.line, caret.col);
+            self.buffer.insert(char_idx, &text);
+            actions.push(EditAction::Insert { char_idx, text: text.clone() });
+            caret.col += 1;
+        }
+
+        self.push_undo(EditAction::Batch { actions });
+        self.set_carets(carets);
+        self.update_scroll();
+    }
+
+    /// `insert_newline` applied to every caret, descending, as one `Batch`.
+    fn insert_newline_multi(&mut self) {
+        let mut carets = self.all_carets_desc();
+        let mut actions = Vec::with_capacity(carets.len());
+
+        for caret in &mut carets {
+            if let Some(sel) = caret.selection.take() {
+                if !sel.is_empty() {
+                    let (sl, sc, el, ec) = sel.normalized();
+                    let start = self.char_idx(sl, sc);
+                    let end = self.char_idx(el, ec);
+                    let deleted = self.buffer.slice(start..end);
+                    self.buffer.remove(start..end);
+                    actions.push(EditAction::Delete { char_idx: start, text: deleted });
+                    caret.line = sl;
+                    caret.col = sc;
+                }
+            }
+
+            let line = self.line(caret.line);
+            let chars: Vec<char> = line.chars().collect();
+            let before: String = chars[..caret.col.min(chars.len())].iter().collect();
+            let indent = if self.auto_indent {
+                before.chars().take_while(|c| c.is_whitespace()).collect::<String>()
+            } else {
+                String::new()
+            };
+
+            let char_idx = self.char_idx(caret.line, caret.col);
+            let text = format!("\n{}", indent);
+            self.buffer.insert(char_idx, &text);
+            actions.push(EditAction::Insert { char_idx, text });
+
+            caret.line += 1;
+            caret.col = indent.len();
+        }
+
+        self.push_undo(EditAction::Batch { actions });
+        self.set_carets(carets);
+        self.update_scroll();
+    }
+
+    /// `delete_backward` applied to every caret, descending, as one `Batch`.
+    fn delete_backward_multi(&mut self) {
+        let mut carets = self.all_carets_desc();
+        let mut actions = Vec::with_capacity(carets.len());
+
+        for caret in &mut carets {
+            if let Some(sel) = caret.selection.take() {
+                if !sel.is_empty() {
+                    let (sl, sc, el, ec) = sel.normalized();
+                    let start = self.char_idx(sl, sc);
+                    let end = self.char_idx(el, ec);
+                    let deleted = self.buffer.slice(start..end);
+                    self.buffer.remove(start..end);
+                    actions.push(EditAction::Delete { char_idx: start, text: deleted });
+                    caret.line = sl;
+                    caret.col = sc;
+                    continue;
+                }
+            }
+
+            if caret.col > 0 {
+                let char_idx = self.char_idx(caret.line, caret.col) - 1;
+                let deleted = self.buffer.slice(char_idx..char_idx + 1);
+                self.buffer.remove(char_idx..char_idx + 1);
+                actions.push(EditAction::Delete { char_idx, text: deleted });
+                caret.col -= 1;
+            } else if caret.line > 0 {
+                let char_idx = self.char_idx(caret.line, 0) - 1;
+                self.buffer.remove(char_idx..char_idx + 1);
+                actions.push(EditAction::Delete { char_idx, text: "\n".to_string() });
+                caret.line -= 1;
+                caret.col = self.line_len(caret.line);
+            }
+        }
+
+        if !actions.is_empty() {
+            self.push_undo(EditAction::Batch { actions });
+        }
+        self.set_carets(carets);
+        self.update_scroll();
+    }
+
+    /// `delete_forward` applied at every caret (descending, so an earlier
+    /// caret's removal never shifts the char indices a later caret still
+    /// needs), used when secondary carets are active.
+    fn delete_forward_multi(&mut self) {
+        let mut carets = self.all_carets_desc();
+        let mut actions = Vec::with_capacity(carets.len());
+
+        for caret in &mut carets {
+            if let Some(sel) = caret.selection.take() {
+                if !sel.is_empty() {
+                    let (sl, sc, el, ec) = sel.normalized();
+                    let start = self.char_idx(sl, sc);
+                    let end = self.char_idx(el, ec);
+                    let deleted = self.buffer.slice(start..end);
+                    self.buffer.remove(start..end);
+                    actions.push(EditAction::Delete { char_idx: start, text: deleted });
+                    caret.line = sl;
+                    caret.col = sc;
+                    continue;
+                }
+            }
 
-            let action = EditAction::MergeLine {
-                line: self.cursor_line,
-                col: self.cursor_col,
-            };
+            let line_len = self.line_len(caret.line);
+            if caret.col < line_len {
+                let char_idx = self.char_idx(caret.line, caret.col);
+                let deleted = self.buffer.slice(char_idx..char_idx + 1);
+                self.buffer.remove(char_idx..char_idx + 1);
+                actions.push(EditAction::Delete { char_idx, text: deleted });
+            } else if caret.line + 1 < self.line_count() {
+                let char_idx = self.char_idx(caret.line, caret.col);
+                self.buffer.remove(char_idx..char_idx + 1);
+                actions.push(EditAction::Delete { char_idx, text: "\n".to_string() });
+            }
+        }
 
-            self.push_undo(action);
+        if !actions.is_empty() {
+            self.push_undo(EditAction::Batch { actions });
         }
+        self.set_carets(carets);
         self.update_scroll();
     }
 
-    /// 앞으로 삭제 (Delete)
-    pub fn delete_forward(&mut self) {
-        if self.selection.is_some() {
-            self.delete_selection();
-            return;
+    /// `delete_selection` applied to every caret that has one, descending,
+    /// as one `Batch`. A no-op (like the single-caret version) if none do.
+    fn delete_selection_multi(&mut self) {
+        let mut carets = self.all_carets_desc();
+        let mut actions = Vec::new();
+        let mut any = false;
+
+        for caret in &mut carets {
+            if let Some(sel) = caret.selection.take() {
+                if !sel.is_empty() {
+                    any = true;
+                    let (sl, sc, el, ec) = sel.normalized();
+                    let start = self.char_idx(sl, sc);
+                    let end = self.char_idx(el, ec);
+                    let deleted = self.buffer.slice(start..end);
+                    self.buffer.remove(start..end);
+                    actions.push(EditAction::Delete { char_idx: start, text: deleted });
+                    caret.line = sl;
+                    caret.col = sc;
+                }
+            }
         }
 
-        let line_len = self.lines[self.cursor_line].chars().count();
-        if self.cursor_col < line_len {
-            let line = &mut self.lines[self.cursor_line];
-            let mut chars: Vec<char> = line.chars().collect();
-            let deleted = chars.remove(self.cursor_col);
-            *line = chars.into_iter().collect();
-
-            let action = EditAction::Delete {
-                line: self.cursor_line,
-                col: self.cursor_col,
-                text: deleted.to_string(),
-            };
-
-            self.push_undo(action);
-        } else if self.cursor_line + 1 < self.lines.len() {
-            // 다음 줄과 병합
-            let next_line = self.lines.remove(self.cursor_line + 1);
-            self.lines[self.cursor_line].push_str(&next_line);
-
-            let action = EditAction::MergeLine {
-                line: self.cursor_line,
-                col: self.cursor_col,
-            };
-
-            self.push_undo(action);
+        if any {
+            self.push_undo(EditAction::Batch { actions });
+            self.set_carets(carets);
+            self.update_scroll();
         }
     }
 
-    /// 선택 영역 삭제
-    pub fn delete_selection(&mut self) {
-        let sel = match self.selection.take() {
-            Some(s) if !s.is_empty() => s,
-            _ => return,
+    /// The secondary caret (or the primary one, if it's further down)
+    /// with the greatest line number.
+    fn bottom_caret(&self) -> Caret {
+        let mut best = Caret {
+            line: self.cursor_line,
+            col: self.cursor_col,
+            selection: self.selection,
         };
+        for c in &self.secondary_carets {
+            if c.line > best.line {
+                best = *c;
+            }
+        }
+        best
+    }
 
-        let (start_line, start_col, end_line, end_col) = sel.normalized();
-
-        if start_line == end_line {
-            // 같은 줄 내 삭제
-            let line = &mut self.lines[start_line];
-            let chars: Vec<char> = line.chars().collect();
-            let deleted: String = chars[start_col..end_col].iter().collect();
-            let new_line: String = chars[..start_col]
-                .iter()
-                .chain(chars[end_col..].iter())
-                .collect();
-            *line = new_line;
-
-            self.push_undo(EditAction::Delete {
-                line: start_line,
-                col: start_col,
-                text: deleted,
-            });
-        } else {
-            // 여러 줄 삭제
-            let mut actions = Vec::new();
-
-            // 시작 줄 처리
-            let first_chars: Vec<char> = self.lines[start_line].chars().collect();
-            let first_part: String = first_chars[..start_col].iter().collect();
-
-            // 끝 줄 처리
-            let last_chars: Vec<char> = self.lines[end_line].chars().collect();
-            let last_part: String = last_chars[end_col..].iter().collect();
-
-            // 중간 줄들 저장 (undo용)
-            for i in (start_line + 1..=end_line).rev() {
-                actions.push(EditAction::DeleteLine {
-                    line: i,
-                    content: self.lines[i].clone(),
-                });
+    /// The secondary caret (or the primary one, if it's further up) with
+    /// the smallest line number.
+    fn top_caret(&self) -> Caret {
+        let mut best = Caret {
+            line: self.cursor_line,
+            col: self.cursor_col,
+            selection: self.selection,
+        };
+        for c in &self.secondary_carets {
+            if c.line < best.line {
+                best = *c;
             }
+        }
+        best
+    }
 
-            // 줄 병합
-            self.lines[start_line] = format!("{}{}", first_part, last_part);
+    /// Add a secondary caret on the line below the bottom-most existing
+    /// caret, at the same column clamped to that line's length.
+    pub fn add_caret_below(&mut self) {
+        let base = self.bottom_caret();
+        if base.line + 1 < self.line_count() {
+            let new_line = base.line + 1;
+            let new_col = base.col.min(self.line_len(new_line));
+            self.secondary_carets.push(Caret {
+                line: new_line,
+                col: new_col,
+                selection: None,
+            });
+        }
+    }
 
-            // 중간 줄들 제거
-            for _ in start_line + 1..=end_line {
-                if start_line + 1 < self.lines.len() {
-                    self.lines.remove(start_line + 1);
-                }
-            }
+    /// Add a secondary caret on the line above the top-most existing
+    /// caret, at the same column clamped to that line's length.
+    pub fn add_caret_above(&mut self) {
+        let base = self.top_caret();
+        if base.line > 0 {
+            let new_line = base.line - 1;
+            let new_col = base.col.min(self.line_len(new_line));
+            self.secondary_carets.push(Caret {
+                line: new_line,
+                col: new_col,
+                selection: None,
+            });
+        }
+    }
 
-            self.push_undo(EditAction::Batch { actions });
+    /// Turn every entry in `match_positions` into an active caret with
+    /// its match selected, so a subsequent edit (type to replace, delete,
+    /// ...) touches every occurrence at once. Requires `perform_find` to
+    /// have already populated `match_positions`.
+    pub fn select_all_occurrences(&mut self) {
+        if self.match_positions.is_empty() {
+            return;
         }
+        let mut carets: Vec<Caret> = self
+            .match_positions
+            .iter()
+            .map(|&(line, start, end)| Caret {
+                line,
+                col: end,
+                selection: Some(Selection {
+                    start_line: line,
+                    start_col: start,
+                    end_line: line,
+                    end_col: end,
+                }),
+            })
+            .collect();
+        let primary = carets.remove(0);
+        self.cursor_line = primary.line;
+        self.cursor_col = primary.col;
+        self.selection = primary.selection;
+        self.secondary_carets = carets;
+    }
 
-        self.cursor_line = start_line;
-        self.cursor_col = start_col;
-        self.update_scroll();
+    /// Add a secondary caret at the next occurrence of the find term
+    /// (wrapping), leaving existing carets untouched — the incremental
+    /// "add cursor at next match" command.
+    pub fn add_caret_at_next_match(&mut self) {
+        if self.match_positions.is_empty() {
+            return;
+        }
+        self.current_match = (self.current_match + 1) % self.match_positions.len();
+        let (line, start, end) = self.match_positions[self.current_match];
+        self.secondary_carets.push(Caret {
+            line,
+            col: end,
+            selection: Some(Selection {
+                start_line: line,
+                start_col: start,
+                end_line: line,
+                end_col: end,
+            }),
+        });
     }
 
     /// 선택된 텍스트 가져오기
     pub fn get_selected_text(&self) -> String {
         let sel = match &self.selection {
-            Some(s) if !s.is_empty() => s,
+            Some(s) if !s.is_empty() => *s,
             _ => return String::new(),
         };
 
-        let (start_line, start_col, end_line, end_col) = sel.normalized();
-
-        if start_line == end_line {
-            let chars: Vec<char> = self.lines[start_line].chars().collect();
-            chars[start_col..end_col].iter().collect()
-        } else {
-            let mut result = String::new();
-
-            // 첫 줄
-            let first_chars: Vec<char> = self.lines[start_line].chars().collect();
-            result.push_str(&first_chars[start_col..].iter().collect::<String>());
-
-            // 중간 줄
-            for i in start_line + 1..end_line {
-                result.push('\n');
-                result.push_str(&self.lines[i]);
-            }
-
-            // 마지막 줄
-            result.push('\n');
-            let last_chars: Vec<char> = self.lines[end_line].chars().collect();
-            result.push_str(&last_chars[..end_col].iter().collect::<String>());
-
-            result
+        if self.block_selection {
+            // Block selection spans `secondary_carets`, one per row below
+            // the anchor row held in `selection` — join every row's own
+            // column slice instead of the linear char range between the
+            // first and last row, which would sweep in whole lines.
+            let bottom = self.secondary_carets.iter().map(|c| c.line).max().unwrap_or(sel.start_line);
+            return (sel.start_line..=bottom)
+                .map(|line| {
+                    if line == sel.start_line {
+                        let (_, sc, _, ec) = sel.normalized();
+                        self.line(line).chars().skip(sc).take(ec - sc).collect::<String>()
+                    } else if let Some(caret) = self.secondary_carets.iter().find(|c| c.line == line) {
+                        let (_, sc, _, ec) = caret.selection.map(|s| s.normalized()).unwrap_or((line, 0, line, 0));
+                        self.line(line).chars().skip(sc).take(ec - sc).collect::<String>()
+                    } else {
+                        String::new()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
         }
+
+        let (start_line, start_col, end_line, end_col) = sel.normalized();
+        let start_char = self.char_idx(start_line, start_col);
+        let end_char = self.char_idx(end_line, end_col);
+        self.buffer.slice(start_char..end_char)
     }
 
     /// 복사
@@ -638,55 +1694,64 @@ impl EditorState {
 
     /// 전체 선택
     pub fn select_all(&mut self) {
-        if !self.lines.is_empty() {
-            let last_line = self.lines.len() - 1;
-            let last_col = self.lines[last_line].chars().count();
-            self.selection = Some(Selection {
-                start_line: 0,
-                start_col: 0,
-                end_line: last_line,
-                end_col: last_col,
-            });
-            self.cursor_line = last_line;
-            self.cursor_col = last_col;
-        }
+        let last_line = self.line_count() - 1;
+        let last_col = self.line_len(last_line);
+        self.selection = Some(Selection {
+            start_line: 0,
+            start_col: 0,
+            end_line: last_line,
+            end_col: last_col,
+        });
+        self.cursor_line = last_line;
+        self.cursor_col = last_col;
     }
 
     /// 줄 복제
     pub fn duplicate_line(&mut self) {
-        let line_content = self.lines[self.cursor_line].clone();
-        self.lines.insert(self.cursor_line + 1, line_content.clone());
+        let content = self.line(self.cursor_line);
+        let char_idx = self.char_idx(self.cursor_line, 0) + content.chars().count();
+        let text = format!("\n{}", content);
+        self.buffer.insert(char_idx, &text);
         self.cursor_line += 1;
 
-        self.push_undo(EditAction::InsertLine {
-            line: self.cursor_line,
-            content: line_content,
-        });
+        self.push_undo(EditAction::Insert { char_idx, text });
         self.update_scroll();
     }
 
     /// 줄 삭제
     pub fn delete_line(&mut self) {
-        if self.lines.len() > 1 {
-            let content = self.lines.remove(self.cursor_line);
+        if self.line_count() <= 1 {
+            return;
+        }
 
-            self.push_undo(EditAction::DeleteLine {
-                line: self.cursor_line,
-                content,
-            });
+        let is_last_line = self.cursor_line + 1 == self.line_count();
+        let (start, end) = if is_last_line {
+            (self.char_idx(self.cursor_line, 0) - 1, self.buffer.len_chars())
+        } else {
+            (self.char_idx(self.cursor_line, 0), self.char_idx(self.cursor_line + 1, 0))
+        };
 
-            if self.cursor_line >= self.lines.len() {
-                self.cursor_line = self.lines.len() - 1;
-            }
-            self.cursor_col = self.cursor_col.min(self.lines[self.cursor_line].chars().count());
-            self.update_scroll();
+        let deleted = self.buffer.slice(start..end);
+        self.buffer.remove(start..end);
+
+        self.push_undo(EditAction::Delete {
+            char_idx: start,
+            text: deleted,
+        });
+
+        if self.cursor_line >= self.line_count() {
+            self.cursor_line = self.line_count() - 1;
         }
+        self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_line));
+        self.update_scroll();
     }
 
     /// 줄 위로 이동
     pub fn move_line_up(&mut self) {
         if self.cursor_line > 0 {
-            self.lines.swap(self.cursor_line, self.cursor_line - 1);
+            let mut all = self.lines();
+            all.swap(self.cursor_line, self.cursor_line - 1);
+            self.buffer = Rope::from_str(&all.join("\n"));
             self.cursor_line -= 1;
             self.modified = true;
             self.update_scroll();
@@ -695,8 +1760,10 @@ impl EditorState {
 
     /// 줄 아래로 이동
     pub fn move_line_down(&mut self) {
-        if self.cursor_line + 1 < self.lines.len() {
-            self.lines.swap(self.cursor_line, self.cursor_line + 1);
+        if self.cursor_line + 1 < self.line_count() {
+            let mut all = self.lines();
+            all.swap(self.cursor_line, self.cursor_line + 1);
+            self.buffer = Rope::from_str(&all.join("\n"));
             self.cursor_line += 1;
             self.modified = true;
             self.update_scroll();
@@ -705,6 +1772,9 @@ impl EditorState {
 
     /// 커서 이동
     pub fn move_cursor(&mut self, line_delta: i32, col_delta: i32, extend_selection: bool) {
+        self.last_edit_kind = None;
+        self.block_selection = false;
+        self.block_anchor = None;
         if extend_selection {
             if self.selection.is_none() {
                 self.selection = Some(Selection::new(self.cursor_line, self.cursor_col));
@@ -716,27 +1786,27 @@ impl EditorState {
         // 줄 이동
         let new_line = (self.cursor_line as i32 + line_delta)
             .max(0)
-            .min(self.lines.len().saturating_sub(1) as i32) as usize;
+            .min(self.line_count().saturating_sub(1) as i32) as usize;
 
         if new_line != self.cursor_line {
             self.cursor_line = new_line;
-            let line_len = self.lines[self.cursor_line].chars().count();
+            let line_len = self.line_len(self.cursor_line);
             self.cursor_col = self.cursor_col.min(line_len);
         }
 
         // 열 이동
         if col_delta != 0 {
-            let line_len = self.lines[self.cursor_line].chars().count();
+            let line_len = self.line_len(self.cursor_line);
             let new_col = (self.cursor_col as i32 + col_delta).max(0) as usize;
 
-            if new_col > line_len && col_delta > 0 && self.cursor_line + 1 < self.lines.len() {
+            if new_col > line_len && col_delta > 0 && self.cursor_line + 1 < self.line_count() {
                 // 다음 줄로 이동
                 self.cursor_line += 1;
                 self.cursor_col = 0;
             } else if new_col > self.cursor_col && col_delta < 0 && self.cursor_line > 0 {
                 // 이전 줄 끝으로 이동
                 self.cursor_line -= 1;
-                self.cursor_col = self.lines[self.cursor_line].chars().count();
+                self.cursor_col = self.line_len(self.cursor_line);
             } else {
                 self.cursor_col = new_col.min(line_len);
             }
@@ -754,6 +1824,9 @@ impl EditorState {
 
     /// 줄 시작으로
     pub fn move_to_line_start(&mut self, extend_selection: bool) {
+        self.last_edit_kind = None;
+        self.block_selection = false;
+        self.block_anchor = None;
         if extend_selection {
             if self.selection.is_none() {
                 self.selection = Some(Selection::new(self.cursor_line, self.cursor_col));
@@ -763,7 +1836,7 @@ impl EditorState {
         }
 
         // 첫 번째 비공백 문자로 이동, 이미 거기 있으면 줄 시작으로
-        let line = &self.lines[self.cursor_line];
+        let line = self.line(self.cursor_line);
         let first_non_ws = line.chars().position(|c| !c.is_whitespace()).unwrap_or(0);
 
         if self.cursor_col == first_non_ws || self.cursor_col == 0 {
@@ -779,6 +1852,9 @@ impl EditorState {
 
     /// 줄 끝으로
     pub fn move_to_line_end(&mut self, extend_selection: bool) {
+        self.last_edit_kind = None;
+        self.block_selection = false;
+        self.block_anchor = None;
         if extend_selection {
             if self.selection.is_none() {
                 self.selection = Some(Selection::new(self.cursor_line, self.cursor_col));
@@ -787,7 +1863,7 @@ impl EditorState {
             self.selection = None;
         }
 
-        self.cursor_col = self.lines[self.cursor_line].chars().count();
+        self.cursor_col = self.line_len(self.cursor_line);
 
         if let Some(ref mut sel) = self.selection {
             sel.end_col = self.cursor_col;
@@ -813,86 +1889,73 @@ impl EditorState {
     }
 
     /// 괄호 매칭 찾기
+    ///
+    /// Walks `TokenType::Bracket` tokens (via the same `token_cache` the
+    /// renderer uses) rather than raw characters, so a `(` inside a string
+    /// or comment never counts toward the depth -- the tokenizer already
+    /// classified it as `String`/`Comment`, not `Bracket`. Matching reuses
+    /// `Token::bracket_depth`, which `fill_spans_and_bracket_depth` already
+    /// threads across lines: an opening and its closing bracket always
+    /// share the same depth value.
     fn find_matching_bracket(&mut self) {
         self.matching_bracket = None;
 
-        if self.cursor_line >= self.lines.len() {
+        if self.cursor_line >= self.line_count() {
             return;
         }
 
-        let line = &self.lines[self.cursor_line];
-        let chars: Vec<char> = line.chars().collect();
+        let line_count = self.line_count();
+        let all_tokens = self.visible_tokens(0, line_count);
 
-        if self.cursor_col >= chars.len() {
+        let Some(cur_token) = all_tokens[self.cursor_line].iter().find(|t| {
+            t.token_type == TokenType::Bracket && self.cursor_col >= t.start && self.cursor_col < t.end
+        }) else {
             return;
-        }
+        };
+        let Some(depth) = cur_token.bracket_depth else {
+            return;
+        };
 
-        let current_char = chars[self.cursor_col];
-        let (opening, closing, forward) = match current_char {
-            '(' => ('(', ')', true),
-            ')' => ('(', ')', false),
-            '[' => ('[', ']', true),
-            ']' => ('[', ']', false),
-            '{' => ('{', '}', true),
-            '}' => ('{', '}', false),
-            '<' => ('<', '>', true),
-            '>' => ('<', '>', false),
+        let (closing, forward) = match cur_token.text.as_str() {
+            "(" => (")", true),
+            "[" => ("]", true),
+            "{" => ("}", true),
+            ")" => ("(", false),
+            "]" => ("[", false),
+            "}" => ("{", false),
             _ => return,
         };
-
-        let mut depth = 1;
+        let cur_start = cur_token.start;
 
         if forward {
-            // 앞으로 검색
-            let mut line_idx = self.cursor_line;
-            let mut col_idx = self.cursor_col + 1;
-
-            while line_idx < self.lines.len() {
-                let line_chars: Vec<char> = self.lines[line_idx].chars().collect();
-                while col_idx < line_chars.len() {
-                    if line_chars[col_idx] == closing {
-                        depth -= 1;
-                        if depth == 0 {
-                            self.matching_bracket = Some((line_idx, col_idx));
-                            return;
-                        }
-                    } else if line_chars[col_idx] == opening {
-                        depth += 1;
+            for line_idx in self.cursor_line..line_count {
+                for t in &all_tokens[line_idx] {
+                    if line_idx == self.cursor_line && t.start <= cur_start {
+                        continue;
+                    }
+                    if t.token_type == TokenType::Bracket
+                        && t.bracket_depth == Some(depth)
+                        && t.text == closing
+                    {
+                        self.matching_bracket = Some((line_idx, t.start));
+                        return;
                     }
-                    col_idx += 1;
                 }
-                line_idx += 1;
-                col_idx = 0;
             }
         } else {
-            // 뒤로 검색
-            let mut line_idx = self.cursor_line;
-            let mut col_idx = self.cursor_col.saturating_sub(1);
-
-            loop {
-                let line_chars: Vec<char> = self.lines[line_idx].chars().collect();
-                loop {
-                    if col_idx < line_chars.len() {
-                        if line_chars[col_idx] == opening {
-                            depth -= 1;
-                            if depth == 0 {
-                                self.matching_bracket = Some((line_idx, col_idx));
-                                return;
-                            }
-                        } else if line_chars[col_idx] == closing {
-                            depth += 1;
-                        }
+            for line_idx in (0..=self.cursor_line).rev() {
+                for t in all_tokens[line_idx].iter().rev() {
+                    if line_idx == self.cursor_line && t.start >= cur_start {
+                        continue;
                     }
-                    if col_idx == 0 {
-                        break;
+                    if t.token_type == TokenType::Bracket
+                        && t.bracket_depth == Some(depth)
+                        && t.text == closing
+                    {
+                        self.matching_bracket = Some((line_idx, t.start));
+                        return;
                     }
-                    col_idx -= 1;
-                }
-                if line_idx == 0 {
-                    break;
                 }
-                line_idx -= 1;
-                col_idx = self.lines[line_idx].chars().count().saturating_sub(1);
             }
         }
     }
@@ -905,26 +1968,31 @@ impl EditorState {
             return;
         }
 
-        let pattern = if self.find_options.use_regex {
-            self.find_term.clone()
-        } else {
-            regex::escape(&self.find_term)
-        };
-
-        let pattern = if self.find_options.whole_word {
-            format!(r"\b{}\b", pattern)
-        } else {
-            pattern
-        };
-
-        let regex = if self.find_options.case_sensitive {
-            Regex::new(&pattern)
-        } else {
-            Regex::new(&format!("(?i){}", pattern))
-        };
-
-        if let Ok(re) = regex {
-            for (line_idx, line) in self.lines.iter().enumerate() {
+        if self.find_options.fuzzy {
+            let mut scored: Vec<(usize, usize, usize, i64)> = Vec::new();
+            for (line_idx, line) in self.lines().iter().enumerate() {
+                if let Some((start, end, score)) =
+                    fuzzy_score_line(line, &self.find_term, self.find_options.case_sensitive)
+                {
+                    scored.push((line_idx, start, end, score));
+                }
+            }
+            // 점수 내림차순: find_next/find_prev가 제일 좋은 매치부터 훑는다
+            scored.sort_by(|a, b| b.3.cmp(&a.3));
+            self.match_positions = scored.into_iter().map(|(l, s, e, _)| (l, s, e)).collect();
+        } else if self.find_options.composite {
+            if let Some(query) = self.compile_composite_query() {
+                for (line_idx, line) in self.lines().iter().enumerate() {
+                    let (qualifies, mut spans) = query.eval(line);
+                    if qualifies {
+                        spans.sort_by_key(|s| s.0);
+                        self.match_positions
+                            .extend(spans.into_iter().map(|(s, e)| (line_idx, s, e)));
+                    }
+                }
+            }
+        } else if let Some(re) = self.compiled_find_regex() {
+            for (line_idx, line) in self.lines().iter().enumerate() {
                 for mat in re.find_iter(line) {
                     self.match_positions.push((line_idx, mat.start(), mat.end()));
                 }
@@ -971,109 +2039,512 @@ impl EditorState {
         }
     }
 
-    /// 바꾸기
-    pub fn replace_current(&mut self) {
-        if self.match_positions.is_empty() || self.current_match >= self.match_positions.len() {
-            return;
+    /// Compile the current find pattern exactly as `perform_find` does, so
+    /// every consumer (search, replace-one, replace-all) agrees on what
+    /// "the match" and its capture groups are.
+    fn compiled_find_regex(&self) -> Option<Regex> {
+        self.compile_pattern(&self.find_term)
+    }
+
+    /// Compile one leaf term under the current case/regex/whole-word
+    /// flags — shared by `compiled_find_regex` and composite-query leaves
+    /// so every pattern in a `foo & !bar` expression honors the same
+    /// options as a plain single-term search.
+    fn compile_pattern(&self, term: &str) -> Option<Regex> {
+        let pattern = if self.find_options.use_regex {
+            term.to_string()
+        } else {
+            regex::escape(term)
+        };
+
+        let pattern = if self.find_options.whole_word {
+            format!(r"\b{}\b", pattern)
+        } else {
+            pattern
+        };
+
+        if self.find_options.case_sensitive {
+            Regex::new(&pattern).ok()
+        } else {
+            Regex::new(&format!("(?i){}", pattern)).ok()
+        }
+    }
+
+    /// Parse and compile `find_term` as a composite `&`/`|`/`!` expression
+    /// tree of leaf patterns, honoring the current search flags on every
+    /// leaf.
+    fn compile_composite_query(&self) -> Option<CompiledQuery> {
+        let expr = parse_query(&self.find_term)?;
+        self.compile_query_expr(&expr)
+    }
+
+    fn compile_query_expr(&self, expr: &QueryExpr) -> Option<CompiledQuery> {
+        Some(match expr {
+            QueryExpr::Leaf(term) => CompiledQuery::Leaf(self.compile_pattern(term)?),
+            QueryExpr::Not(inner) => CompiledQuery::Not(Box::new(self.compile_query_expr(inner)?)),
+            QueryExpr::And(lhs, rhs) => CompiledQuery::And(
+                Box::new(self.compile_query_expr(lhs)?),
+                Box::new(self.compile_query_expr(rhs)?),
+            ),
+            QueryExpr::Or(lhs, rhs) => CompiledQuery::Or(
+                Box::new(self.compile_query_expr(lhs)?),
+                Box::new(self.compile_query_expr(rhs)?),
+            ),
+        })
+    }
+
+    /// Expand `replace_input` against `matched_text` via `Regex::replace`,
+    /// so `$1`/`${name}` backreferences resolve identically whether the
+    /// user replaces one match (`replace_current`) or all of them
+    /// (`replace_all`). Falls back to the raw replacement text when the
+    /// pattern doesn't compile.
+    fn expand_replacement(&self, matched_text: &str) -> String {
+        match self.compiled_find_regex() {
+            Some(re) => re.replace(matched_text, self.replace_input.as_str()).to_string(),
+            None => self.replace_input.clone(),
+        }
+    }
+
+    /// What the current match would become if `replace_current` ran right
+    /// now, with capture groups expanded — used to render a live preview
+    /// in the find/replace footer before the user confirms.
+    pub fn replacement_preview(&self) -> Option<String> {
+        let (line, start, end) = *self.match_positions.get(self.current_match)?;
+        let matched_text = self.line(line).chars().skip(start).take(end - start).collect::<String>();
+        Some(self.expand_replacement(&matched_text))
+    }
+
+    /// 바꾸기
+    pub fn replace_current(&mut self) {
+        if self.match_positions.is_empty() || self.current_match >= self.match_positions.len() {
+            return;
+        }
+
+        let (line, start, end) = self.match_positions[self.current_match];
+
+        // 선택 영역이 현재 매치와 일치하는지 확인
+        let sel = self.selection.as_ref();
+        if sel.is_some_and(|s| {
+            let (sl, sc, el, ec) = s.normalized();
+            sl == line && sc == start && el == line && ec == end
+        }) {
+            // 바꾸기 실행: 기존 매치를 지우고 바꿀 텍스트를 삽입 (캡처 그룹 확장)
+            let char_idx = self.char_idx(line, start);
+            let old_text = self.buffer.slice(char_idx..self.char_idx(line, end));
+            let new_text = self.expand_replacement(&old_text);
+
+            self.buffer.remove(char_idx..char_idx + old_text.chars().count());
+            self.buffer.insert(char_idx, &new_text);
+
+            self.push_undo(EditAction::Batch {
+                actions: vec![
+                    EditAction::Delete {
+                        char_idx,
+                        text: old_text,
+                    },
+                    EditAction::Insert {
+                        char_idx,
+                        text: new_text,
+                    },
+                ],
+            });
+
+            self.selection = None;
+            self.perform_find();
+            self.find_next();
+        }
+    }
+
+    /// 모두 바꾸기
+    pub fn replace_all(&mut self) {
+        if self.find_term.is_empty() {
+            return;
+        }
+
+        if let Some(re) = self.compiled_find_regex() {
+            let old_full = self.buffer.as_str().to_string();
+            let mut any_change = false;
+            let new_full = old_full
+                .lines()
+                .map(|line| {
+                    let replaced = re.replace_all(line, self.replace_input.as_str()).to_string();
+                    if replaced != line {
+                        any_change = true;
+                    }
+                    replaced
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            if any_change {
+                self.buffer = Rope::from_str(&new_full);
+                self.push_undo(EditAction::Batch {
+                    actions: vec![
+                        EditAction::Delete {
+                            char_idx: 0,
+                            text: old_full,
+                        },
+                        EditAction::Insert {
+                            char_idx: 0,
+                            text: new_full,
+                        },
+                    ],
+                });
+            }
+
+            self.selection = None;
+            self.perform_find();
+        }
+    }
+
+    /// 줄 번호로 이동: `line`, `line:col`, `NN%`, `+N`/`-N` 형식을 모두 받는다.
+    ///
+    /// - `line` / `line:col`: 1-based 절대 줄(과 선택적 열)로 이동
+    /// - `NN%`: 파일 전체에서 NN% 지점의 줄로 이동
+    /// - `+N` / `-N`: 현재 줄 기준 상대 이동
+    ///
+    /// 줄은 버퍼 범위로, 열은 대상 줄 길이로 clamp된다.
+    pub fn goto_line(&mut self, input: &str) {
+        let input = input.trim();
+        if input.is_empty() {
+            return;
+        }
+
+        // line:col은 열도 지정하므로 따로 처리하고 반환한다
+        if let Some((line_part, col_part)) = input.split_once(':') {
+            let (Ok(line_num), Ok(col)) = (line_part.parse::<usize>(), col_part.parse::<usize>())
+            else {
+                return;
+            };
+            if line_num == 0 {
+                return;
+            }
+            let target_line = (line_num - 1).min(self.line_count().saturating_sub(1));
+            self.cursor_line = target_line;
+            self.cursor_col = col.min(self.line_len(target_line));
+            self.selection = None;
+            self.update_scroll();
+            return;
+        }
+
+        // 나머지 형식은 줄만 바꾸고, 열은 상대 이동만 유지하고 그 외엔 0으로 리셋한다
+        let (target_line, keep_col) = if let Some(pct) = input.strip_suffix('%') {
+            match pct.parse::<usize>() {
+                Ok(pct) => (pct.min(100) * self.line_count() / 100, false),
+                Err(_) => return,
+            }
+        } else if let Some(rel) = input.strip_prefix('+') {
+            match rel.parse::<usize>() {
+                Ok(delta) => (self.cursor_line + delta, true),
+                Err(_) => return,
+            }
+        } else if let Some(rel) = input.strip_prefix('-') {
+            match rel.parse::<usize>() {
+                Ok(delta) => (self.cursor_line.saturating_sub(delta), true),
+                Err(_) => return,
+            }
+        } else {
+            match input.parse::<usize>() {
+                Ok(line_num) if line_num > 0 => (line_num - 1, false),
+                _ => return,
+            }
+        };
+
+        let target_line = target_line.min(self.line_count().saturating_sub(1));
+        if target_line < self.line_count() {
+            self.cursor_line = target_line;
+            self.cursor_col = if keep_col {
+                self.cursor_col.min(self.line_len(target_line))
+            } else {
+                0
+            };
+            self.selection = None;
+            self.update_scroll();
+        }
+    }
+
+    /// Char offset of the next word boundary after `idx` (Vim's `w`):
+    /// skip the rest of the current word (or punctuation run), then skip
+    /// whitespace, landing on the first char of the next word.
+    fn word_forward_char_idx(&self, idx: usize) -> usize {
+        let chars: Vec<char> = self.buffer.as_str().chars().collect();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let mut i = idx;
+        if i < chars.len() && is_word(chars[i]) {
+            while i < chars.len() && is_word(chars[i]) {
+                i += 1;
+            }
+        } else if i < chars.len() && !chars[i].is_whitespace() {
+            while i < chars.len() && !is_word(chars[i]) && !chars[i].is_whitespace() {
+                i += 1;
+            }
+        }
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        i
+    }
+
+    /// Char offset of the previous word boundary before `idx`: mirrors
+    /// `word_forward_char_idx` walking backward, skipping whitespace first
+    /// and then the word/punctuation run behind it.
+    fn word_backward_char_idx(&self, idx: usize) -> usize {
+        let chars: Vec<char> = self.buffer.as_str().chars().collect();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let mut i = idx;
+        while i > 0 && chars[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        if i > 0 && is_word(chars[i - 1]) {
+            while i > 0 && is_word(chars[i - 1]) {
+                i -= 1;
+            }
+        } else if i > 0 && !chars[i - 1].is_whitespace() {
+            while i > 0 && !is_word(chars[i - 1]) && !chars[i - 1].is_whitespace() {
+                i -= 1;
+            }
+        }
+        i
+    }
+
+    /// Word-wise cursor navigation (Ctrl+Alt+Left/Right), optionally
+    /// extending the selection the same way `move_cursor` does under
+    /// Shift.
+    pub fn move_word(&mut self, forward: bool, extend_selection: bool) {
+        self.last_edit_kind = None;
+        self.block_selection = false;
+        self.block_anchor = None;
+        if extend_selection {
+            if self.selection.is_none() {
+                self.selection = Some(Selection::new(self.cursor_line, self.cursor_col));
+            }
+        } else {
+            self.selection = None;
+        }
+
+        let idx = self.char_idx(self.cursor_line, self.cursor_col);
+        let idx = if forward {
+            self.word_forward_char_idx(idx)
+        } else {
+            self.word_backward_char_idx(idx)
+        };
+        self.cursor_line = self.buffer.char_to_line(idx);
+        self.cursor_col = idx - self.buffer.line_to_char(self.cursor_line);
+
+        if let Some(ref mut sel) = self.selection {
+            sel.end_line = self.cursor_line;
+            sel.end_col = self.cursor_col;
+        }
+
+        self.update_scroll();
+    }
+
+    /// Line index of the next/previous blank-line-delimited paragraph
+    /// boundary from `from_line` — used for Ctrl+Shift+Up/Down "move
+    /// selection by block". Skips any blank lines immediately adjacent to
+    /// `from_line` first so repeated presses step block by block instead
+    /// of stalling on the boundary they just reached.
+    fn paragraph_boundary_line(&self, from_line: usize, forward: bool) -> usize {
+        let is_blank = |l: usize| self.line(l).trim().is_empty();
+        let last = self.line_count().saturating_sub(1);
+        let mut i = from_line;
+        if forward {
+            while i < last && is_blank(i) {
+                i += 1;
+            }
+            while i < last && !is_blank(i + 1) {
+                i += 1;
+            }
+            (i + 1).min(last)
+        } else {
+            while i > 0 && is_blank(i) {
+                i -= 1;
+            }
+            while i > 0 && !is_blank(i - 1) {
+                i -= 1;
+            }
+            i.saturating_sub(1)
+        }
+    }
+
+    /// Extend the selection up/down by one paragraph block (Ctrl+Shift+Up/Down).
+    pub fn extend_selection_by_block(&mut self, forward: bool) {
+        self.last_edit_kind = None;
+        if self.selection.is_none() {
+            self.selection = Some(Selection::new(self.cursor_line, self.cursor_col));
         }
 
-        let (line, start, end) = self.match_positions[self.current_match];
-
-        // 선택 영역이 현재 매치와 일치하는지 확인
-        let sel = self.selection.as_ref();
-        if sel.is_some_and(|s| {
-            let (sl, sc, el, ec) = s.normalized();
-            sl == line && sc == start && el == line && ec == end
-        }) {
-            // 바꾸기 실행
-            let line_content = &self.lines[line];
-            let chars: Vec<char> = line_content.chars().collect();
-            let new_line: String = chars[..start]
-                .iter()
-                .chain(self.replace_input.chars().collect::<Vec<_>>().iter())
-                .chain(chars[end..].iter())
-                .collect();
+        self.cursor_line = self.paragraph_boundary_line(self.cursor_line, forward);
+        self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_line));
 
-            let old_content = self.lines[line].clone();
-            self.lines[line] = new_line;
+        if let Some(ref mut sel) = self.selection {
+            sel.end_line = self.cursor_line;
+            sel.end_col = self.cursor_col;
+        }
 
-            self.push_undo(EditAction::Replace {
-                line,
-                old_content,
-                new_content: self.lines[line].clone(),
-            });
+        self.update_scroll();
+    }
 
-            self.selection = None;
-            self.perform_find();
-            self.find_next();
+    /// Extend a rectangular (column/block) selection by one step
+    /// (Alt+Shift+arrows). Unlike the line-wise `move_cursor` selection,
+    /// the rectangle is rebuilt from `block_anchor` to the cursor on every
+    /// step and unpacked one row at a time into `selection` (the anchor's
+    /// row) plus `secondary_carets` (every other row) — so the existing
+    /// multi-caret machinery (insert/delete/copy, and its renderer) already
+    /// knows how to show and edit a column block without any code of its
+    /// own for it.
+    pub fn extend_block_selection(&mut self, line_delta: i32, col_delta: i32) {
+        self.last_edit_kind = None;
+        if !self.block_selection {
+            self.block_selection = true;
+            self.block_anchor = Some((self.cursor_line, self.cursor_col));
         }
-    }
 
-    /// 모두 바꾸기
-    pub fn replace_all(&mut self) {
-        if self.find_term.is_empty() {
-            return;
+        let new_line = (self.cursor_line as i32 + line_delta)
+            .max(0)
+            .min(self.line_count().saturating_sub(1) as i32) as usize;
+        self.cursor_line = new_line;
+        if col_delta != 0 {
+            self.cursor_col = (self.cursor_col as i32 + col_delta).max(0) as usize;
         }
 
-        let pattern = if self.find_options.use_regex {
-            self.find_term.clone()
-        } else {
-            regex::escape(&self.find_term)
-        };
+        self.rebuild_block_selection();
+        self.update_scroll();
+    }
 
-        let pattern = if self.find_options.whole_word {
-            format!(r"\b{}\b", pattern)
-        } else {
-            pattern
+    /// Rebuild `selection`/`secondary_carets` from `block_anchor` to the
+    /// current cursor. Every row in the rectangle gets its own
+    /// single-line `Selection` spanning the same `[left, right)` column
+    /// range, clipped to that row's own length.
+    fn rebuild_block_selection(&mut self) {
+        let Some((anchor_line, anchor_col)) = self.block_anchor else {
+            return;
         };
-
-        let regex = if self.find_options.case_sensitive {
-            Regex::new(&pattern)
-        } else {
-            Regex::new(&format!("(?i){}", pattern))
+        let top = anchor_line.min(self.cursor_line);
+        let bottom = anchor_line.max(self.cursor_line);
+        let left = anchor_col.min(self.cursor_col);
+        let right = anchor_col.max(self.cursor_col);
+
+        let row_selection = |state: &Self, line: usize| {
+            let len = state.line_len(line);
+            Selection {
+                start_line: line,
+                start_col: left.min(len),
+                end_line: line,
+                end_col: right.min(len),
+            }
         };
 
-        if let Ok(re) = regex {
-            let mut actions = Vec::new();
+        self.selection = Some(row_selection(self, top));
+        self.secondary_carets = (top + 1..=bottom)
+            .map(|line| {
+                let sel = row_selection(self, line);
+                Caret { line, col: sel.end_col, selection: Some(sel) }
+            })
+            .collect();
+    }
 
-            for (line_idx, line) in self.lines.iter_mut().enumerate() {
-                let old_content = line.clone();
-                let new_content = re.replace_all(line, self.replace_input.as_str()).to_string();
+    /// Char range covering `count` whole lines starting at `start_line`,
+    /// including the separating `\n` so the lines actually disappear
+    /// rather than leaving a blank line behind — except when the range
+    /// reaches the last line, where it instead eats the newline *before*
+    /// `start_line` so deleting the final line doesn't leave a dangling
+    /// empty one (same trick `delete_line` already uses).
+    fn linewise_range(&self, start_line: usize, count: usize) -> std::ops::Range<usize> {
+        let last_affected = (start_line + count.max(1) - 1).min(self.line_count() - 1);
+        let reaches_end = last_affected + 1 == self.line_count();
+        if reaches_end && start_line > 0 {
+            self.char_idx(start_line, 0) - 1..self.buffer.len_chars()
+        } else if reaches_end {
+            0..self.buffer.len_chars()
+        } else {
+            self.char_idx(start_line, 0)..self.char_idx(last_affected + 1, 0)
+        }
+    }
 
-                if old_content != new_content {
-                    actions.push(EditAction::Replace {
-                        line: line_idx,
-                        old_content,
-                        new_content: new_content.clone(),
-                    });
-                    *line = new_content;
+    /// Charwise target of a single-key motion (`w`, `$`, `0`, `h`, `l`)
+    /// repeated `count` times, as `(line, col)`. Returns `None` for a key
+    /// that isn't one of the motions an operator can combine with.
+    fn resolve_charwise_motion(&self, motion: char, count: usize) -> Option<(usize, usize)> {
+        let count = count.max(1);
+        match motion {
+            'w' => {
+                let mut idx = self.char_idx(self.cursor_line, self.cursor_col);
+                for _ in 0..count {
+                    idx = self.word_forward_char_idx(idx);
                 }
-            }
+                let line = self.buffer.char_to_line(idx);
+                let col = idx - self.buffer.line_to_char(line);
+                Some((line, col))
+            }
+            '$' => Some((self.cursor_line, self.line_len(self.cursor_line))),
+            '0' => Some((self.cursor_line, 0)),
+            'h' => Some((self.cursor_line, self.cursor_col.saturating_sub(count))),
+            'l' => Some((
+                self.cursor_line,
+                (self.cursor_col + count).min(self.line_len(self.cursor_line)),
+            )),
+            _ => None,
+        }
+    }
 
-            if !actions.is_empty() {
-                self.push_undo(EditAction::Batch { actions });
+    /// Resolve `operator × motion × count` into a range (via `selection`,
+    /// same as a manual Shift-selection) and apply it with the existing
+    /// `delete_selection`/`copy` primitives. `Change` additionally drops
+    /// the editor into `Insert` mode at the deletion point.
+    fn apply_operator_motion(&mut self, op: Operator, motion: char, count: usize) {
+        if let Some((line, col)) = self.resolve_charwise_motion(motion, count) {
+            self.selection = Some(Selection {
+                start_line: self.cursor_line,
+                start_col: self.cursor_col,
+                end_line: line,
+                end_col: col,
+            });
+            match op {
+                Operator::Yank => {
+                    self.copy();
+                    self.selection = None;
+                }
+                Operator::Delete => self.delete_selection(),
+                Operator::Change => {
+                    self.delete_selection();
+                    self.mode = EditMode::Insert;
+                }
             }
-
-            self.selection = None;
-            self.perform_find();
         }
     }
 
-    /// 줄 번호로 이동
-    pub fn goto_line(&mut self, line_str: &str) {
-        if let Ok(line_num) = line_str.parse::<usize>() {
-            if line_num > 0 && line_num <= self.lines.len() {
-                self.cursor_line = line_num - 1;
+    /// Resolve a doubled operator key (`dd`, `cc`, `yy`) linewise over
+    /// `count` lines starting at the cursor's line.
+    fn apply_operator_linewise(&mut self, op: Operator, count: usize) {
+        let range = self.linewise_range(self.cursor_line, count);
+        match op {
+            Operator::Yank => {
+                self.clipboard = self.buffer.slice(range);
+            }
+            Operator::Delete | Operator::Change => {
+                let deleted = self.buffer.slice(range.clone());
+                self.buffer.remove(range.clone());
+                self.clipboard = deleted.clone();
+                self.push_undo(EditAction::Delete {
+                    char_idx: range.start,
+                    text: deleted,
+                });
+                if self.cursor_line >= self.line_count() {
+                    self.cursor_line = self.line_count() - 1;
+                }
                 self.cursor_col = 0;
-                self.selection = None;
+                if op == Operator::Change {
+                    self.mode = EditMode::Insert;
+                }
                 self.update_scroll();
             }
         }
     }
 }
 
-pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
-    let state = match &app.editor_state {
+pub fn draw(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
+    let state = match &mut app.editor_state {
         Some(s) => s,
         None => return,
     };
@@ -1096,11 +2567,15 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     }
 
     // Header
-    let file_name = state
-        .file_path
-        .file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "New File".to_string());
+    let file_name = if app.bulk_rename_files.is_some() {
+        "Bulk Rename".to_string()
+    } else {
+        state
+            .file_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "New File".to_string())
+    };
 
     let header = Line::from(vec![
         Span::styled(
@@ -1140,16 +2615,21 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     // 선택 영역 정규화
     let selection = state.selection.as_ref().map(|s| s.normalized());
 
-    // 하이라이터
-    let mut highlighter = state.highlighter.clone();
-    if let Some(ref mut hl) = highlighter {
-        hl.reset();
-        for line in state.lines.iter().take(state.scroll) {
-            hl.tokenize_line(line);
-        }
-    }
+    let lines = state.lines();
 
-    for (i, line) in state.lines.iter().skip(state.scroll).take(content_height).enumerate() {
+    // 디스크 대비 diff 표시 (토글된 경우에만 계산)
+    let diff_tags = if state.diff_mode {
+        state.diff_against_disk()
+    } else {
+        Vec::new()
+    };
+
+    // 하이라이터: 보이는 줄의 토큰은 캐시에서 가져오고(스타일 조회용 하이라이터만 별도 보관),
+    // 스크롤 위치까지 매 프레임 재토큰화하지 않는다
+    let highlighter = state.highlighter.clone();
+    let visible_tokens = state.visible_tokens(state.scroll, content_height);
+
+    for (i, line) in lines.iter().skip(state.scroll).take(content_height).enumerate() {
         let line_num = state.scroll + i;
         let is_cursor_line = line_num == state.cursor_line;
 
@@ -1162,6 +2642,17 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             theme.dim_style()
         };
 
+        let diff_marker = match diff_tags
+            .binary_search_by_key(&line_num, |&(idx, _)| idx)
+            .ok()
+            .map(|i| diff_tags[i].1)
+        {
+            Some(DiffLineTag::Added) => Span::styled("+", Style::default().fg(theme.success)),
+            Some(DiffLineTag::Modified) => Span::styled("~", Style::default().fg(theme.warning)),
+            Some(DiffLineTag::Removed) => Span::styled("-", Style::default().fg(theme.error)),
+            None => Span::raw(" "),
+        };
+
         let line_num_span = Span::styled(format!("{:4} ", line_num + 1), line_num_style);
 
         // 라인 렌더링
@@ -1170,12 +2661,17 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             line_num,
             state,
             &selection,
-            &mut highlighter,
+            visible_tokens.get(i).map(Vec::as_slice).unwrap_or(&[]),
+            highlighter.as_ref(),
             theme,
             is_cursor_line,
         );
 
-        let mut spans = vec![line_num_span];
+        let mut spans = if state.diff_mode {
+            vec![diff_marker, line_num_span]
+        } else {
+            vec![line_num_span]
+        };
         spans.extend(content_spans);
 
         frame.render_widget(
@@ -1185,7 +2681,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     }
 
     // 스크롤바
-    let total_lines = state.lines.len();
+    let total_lines = lines.len();
     if total_lines > content_height {
         let scrollbar = Scrollbar::default()
             .orientation(ScrollbarOrientation::VerticalRight)
@@ -1221,9 +2717,29 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                     Paragraph::new(goto_line).style(theme.status_bar_style()),
                     Rect::new(inner.x, footer_y, inner.width, 1),
                 );
+            } else if state.save_as_mode {
+                let save_as_line = Line::from(vec![
+                    Span::styled("Save as: ", theme.header_style()),
+                    Span::styled(&state.save_as_input, theme.normal_style()),
+                    Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+                ]);
+                frame.render_widget(
+                    Paragraph::new(save_as_line).style(theme.status_bar_style()),
+                    Rect::new(inner.x, footer_y, inner.width, 1),
+                );
             } else {
                 let mut footer_spans = vec![];
 
+                if state.vim_mode {
+                    let mode_label = match state.mode {
+                        EditMode::Normal => "-- NORMAL -- ",
+                        EditMode::Insert => "-- INSERT -- ",
+                        EditMode::Visual => "-- VISUAL -- ",
+                        EditMode::VisualLine => "-- VISUAL LINE -- ",
+                    };
+                    footer_spans.push(Span::styled(mode_label, theme.header_style()));
+                }
+
                 if state.modified {
                     footer_spans.push(Span::styled("Modified ", theme.warning_style()));
                 }
@@ -1254,10 +2770,12 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         }
         FindReplaceMode::Find | FindReplaceMode::Replace => {
             let find_opts = format!(
-                "[{}{}{}]",
+                "[{}{}{}{}{}]",
                 if state.find_options.case_sensitive { "Aa" } else { "aa" },
                 if state.find_options.use_regex { " Re" } else { "" },
-                if state.find_options.whole_word { " W" } else { "" }
+                if state.find_options.whole_word { " W" } else { "" },
+                if state.find_options.fuzzy { " Fz" } else { "" },
+                if state.find_options.composite { " Bool" } else { "" }
             );
 
             let match_info = if !state.match_positions.is_empty() {
@@ -1299,6 +2817,11 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                 if state.input_focus == 1 {
                     spans.push(Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)));
                 }
+
+                if let Some(preview) = state.replacement_preview() {
+                    spans.push(Span::styled(" → ", theme.dim_style()));
+                    spans.push(Span::styled(preview, theme.success_style()));
+                }
             }
 
             spans.push(Span::styled(match_info, theme.success_style()));
@@ -1318,7 +2841,8 @@ fn render_editor_line(
     line_num: usize,
     state: &EditorState,
     selection: &Option<(usize, usize, usize, usize)>,
-    highlighter: &mut Option<SyntaxHighlighter>,
+    tokens: &[Token],
+    highlighter: Option<&SyntaxHighlighter>,
     theme: &Theme,
     is_cursor_line: bool,
 ) -> Vec<Span<'static>> {
@@ -1338,14 +2862,28 @@ fn render_editor_line(
         None
     };
 
-    // 문법 강조 토큰 가져오기
-    let tokens = if let Some(ref mut hl) = highlighter {
-        hl.tokenize_line(line)
-    } else {
-        vec![]
-    };
-
-    // 토큰이 있으면 토큰 기반 렌더링
+    // 보조 커서의 선택 영역 중 이 줄에 걸치는 것
+    let secondary_selections: Vec<(usize, usize)> = state
+        .secondary_carets
+        .iter()
+        .filter_map(|c| c.selection.map(|s| s.normalized()))
+        .filter(|(sl, _, el, _)| *sl <= line_num && line_num <= *el)
+        .map(|(sl, sc, el, ec)| {
+            let start = if line_num == sl { sc } else { 0 };
+            let end = if line_num == el { ec } else { chars.len() };
+            (start, end)
+        })
+        .collect();
+
+    // 이 줄에 있는 보조 커서의 열
+    let secondary_cols: Vec<usize> = state
+        .secondary_carets
+        .iter()
+        .filter(|c| c.line == line_num)
+        .map(|c| c.col)
+        .collect();
+
+    // 토큰이 있으면 토큰 기반 렌더링 (캐시에서 가져온 토큰)
     if !tokens.is_empty() {
         let mut char_idx = 0;
 
@@ -1356,8 +2894,8 @@ fn render_editor_line(
 
             for (i, c) in token_chars.iter().enumerate() {
                 let pos = token_start + i;
-                let mut style = if let Some(ref mut hl) = highlighter {
-                    hl.style_for(token.token_type)
+                let mut style = if let Some(hl) = highlighter {
+                    hl.style_for_token(token)
                 } else {
                     theme.normal_style()
                 };
@@ -1369,6 +2907,13 @@ fn render_editor_line(
                     }
                 }
 
+                // 보조 커서의 선택 영역
+                for (sel_start, sel_end) in &secondary_selections {
+                    if pos >= *sel_start && pos < *sel_end {
+                        style = style.bg(theme.bg_selected).add_modifier(Modifier::UNDERLINED);
+                    }
+                }
+
                 // 검색 매치 하이라이트
                 for (ml, ms, me) in &state.match_positions {
                     if *ml == line_num && pos >= *ms && pos < *me {
@@ -1388,6 +2933,11 @@ fn render_editor_line(
                     style = theme.selected_style();
                 }
 
+                // 보조 커서 하이라이트 (선택 영역 유무와 관계없이 커서 칸을 칠한다)
+                if secondary_cols.contains(&pos) {
+                    style = Style::default().bg(theme.info).fg(Color::Black);
+                }
+
                 spans.push(Span::styled(c.to_string(), style));
             }
 
@@ -1398,6 +2948,11 @@ fn render_editor_line(
         if is_cursor_line && state.cursor_col >= chars.len() && state.selection.is_none() {
             spans.push(Span::styled(" ", theme.selected_style()));
         }
+        for caret in state.secondary_carets.iter().filter(|c| c.line == line_num) {
+            if caret.col >= chars.len() {
+                spans.push(Span::styled(" ", Style::default().bg(theme.info)));
+            }
+        }
     } else {
         // 토큰 없이 문자 단위 렌더링
         for (i, c) in chars.iter().enumerate() {
@@ -1410,6 +2965,13 @@ fn render_editor_line(
                 }
             }
 
+            // 보조 커서의 선택 영역
+            for (sel_start, sel_end) in &secondary_selections {
+                if i >= *sel_start && i < *sel_end {
+                    style = style.bg(theme.bg_selected).add_modifier(Modifier::UNDERLINED);
+                }
+            }
+
             // 검색 매치
             for (ml, ms, me) in &state.match_positions {
                 if *ml == line_num && i >= *ms && i < *me {
@@ -1429,6 +2991,11 @@ fn render_editor_line(
                 style = theme.selected_style();
             }
 
+            // 보조 커서
+            if secondary_cols.contains(&i) {
+                style = Style::default().bg(theme.info).fg(Color::Black);
+            }
+
             spans.push(Span::styled(c.to_string(), style));
         }
 
@@ -1436,12 +3003,23 @@ fn render_editor_line(
         if is_cursor_line && state.cursor_col >= chars.len() && state.selection.is_none() {
             spans.push(Span::styled(" ", theme.selected_style()));
         }
+        for caret in state.secondary_carets.iter().filter(|c| c.line == line_num) {
+            if caret.col >= chars.len() {
+                spans.push(Span::styled(" ", Style::default().bg(theme.info)));
+            }
+        }
     }
 
     if spans.is_empty() {
         // 빈 줄에 커서 표시
         if is_cursor_line && state.selection.is_none() {
             spans.push(Span::styled(" ", theme.selected_style()));
+        } else if state
+            .secondary_carets
+            .iter()
+            .any(|c| c.line == line_num && c.col == 0)
+        {
+            spans.push(Span::styled(" ", Style::default().bg(theme.info)));
         } else {
             spans.push(Span::styled(" ", theme.normal_style()));
         }
@@ -1450,6 +3028,163 @@ fn render_editor_line(
     spans
 }
 
+/// Vim `Normal`/`Visual`/`Visual-Line` key handling. Returns `true` if the
+/// key was consumed by the modal layer; `false` lets `handle_input` fall
+/// through to the regular Ctrl-shortcut / movement handling below it.
+fn handle_vim_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> bool {
+    let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+    let state = match &mut app.editor_state {
+        Some(s) => s,
+        None => return false,
+    };
+
+    // Vim 자체의 Ctrl-R(redo)만 여기서 처리하고, 나머지 Ctrl 단축키는
+    // handle_input의 일반 처리로 넘긴다
+    if ctrl && code != KeyCode::Char('r') {
+        return false;
+    }
+
+    // 숫자 카운트 누적 (앞에 0이 오면 "줄 시작"의 0이므로 제외)
+    if let KeyCode::Char(c) = code {
+        if c.is_ascii_digit() && (c != '0' || state.count != 0) && !ctrl {
+            state.count = state.count * 10 + c.to_digit(10).unwrap() as usize;
+            return true;
+        }
+    }
+    let count = if state.count == 0 { 1 } else { state.count };
+
+    // 연산자 대기 중이면 다음 입력을 모션으로 해석한다
+    if let Some(op) = state.pending_operator.take() {
+        match code {
+            KeyCode::Char('d') if op == Operator::Delete => state.apply_operator_linewise(op, count),
+            KeyCode::Char('c') if op == Operator::Change => state.apply_operator_linewise(op, count),
+            KeyCode::Char('y') if op == Operator::Yank => state.apply_operator_linewise(op, count),
+            KeyCode::Char(m @ ('w' | '$' | '0' | 'h' | 'l')) => state.apply_operator_motion(op, m, count),
+            KeyCode::Esc => {}
+            _ => {}
+        }
+        state.count = 0;
+        return true;
+    }
+
+    if state.mode == EditMode::Visual || state.mode == EditMode::VisualLine {
+        match code {
+            KeyCode::Char('h') => state.move_cursor(0, -(count as i32), true),
+            KeyCode::Char('l') => state.move_cursor(0, count as i32, true),
+            KeyCode::Char('j') => state.move_cursor(count as i32, 0, true),
+            KeyCode::Char('k') => state.move_cursor(-(count as i32), 0, true),
+            KeyCode::Char('0') => state.move_to_line_start(true),
+            KeyCode::Char('$') => state.move_to_line_end(true),
+            KeyCode::Char('d') | KeyCode::Char('x') => {
+                state.delete_selection();
+                state.mode = EditMode::Normal;
+            }
+            KeyCode::Char('c') => {
+                state.delete_selection();
+                state.mode = EditMode::Insert;
+            }
+            KeyCode::Char('y') => {
+                state.copy();
+                state.selection = None;
+                state.mode = EditMode::Normal;
+            }
+            KeyCode::Esc => {
+                state.selection = None;
+                state.mode = EditMode::Normal;
+            }
+            _ => {
+                state.count = 0;
+                return false;
+            }
+        }
+        state.count = 0;
+        return true;
+    }
+
+    // Normal 모드
+    match code {
+        KeyCode::Char('h') => state.move_cursor(0, -(count as i32), false),
+        KeyCode::Char('l') => state.move_cursor(0, count as i32, false),
+        KeyCode::Char('j') => state.move_cursor(count as i32, 0, false),
+        KeyCode::Char('k') => state.move_cursor(-(count as i32), 0, false),
+        KeyCode::Char('0') => state.move_to_line_start(false),
+        KeyCode::Char('$') => state.move_to_line_end(false),
+        KeyCode::Char('w') => {
+            if let Some((line, col)) = state.resolve_charwise_motion('w', count) {
+                state.cursor_line = line;
+                state.cursor_col = col;
+                state.update_scroll();
+            }
+        }
+        KeyCode::Char('i') => state.mode = EditMode::Insert,
+        KeyCode::Char('a') => {
+            state.move_cursor(0, 1, false);
+            state.mode = EditMode::Insert;
+        }
+        KeyCode::Char('o') => {
+            state.move_to_line_end(false);
+            state.insert_newline();
+            state.mode = EditMode::Insert;
+        }
+        KeyCode::Char('O') => {
+            state.move_to_line_start(false);
+            state.insert_newline();
+            state.move_cursor(-1, 0, false);
+            let line_len = state.line_len(state.cursor_line);
+            state.cursor_col = line_len;
+            state.mode = EditMode::Insert;
+        }
+        KeyCode::Char('v') => {
+            state.selection = Some(Selection::new(state.cursor_line, state.cursor_col));
+            state.mode = EditMode::Visual;
+        }
+        KeyCode::Char('V') => {
+            let line_len = state.line_len(state.cursor_line);
+            state.selection = Some(Selection {
+                start_line: state.cursor_line,
+                start_col: 0,
+                end_line: state.cursor_line,
+                end_col: line_len,
+            });
+            state.mode = EditMode::VisualLine;
+        }
+        // 연산자는 뒤따르는 모션이 count를 읽어야 하므로 여기서 리셋하지 않는다
+        KeyCode::Char('d') => {
+            state.pending_operator = Some(Operator::Delete);
+            return true;
+        }
+        KeyCode::Char('c') => {
+            state.pending_operator = Some(Operator::Change);
+            return true;
+        }
+        KeyCode::Char('y') => {
+            state.pending_operator = Some(Operator::Yank);
+            return true;
+        }
+        KeyCode::Char('p') => state.paste(),
+        KeyCode::Char('u') => state.undo(),
+        KeyCode::Char('r') if ctrl => state.redo(),
+        KeyCode::Esc => {
+            state.count = 0;
+            return false;
+        }
+        _ => {
+            state.count = 0;
+            return false;
+        }
+    }
+    state.count = 0;
+    true
+}
+
+/// Dispatches on the full `(KeyCode, KeyModifiers)` pair rather than one
+/// modifier at a time, so combinations like Ctrl+Alt+Left (word nav) and
+/// Ctrl+Shift+Up (extend selection by block) resolve to their own branch
+/// instead of being shadowed by a single-modifier match. Whether the
+/// terminal actually reports those combinations (key release/repeat,
+/// Ctrl+Alt at all) depends on the kitty keyboard protocol enhancement
+/// flags being pushed at startup, which is the terminal setup's job, not
+/// this dispatcher's — it just matches whatever modifiers arrive.
 pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
     let state = match &mut app.editor_state {
         Some(s) => s,
@@ -1471,7 +3206,7 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             KeyCode::Backspace => {
                 state.goto_input.pop();
             }
-            KeyCode::Char(c) if c.is_ascii_digit() => {
+            KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, ':' | '%' | '+' | '-') => {
                 state.goto_input.push(c);
             }
             _ => {}
@@ -1479,6 +3214,50 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
         return;
     }
 
+    // Save As 모드
+    if state.save_as_mode {
+        match code {
+            KeyCode::Esc => {
+                state.save_as_mode = false;
+                state.save_as_input.clear();
+            }
+            KeyCode::Enter => {
+                let input = state.save_as_input.clone();
+                state.save_as_mode = false;
+                state.save_as_input.clear();
+                if !input.is_empty() {
+                    let new_path = PathBuf::from(&input);
+                    let new_path = if new_path.is_relative() {
+                        state
+                            .file_path
+                            .parent()
+                            .map(|dir| dir.join(&new_path))
+                            .unwrap_or(new_path)
+                    } else {
+                        new_path
+                    };
+                    match state.save_file_as(new_path.clone()) {
+                        Ok(_) => {
+                            app.show_message(&format!("Saved as {}", new_path.display()));
+                            app.refresh_panels();
+                        }
+                        Err(e) => {
+                            app.show_message(&format!("Save error: {}", e));
+                        }
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                state.save_as_input.pop();
+            }
+            KeyCode::Char(c) => {
+                state.save_as_input.push(c);
+            }
+            _ => {}
+        }
+        return;
+    }
+
     // Find/Replace 모드
     if state.find_mode != FindReplaceMode::None {
         match code {
@@ -1513,6 +3292,12 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             KeyCode::Char('w') if modifiers.contains(KeyModifiers::CONTROL) => {
                 state.find_options.whole_word = !state.find_options.whole_word;
             }
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                state.find_options.fuzzy = !state.find_options.fuzzy;
+            }
+            KeyCode::Char('b') if modifiers.contains(KeyModifiers::CONTROL) => {
+                state.find_options.composite = !state.find_options.composite;
+            }
             KeyCode::Char('a') if modifiers.contains(KeyModifiers::CONTROL) => {
                 // 모두 바꾸기
                 if state.find_mode == FindReplaceMode::Replace {
@@ -1526,6 +3311,11 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
                 state.find_prev();
             }
+            KeyCode::Char('l') if modifiers.contains(KeyModifiers::CONTROL) => {
+                // 찾은 모든 위치에 멀티 커서 생성
+                state.select_all_occurrences();
+                state.find_mode = FindReplaceMode::None;
+            }
             KeyCode::Char(c) => {
                 if state.input_focus == 0 {
                     state.find_input.push(c);
@@ -1544,10 +3334,54 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
         return;
     }
 
+    // Vim 모드: Normal/Visual일 때는 hjkl 등 모달 키가 우선이고, Ctrl
+    // 단축키(저장/종료/undo 등)는 아래로 그대로 통과한다.
+    if app.editor_state.as_ref().is_some_and(|s| s.vim_mode && s.mode != EditMode::Insert) {
+        if handle_vim_key(app, code, modifiers) {
+            return;
+        }
+    } else if app.editor_state.as_ref().is_some_and(|s| s.vim_mode && s.mode == EditMode::Insert)
+        && code == KeyCode::Esc
+        && !modifiers.contains(KeyModifiers::CONTROL)
+    {
+        app.editor_state.as_mut().unwrap().mode = EditMode::Normal;
+        return;
+    }
+
+    let state = match &mut app.editor_state {
+        Some(s) => s,
+        None => return,
+    };
+
+    // Ctrl+Alt+D는 아래 Ctrl 조합의 Ctrl+D(줄 복제)에 가려지지 않도록 먼저 처리한다
+    if modifiers.contains(KeyModifiers::CONTROL)
+        && modifiers.contains(KeyModifiers::ALT)
+        && code == KeyCode::Char('d')
+    {
+        state.add_caret_at_next_match();
+        return;
+    }
+
     // Ctrl 조합
     if modifiers.contains(KeyModifiers::CONTROL) {
         match code {
+            KeyCode::Char('s') | KeyCode::Char('S') if modifiers.contains(KeyModifiers::SHIFT) => {
+                if app.bulk_rename_files.is_some() {
+                    return;
+                }
+                state.save_as_mode = true;
+                state.save_as_input = state
+                    .file_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                return;
+            }
             KeyCode::Char('s') => {
+                if app.bulk_rename_files.is_some() {
+                    app.execute_bulk_rename();
+                    return;
+                }
                 match state.save_file() {
                     Ok(_) => {
                         app.show_message("File saved!");
@@ -1560,7 +3394,10 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
                 return;
             }
             KeyCode::Char('q') => {
-                if !state.modified {
+                if app.bulk_rename_files.is_some() {
+                    app.bulk_rename_files = None;
+                    app.current_screen = Screen::DualPanel;
+                } else if !state.modified {
                     app.current_screen = Screen::DualPanel;
                 } else {
                     app.show_message("Unsaved changes! ^S to save, ^X to discard");
@@ -1568,6 +3405,7 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
                 return;
             }
             KeyCode::Char('x') => {
+                app.bulk_rename_files = None;
                 // Discard changes - go back to previous screen (viewer) or dual panel
                 if let Some(Screen::FileViewer) = app.previous_screen {
                     // 에디터의 커서 위치를 뷰어에 전달
@@ -1634,8 +3472,8 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             }
             KeyCode::End => {
                 // 파일 끝으로
-                state.cursor_line = state.lines.len().saturating_sub(1);
-                state.cursor_col = state.lines[state.cursor_line].chars().count();
+                state.cursor_line = state.line_count().saturating_sub(1);
+                state.cursor_col = state.line_len(state.cursor_line);
                 state.selection = None;
                 state.update_scroll();
                 return;
@@ -1644,6 +3482,85 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
         }
     }
 
+    // Ctrl+Alt 조합: 멀티 커서 추가
+    if modifiers.contains(KeyModifiers::CONTROL) && modifiers.contains(KeyModifiers::ALT) {
+        match code {
+            KeyCode::Down => {
+                state.add_caret_below();
+                return;
+            }
+            KeyCode::Up => {
+                state.add_caret_above();
+                return;
+            }
+            KeyCode::Char('d') => {
+                state.add_caret_at_next_match();
+                return;
+            }
+            KeyCode::Left => {
+                state.move_word(false, false);
+                return;
+            }
+            KeyCode::Right => {
+                state.move_word(true, false);
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    // Ctrl+Shift 조합: 블록(문단) 단위로 선택 확장
+    if modifiers.contains(KeyModifiers::CONTROL)
+        && modifiers.contains(KeyModifiers::SHIFT)
+        && !modifiers.contains(KeyModifiers::ALT)
+    {
+        match code {
+            KeyCode::Up => {
+                state.extend_selection_by_block(false);
+                return;
+            }
+            KeyCode::Down => {
+                state.extend_selection_by_block(true);
+                return;
+            }
+            KeyCode::Left => {
+                state.move_word(false, true);
+                return;
+            }
+            KeyCode::Right => {
+                state.move_word(true, true);
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    // Alt+Shift 조합: 열(컬럼/블록) 선택 확장
+    if modifiers.contains(KeyModifiers::ALT)
+        && modifiers.contains(KeyModifiers::SHIFT)
+        && !modifiers.contains(KeyModifiers::CONTROL)
+    {
+        match code {
+            KeyCode::Up => {
+                state.extend_block_selection(-1, 0);
+                return;
+            }
+            KeyCode::Down => {
+                state.extend_block_selection(1, 0);
+                return;
+            }
+            KeyCode::Left => {
+                state.extend_block_selection(0, -1);
+                return;
+            }
+            KeyCode::Right => {
+                state.extend_block_selection(0, 1);
+                return;
+            }
+            _ => {}
+        }
+    }
+
     // Alt 조합
     if modifiers.contains(KeyModifiers::ALT) {
         match code {
@@ -1655,6 +3572,26 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
                 state.move_line_down();
                 return;
             }
+            KeyCode::Char('g') => {
+                state.diff_mode = !state.diff_mode;
+                return;
+            }
+            KeyCode::Char('v') => {
+                // Vim 모드 토글: 끌 때는 보통의 insert-always 키 입력으로 되돌아간다
+                state.vim_mode = !state.vim_mode;
+                state.mode = EditMode::Insert;
+                state.count = 0;
+                state.pending_operator = None;
+                return;
+            }
+            KeyCode::Char('n') if state.diff_mode => {
+                state.goto_next_diff_hunk();
+                return;
+            }
+            KeyCode::Char('p') if state.diff_mode => {
+                state.goto_prev_diff_hunk();
+                return;
+            }
             _ => {}
         }
     }
@@ -1664,8 +3601,15 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
 
     match code {
         KeyCode::Esc => {
-            if state.selection.is_some() {
+            state.block_selection = false;
+            state.block_anchor = None;
+            if !state.secondary_carets.is_empty() {
+                state.secondary_carets.clear();
+            } else if state.selection.is_some() {
                 state.selection = None;
+            } else if app.bulk_rename_files.is_some() {
+                app.bulk_rename_files = None;
+                app.current_screen = Screen::DualPanel;
             } else if state.modified {
                 app.show_message("Unsaved changes! ^S to save, ^X to discard");
             } else {
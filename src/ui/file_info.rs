@@ -1,4 +1,8 @@
 use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
 use crossterm::event::KeyCode;
 use ratatui::{
     layout::Rect,
@@ -8,12 +12,162 @@ use ratatui::{
 };
 
 use super::{app::{App, Screen}, theme::Theme};
-use crate::utils::format::{format_size, format_permissions};
+use crate::services::dir_stats::{calculate_dir_stats, DirStats};
+use crate::services::metadata::{self, ExifInfo, FileHashes};
+use crate::utils::format::{format_bytes_exact, format_permissions, format_size_with_unit};
+
+/// Result of the background work `FileInfoState` kicks off: a size/file/dir
+/// count update for directories (`done` distinguishes a streamed partial
+/// total from the final one), or hashes/EXIF for files.
+enum InfoMessage {
+    DirectoryStats { stats: DirStats, done: bool },
+    FileDetails { exif: Option<ExifInfo>, hashes: Option<FileHashes> },
+}
+
+/// Async state backing the file-info screen. Directory size and per-file
+/// hashing/EXIF extraction can be slow on large trees/files, so both run on
+/// a background thread and get polled once per frame, mirroring
+/// `FileOperationProgress`. A directory's size walk streams partial totals
+/// as it goes, so the dialog's numbers climb instead of sitting on
+/// "Calculating..." until the whole tree is done.
+pub struct FileInfoState {
+    receiver: Option<Receiver<InfoMessage>>,
+    pub calculating: bool,
+
+    pub dir_size: Option<u64>,
+    pub dir_file_count: Option<usize>,
+    pub dir_dir_count: Option<usize>,
+    /// Set for exactly the poll that received the final (non-partial)
+    /// directory stats, so the caller knows to cache the result instead of
+    /// re-inserting it every frame afterward.
+    pub dir_stats_just_completed: bool,
+
+    pub exif: Option<ExifInfo>,
+    pub hashes: Option<FileHashes>,
+}
+
+impl FileInfoState {
+    pub fn new() -> Self {
+        Self {
+            receiver: None,
+            calculating: false,
+            dir_size: None,
+            dir_file_count: None,
+            dir_dir_count: None,
+            dir_stats_just_completed: false,
+            exif: None,
+            hashes: None,
+        }
+    }
+
+    /// Start recursively sizing a directory on a background thread,
+    /// streaming partial totals every [`PROGRESS_BATCH`][crate::services::dir_stats]
+    /// entries so the dialog doesn't sit frozen on "Calculating..." for a
+    /// large tree.
+    pub fn start_calculation(&mut self, path: &Path) {
+        let (tx, rx) = mpsc::channel();
+        let path = path.to_path_buf();
+        let cancel_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let result = calculate_dir_stats(&path, &cancel_flag, |stats| {
+                let _ = progress_tx.send(InfoMessage::DirectoryStats { stats, done: false });
+            });
+            if let Ok(stats) = result {
+                let _ = tx.send(InfoMessage::DirectoryStats { stats, done: true });
+            }
+        });
+
+        self.receiver = Some(rx);
+        self.calculating = true;
+    }
+
+    /// Populate directory stats straight from a path+mtime cache hit,
+    /// skipping the background walk entirely so reopening the dialog on an
+    /// unchanged directory is instant.
+    pub fn set_cached_stats(&mut self, stats: DirStats) {
+        self.dir_size = Some(stats.total_bytes);
+        self.dir_file_count = Some(stats.file_count);
+        self.dir_dir_count = Some(stats.dir_count);
+        self.calculating = false;
+    }
+
+    /// Start EXIF extraction and content hashing for a file on a background
+    /// thread.
+    pub fn start_file_analysis(&mut self, path: &Path) {
+        let (tx, rx) = mpsc::channel();
+        let path = path.to_path_buf();
+
+        thread::spawn(move || {
+            let exif = metadata::read_exif(&path);
+            let hashes = metadata::compute_hashes(&path).ok();
+            let _ = tx.send(InfoMessage::FileDetails { exif, hashes });
+        });
+
+        self.receiver = Some(rx);
+        self.calculating = true;
+    }
+
+    /// Drain every pending result. Returns true if still calculating.
+    /// Directory stats can queue up several partial updates per frame, so
+    /// this drains the whole backlog rather than taking one message at a
+    /// time, keeping the dialog's numbers from lagging behind the walker.
+    pub fn poll(&mut self) -> bool {
+        if !self.calculating {
+            return false;
+        }
+
+        self.dir_stats_just_completed = false;
+
+        if let Some(ref receiver) = self.receiver {
+            while let Ok(msg) = receiver.try_recv() {
+                match msg {
+                    InfoMessage::DirectoryStats { stats, done } => {
+                        self.dir_size = Some(stats.total_bytes);
+                        self.dir_file_count = Some(stats.file_count);
+                        self.dir_dir_count = Some(stats.dir_count);
+                        if done {
+                            self.calculating = false;
+                            self.dir_stats_just_completed = true;
+                        }
+                    }
+                    InfoMessage::FileDetails { exif, hashes } => {
+                        self.exif = exif;
+                        self.hashes = hashes;
+                        self.calculating = false;
+                    }
+                }
+            }
+        }
+
+        self.calculating
+    }
+}
 
 pub fn draw(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
     // Draw dual panel in background first
     super::draw::draw_dual_panel_background(frame, app, area, theme);
 
+    // Drain any background directory-size / EXIF-and-hash result, and cache
+    // a freshly completed directory walk so reopening this dialog on the
+    // same, unmodified directory is instant next time.
+    if let Some(ref mut state) = app.file_info_state {
+        state.poll();
+        if state.dir_stats_just_completed {
+            if let (Some(total_bytes), Some(file_count), Some(dir_count)) =
+                (state.dir_size, state.dir_file_count, state.dir_dir_count)
+            {
+                if let Ok(mtime) = fs::metadata(&app.info_file_path).and_then(|m| m.modified()) {
+                    app.dir_stats_cache.insert(
+                        app.info_file_path.clone(),
+                        (mtime, DirStats { total_bytes, file_count, dir_count }),
+                    );
+                }
+            }
+        }
+    }
+
     // Build content first to calculate required height
     let path = &app.info_file_path;
     let metadata = fs::metadata(path);
@@ -36,7 +190,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
             "File"
         };
         lines.push(info_line("Type", file_type, theme));
-        lines.push(info_line("Size", &format_size(meta.len()), theme));
+        lines.push(info_line("Size", &size_with_exact_bytes(meta.len(), theme), theme));
 
         #[cfg(unix)]
         {
@@ -72,6 +226,21 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
                 lines.push(Line::from(Span::raw("")));
                 lines.push(info_line("Items", &count.to_string(), theme));
             }
+
+            lines.push(match &app.file_info_state {
+                Some(state) if state.calculating => info_line("Total Size", "Calculating...", theme),
+                Some(state) => match (state.dir_size, state.dir_file_count, state.dir_dir_count) {
+                    (Some(size), Some(files), Some(dirs)) => info_line(
+                        "Total Size",
+                        &format!("{} ({} files, {} dirs)", size_with_exact_bytes(size, theme), files, dirs),
+                        theme,
+                    ),
+                    _ => info_line("Total Size", "Unknown", theme),
+                },
+                None => info_line("Total Size", "Unknown", theme),
+            });
+        } else {
+            push_file_details(&mut lines, app.file_info_state.as_ref(), theme);
         }
     } else {
         lines.push(Line::from(Span::styled(
@@ -116,6 +285,17 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
     frame.render_widget(paragraph, inner);
 }
 
+/// Human-readable size alongside the exact byte count, e.g.
+/// `1.4 GiB (1,503,238,553 bytes)`, since the human form alone loses
+/// precision that matters for this dialog.
+fn size_with_exact_bytes(bytes: u64, theme: &Theme) -> String {
+    format!(
+        "{} ({} bytes)",
+        format_size_with_unit(bytes, theme.size_unit),
+        format_bytes_exact(bytes)
+    )
+}
+
 fn info_line<'a>(label: &str, value: &str, theme: &Theme) -> Line<'a> {
     Line::from(vec![
         Span::styled(format!("{:12}", label), theme.dim_style()),
@@ -123,6 +303,47 @@ fn info_line<'a>(label: &str, value: &str, theme: &Theme) -> Line<'a> {
     ])
 }
 
+/// Append EXIF and content-hash rows for a regular file. Falls back to
+/// "Calculating..." while the background thread is still running and omits
+/// EXIF entirely when the file carries none.
+fn push_file_details(lines: &mut Vec<Line>, state: Option<&FileInfoState>, theme: &Theme) {
+    let state = match state {
+        Some(s) => s,
+        None => return,
+    };
+
+    if state.calculating {
+        lines.push(Line::from(Span::raw("")));
+        lines.push(info_line("Hashes", "Calculating...", theme));
+        return;
+    }
+
+    if let Some(exif) = &state.exif {
+        lines.push(Line::from(Span::raw("")));
+        if let Some(model) = &exif.camera_model {
+            lines.push(info_line("Camera", model, theme));
+        }
+        if let Some(captured_at) = &exif.captured_at {
+            lines.push(info_line("Captured", captured_at, theme));
+        }
+        if let Some((w, h)) = exif.dimensions {
+            lines.push(info_line("Dimensions", &format!("{}x{}", w, h), theme));
+        }
+        if let Some(orientation) = exif.orientation {
+            lines.push(info_line("Orientation", &orientation.to_string(), theme));
+        }
+        if let Some((lat, lon)) = exif.gps {
+            lines.push(info_line("GPS", &format!("{:.6}, {:.6}", lat, lon), theme));
+        }
+    }
+
+    if let Some(hashes) = &state.hashes {
+        lines.push(Line::from(Span::raw("")));
+        lines.push(info_line("MD5", &hashes.md5, theme));
+        lines.push(info_line("SHA-256", &hashes.sha256, theme));
+    }
+}
+
 pub fn handle_input(app: &mut App, _code: KeyCode) {
     // Any key closes the info dialog
     app.current_screen = Screen::DualPanel;
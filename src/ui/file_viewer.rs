@@ -1,26 +1,49 @@
 use crossterm::event::{KeyCode, KeyModifiers};
+use memmap2::Mmap;
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
 
 use super::{
     app::{App, Screen},
-    syntax::{Language, SyntaxHighlighter},
+    syntax::{Language, LexerState, SyntaxHighlighter, TokenType},
     theme::Theme,
 };
 
+/// How many lines `ViewerState::highlighter_synced_to` will replay forward
+/// from the nearest cached state before giving up and rendering the target
+/// line plain. Bounds the worst case (a huge file with no cache yet, jumped
+/// to near the end via `goto`) to a fixed amount of work per frame instead
+/// of re-tokenizing from line 0.
+const MAX_HIGHLIGHT_RESCAN: usize = 2000;
+
+/// Files at or above this size skip full materialization in `load_file`:
+/// instead of reading the whole file into `lines`/`raw_bytes`, it's
+/// memory-mapped and only a line-offset index is built, so opening a huge
+/// log stays near-instant and memory stays flat.
+const LARGE_FILE_THRESHOLD: u64 = 2 * 1024 * 1024; // 2 MB
+
+/// Column count a content row is capped to when `readable_width_mode` is
+/// on, centered in the pane -- comfortable prose reading shouldn't stretch
+/// across a wide terminal.
+const READABLE_WIDTH: u16 = 100;
+
 /// 뷰어 모드
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ViewerMode {
     Text,
     Hex,
+    /// Rendered view for a file whose language resolved to
+    /// `Language::Markdown`; see `ViewerState::toggle_markdown_view` and
+    /// `crate::utils::markdown::render_markdown`.
+    Markdown,
 }
 
 /// 검색 옵션
@@ -29,10 +52,106 @@ pub struct SearchOptions {
     pub case_sensitive: bool,
     pub use_regex: bool,
     pub whole_word: bool,
+    /// Search the joined buffer instead of line-by-line, so a pattern can
+    /// match across line boundaries (e.g. `\}\s*\n\s*else`). See
+    /// `ViewerState::perform_multiline_search`.
+    pub multiline: bool,
+    /// Rank lines by in-order (not necessarily contiguous) character match instead of exact/regex
+    /// matching. See `ViewerState::perform_fuzzy_search`.
+    pub fuzzy: bool,
+}
+
+/// A leaf or boolean combination of leaves parsed out of a search term,
+/// e.g. `error & !debug` or `TODO | FIXME`. `&` binds tighter than `|`,
+/// and parentheses group explicitly. A term with no operators parses to a
+/// single `Leaf` covering the whole string, so plain literal/regex search
+/// is unchanged. See `ViewerState::eval_search_expr`.
+#[derive(Debug, Clone)]
+enum SearchExpr {
+    Leaf(String),
+    And(Box<SearchExpr>, Box<SearchExpr>),
+    Or(Box<SearchExpr>, Box<SearchExpr>),
+    Not(Box<SearchExpr>),
+}
+
+impl SearchExpr {
+    /// Recursive-descent parse of `input` into a `SearchExpr` tree.
+    fn parse(input: &str) -> SearchExpr {
+        let tokens: Vec<char> = input.chars().collect();
+        let mut pos = 0;
+        parse_or(&tokens, &mut pos)
+    }
+}
+
+fn parse_or(tokens: &[char], pos: &mut usize) -> SearchExpr {
+    let mut node = parse_and(tokens, pos);
+    loop {
+        skip_ws(tokens, pos);
+        if tokens.get(*pos) == Some(&'|') {
+            *pos += 1;
+            let rhs = parse_and(tokens, pos);
+            node = SearchExpr::Or(Box::new(node), Box::new(rhs));
+        } else {
+            break;
+        }
+    }
+    node
+}
+
+fn parse_and(tokens: &[char], pos: &mut usize) -> SearchExpr {
+    let mut node = parse_unary(tokens, pos);
+    loop {
+        skip_ws(tokens, pos);
+        if tokens.get(*pos) == Some(&'&') {
+            *pos += 1;
+            let rhs = parse_unary(tokens, pos);
+            node = SearchExpr::And(Box::new(node), Box::new(rhs));
+        } else {
+            break;
+        }
+    }
+    node
+}
+
+fn parse_unary(tokens: &[char], pos: &mut usize) -> SearchExpr {
+    skip_ws(tokens, pos);
+    if tokens.get(*pos) == Some(&'!') {
+        *pos += 1;
+        return SearchExpr::Not(Box::new(parse_unary(tokens, pos)));
+    }
+    parse_primary(tokens, pos)
+}
+
+fn parse_primary(tokens: &[char], pos: &mut usize) -> SearchExpr {
+    skip_ws(tokens, pos);
+    if tokens.get(*pos) == Some(&'(') {
+        *pos += 1;
+        let node = parse_or(tokens, pos);
+        skip_ws(tokens, pos);
+        if tokens.get(*pos) == Some(&')') {
+            *pos += 1;
+        }
+        return node;
+    }
+
+    let start = *pos;
+    while let Some(&c) = tokens.get(*pos) {
+        if matches!(c, '&' | '|' | '!' | '(' | ')') {
+            break;
+        }
+        *pos += 1;
+    }
+    let literal: String = tokens[start..*pos].iter().collect();
+    SearchExpr::Leaf(literal.trim().to_string())
+}
+
+fn skip_ws(tokens: &[char], pos: &mut usize) {
+    while matches!(tokens.get(*pos), Some(c) if c.is_whitespace()) {
+        *pos += 1;
+    }
 }
 
 /// 뷰어 상태
-#[derive(Debug)]
 pub struct ViewerState {
     pub file_path: PathBuf,
     pub lines: Vec<String>,
@@ -50,9 +169,52 @@ pub struct ViewerState {
     pub match_lines: Vec<usize>,
     pub match_positions: Vec<(usize, usize, usize)>, // (line, start, end)
     pub current_match: usize,
+    /// Fuzzy match score for each entry in `match_lines`, same order,
+    /// populated only when `search_options.fuzzy` is set. Shown next to the
+    /// `(n/N)` counter so the user can gauge match quality.
+    pub match_scores: Vec<i64>,
 
     // 북마크
-    pub bookmarks: HashSet<usize>,
+    /// Line number -> optional label, ordered by line so the picker can
+    /// list them top-to-bottom without re-sorting.
+    pub bookmarks: BTreeMap<usize, String>,
+    /// Set while capturing an optional label for a bookmark just toggled
+    /// on, mirroring `goto_mode`/`goto_input`'s input-capture pattern.
+    pub bookmark_label_mode: bool,
+    pub bookmark_label_input: String,
+    /// Line the pending label capture applies to.
+    bookmark_label_line: usize,
+
+    // 북마크 피커
+    pub bookmark_picker_mode: bool,
+    pub bookmark_picker_filter: String,
+    pub bookmark_picker_selected: usize,
+
+    // 코드 폴딩
+    /// Start lines currently collapsed. Rendering skips every line up to
+    /// (and including) `fold_regions[start]` and shows a one-line `⋯`
+    /// summary in its place.
+    pub folded: HashSet<usize>,
+    /// Foldable `(start_line, end_line_inclusive)` regions detected in the
+    /// current file, keyed by start line. Computed once in `load_file` by
+    /// merging the indentation-based and brace/heading-based detectors, not
+    /// recomputed per frame -- only `folded` (which of these are collapsed)
+    /// changes during normal use.
+    fold_regions: BTreeMap<usize, usize>,
+
+    // 거터 / 들여쓰기 가이드 / 읽기 좋은 폭
+    /// Show each row's line number relative to `scroll` (vim's
+    /// `relativenumber`) instead of its absolute value, except for `scroll`
+    /// itself which always shows the absolute number. The gutter stays the
+    /// same fixed width either way, so this never shifts content columns.
+    pub relative_line_numbers: bool,
+    /// Draw a faint `│` glyph at every other leading-whitespace column, a
+    /// non-wrap-mode-only overlay applied to the already-assembled display
+    /// spans so it can never disturb the byte offsets search highlighting
+    /// relies on.
+    pub show_indent_guides: bool,
+    /// Cap content rows to `READABLE_WIDTH` columns, centered in the pane.
+    pub readable_width_mode: bool,
 
     // Goto line
     pub goto_mode: bool,
@@ -61,6 +223,12 @@ pub struct ViewerState {
     // 문법 강조
     pub language: Language,
     pub highlighter: Option<SyntaxHighlighter>,
+    /// End-of-line lexer state cached per source line, indexed the same as
+    /// `lines`, so resuming highlighting at an arbitrary scroll position
+    /// doesn't require re-tokenizing the file from line 0. Populated
+    /// lazily by `highlighter_synced_to` as lines are visited; cleared
+    /// whenever `lines` is replaced by `load_file`.
+    highlight_cache: Vec<Option<LexerState>>,
 
     // 인코딩
     pub encoding: String,
@@ -69,6 +237,47 @@ pub struct ViewerState {
     // 파일 정보
     pub file_size: u64,
     pub total_lines: usize,
+
+    // 대용량 파일 (메모리 매핑)
+    /// Memory map backing `line_offsets` for a file at/above
+    /// `LARGE_FILE_THRESHOLD`. `None` for normal files, which load fully
+    /// into `lines`/`raw_bytes` instead.
+    mmap: Option<Mmap>,
+    /// Byte offset where each line begins, indexed by line number. Only
+    /// populated alongside `mmap`; `lines` stays empty in that case and
+    /// `draw` slices text out of the map on demand via `line_at`.
+    line_offsets: Vec<usize>,
+    /// Whether `mmap`/`line_offsets` are backing this file instead of
+    /// `lines`. Also forces syntax highlighting and word wrap off, the way
+    /// a hard styling-size cap would, to bound per-frame cost.
+    pub is_large_file: bool,
+
+    // EPUB
+    /// Whether `lines` holds a flattened EPUB rendering rather than a
+    /// normal file's text, so chapter navigation and heading emphasis
+    /// apply. Search/bookmarks/word-wrap need no special-casing since they
+    /// already just operate on `lines`.
+    pub is_epub: bool,
+    pub epub_chapters: Vec<crate::services::epub::EpubChapter>,
+    /// Indices into `lines` that came from an EPUB heading tag, rendered
+    /// in bold instead of going through the (absent, for EPUBs) syntax
+    /// highlighter.
+    pub epub_bold_lines: HashSet<usize>,
+    pub epub_toc_mode: bool,
+    pub epub_toc_selected: usize,
+}
+
+impl std::fmt::Debug for ViewerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ViewerState")
+            .field("file_path", &self.file_path)
+            .field("scroll", &self.scroll)
+            .field("mode", &self.mode)
+            .field("total_lines", &self.total_lines)
+            .field("is_large_file", &self.is_large_file)
+            .field("mmap", &self.mmap.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl ViewerState {
@@ -88,15 +297,36 @@ impl ViewerState {
             match_lines: Vec::new(),
             match_positions: Vec::new(),
             current_match: 0,
-            bookmarks: HashSet::new(),
+            match_scores: Vec::new(),
+            bookmarks: BTreeMap::new(),
+            bookmark_label_mode: false,
+            bookmark_label_input: String::new(),
+            bookmark_label_line: 0,
+            bookmark_picker_mode: false,
+            bookmark_picker_filter: String::new(),
+            bookmark_picker_selected: 0,
+            folded: HashSet::new(),
+            fold_regions: BTreeMap::new(),
+            relative_line_numbers: false,
+            show_indent_guides: false,
+            readable_width_mode: false,
             goto_mode: false,
             goto_input: String::new(),
             language: Language::Plain,
             highlighter: None,
+            highlight_cache: Vec::new(),
             encoding: "UTF-8".to_string(),
             is_binary: false,
             file_size: 0,
             total_lines: 0,
+            mmap: None,
+            line_offsets: Vec::new(),
+            is_large_file: false,
+            is_epub: false,
+            epub_chapters: Vec::new(),
+            epub_bold_lines: HashSet::new(),
+            epub_toc_mode: false,
+            epub_toc_selected: 0,
         }
     }
 
@@ -106,9 +336,37 @@ impl ViewerState {
         self.scroll = 0;
         self.horizontal_scroll = 0;
         self.bookmarks.clear();
+        self.bookmark_label_mode = false;
+        self.bookmark_label_input.clear();
+        self.bookmark_picker_mode = false;
+        self.bookmark_picker_filter.clear();
+        self.bookmark_picker_selected = 0;
         self.search_term.clear();
         self.match_lines.clear();
         self.match_positions.clear();
+        self.match_scores.clear();
+        self.lines = Vec::new();
+        self.raw_bytes = Vec::new();
+        self.mmap = None;
+        self.line_offsets.clear();
+        self.is_large_file = false;
+        self.highlight_cache.clear();
+        self.is_epub = false;
+        self.epub_chapters.clear();
+        self.epub_bold_lines.clear();
+        self.epub_toc_mode = false;
+        self.epub_toc_selected = 0;
+        self.folded.clear();
+        self.fold_regions.clear();
+
+        if path.extension().and_then(|e| e.to_str()).map(|e| e.eq_ignore_ascii_case("epub")).unwrap_or(false) {
+            return self.load_epub(path);
+        }
+
+        let metadata_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if metadata_size >= LARGE_FILE_THRESHOLD && self.load_large_file(path)? {
+            return Ok(());
+        }
 
         // 파일 읽기
         let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
@@ -144,15 +402,220 @@ impl ViewerState {
 
         self.total_lines = self.lines.len();
 
-        // 언어 감지 및 하이라이터 초기화
-        self.language = Language::from_extension(path);
+        // 언어 감지 및 하이라이터 초기화 (languages.toml의 커스텀 언어 포함)
+        self.language = Language::resolve_with_custom(
+            path,
+            &crate::services::custom_languages::CustomLanguages::load(),
+        );
         if !self.is_binary {
             self.highlighter = Some(SyntaxHighlighter::new(self.language));
+            self.highlight_cache = vec![None; self.lines.len()];
+        }
+
+        if matches!(self.language, Language::Markdown) && !self.is_binary {
+            self.mode = ViewerMode::Markdown;
         }
 
+        if !self.is_binary {
+            self.fold_regions = self.compute_fold_regions();
+        }
+
+        Ok(())
+    }
+
+    /// Memory-map `path` and index line-start offsets instead of
+    /// materializing every line, for a file at/above `LARGE_FILE_THRESHOLD`.
+    /// Returns `Ok(true)` once this has fully populated viewer state;
+    /// `Ok(false)` means the file turned out to be binary, so the caller
+    /// should fall back to the normal full-read/hex-view path instead.
+    fn load_large_file(&mut self, path: &PathBuf) -> Result<bool, String> {
+        let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|e| e.to_string())?;
+
+        if self.detect_binary(&mmap[..mmap.len().min(8192)]) {
+            return Ok(false);
+        }
+
+        let mut offsets = vec![0usize];
+        for (i, &b) in mmap.iter().enumerate() {
+            if b == b'\n' && i + 1 < mmap.len() {
+                offsets.push(i + 1);
+            }
+        }
+
+        self.file_size = mmap.len() as u64;
+        self.mode = ViewerMode::Text;
+        self.encoding = "UTF-8".to_string();
+        self.is_binary = false;
+        self.total_lines = offsets.len();
+        self.line_offsets = offsets;
+
+        self.language = Language::resolve_with_custom(
+            path,
+            &crate::services::custom_languages::CustomLanguages::load(),
+        );
+        // A hard cap on styling cost for huge files, same idea as skipping
+        // syntax highlighting: word wrap needs every visible line pre-split
+        // up front, which is fine per-row but not worth the complexity here.
+        self.word_wrap = false;
+        self.highlighter = None;
+
+        self.mmap = Some(mmap);
+        self.is_large_file = true;
+
+        Ok(true)
+    }
+
+    /// Load an `.epub` archive, flattening its chapters into `self.lines` in
+    /// spine order with per-chapter starting line numbers recorded in
+    /// `epub_chapters`. Search, bookmarks, and word-wrap then work over
+    /// `lines` exactly as they already do for a normal text file -- only
+    /// chapter navigation and heading emphasis need to know this came from
+    /// an EPUB.
+    fn load_epub(&mut self, path: &PathBuf) -> Result<(), String> {
+        let book = crate::services::epub::load_epub(path)?;
+
+        self.file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        self.mode = ViewerMode::Text;
+        self.encoding = "UTF-8".to_string();
+        self.is_binary = false;
+        self.word_wrap = true;
+        self.language = Language::Plain;
+        self.highlighter = None;
+
+        self.lines = book.lines;
+        self.epub_chapters = book.chapters;
+        self.epub_bold_lines = book.bold_lines;
+        self.is_epub = true;
+        self.total_lines = self.lines.len();
+
         Ok(())
     }
 
+    /// Index into `epub_chapters` of the chapter the current scroll position
+    /// is inside -- the last chapter whose `start_line` is at or before
+    /// `scroll`. A no-op concept outside EPUB mode, so callers check
+    /// `is_epub` first.
+    fn current_epub_chapter(&self) -> usize {
+        self.epub_chapters
+            .iter()
+            .rposition(|c| c.start_line <= self.scroll)
+            .unwrap_or(0)
+    }
+
+    /// Jump to the start of the next chapter, if any.
+    pub fn next_epub_chapter(&mut self) {
+        if !self.is_epub {
+            return;
+        }
+        let next = self.current_epub_chapter() + 1;
+        if let Some(chapter) = self.epub_chapters.get(next) {
+            self.scroll = chapter.start_line;
+        }
+    }
+
+    /// Jump to the start of the previous chapter, if any. Mirrors
+    /// `next_epub_chapter`, but lands on the start of the *current* chapter
+    /// first when the cursor has scrolled past its first line, the way most
+    /// e-readers' "previous chapter" behaves.
+    pub fn prev_epub_chapter(&mut self) {
+        if !self.is_epub {
+            return;
+        }
+        let current = self.current_epub_chapter();
+        let current_start = self.epub_chapters.get(current).map(|c| c.start_line).unwrap_or(0);
+        if self.scroll > current_start {
+            self.scroll = current_start;
+        } else if current > 0 {
+            self.scroll = self.epub_chapters[current - 1].start_line;
+        }
+    }
+
+    /// Open the table-of-contents overlay with the current chapter
+    /// pre-selected.
+    pub fn open_epub_toc(&mut self) {
+        if !self.is_epub || self.epub_chapters.is_empty() {
+            return;
+        }
+        self.epub_toc_selected = self.current_epub_chapter();
+        self.epub_toc_mode = true;
+    }
+
+    /// Jump to the chapter highlighted in the table-of-contents overlay and
+    /// close it.
+    pub fn jump_to_selected_epub_chapter(&mut self) {
+        if let Some(chapter) = self.epub_chapters.get(self.epub_toc_selected) {
+            self.scroll = chapter.start_line;
+        }
+        self.epub_toc_mode = false;
+    }
+
+    /// Text of line `idx`, decoding lossily. For a normal file this is just
+    /// `lines[idx]`; for a large file it's sliced directly out of the
+    /// memory map via `line_offsets`, so rendering a frame only ever copies
+    /// the handful of lines actually on screen.
+    pub fn line_at(&self, idx: usize) -> String {
+        let Some(mmap) = &self.mmap else {
+            return self.lines.get(idx).cloned().unwrap_or_default();
+        };
+        let Some(&start) = self.line_offsets.get(idx) else {
+            return String::new();
+        };
+        let end = self.line_offsets.get(idx + 1).copied().unwrap_or(mmap.len());
+        let mut slice = &mmap[start..end];
+        if slice.last() == Some(&b'\n') {
+            slice = &slice[..slice.len() - 1];
+        }
+        if slice.last() == Some(&b'\r') {
+            slice = &slice[..slice.len() - 1];
+        }
+        String::from_utf8_lossy(slice).into_owned()
+    }
+
+    /// A highlighter pre-loaded with the lexer state needed to correctly
+    /// tokenize `lines[start_line]` onward, without replaying the file from
+    /// line 0 every time. Searches `highlight_cache` backward for the
+    /// nearest line whose end-state is already known, replays forward from
+    /// there using the full source lines (caching each newly computed
+    /// end-state as it goes), and gives up -- returning `None`, so the
+    /// caller renders those lines unstyled -- if the gap to close is more
+    /// than `MAX_HIGHLIGHT_RESCAN` lines. This is what lets a block comment
+    /// opened far above the viewport still color correctly once the cache
+    /// has caught up, while bounding the one-off cost of jumping (e.g. via
+    /// `goto`) into an unvisited part of a huge file.
+    fn highlighter_synced_to(&mut self, start_line: usize) -> Option<SyntaxHighlighter> {
+        let mut hl = self.highlighter.clone()?;
+        hl.reset();
+
+        if start_line == 0 {
+            return Some(hl);
+        }
+
+        let mut resume_from = 0;
+        let mut resume_state = LexerState::default();
+        for i in (0..start_line).rev() {
+            if let Some(Some(cached)) = self.highlight_cache.get(i) {
+                resume_from = i + 1;
+                resume_state = cached.clone();
+                break;
+            }
+        }
+
+        if start_line - resume_from > MAX_HIGHLIGHT_RESCAN {
+            return None;
+        }
+
+        hl.set_lexer_state(resume_state);
+        for i in resume_from..start_line {
+            hl.tokenize_line(&self.lines[i]);
+            if let Some(slot) = self.highlight_cache.get_mut(i) {
+                *slot = Some(hl.lexer_state());
+            }
+        }
+
+        Some(hl)
+    }
+
     /// 바이너리 파일 감지
     fn detect_binary(&self, bytes: &[u8]) -> bool {
         // 처음 8KB를 검사
@@ -208,53 +671,251 @@ impl ViewerState {
     }
 
     /// 검색 수행
+    ///
+    /// Large files are rendered straight off the mmap (`line_offsets`) with
+    /// `self.lines` left empty, but every matcher below — plain, multiline
+    /// and fuzzy — scans `self.lines`. Rather than teach each of them to
+    /// read through `line_at` one line at a time (regex/fuzzy search over a
+    /// multi-GB mmap line-by-line would be far slower than the buffered
+    /// path), searching above the large-file threshold is left unsupported:
+    /// this returns with no matches, and `draw`'s footer reports the search
+    /// as unavailable instead of showing zero matches.
     pub fn perform_search(&mut self) {
         self.match_lines.clear();
         self.match_positions.clear();
+        self.match_scores.clear();
 
         if self.search_term.is_empty() {
             return;
         }
 
+        if self.is_large_file {
+            return;
+        }
+
+        if self.search_options.fuzzy {
+            self.perform_fuzzy_search();
+            self.current_match = 0;
+            self.scroll_to_current_match();
+            return;
+        }
+
+        if self.search_options.multiline {
+            // `(?s)` so `.` crosses line boundaries and `(?m)` so `^`/`$`
+            // still anchor to individual lines within the joined buffer.
+            // Multiline search runs the whole term as one pattern rather
+            // than through the composite `&`/`|`/`!` evaluator below,
+            // since a single regex already has to span the whole buffer.
+            let pattern = if self.search_options.use_regex {
+                self.search_term.clone()
+            } else {
+                regex::escape(&self.search_term)
+            };
+            let pattern = if self.search_options.whole_word {
+                format!(r"\b{}\b", pattern)
+            } else {
+                pattern
+            };
+            let mut inline_flags = String::from("sm");
+            if !self.search_options.case_sensitive {
+                inline_flags.push('i');
+            }
+            let pattern = format!("(?{}){}", inline_flags, pattern);
+
+            if let Ok(re) = Regex::new(&pattern) {
+                self.perform_multiline_search(&re);
+            }
+        } else {
+            let expr = SearchExpr::parse(&self.search_term);
+            for (line_idx, line) in self.lines.iter().enumerate() {
+                let (matched, positions) = self.eval_search_expr(&expr, line);
+                if matched {
+                    for (start, end) in positions {
+                        self.match_positions.push((line_idx, start, end));
+                    }
+                    self.match_lines.push(line_idx);
+                }
+            }
+        }
+
+        self.current_match = 0;
+        self.scroll_to_current_match();
+    }
+
+    /// Compile a single leaf's text into a `Regex`, honoring
+    /// `case_sensitive`/`use_regex`/`whole_word` the same way the plain
+    /// (non-composite) search used to build its one pattern.
+    fn build_leaf_pattern(&self, leaf_text: &str) -> Option<Regex> {
+        if leaf_text.is_empty() {
+            return None;
+        }
         let pattern = if self.search_options.use_regex {
-            self.search_term.clone()
+            leaf_text.to_string()
         } else {
-            regex::escape(&self.search_term)
+            regex::escape(leaf_text)
         };
-
         let pattern = if self.search_options.whole_word {
             format!(r"\b{}\b", pattern)
         } else {
             pattern
         };
-
-        let regex = if self.search_options.case_sensitive {
-            Regex::new(&pattern)
+        let pattern = if self.search_options.case_sensitive {
+            pattern
         } else {
-            Regex::new(&format!("(?i){}", pattern))
+            format!("(?i){}", pattern)
         };
+        Regex::new(&pattern).ok()
+    }
 
-        if let Ok(re) = regex {
-            for (line_idx, line) in self.lines.iter().enumerate() {
-                let mut has_match = false;
-                for mat in re.find_iter(line) {
-                    self.match_positions.push((line_idx, mat.start(), mat.end()));
-                    has_match = true;
+    /// Evaluate a (possibly composite) search expression against `line`,
+    /// returning whether it matched and the byte ranges to highlight.
+    /// `&`/`|` combine their children's highlight ranges; `!` always
+    /// contributes none of its own, so a negated sub-pattern that happens
+    /// to occur in the line never gets highlighted.
+    fn eval_search_expr(&self, expr: &SearchExpr, line: &str) -> (bool, Vec<(usize, usize)>) {
+        match expr {
+            SearchExpr::Leaf(text) => {
+                let Some(re) = self.build_leaf_pattern(text) else {
+                    return (false, Vec::new());
+                };
+                let positions: Vec<(usize, usize)> =
+                    re.find_iter(line).map(|m| (m.start(), m.end())).collect();
+                let matched = !positions.is_empty();
+                (matched, positions)
+            }
+            SearchExpr::And(a, b) => {
+                let (matched_a, positions_a) = self.eval_search_expr(a, line);
+                let (matched_b, positions_b) = self.eval_search_expr(b, line);
+                if matched_a && matched_b {
+                    let mut positions = positions_a;
+                    positions.extend(positions_b);
+                    (true, positions)
+                } else {
+                    (false, Vec::new())
                 }
-                if has_match {
+            }
+            SearchExpr::Or(a, b) => {
+                let (matched_a, positions_a) = self.eval_search_expr(a, line);
+                let (matched_b, positions_b) = self.eval_search_expr(b, line);
+                let mut positions = positions_a;
+                positions.extend(positions_b);
+                (matched_a || matched_b, positions)
+            }
+            SearchExpr::Not(inner) => {
+                let (matched, _) = self.eval_search_expr(inner, line);
+                (!matched, Vec::new())
+            }
+        }
+    }
+
+    /// Search-match byte ranges within `fragment`, honoring the full search
+    /// engine (`use_regex`/`case_sensitive`/`whole_word`, and `&`/`|`/`!`
+    /// composition via `eval_search_expr`) rather than a naive substring
+    /// scan. Used to highlight a word-wrapped slice of a line: unlike
+    /// `match_positions`, wrapped fragments aren't precomputed by
+    /// `perform_search` since wrapping depends on the terminal width, so
+    /// this re-runs the matcher against just the visible fragment on
+    /// every draw.
+    fn search_positions_in(&self, fragment: &str) -> Vec<(usize, usize)> {
+        if self.search_term.is_empty() {
+            return Vec::new();
+        }
+        let expr = SearchExpr::parse(&self.search_term);
+        self.eval_search_expr(&expr, fragment).1
+    }
+
+    /// Multiline variant of the search above: the regex runs once against
+    /// the whole file joined with `\n`, so a pattern can match across line
+    /// boundaries. Each match's buffer-relative byte range is translated
+    /// back into one `(line, start_col, end_col)` entry per line it
+    /// touches -- full-line spans for lines in the middle of a match -- via
+    /// a binary search over each line's starting offset in the buffer.
+    fn perform_multiline_search(&mut self, re: &Regex) {
+        if self.lines.is_empty() {
+            return;
+        }
+
+        let mut line_offsets = Vec::with_capacity(self.lines.len());
+        let mut buffer = String::new();
+        for line in &self.lines {
+            line_offsets.push(buffer.len());
+            buffer.push_str(line);
+            buffer.push('\n');
+        }
+
+        let offset_to_line = |offset: usize| -> usize {
+            match line_offsets.binary_search(&offset) {
+                Ok(idx) => idx,
+                Err(idx) => idx.saturating_sub(1),
+            }
+        };
+
+        for mat in re.find_iter(&buffer) {
+            let start_line = offset_to_line(mat.start());
+            let end_line = offset_to_line(mat.end().saturating_sub(1).max(mat.start()));
+
+            for line_idx in start_line..=end_line {
+                let line_start = line_offsets[line_idx];
+                let line_len = self.lines[line_idx].len();
+
+                let start_col = if line_idx == start_line {
+                    mat.start() - line_start
+                } else {
+                    0
+                };
+                let end_col = if line_idx == end_line {
+                    (mat.end() - line_start).min(line_len)
+                } else {
+                    line_len
+                };
+
+                self.match_positions.push((line_idx, start_col, end_col));
+                if !self.match_lines.contains(&line_idx) {
                     self.match_lines.push(line_idx);
                 }
             }
         }
+    }
 
-        self.current_match = 0;
-        self.scroll_to_current_match();
+    /// Fuzzy variant of the search above: ranks every line that contains
+    /// the search term's characters in order (not necessarily contiguous)
+    /// by relevance, using the same scorer already backing the panel's
+    /// quick-filter highlighting, rather than pulling in a separate fuzzy
+    /// matching crate for an equivalent purpose. `match_lines` and
+    /// `match_scores` end up in descending score order, so `next_match`/
+    /// `prev_match` walk best-to-worst. `match_positions` stores each
+    /// matched character's index directly (`fuzzy_match` already returns
+    /// char, not byte, indices), one entry per character, for
+    /// `highlight_search_in_line`.
+    fn perform_fuzzy_search(&mut self) {
+        let mut ranked: Vec<(i64, usize, Vec<usize>)> = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(line_idx, line)| {
+                crate::ui::advanced_search::fuzzy_match(line, &self.search_term)
+                    .map(|(score, indices)| (score, line_idx, indices))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.0.cmp(&a.0));
+
+        for (score, line_idx, indices) in ranked {
+            for char_idx in indices {
+                self.match_positions.push((line_idx, char_idx, char_idx + 1));
+            }
+            self.match_lines.push(line_idx);
+            self.match_scores.push(score);
+        }
     }
 
-    /// 현재 매치로 스크롤
+    /// 현재 매치로 스크롤. For a multiline match, `match_lines` records its
+    /// start line before any of the lines after it, so this lands on the
+    /// first line of the match rather than somewhere in its middle.
     pub fn scroll_to_current_match(&mut self) {
         if !self.match_lines.is_empty() && self.current_match < self.match_lines.len() {
             let line = self.match_lines[self.current_match];
+            self.unfold_containing(line);
             self.scroll = line.saturating_sub(5);
         }
     }
@@ -279,15 +940,29 @@ impl ViewerState {
         }
     }
 
-    /// 북마크 토글
+    /// 북마크 토글: removing an existing bookmark happens immediately, but
+    /// adding one opens `bookmark_label_mode` so the user can type an
+    /// optional label first -- see `confirm_bookmark_label`.
     pub fn toggle_bookmark(&mut self, line: usize) {
-        if self.bookmarks.contains(&line) {
+        if self.bookmarks.contains_key(&line) {
             self.bookmarks.remove(&line);
         } else {
-            self.bookmarks.insert(line);
+            self.bookmark_label_mode = true;
+            self.bookmark_label_input.clear();
+            self.bookmark_label_line = line;
         }
     }
 
+    /// Finish labeling the pending bookmark (an empty input just means no
+    /// label) and store it.
+    pub fn confirm_bookmark_label(&mut self) {
+        let line = self.bookmark_label_line;
+        let label = self.bookmark_label_input.trim().to_string();
+        self.bookmarks.insert(line, label);
+        self.bookmark_label_mode = false;
+        self.bookmark_label_input.clear();
+    }
+
     /// 다음 북마크로 이동
     pub fn goto_next_bookmark(&mut self) {
         if self.bookmarks.is_empty() {
@@ -296,17 +971,17 @@ impl ViewerState {
 
         // 현재 화면에 보이는 첫 번째 줄 기준
         let current_line = self.scroll + 5; // 화면 중앙 근처
-        let mut sorted: Vec<_> = self.bookmarks.iter().copied().collect();
-        sorted.sort();
-
-        for &bm in &sorted {
+        for &bm in self.bookmarks.keys() {
             if bm > current_line {
+                self.unfold_containing(bm);
                 self.scroll = bm.saturating_sub(5);
                 return;
             }
         }
         // 처음 북마크로 순환
-        self.scroll = sorted[0].saturating_sub(5);
+        let first = *self.bookmarks.keys().next().unwrap();
+        self.unfold_containing(first);
+        self.scroll = first.saturating_sub(5);
     }
 
     /// 이전 북마크로 이동
@@ -317,24 +992,231 @@ impl ViewerState {
 
         // 현재 화면에 보이는 첫 번째 줄 기준
         let current_line = self.scroll + 5; // 화면 중앙 근처
-        let mut sorted: Vec<_> = self.bookmarks.iter().copied().collect();
-        sorted.sort();
-        sorted.reverse();
-
-        for &bm in &sorted {
+        for &bm in self.bookmarks.keys().rev() {
             if bm < current_line {
+                self.unfold_containing(bm);
                 self.scroll = bm.saturating_sub(5);
                 return;
             }
         }
         // 마지막 북마크로 순환
-        self.scroll = sorted[0].saturating_sub(5);
+        let last = *self.bookmarks.keys().next_back().unwrap();
+        self.unfold_containing(last);
+        self.scroll = last.saturating_sub(5);
+    }
+
+    /// Open the bookmark picker with an empty filter, selecting the first
+    /// entry.
+    pub fn open_bookmark_picker(&mut self) {
+        if self.bookmarks.is_empty() {
+            return;
+        }
+        self.bookmark_picker_mode = true;
+        self.bookmark_picker_filter.clear();
+        self.bookmark_picker_selected = 0;
+    }
+
+    /// Bookmarks matching `bookmark_picker_filter` against either the label
+    /// or the bookmarked line's text, case-insensitively -- empty filter
+    /// matches everything. Ordered by line, same as `bookmarks` itself.
+    pub fn filtered_bookmarks(&self) -> Vec<(usize, &str)> {
+        let needle = self.bookmark_picker_filter.to_lowercase();
+        self.bookmarks
+            .iter()
+            .filter(|(line, label)| {
+                needle.is_empty()
+                    || label.to_lowercase().contains(&needle)
+                    || self
+                        .lines
+                        .get(**line)
+                        .map(|text| text.to_lowercase().contains(&needle))
+                        .unwrap_or(false)
+            })
+            .map(|(line, label)| (*line, label.as_str()))
+            .collect()
+    }
+
+    /// Scroll to the currently-selected entry in the filtered bookmark list
+    /// and close the picker.
+    pub fn jump_to_selected_bookmark(&mut self) {
+        if let Some((line, _)) = self.filtered_bookmarks().get(self.bookmark_picker_selected) {
+            let line = *line;
+            self.unfold_containing(line);
+            self.scroll = line;
+        }
+        self.bookmark_picker_mode = false;
+        self.bookmark_picker_filter.clear();
+    }
+
+    /// Detect every foldable region in the current file by merging the
+    /// indentation-based detector (works for any language) with the
+    /// brace/heading-based one (more precise for languages that have
+    /// braces, and for Markdown). Where both detect a fold starting on the
+    /// same line, the brace/heading region wins since it tracks real
+    /// syntax rather than guessing from whitespace. Called once from
+    /// `load_file`, not per frame.
+    fn compute_fold_regions(&self) -> BTreeMap<usize, usize> {
+        let mut regions = self.detect_indentation_folds();
+        for (start, end) in self.detect_brace_or_heading_folds() {
+            regions.insert(start, end);
+        }
+        regions
+    }
+
+    /// A line that starts a deeper-indented block folds everything below
+    /// it that stays more indented, stopping at the first line (blank
+    /// lines don't count) that returns to its indentation or shallower.
+    fn detect_indentation_folds(&self) -> BTreeMap<usize, usize> {
+        let mut regions = BTreeMap::new();
+        let indent_of = |line: &str| line.chars().take_while(|c| *c == ' ' || *c == '\t').count();
+
+        let n = self.lines.len();
+        for i in 0..n {
+            let line = &self.lines[i];
+            if line.trim().is_empty() {
+                continue;
+            }
+            let indent = indent_of(line);
+            let mut end = i;
+            let mut j = i + 1;
+            while j < n {
+                let next = &self.lines[j];
+                if next.trim().is_empty() {
+                    j += 1;
+                    continue;
+                }
+                if indent_of(next) > indent {
+                    end = j;
+                    j += 1;
+                } else {
+                    break;
+                }
+            }
+            if end > i {
+                regions.insert(i, end);
+            }
+        }
+        regions
+    }
+
+    /// Brace-matched `{ ... }` spans via the existing tokenizer for regular
+    /// code, or heading-delimited sections (a heading folds everything
+    /// until the next heading of equal-or-higher level) for Markdown. An
+    /// EPUB gets no fold regions: `load_epub` does not call
+    /// `compute_fold_regions`, so the `is_epub` case here is never reached.
+    fn detect_brace_or_heading_folds(&self) -> BTreeMap<usize, usize> {
+        if matches!(self.language, Language::Markdown) || self.is_epub {
+            return self.detect_heading_folds();
+        }
+
+        let mut regions = BTreeMap::new();
+        let Some(highlighter) = &self.highlighter else {
+            return regions;
+        };
+        let mut hl = highlighter.clone();
+        hl.reset();
+
+        let mut open_stack: Vec<usize> = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            for token in hl.tokenize_line(line) {
+                if token.token_type != TokenType::Bracket {
+                    continue;
+                }
+                match token.text.as_str() {
+                    "{" => open_stack.push(i),
+                    "}" => {
+                        if let Some(open) = open_stack.pop() {
+                            if i > open {
+                                regions.insert(open, i);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        regions
+    }
+
+    /// A line starting with `#`s folds until the next heading whose level
+    /// (number of leading `#`s) is the same or shallower, or end of file.
+    fn detect_heading_folds(&self) -> BTreeMap<usize, usize> {
+        let mut regions = BTreeMap::new();
+        let heading_level = |line: &str| {
+            let hashes = line.chars().take_while(|c| *c == '#').count();
+            if hashes > 0 && hashes <= 6 { Some(hashes) } else { None }
+        };
+
+        let n = self.lines.len();
+        for i in 0..n {
+            let Some(level) = heading_level(&self.lines[i]) else {
+                continue;
+            };
+            let mut end = i;
+            for (j, line) in self.lines.iter().enumerate().skip(i + 1) {
+                match heading_level(line) {
+                    Some(next_level) if next_level <= level => break,
+                    _ => end = j,
+                }
+            }
+            if end > i {
+                regions.insert(i, end);
+            }
+        }
+        regions
+    }
+
+    /// End line of the fold region starting at `start_line`, if any.
+    fn fold_end(&self, start_line: usize) -> Option<usize> {
+        self.fold_regions.get(&start_line).copied()
+    }
+
+    /// Toggle the fold whose start line is `self.scroll` -- consistent with
+    /// `toggle_bookmark`'s existing shorthand of treating `scroll` as "the
+    /// current line".
+    pub fn toggle_fold_at_cursor(&mut self) {
+        let line = self.scroll;
+        if self.fold_end(line).is_none() {
+            return;
+        }
+        if self.folded.contains(&line) {
+            self.folded.remove(&line);
+        } else {
+            self.folded.insert(line);
+        }
+    }
+
+    /// Collapse every foldable region in the file.
+    pub fn fold_all(&mut self) {
+        self.folded = self.fold_regions.keys().copied().collect();
+    }
+
+    /// Expand every folded region.
+    pub fn unfold_all(&mut self) {
+        self.folded.clear();
+    }
+
+    /// Expand whichever folded region, if any, has `line` somewhere inside
+    /// it (strictly after the start line, since the start line is always
+    /// visible). Used to make sure a search match, bookmark jump, or
+    /// `goto_line` destination is never left hidden inside a collapsed
+    /// fold.
+    fn unfold_containing(&mut self, line: usize) {
+        let hit = self
+            .fold_regions
+            .iter()
+            .find(|(&start, &end)| start < line && line <= end)
+            .map(|(&start, _)| start);
+        if let Some(start) = hit {
+            self.folded.remove(&start);
+        }
     }
 
     /// 줄 번호로 이동
     pub fn goto_line(&mut self, line_str: &str) {
         if let Ok(line_num) = line_str.parse::<usize>() {
-            if line_num > 0 && line_num <= self.lines.len() {
+            if line_num > 0 && line_num <= self.total_lines {
+                self.unfold_containing(line_num - 1);
                 self.scroll = (line_num - 1).saturating_sub(5);
             }
         }
@@ -357,13 +1239,31 @@ impl ViewerState {
                     self.lines = content.lines().map(String::from).collect();
                 }
             }
+            ViewerMode::Markdown => {
+                self.mode = ViewerMode::Hex;
+                self.lines = self.format_hex_view(&self.raw_bytes);
+            }
         }
         self.scroll = 0;
     }
+
+    /// Toggle between the rendered Markdown view and raw text for a file
+    /// the viewer auto-detected as Markdown. A no-op for any other
+    /// language, and for binary files already locked into Hex mode.
+    pub fn toggle_markdown_view(&mut self) {
+        if self.is_binary || !matches!(self.language, Language::Markdown) {
+            return;
+        }
+        self.mode = match self.mode {
+            ViewerMode::Markdown => ViewerMode::Text,
+            _ => ViewerMode::Markdown,
+        };
+        self.scroll = 0;
+    }
 }
 
-pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
-    let state = match &app.viewer_state {
+pub fn draw(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
+    let state = match &mut app.viewer_state {
         Some(s) => s,
         None => return,
     };
@@ -379,8 +1279,28 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         return;
     }
 
+    // For Markdown mode, render the whole document once up front: the
+    // block tree doesn't map 1:1 to source lines (a heading or list item
+    // can collapse/expand line count), so `total_lines` for the header and
+    // scroll bound has to come from the rendered output, not
+    // `state.total_lines`. This mirrors how word-wrap mode below already
+    // keeps `total_lines` as the unwrapped count for the header while
+    // rendering a separately-computed wrapped line list.
+    let markdown_lines: Vec<Line<'static>> = if state.mode == ViewerMode::Markdown {
+        crate::utils::markdown::render_markdown(
+            &state.lines.join("\n"),
+            crate::utils::markdown::MarkdownTheme::from_theme(theme),
+        )
+    } else {
+        Vec::new()
+    };
+
     // Header
-    let total_lines = state.lines.len();
+    let total_lines = if state.mode == ViewerMode::Markdown {
+        markdown_lines.len()
+    } else {
+        state.total_lines
+    };
     let visible_lines = (inner.height - 2) as usize;
     let end_line = (state.scroll + visible_lines).min(total_lines);
     let percentage = if total_lines > 0 {
@@ -392,6 +1312,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     let mode_str = match state.mode {
         ViewerMode::Text => state.language.name(),
         ViewerMode::Hex => "Hex",
+        ViewerMode::Markdown => "Markdown",
     };
 
     let header = Line::from(vec![
@@ -428,18 +1349,28 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     let content_height = (inner.height - 2) as usize;
     let content_width = (inner.width - 5) as usize; // 줄 번호 공간 제외
 
-    // 하이라이터 리셋
-    let mut highlighter = state.highlighter.clone();
-    if let Some(ref mut hl) = highlighter {
-        hl.reset();
-        // 스크롤 전까지 상태 업데이트
-        for line in state.lines.iter().take(state.scroll) {
-            hl.tokenize_line(line);
-        }
-    }
+    // Markdown 렌더 모드: 이미 스타일이 입혀진 줄들을 그대로 그리며, word
+    // wrap이 켜져 있으면 너비에 맞춰 다시 감아 렌더링한다. 북마크/검색
+    // 하이라이트는 원본 소스 줄 번호 기준이라 렌더된 줄과 1:1로 대응하지
+    // 않으므로 이 모드에서는 적용하지 않는다.
+    if state.mode == ViewerMode::Markdown {
+        let rendered = if state.word_wrap {
+            crate::utils::markdown::render_markdown_wrapped(
+                &state.lines.join("\n"),
+                crate::utils::markdown::MarkdownTheme::from_theme(theme),
+                content_width,
+            )
+        } else {
+            markdown_lines.clone()
+        };
 
-    // Word wrap 모드일 경우 표시할 줄들을 미리 계산
-    if state.word_wrap {
+        for (i, line) in rendered.iter().skip(state.scroll).take(content_height).enumerate() {
+            frame.render_widget(
+                Paragraph::new(line.clone()),
+                Rect::new(inner.x, inner.y + 1 + i as u16, inner.width, 1),
+            );
+        }
+    } else if state.word_wrap && !state.is_large_file {
         // wrapped 줄 목록 생성: (원본 줄 번호, 원본 줄 참조, 줄 내용, 첫 줄 여부)
         let mut wrapped_lines: Vec<(usize, String, bool)> = Vec::new();
 
@@ -456,11 +1387,9 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             }
         }
 
-        // 하이라이터 리셋 for word wrap mode
-        let mut hl_for_wrap = state.highlighter.clone();
-        if let Some(ref mut hl) = hl_for_wrap {
-            hl.reset();
-        }
+        // Synced lazily below, the first time each original line is
+        // reached -- see `highlighter_synced_to`.
+        let mut hl_for_wrap: Option<SyntaxHighlighter> = None;
         let mut last_orig_line: Option<usize> = None;
 
         // 스크롤 위치부터 렌더링
@@ -472,7 +1401,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         {
             let is_match = state.match_lines.contains(orig_line_num);
             let is_current_match = state.match_lines.get(state.current_match) == Some(orig_line_num);
-            let is_bookmarked = state.bookmarks.contains(orig_line_num);
+            let is_bookmarked = state.bookmarks.contains_key(orig_line_num);
 
             // 줄 번호 (첫 줄만 표시)
             let line_num_style = if is_bookmarked {
@@ -504,48 +1433,51 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                 vec![Span::styled(display_text.clone(), line_bg_style)]
             } else if !state.search_term.is_empty() {
                 // 검색어 하이라이트 (wrapped 텍스트에 대해)
-                highlight_search_in_wrapped_line(display_text, &state.search_term, line_bg_style, theme)
-            } else if let Some(ref mut hl) = hl_for_wrap {
-                // 새로운 원본 줄이면 하이라이터 상태 업데이트
+                highlight_search_in_wrapped_line(
+                    display_text,
+                    &state.search_positions_in(display_text),
+                    line_bg_style,
+                    theme,
+                )
+            } else if state.highlighter.is_some() {
+                // 새로운 원본 줄이면 캐시를 이용해 하이라이터 상태를 맞춘다
+                // (줄 0부터 다시 재생하지 않는다).
                 if last_orig_line != Some(*orig_line_num) {
-                    // 이전에 처리하지 않은 줄들의 상태 업데이트
-                    if let Some(last) = last_orig_line {
-                        for skip_idx in (last + 1)..*orig_line_num {
-                            if skip_idx < state.lines.len() {
-                                hl.tokenize_line(&state.lines[skip_idx]);
-                            }
-                        }
-                    } else {
-                        // 처음 시작 시 스크롤 전까지의 줄들 처리
-                        for skip_idx in 0..*orig_line_num {
-                            if skip_idx < state.lines.len() {
-                                hl.tokenize_line(&state.lines[skip_idx]);
-                            }
-                        }
-                    }
+                    hl_for_wrap = state.highlighter_synced_to(*orig_line_num);
                     last_orig_line = Some(*orig_line_num);
                 }
 
-                // wrapped 텍스트에 대해 토큰화
-                let tokens = hl.tokenize_line(display_text);
-                if tokens.is_empty() {
-                    vec![Span::styled(display_text.clone(), line_bg_style)]
+                if let Some(ref mut hl) = hl_for_wrap {
+                    // wrapped 텍스트에 대해 토큰화
+                    let tokens = hl.tokenize_line(display_text);
+                    if tokens.is_empty() {
+                        vec![Span::styled(display_text.clone(), line_bg_style)]
+                    } else {
+                        tokens
+                            .into_iter()
+                            .map(|token| {
+                                let style = hl.style_for_token(&token);
+                                let final_style = match line_bg_style.bg {
+                                    Some(bg) => style.bg(bg),
+                                    None => style,
+                                };
+                                Span::styled(token.text, final_style)
+                            })
+                            .collect()
+                    }
                 } else {
-                    tokens
-                        .into_iter()
-                        .map(|token| {
-                            let style = hl.style_for(token.token_type);
-                            let final_style = match line_bg_style.bg {
-                                Some(bg) => style.bg(bg),
-                                None => style,
-                            };
-                            Span::styled(token.text, final_style)
-                        })
-                        .collect()
+                    // Too far from any cached sync point to catch up this
+                    // frame -- render plain rather than re-scanning from 0.
+                    vec![Span::styled(display_text.clone(), line_bg_style)]
                 }
             } else {
                 vec![Span::styled(display_text.clone(), line_bg_style)]
             };
+            let content_spans = if state.is_epub && state.epub_bold_lines.contains(orig_line_num) {
+                bold_spans(content_spans)
+            } else {
+                content_spans
+            };
 
             let mut spans = vec![line_num_span];
             spans.extend(content_spans);
@@ -557,11 +1489,48 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         }
     } else {
         // 일반 모드 (word wrap 없음)
-        for (i, line) in state.lines.iter().skip(state.scroll).take(content_height).enumerate() {
-            let line_num = state.scroll + i;
+        // Sync the highlighter up to the top of the viewport using the
+        // per-line cache instead of replaying from line 0 every frame.
+        let mut highlighter = if state.is_large_file {
+            None
+        } else {
+            state.highlighter_synced_to(state.scroll)
+        };
+
+        // A large file keeps `lines` empty, so the visible window is sliced
+        // lazily out of the mmap here instead of iterating it directly;
+        // folding never applies there since `fold_regions` is only
+        // populated for normally-loaded files. Otherwise walk real lines
+        // from `scroll`, collapsing a folded region into a single `⋯`
+        // summary row so each row drawn still corresponds to one budgeted
+        // line of `content_height`, not one source line.
+        let visible: Vec<(usize, String, bool)> = if state.is_large_file {
+            (state.scroll..(state.scroll + content_height).min(state.total_lines))
+                .map(|idx| (idx, state.line_at(idx), false))
+                .collect()
+        } else {
+            let mut rows = Vec::new();
+            let mut line_num = state.scroll;
+            while rows.len() < content_height && line_num < state.lines.len() {
+                if state.folded.contains(&line_num) {
+                    if let Some(end) = state.fold_end(line_num) {
+                        rows.push((line_num, format!("⋯ {} folded lines", end - line_num), true));
+                        line_num = end + 1;
+                        continue;
+                    }
+                }
+                rows.push((line_num, state.lines[line_num].clone(), false));
+                line_num += 1;
+            }
+            rows
+        };
+
+        for (i, (line_num, line, is_fold_marker)) in visible.iter().enumerate() {
+            let line_num = *line_num;
             let is_match = state.match_lines.contains(&line_num);
             let is_current_match = state.match_lines.get(state.current_match) == Some(&line_num);
-            let is_bookmarked = state.bookmarks.contains(&line_num);
+            let is_bookmarked = state.bookmarks.contains_key(&line_num);
+            let is_foldable = state.fold_end(line_num).is_some();
 
             // 줄 번호
             let line_num_style = if is_bookmarked {
@@ -570,8 +1539,21 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                 theme.dim_style()
             };
 
+            let marker = if is_foldable {
+                if *is_fold_marker { "+" } else { "-" }
+            } else {
+                " "
+            };
+            // Always formatted into the same fixed-width `{:4}` slot
+            // regardless of relative-vs-absolute mode, so toggling it never
+            // shifts the content columns over.
+            let display_num = if state.relative_line_numbers && line_num != state.scroll {
+                (line_num as i64 - state.scroll as i64).unsigned_abs()
+            } else {
+                (line_num + 1) as u64
+            };
             let line_num_span = Span::styled(
-                format!("{:4} ", line_num + 1),
+                format!("{:4}{} ", display_num, marker),
                 line_num_style,
             );
 
@@ -585,18 +1567,29 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             };
 
             // 콘텐츠 렌더링
-            let content_spans = if state.mode == ViewerMode::Hex {
+            let content_spans = if *is_fold_marker {
+                vec![Span::styled(line.clone(), theme.dim_style())]
+            } else if state.mode == ViewerMode::Hex {
                 render_hex_line(line, theme)
             } else if !state.search_term.is_empty() {
                 highlight_search_in_line(line, &state.match_positions, line_num, line_bg_style, theme)
             } else if let Some(ref mut hl) = highlighter {
-                render_syntax_highlighted_line(line, hl, line_bg_style)
+                let spans = render_syntax_highlighted_line(line, hl, line_bg_style);
+                if let Some(slot) = state.highlight_cache.get_mut(line_num) {
+                    *slot = Some(hl.lexer_state());
+                }
+                spans
             } else {
                 vec![Span::styled(line.clone(), line_bg_style)]
             };
+            let content_spans = if state.is_epub && state.epub_bold_lines.contains(&line_num) {
+                bold_spans(content_spans)
+            } else {
+                content_spans
+            };
 
             // 수평 스크롤 적용
-            let final_spans = if state.horizontal_scroll > 0 {
+            let final_spans = if state.horizontal_scroll > 0 && !is_fold_marker {
                 let display_line: String = line.chars().skip(state.horizontal_scroll).collect();
                 if content_spans.len() == 1 {
                     vec![Span::styled(display_line, content_spans[0].style)]
@@ -607,13 +1600,25 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             } else {
                 content_spans
             };
+            let final_spans = if state.show_indent_guides && !is_fold_marker {
+                apply_indent_guides(final_spans)
+            } else {
+                final_spans
+            };
 
             let mut spans = vec![line_num_span];
             spans.extend(final_spans);
 
+            let row_width = if state.readable_width_mode {
+                READABLE_WIDTH.min(inner.width)
+            } else {
+                inner.width
+            };
+            let row_x = inner.x + (inner.width.saturating_sub(row_width)) / 2;
+
             frame.render_widget(
                 Paragraph::new(Line::from(spans)),
-                Rect::new(inner.x, inner.y + 1 + i as u16, inner.width, 1),
+                Rect::new(row_x, inner.y + 1 + i as u16, row_width, 1),
             );
         }
     }
@@ -652,12 +1657,24 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Paragraph::new(goto_line).style(theme.status_bar_style()),
             Rect::new(inner.x, footer_y, inner.width, 1),
         );
+    } else if state.bookmark_label_mode {
+        let label_line = Line::from(vec![
+            Span::styled("Bookmark label (Enter to save, Esc to skip): ", theme.header_style()),
+            Span::styled(&state.bookmark_label_input, theme.normal_style()),
+            Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+        ]);
+        frame.render_widget(
+            Paragraph::new(label_line).style(theme.status_bar_style()),
+            Rect::new(inner.x, footer_y, inner.width, 1),
+        );
     } else if state.search_mode {
         let search_opts = format!(
-            "[{}{}{}]",
+            "[{}{}{}{}{}]",
             if state.search_options.case_sensitive { "Aa" } else { "aa" },
             if state.search_options.use_regex { " Re" } else { "" },
-            if state.search_options.whole_word { " W" } else { "" }
+            if state.search_options.whole_word { " W" } else { "" },
+            if state.search_options.multiline { " Ml" } else { "" },
+            if state.search_options.fuzzy { " Fz" } else { "" }
         );
         let search_line = Line::from(vec![
             Span::styled("Search: ", theme.header_style()),
@@ -670,9 +1687,27 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Rect::new(inner.x, footer_y, inner.width, 1),
         );
     } else {
-        let search_info = if !state.search_term.is_empty() {
+        let search_info = if !state.search_term.is_empty() && state.is_large_file {
+            format!("\"{}\" search unavailable above the large-file threshold ", {
+                if state.search_term.chars().count() > 20 {
+                    let truncated: String = state.search_term.chars().take(17).collect();
+                    format!("{}...", truncated)
+                } else {
+                    state.search_term.clone()
+                }
+            })
+        } else if !state.search_term.is_empty() {
+            let score_suffix = if state.search_options.fuzzy {
+                state
+                    .match_scores
+                    .get(state.current_match)
+                    .map(|score| format!(" score {}", score))
+                    .unwrap_or_default()
+            } else {
+                String::new()
+            };
             format!(
-                "\"{}\" {} matches ({}/{}) ",
+                "\"{}\" {} matches ({}/{}){} ",
                 if state.search_term.chars().count() > 20 {
                     let truncated: String = state.search_term.chars().take(17).collect();
                     format!("{}...", truncated)
@@ -685,7 +1720,8 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                 } else {
                     state.current_match + 1
                 },
-                state.match_lines.len()
+                state.match_lines.len(),
+                score_suffix
             )
         } else {
             String::new()
@@ -703,15 +1739,34 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         }
 
         // 단축키 표시: 첫 글자 강조
-        let shortcuts = [
+        let mut shortcuts = vec![
             ("q", "uit "),
             ("e", "dit "),
             ("/", "search "),
             ("g", "oto "),
             ("b", "mark "),
             ("w", "rap "),
-            ("H", "ex"),
+            ("H", "ex "),
         ];
+        if matches!(state.language, Language::Markdown) {
+            shortcuts.push(("m", "d view"));
+        }
+        if !state.bookmarks.is_empty() {
+            shortcuts.push(("p", "icker "));
+        }
+        if !state.fold_regions.is_empty() {
+            shortcuts.push(("z", "fold "));
+            shortcuts.push(("a", "/"));
+            shortcuts.push(("A", " fold all "));
+        }
+        shortcuts.push(("r", "el# "));
+        shortcuts.push(("i", "ndent "));
+        shortcuts.push(("c", "enter "));
+        if state.is_epub {
+            shortcuts.push(("(", "/"));
+            shortcuts.push((")", " chapter "));
+            shortcuts.push(("t", "oc"));
+        }
 
         for (key, rest) in shortcuts {
             footer_spans.push(Span::styled(key, theme.header_style()));
@@ -732,6 +1787,158 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Rect::new(inner.x, footer_y, inner.width, 1),
         );
     }
+
+    if state.epub_toc_mode {
+        draw_epub_toc(frame, state, area, theme);
+    }
+
+    if state.bookmark_picker_mode {
+        draw_bookmark_picker(frame, state, area, theme);
+    }
+}
+
+/// Floating picker listing every bookmark as "line  [label] preview text"
+/// (the bracketed label is left out for an unlabeled bookmark),
+/// filterable by typing, mirroring `draw_epub_toc`'s self-drawn overlay
+/// rather than the shared `dialogs` system since the viewer renders itself.
+fn draw_bookmark_picker(frame: &mut Frame, state: &ViewerState, area: Rect, theme: &Theme) {
+    let matches = state.filtered_bookmarks();
+
+    let width = 70u16.min(area.width.saturating_sub(4));
+    let height = (matches.len() as u16 + 4).min(area.height.saturating_sub(4)).max(6);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let dialog_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Bookmarks ")
+        .title_style(theme.header_style())
+        .borders(Borders::ALL)
+        .border_style(theme.border_style(true));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if inner.height == 0 {
+        return;
+    }
+
+    let filter_area = Rect::new(inner.x, inner.y, inner.width, 1);
+    let filter_line = Line::from(vec![
+        Span::styled("Filter: ", theme.dim_style()),
+        Span::styled(&state.bookmark_picker_filter, theme.normal_style()),
+        Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+    ]);
+    frame.render_widget(Paragraph::new(filter_line), filter_area);
+
+    let list_area = Rect::new(inner.x, inner.y + 1, inner.width, inner.height.saturating_sub(1));
+    let visible_rows = list_area.height as usize;
+    let scroll_offset = state.bookmark_picker_selected.saturating_sub(visible_rows.saturating_sub(1));
+
+    let lines: Vec<Line> = matches
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(visible_rows)
+        .map(|(i, (line, label))| {
+            let style = if i == state.bookmark_picker_selected {
+                theme.selected_style()
+            } else {
+                theme.normal_style()
+            };
+            let preview = state.lines.get(*line).map(String::as_str).unwrap_or("");
+            let text = if label.is_empty() {
+                format!("{:>5}  {}", line + 1, preview)
+            } else {
+                format!("{:>5}  [{}] {}", line + 1, label, preview)
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), list_area);
+}
+
+/// Floating table-of-contents overlay for an EPUB, letting the user pick a
+/// chapter by its nav label -- a simpler, self-drawn cousin of
+/// `dialogs::draw_completion_list` since the viewer renders itself rather
+/// than going through the shared dialog system.
+fn draw_epub_toc(frame: &mut Frame, state: &ViewerState, area: Rect, theme: &Theme) {
+    let width = 60u16.min(area.width.saturating_sub(4));
+    let height = (state.epub_chapters.len() as u16 + 2).min(area.height.saturating_sub(4)).max(5);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let dialog_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Table of Contents ")
+        .title_style(theme.header_style())
+        .borders(Borders::ALL)
+        .border_style(theme.border_style(true));
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let visible_rows = inner.height as usize;
+    let scroll_offset = state.epub_toc_selected.saturating_sub(visible_rows.saturating_sub(1));
+
+    let lines: Vec<Line> = state
+        .epub_chapters
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(visible_rows)
+        .map(|(i, chapter)| {
+            let style = if i == state.epub_toc_selected {
+                theme.selected_style()
+            } else {
+                theme.normal_style()
+            };
+            Line::from(Span::styled(chapter.label.clone(), style))
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Apply `Modifier::BOLD` to every span, used for EPUB heading lines which
+/// have no syntax highlighter to lean on for emphasis.
+fn bold_spans(spans: Vec<Span<'static>>) -> Vec<Span<'static>> {
+    spans
+        .into_iter()
+        .map(|s| Span::styled(s.content, s.style.add_modifier(Modifier::BOLD)))
+        .collect()
+}
+
+/// Overlay a faint `│` at every other leading-whitespace column, operating
+/// on the already-assembled display spans (each char kept in place, one for
+/// one) so it never touches the byte offsets search highlighting and
+/// horizontal scroll rely on -- this runs strictly after those are applied.
+fn apply_indent_guides(spans: Vec<Span<'static>>) -> Vec<Span<'static>> {
+    let mut result = Vec::with_capacity(spans.len());
+    let mut col = 0usize;
+    let mut in_leading_ws = true;
+    for span in spans {
+        if !in_leading_ws {
+            result.push(span);
+            continue;
+        }
+        let style = span.style;
+        let mut out = String::with_capacity(span.content.len());
+        for ch in span.content.chars() {
+            if in_leading_ws && ch == ' ' {
+                out.push(if col > 0 && col % 2 == 0 { '│' } else { ' ' });
+                col += 1;
+            } else {
+                in_leading_ws = false;
+                out.push(ch);
+            }
+        }
+        result.push(Span::styled(out, style));
+    }
+    result
 }
 
 /// 헥스 라인 렌더링
@@ -825,25 +2032,29 @@ fn highlight_search_in_line(
     spans
 }
 
-/// Wrapped 텍스트에서 검색어 하이라이트
+/// Wrapped 텍스트에서 검색어 하이라이트. `positions` are byte ranges within
+/// `line` already produced by the real matcher (`ViewerState::search_positions_in`,
+/// itself `eval_search_expr` run against this fragment), so regex/case/
+/// whole-word options are honored for free, zero-width matches can't loop
+/// (the `regex` crate's `find_iter` already guarantees forward progress),
+/// and a match that would run past the end of this wrapped fragment is
+/// naturally clipped since the matcher never saw anything beyond it.
 fn highlight_search_in_wrapped_line(
     line: &str,
-    search_term: &str,
+    positions: &[(usize, usize)],
     base_style: Style,
     theme: &Theme,
 ) -> Vec<Span<'static>> {
-    if search_term.is_empty() {
+    if positions.is_empty() {
         return vec![Span::styled(line.to_string(), base_style)];
     }
 
-    let lower_line = line.to_lowercase();
-    let lower_term = search_term.to_lowercase();
-
     let mut spans = Vec::new();
     let mut last_end = 0;
 
-    for (start, _) in lower_line.match_indices(&lower_term) {
-        let end = start + search_term.len();
+    for &(start, end) in positions {
+        let start = start.min(line.len());
+        let end = end.min(line.len());
 
         if start > last_end {
             spans.push(Span::styled(
@@ -857,7 +2068,7 @@ fn highlight_search_in_wrapped_line(
                 .fg(ratatui::style::Color::Black)
                 .bg(theme.warning),
         ));
-        last_end = end;
+        last_end = end.max(last_end);
     }
 
     if last_end < line.len() {
@@ -889,7 +2100,7 @@ fn render_syntax_highlighted_line(
     tokens
         .into_iter()
         .map(|token| {
-            let style = highlighter.style_for(token.token_type);
+            let style = highlighter.style_for_token(&token);
             // 배경색이 있는 경우 (선택된 라인 등) 배경색 유지
             let final_style = match base_style.bg {
                 Some(bg) => style.bg(bg),
@@ -929,6 +2140,76 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
         return;
     }
 
+    // 북마크 라벨 입력
+    if state.bookmark_label_mode {
+        match code {
+            KeyCode::Esc => {
+                state.bookmark_label_mode = false;
+                state.bookmark_label_input.clear();
+            }
+            KeyCode::Enter => {
+                state.confirm_bookmark_label();
+            }
+            KeyCode::Backspace => {
+                state.bookmark_label_input.pop();
+            }
+            KeyCode::Char(c) => {
+                state.bookmark_label_input.push(c);
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    // 북마크 피커
+    if state.bookmark_picker_mode {
+        match code {
+            KeyCode::Esc => {
+                state.bookmark_picker_mode = false;
+                state.bookmark_picker_filter.clear();
+            }
+            KeyCode::Enter => {
+                state.jump_to_selected_bookmark();
+            }
+            KeyCode::Backspace => {
+                state.bookmark_picker_filter.pop();
+                state.bookmark_picker_selected = 0;
+            }
+            KeyCode::Up => {
+                state.bookmark_picker_selected = state.bookmark_picker_selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                if state.bookmark_picker_selected + 1 < state.filtered_bookmarks().len() {
+                    state.bookmark_picker_selected += 1;
+                }
+            }
+            KeyCode::Char(c) => {
+                state.bookmark_picker_filter.push(c);
+                state.bookmark_picker_selected = 0;
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    // EPUB 목차 오버레이
+    if state.epub_toc_mode {
+        match code {
+            KeyCode::Esc => state.epub_toc_mode = false,
+            KeyCode::Enter => state.jump_to_selected_epub_chapter(),
+            KeyCode::Up => {
+                state.epub_toc_selected = state.epub_toc_selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                if state.epub_toc_selected + 1 < state.epub_chapters.len() {
+                    state.epub_toc_selected += 1;
+                }
+            }
+            _ => {}
+        }
+        return;
+    }
+
     // 검색 모드
     if state.search_mode {
         match code {
@@ -956,6 +2237,14 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
                 // Ctrl+W: 단어 단위 검색 토글
                 state.search_options.whole_word = !state.search_options.whole_word;
             }
+            KeyCode::Char('l') if modifiers.contains(KeyModifiers::CONTROL) => {
+                // Ctrl+L: 여러 줄에 걸친 매칭 토글
+                state.search_options.multiline = !state.search_options.multiline;
+            }
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                // Ctrl+U: 퍼지(fuzzy) 검색 토글
+                state.search_options.fuzzy = !state.search_options.fuzzy;
+            }
             KeyCode::Char(c) => {
                 state.search_input.push(c);
             }
@@ -993,7 +2282,7 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             state.scroll = state.scroll.saturating_sub(1);
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            if state.scroll + visible_lines < state.lines.len() {
+            if state.scroll + visible_lines < state.total_lines {
                 state.scroll += 1;
             }
         }
@@ -1014,11 +2303,24 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             // 헥스 모드 토글
             state.toggle_mode();
         }
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            // Markdown 렌더 모드 토글 (Markdown 파일에서만 동작)
+            state.toggle_markdown_view();
+        }
+        KeyCode::Char('(') => {
+            state.prev_epub_chapter();
+        }
+        KeyCode::Char(')') => {
+            state.next_epub_chapter();
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            state.open_epub_toc();
+        }
         KeyCode::PageUp => {
             state.scroll = state.scroll.saturating_sub(visible_lines);
         }
         KeyCode::PageDown => {
-            let max = state.lines.len().saturating_sub(visible_lines);
+            let max = state.total_lines.saturating_sub(visible_lines);
             state.scroll = (state.scroll + visible_lines).min(max);
         }
         KeyCode::Home | KeyCode::Char('g') if !modifiers.contains(KeyModifiers::SHIFT) => {
@@ -1028,7 +2330,7 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             }
         }
         KeyCode::End | KeyCode::Char('G') => {
-            state.scroll = state.lines.len().saturating_sub(visible_lines);
+            state.scroll = state.total_lines.saturating_sub(visible_lines);
         }
         KeyCode::Char('/') => {
             state.search_mode = true;
@@ -1061,6 +2363,34 @@ pub fn handle_input(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             // 다음 북마크
             state.goto_next_bookmark();
         }
+        KeyCode::Char('p') | KeyCode::Char('P') => {
+            // 북마크 피커: 라벨/미리보기로 필터링해 바로 이동
+            state.open_bookmark_picker();
+        }
+        KeyCode::Char('z') => {
+            // 커서 위치의 폴드 토글
+            state.toggle_fold_at_cursor();
+        }
+        KeyCode::Char('a') => {
+            // 모든 폴드 접기
+            state.fold_all();
+        }
+        KeyCode::Char('A') => {
+            // 모든 폴드 펼치기
+            state.unfold_all();
+        }
+        KeyCode::Char('r') => {
+            // 상대 줄 번호 토글
+            state.relative_line_numbers = !state.relative_line_numbers;
+        }
+        KeyCode::Char('i') => {
+            // 들여쓰기 가이드 토글
+            state.show_indent_guides = !state.show_indent_guides;
+        }
+        KeyCode::Char('c') => {
+            // 읽기 좋은 폭으로 가운데 정렬 토글
+            state.readable_width_mode = !state.readable_width_mode;
+        }
         KeyCode::Char('w') | KeyCode::Char('W') => {
             // Word wrap 토글
             state.word_wrap = !state.word_wrap;
@@ -0,0 +1,195 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+use super::{app::App, theme::Theme};
+use crate::utils::format::format_size;
+
+pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_style(true));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.height < 5 {
+        return;
+    }
+
+    let header = Line::from(vec![
+        Span::styled(" Filesystems ", theme.header_style()),
+        Span::styled(
+            format!(" [{} mounts]", app.mounts.len()),
+            theme.dim_style(),
+        ),
+    ]);
+    frame.render_widget(
+        Paragraph::new(header),
+        Rect::new(inner.x, inner.y, inner.width, 1),
+    );
+
+    let device_width = 16;
+    let type_width = 8;
+    let size_width = 10;
+    let bar_width = inner.width.saturating_sub(device_width + type_width + size_width * 3 + 6) as usize;
+
+    let col_header = Line::from(vec![
+        Span::styled(format!("{:width$}", "DEVICE", width = device_width as usize), theme.header_style()),
+        Span::styled(format!("{:width$}", "TYPE", width = type_width as usize), theme.header_style()),
+        Span::styled(format!("{:>width$}", "SIZE", width = size_width as usize), theme.header_style()),
+        Span::styled(format!("{:>width$}", "USED", width = size_width as usize), theme.header_style()),
+        Span::styled(format!("{:>width$}", "FREE", width = size_width as usize), theme.header_style()),
+        Span::styled("  USE%  MOUNTPOINT", theme.header_style()),
+    ]);
+    frame.render_widget(
+        Paragraph::new(col_header),
+        Rect::new(inner.x, inner.y + 1, inner.width, 1),
+    );
+
+    let list_height = (inner.height - 3) as usize;
+    let start_index = app.mounts_selected_index.saturating_sub(list_height / 2);
+    let start_index = start_index.min(app.mounts.len().saturating_sub(list_height));
+
+    for (i, mount) in app.mounts.iter().skip(start_index).take(list_height).enumerate() {
+        let actual_index = start_index + i;
+        let is_cursor = actual_index == app.mounts_selected_index;
+
+        let style = if is_cursor {
+            theme.selected_style()
+        } else {
+            theme.normal_style()
+        };
+
+        let bar = usage_bar(mount.usage_ratio(), bar_width);
+        let bar_style = if is_cursor {
+            style
+        } else {
+            usage_style(mount.usage_ratio(), theme)
+        };
+
+        let line = Line::from(vec![
+            Span::styled(format!("{:width$}", truncate(&mount.device, device_width as usize - 1), width = device_width as usize), style),
+            Span::styled(format!("{:width$}", truncate(&mount.fs_type, type_width as usize - 1), width = type_width as usize), style),
+            Span::styled(format!("{:>width$}", format_size(mount.total_bytes), width = size_width as usize), style),
+            Span::styled(format!("{:>width$}", format_size(mount.used_bytes), width = size_width as usize), style),
+            Span::styled(format!("{:>width$}", format_size(mount.free_bytes), width = size_width as usize), style),
+            Span::styled("  ", style),
+            Span::styled(bar, bar_style),
+            Span::styled(
+                format!(
+                    " {}{}",
+                    mount.mountpoint.display(),
+                    if mount.read_only { " [ro]" } else { "" },
+                ),
+                style,
+            ),
+        ]);
+
+        frame.render_widget(
+            Paragraph::new(line),
+            Rect::new(inner.x, inner.y + 2 + i as u16, inner.width, 1),
+        );
+    }
+
+    let total_mounts = app.mounts.len();
+    if total_mounts > list_height {
+        let scrollbar = Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("▲"))
+            .end_symbol(Some("▼"));
+
+        let mut scrollbar_state = ScrollbarState::new(total_mounts)
+            .position(app.mounts_selected_index);
+
+        let scrollbar_area = Rect::new(
+            inner.x + inner.width - 1,
+            inner.y + 2,
+            1,
+            list_height as u16,
+        );
+
+        frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+    }
+
+    let footer_spans = vec![
+        Span::styled("Enter", theme.header_style()),
+        Span::styled(" switch panel  ", theme.dim_style()),
+        Span::styled("r", theme.header_style()),
+        Span::styled("efresh  ", theme.dim_style()),
+        Span::styled("q", theme.header_style()),
+        Span::styled("uit", theme.dim_style()),
+    ];
+    frame.render_widget(
+        Paragraph::new(Line::from(footer_spans)),
+        Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1),
+    );
+}
+
+/// Color a usage bar by how full it is: green below 70%, yellow up to 90%,
+/// red beyond that, so a glance at the list flags volumes about to fill up.
+fn usage_style(ratio: f64, theme: &Theme) -> ratatui::style::Style {
+    if ratio >= 0.9 {
+        theme.error_style()
+    } else if ratio >= 0.7 {
+        theme.warning_style()
+    } else {
+        theme.success_style()
+    }
+}
+
+/// Render a fixed-width `[###.....]` usage bar for a `0.0..=1.0` ratio.
+fn usage_bar(ratio: f64, width: usize) -> String {
+    if width < 2 {
+        return String::new();
+    }
+    let inner_width = width - 2;
+    let filled = ((ratio.clamp(0.0, 1.0)) * inner_width as f64).round() as usize;
+    let filled = filled.min(inner_width);
+    format!("[{}{}]", "#".repeat(filled), ".".repeat(inner_width - filled))
+}
+
+fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() > max_len {
+        let truncated: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", truncated)
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn handle_input(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.current_screen = super::app::Screen::DualPanel;
+        }
+        KeyCode::Up => {
+            if app.mounts_selected_index > 0 {
+                app.mounts_selected_index -= 1;
+            }
+        }
+        KeyCode::Down => {
+            if app.mounts_selected_index < app.mounts.len().saturating_sub(1) {
+                app.mounts_selected_index += 1;
+            }
+        }
+        KeyCode::Home => {
+            app.mounts_selected_index = 0;
+        }
+        KeyCode::End => {
+            app.mounts_selected_index = app.mounts.len().saturating_sub(1);
+        }
+        KeyCode::Enter => {
+            app.enter_selected_mount();
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            app.mounts = crate::services::filesystems::list_mounts();
+            app.show_message("Refreshed");
+        }
+        _ => {}
+    }
+}
@@ -10,7 +10,9 @@ use unicode_width::UnicodeWidthStr;
 use super::{app::{PanelState, SortBy, SortOrder}, theme::Theme};
 use crate::utils::format::format_size;
 
-pub fn draw(frame: &mut Frame, panel: &mut PanelState, area: Rect, is_active: bool, theme: &Theme) {
+pub fn draw(frame: &mut Frame, panel: &mut PanelState, area: Rect, is_active: bool, theme: &Theme, staged_count: usize) {
+    panel.poll_dir_size_calc();
+
     let inner_width = area.width.saturating_sub(2) as usize;
 
     // Build path display (truncate if too long)
@@ -69,34 +71,43 @@ pub fn draw(frame: &mut Frame, panel: &mut PanelState, area: Rect, is_active: bo
     // File list (visible area)
     let visible_height = (inner.height - 2) as usize; // -2 for header and footer
     let total_files = panel.files.len();
+    panel.last_visible_height = visible_height;
 
-    // 스크롤 오프셋 계산: 커서가 보이는 범위 내에 있으면 스크롤 유지
-    let current_scroll = panel.scroll_offset;
+    // `scroll_offset` is the source of truth (kept in sync by `PanelState::scroll`
+    // for explicit PgUp/PgDn/wheel commands); here we just clamp it to the
+    // valid range and nudge it the minimum amount needed to keep the cursor
+    // in view for plain arrow-key navigation, instead of recentering every
+    // frame.
     let start_index = if total_files <= visible_height {
-        // 파일 개수가 화면보다 적으면 스크롤 없음
         0
-    } else if panel.selected_index >= current_scroll &&
-              panel.selected_index < current_scroll + visible_height {
-        // 커서가 현재 보이는 범위 내에 있으면 스크롤 유지
-        // 단, 스크롤이 유효한 범위인지 확인
-        if current_scroll + visible_height > total_files {
-            total_files - visible_height
-        } else {
-            current_scroll
-        }
     } else {
-        // 커서가 범위 밖이면 center-locked로 조정
-        let half_visible = visible_height / 2;
-        let mut new_start = panel.selected_index.saturating_sub(half_visible);
-        if new_start + visible_height > total_files {
-            new_start = total_files - visible_height;
+        let max_offset = total_files - visible_height;
+        let mut start = panel.scroll_offset.min(max_offset);
+        if panel.selected_index < start {
+            start = panel.selected_index;
+        } else if panel.selected_index >= start + visible_height {
+            start = panel.selected_index + 1 - visible_height;
         }
-        new_start
+        start
     };
 
-    // scroll_offset 업데이트 (패널 전환 시 사용)
     panel.scroll_offset = start_index;
 
+    let tree_prefixes = if panel.tree_mode {
+        Some(build_tree_prefixes(&panel.files))
+    } else {
+        None
+    };
+
+    // Only the fuzzy mode scores individual characters, so only it has
+    // positions worth highlighting in `create_file_line`.
+    let (filter_mode, filter_query) = panel.quick_filter_mode();
+    let fuzzy_query = if filter_mode == super::app::QuickFilterMode::Fuzzy && !filter_query.is_empty() {
+        Some(filter_query.to_string())
+    } else {
+        None
+    };
+
     let visible_files = panel.files.iter().skip(start_index).take(visible_height);
 
     for (i, file) in visible_files.enumerate() {
@@ -104,15 +115,23 @@ pub fn draw(frame: &mut Frame, panel: &mut PanelState, area: Rect, is_active: bo
         let is_cursor = actual_index == panel.selected_index;
         let is_marked = panel.selected_files.contains(&file.name);
         let show_cursor = is_cursor && is_active;
+        let tree_prefix = tree_prefixes.as_ref().map(|p| p[actual_index].as_str()).unwrap_or("");
+        let match_indices = fuzzy_query
+            .as_deref()
+            .and_then(|q| crate::ui::advanced_search::fuzzy_match(&file.name, q))
+            .map(|(_, indices)| indices)
+            .unwrap_or_default();
 
         let line = create_file_line(
             file,
             show_cursor,
             is_marked,
+            tree_prefix,
             name_col,
             size_col,
             date_col,
             theme,
+            &match_indices,
         );
 
         let paragraph = if show_cursor {
@@ -152,12 +171,48 @@ pub fn draw(frame: &mut Frame, panel: &mut PanelState, area: Rect, is_active: bo
     let file_count = panel.files.iter().filter(|f| !f.is_directory).count();
     let total_size: u64 = panel.files.iter().filter(|f| !f.is_directory).map(|f| f.size).sum();
 
-    let footer_text = format!(
-        "{} folders, {} files, {}",
-        dir_count,
-        file_count,
-        crate::utils::format::format_size(total_size)
-    );
+    let footer_text = if panel.quick_filter_active || !panel.quick_filter.is_empty() {
+        let cursor = if panel.quick_filter_active { "_" } else { "" };
+        format!(
+            "Filter: {}{}  ({} matches)",
+            panel.quick_filter,
+            cursor,
+            panel.files.len()
+        )
+    } else if let Some(calc) = &panel.dir_size_calc {
+        let label = calc.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if calc.done {
+            format!(
+                "{}: {} ({} files, {} dirs)",
+                label,
+                format_size(calc.stats.total_bytes),
+                calc.stats.file_count,
+                calc.stats.dir_count
+            )
+        } else {
+            format!(
+                "{}: calculating... {} ({} files so far)",
+                label,
+                format_size(calc.stats.total_bytes),
+                calc.stats.file_count
+            )
+        }
+    } else if staged_count > 0 {
+        format!(
+            "{} folders, {} files, {} ({} staged)",
+            dir_count,
+            file_count,
+            crate::utils::format::format_size(total_size),
+            staged_count
+        )
+    } else {
+        format!(
+            "{} folders, {} files, {}",
+            dir_count,
+            file_count,
+            crate::utils::format::format_size(total_size)
+        )
+    };
     let footer = Line::from(Span::styled(footer_text, theme.dim_style()));
     frame.render_widget(
         Paragraph::new(footer).alignment(ratatui::layout::Alignment::Center),
@@ -225,24 +280,51 @@ fn truncate_to_width(s: &str, max_width: usize) -> String {
     result
 }
 
-/// Pad string to exact display width with spaces
-fn pad_to_width(s: &str, target_width: usize) -> String {
-    let current_width = s.width();
-    if current_width >= target_width {
-        s.to_string()
-    } else {
-        format!("{}{}", s, " ".repeat(target_width - current_width))
+/// Build the `│   `/`├── `/`└── ` connector prefix for every entry in a tree
+/// projection, one pass over the flat (depth-tagged) vector, following lsd's
+/// box-drawing convention: a continuation bar for ancestors with more
+/// siblings below, blank space for ancestors that have already drawn their
+/// last child, and a branch connector for the entry itself.
+fn build_tree_prefixes(files: &[super::app::FileItem]) -> Vec<String> {
+    let mut ancestor_is_last: Vec<bool> = Vec::new();
+    let mut prefixes = Vec::with_capacity(files.len());
+
+    for (i, file) in files.iter().enumerate() {
+        let depth = file.depth as usize;
+        ancestor_is_last.truncate(depth);
+
+        let is_last = files[i + 1..]
+            .iter()
+            .find(|f| f.depth <= file.depth)
+            .map(|f| f.depth < file.depth)
+            .unwrap_or(true);
+
+        let mut prefix = String::new();
+        if depth > 0 {
+            for &last in &ancestor_is_last {
+                prefix.push_str(if last { "    " } else { "\u{2502}   " });
+            }
+            prefix.push_str(if is_last { "\u{2514}\u{2500}\u{2500} " } else { "\u{251c}\u{2500}\u{2500} " });
+        }
+        prefixes.push(prefix);
+
+        ancestor_is_last.truncate(depth);
+        ancestor_is_last.push(is_last);
     }
+
+    prefixes
 }
 
 fn create_file_line(
     file: &super::app::FileItem,
     is_cursor: bool,
     is_marked: bool,
+    tree_prefix: &str,
     name_width: usize,
     size_width: usize,
     date_width: usize,
     theme: &Theme,
+    match_indices: &[usize],
 ) -> Line<'static> {
     let marker = if is_marked { "*" } else { " " };
     let icon = if file.is_directory {
@@ -251,8 +333,9 @@ fn create_file_line(
         theme.chars.file.to_string()
     };
 
-    // Truncate name if needed using unicode display width
-    let effective_name_width = name_width.saturating_sub(2);
+    // Truncate name if needed using unicode display width, leaving room for
+    // the tree connector prefix when present
+    let effective_name_width = name_width.saturating_sub(2).saturating_sub(tree_prefix.width());
     let display_name = if effective_name_width < 4 {
         String::new()
     } else {
@@ -270,10 +353,6 @@ fn create_file_line(
         }
     };
 
-    // Pad name column to exact width using unicode-aware padding
-    let name_with_prefix = format!("{}{}{}", marker, &icon, display_name);
-    let name_col = pad_to_width(&name_with_prefix, name_width);
-
     let size_str = if file.is_directory {
         "<DIR>".to_string()
     } else {
@@ -301,6 +380,16 @@ fn create_file_line(
         theme.selected_style()
     } else if is_marked {
         theme.marked_style()
+    } else if let Some(style) = theme.ls_colors.as_ref().and_then(|colors| {
+        colors.resolve(
+            &file.name,
+            file.is_directory,
+            file.is_symlink,
+            file.is_broken_symlink,
+            file.is_executable(),
+        )
+    }) {
+        style
     } else if file.is_directory {
         theme.directory_style()
     } else {
@@ -313,9 +402,48 @@ fn create_file_line(
         theme.dim_style()
     };
 
-    Line::from(vec![
-        Span::styled(name_col, name_style),
-        Span::styled(size_col, other_style),
-        Span::styled(date_col, other_style),
-    ])
+    let prefix = format!("{}{}{}", marker, tree_prefix, &icon);
+    let prefix_width = prefix.width();
+    let mut name_spans = vec![Span::styled(prefix, name_style)];
+
+    // Only a name shown in full (not truncated with "...") aligns its chars
+    // with the match indices `fuzzy_match` scored against `file.name`.
+    if match_indices.is_empty() || display_name != file.name {
+        name_spans.push(Span::styled(display_name.clone(), name_style));
+    } else {
+        let highlight_style = if is_cursor { name_style } else { theme.marked_style() };
+        let mut run = String::new();
+        let mut run_highlighted = false;
+        for (idx, c) in display_name.chars().enumerate() {
+            let highlighted = match_indices.contains(&idx);
+            if highlighted != run_highlighted && !run.is_empty() {
+                name_spans.push(Span::styled(
+                    run.clone(),
+                    if run_highlighted { highlight_style } else { name_style },
+                ));
+                run.clear();
+            }
+            run_highlighted = highlighted;
+            run.push(c);
+        }
+        if !run.is_empty() {
+            name_spans.push(Span::styled(
+                run,
+                if run_highlighted { highlight_style } else { name_style },
+            ));
+        }
+    }
+
+    let name_content_width = prefix_width + display_name.width();
+    if name_width > name_content_width {
+        name_spans.push(Span::styled(
+            " ".repeat(name_width - name_content_width),
+            name_style,
+        ));
+    }
+
+    let mut spans = name_spans;
+    spans.push(Span::styled(size_col, other_style));
+    spans.push(Span::styled(date_col, other_style));
+    Line::from(spans)
 }
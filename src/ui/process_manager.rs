@@ -21,11 +21,18 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         return;
     }
 
+    let monitor = &app.process_monitor;
+    let visible = monitor.visible();
+
     // Header
     let header = Line::from(vec![
-        Span::styled(" Process Manager ", theme.header_style()),
+        Span::styled(" Process Monitor ", theme.header_style()),
         Span::styled(
-            format!(" [{} processes]", app.processes.len()),
+            format!(
+                " [{} processes, {}ms refresh]",
+                visible.len(),
+                monitor.refresh_interval.as_millis(),
+            ),
             theme.dim_style(),
         ),
     ]);
@@ -36,8 +43,8 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
 
     // Column headers
     let sort_indicator = |field: SortField| -> &str {
-        if app.process_sort_field == field {
-            if app.process_sort_asc { "\u{2191}" } else { "\u{2193}" }
+        if monitor.sort_field == field {
+            if monitor.sort_ascending { "\u{2191}" } else { "\u{2193}" }
         } else {
             " "
         }
@@ -75,15 +82,17 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
 
     // Process list
     let list_height = (inner.height - 5) as usize;
-    let start_index = app.process_selected_index.saturating_sub(list_height / 2);
-    let start_index = start_index.min(app.processes.len().saturating_sub(list_height));
+    let start_index = monitor.selected_index.saturating_sub(list_height / 2);
+    let start_index = start_index.min(visible.len().saturating_sub(list_height));
 
-    for (i, proc) in app.processes.iter().skip(start_index).take(list_height).enumerate() {
+    for (i, proc) in visible.iter().skip(start_index).take(list_height).enumerate() {
         let actual_index = start_index + i;
-        let is_cursor = actual_index == app.process_selected_index;
+        let is_cursor = actual_index == monitor.selected_index;
 
         let style = if is_cursor {
             theme.selected_style()
+        } else if process::is_high_usage(proc) {
+            theme.warning_style()
         } else {
             theme.normal_style()
         };
@@ -109,7 +118,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     }
 
     // 스크롤바
-    let total_processes = app.processes.len();
+    let total_processes = visible.len();
     if total_processes > list_height {
         let scrollbar = Scrollbar::default()
             .orientation(ScrollbarOrientation::VerticalRight)
@@ -117,7 +126,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             .end_symbol(Some("▼"));
 
         let mut scrollbar_state = ScrollbarState::new(total_processes)
-            .position(app.process_selected_index);
+            .position(monitor.selected_index);
 
         let scrollbar_area = Rect::new(
             inner.x + inner.width - 1,
@@ -130,8 +139,8 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     }
 
     // Confirm dialog
-    if let Some(pid) = app.process_confirm_kill {
-        let confirm_text = if app.process_force_kill {
+    if let Some((pid, _)) = monitor.confirm_kill {
+        let confirm_text = if monitor.force_kill {
             format!("Force kill process {}? (y/n)", pid)
         } else {
             format!("Kill process {}? (y/n)", pid)
@@ -141,6 +150,16 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
             Paragraph::new(confirm_line).alignment(ratatui::layout::Alignment::Center),
             Rect::new(inner.x, inner.y + inner.height - 3, inner.width, 1),
         );
+    } else if monitor.filter_active || !monitor.filter.is_empty() {
+        let filter_line = Line::from(vec![
+            Span::styled("Filter: ", theme.dim_style()),
+            Span::styled(&monitor.filter, theme.normal_style()),
+            Span::styled(if monitor.filter_active { "_" } else { "" }, theme.normal_style()),
+        ]);
+        frame.render_widget(
+            Paragraph::new(filter_line),
+            Rect::new(inner.x, inner.y + inner.height - 3, inner.width, 1),
+        );
     }
 
     // Footer - 첫 글자 강조 스타일
@@ -150,6 +169,8 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     let commands = [
         ("k", "ill "),
         ("K", "ill! "),
+        ("/", "filter "),
+        ("+/-", " rate "),
         ("r", "efresh "),
         ("q", "uit "),
     ];
@@ -192,30 +213,48 @@ fn truncate(s: &str, max_len: usize) -> String {
 }
 
 pub fn handle_input(app: &mut App, code: KeyCode) {
+    let monitor = &mut app.process_monitor;
+
     // Handle confirm dialog
-    if app.process_confirm_kill.is_some() {
+    if monitor.confirm_kill.is_some() {
         match code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
-                if let Some(pid) = app.process_confirm_kill {
-                    let result = if app.process_force_kill {
-                        process::force_kill_process(pid)
+                if let Some((pid, starttime)) = monitor.confirm_kill {
+                    let result = if monitor.force_kill {
+                        process::force_kill_process_with_verification(pid, starttime)
                     } else {
-                        process::kill_process(pid)
+                        process::kill_process_with_verification(pid, starttime)
                     };
                     match result {
                         Ok(_) => app.show_message(&format!("Process {} killed", pid)),
                         Err(e) => app.show_message(&format!("Error: {}", e)),
                     }
                     // Refresh process list
-                    app.processes = process::get_process_list();
-                    sort_processes(app);
+                    app.process_monitor.refresh();
                 }
-                app.process_confirm_kill = None;
-                app.process_force_kill = false;
+                app.process_monitor.confirm_kill = None;
+                app.process_monitor.force_kill = false;
             }
             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                app.process_confirm_kill = None;
-                app.process_force_kill = false;
+                monitor.confirm_kill = None;
+                monitor.force_kill = false;
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    // Handle filter text entry
+    if monitor.filter_active {
+        match code {
+            KeyCode::Enter | KeyCode::Esc => {
+                monitor.filter_active = false;
+            }
+            KeyCode::Backspace => {
+                monitor.filter.pop();
+            }
+            KeyCode::Char(c) => {
+                monitor.filter.push(c);
             }
             _ => {}
         }
@@ -227,84 +266,61 @@ pub fn handle_input(app: &mut App, code: KeyCode) {
             app.current_screen = Screen::DualPanel;
         }
         KeyCode::Up => {
-            if app.process_selected_index > 0 {
-                app.process_selected_index -= 1;
+            if monitor.selected_index > 0 {
+                monitor.selected_index -= 1;
             }
         }
         KeyCode::Down => {
-            if app.process_selected_index < app.processes.len().saturating_sub(1) {
-                app.process_selected_index += 1;
+            if monitor.selected_index < monitor.visible().len().saturating_sub(1) {
+                monitor.selected_index += 1;
             }
         }
         KeyCode::PageUp => {
-            app.process_selected_index = app.process_selected_index.saturating_sub(10);
+            monitor.selected_index = monitor.selected_index.saturating_sub(10);
         }
         KeyCode::PageDown => {
-            app.process_selected_index = (app.process_selected_index + 10)
-                .min(app.processes.len().saturating_sub(1));
+            monitor.selected_index = (monitor.selected_index + 10)
+                .min(monitor.visible().len().saturating_sub(1));
         }
         KeyCode::Home => {
-            app.process_selected_index = 0;
+            monitor.selected_index = 0;
         }
         KeyCode::End => {
-            app.process_selected_index = app.processes.len().saturating_sub(1);
+            monitor.selected_index = monitor.visible().len().saturating_sub(1);
         }
         KeyCode::Char('p') | KeyCode::Char('P') => {
-            toggle_sort(app, SortField::Pid);
+            monitor.set_sort(SortField::Pid);
         }
         KeyCode::Char('c') | KeyCode::Char('C') => {
-            toggle_sort(app, SortField::Cpu);
+            monitor.set_sort(SortField::Cpu);
         }
         KeyCode::Char('m') | KeyCode::Char('M') => {
-            toggle_sort(app, SortField::Mem);
+            monitor.set_sort(SortField::Mem);
         }
         KeyCode::Char('n') | KeyCode::Char('N') => {
-            toggle_sort(app, SortField::Command);
+            monitor.set_sort(SortField::Command);
+        }
+        KeyCode::Char('/') => {
+            monitor.filter_active = true;
+        }
+        KeyCode::Char('+') => {
+            monitor.adjust_refresh_interval(true);
+        }
+        KeyCode::Char('-') => {
+            monitor.adjust_refresh_interval(false);
         }
         KeyCode::Char('k') => {
             // 일반 kill (SIGTERM)
-            if let Some(proc) = app.processes.get(app.process_selected_index) {
-                app.process_confirm_kill = Some(proc.pid);
-                app.process_force_kill = false;
-            }
+            monitor.request_kill(false);
         }
         KeyCode::Char('K') => {
             // Force kill (SIGKILL)
-            if let Some(proc) = app.processes.get(app.process_selected_index) {
-                app.process_confirm_kill = Some(proc.pid);
-                app.process_force_kill = true;
-            }
+            monitor.request_kill(true);
         }
         KeyCode::Char('r') | KeyCode::Char('R') => {
-            app.processes = process::get_process_list();
-            sort_processes(app);
+            monitor.refresh();
             app.show_message("Refreshed");
         }
         _ => {}
     }
 }
-
-fn toggle_sort(app: &mut App, field: SortField) {
-    if app.process_sort_field == field {
-        app.process_sort_asc = !app.process_sort_asc;
-    } else {
-        app.process_sort_field = field;
-        app.process_sort_asc = field == SortField::Pid || field == SortField::Command;
-    }
-    sort_processes(app);
-}
-
-fn sort_processes(app: &mut App) {
-    let field = app.process_sort_field;
-    let asc = app.process_sort_asc;
-
-    app.processes.sort_by(|a, b| {
-        let cmp = match field {
-            SortField::Pid => a.pid.cmp(&b.pid),
-            SortField::Cpu => a.cpu.partial_cmp(&b.cpu).unwrap_or(std::cmp::Ordering::Equal),
-            SortField::Mem => a.mem.partial_cmp(&b.mem).unwrap_or(std::cmp::Ordering::Equal),
-            SortField::Command => a.command.cmp(&b.command),
-        };
-        if asc { cmp } else { cmp.reverse() }
-    });
-}
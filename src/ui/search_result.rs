@@ -0,0 +1,520 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+/// How a search term is interpreted by `execute_search_with_mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Case-insensitive substring match against the file/dir name.
+    Substring,
+    /// Shell-style glob (`*`, `?`) match against the file/dir name.
+    Glob,
+    /// Regular expression match against the file/dir name.
+    Regex,
+    /// Match against file content, recording the matching line.
+    Content,
+}
+
+impl SearchMode {
+    pub const ALL: [SearchMode; 4] = [
+        SearchMode::Substring,
+        SearchMode::Glob,
+        SearchMode::Regex,
+        SearchMode::Content,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchMode::Substring => "Substring",
+            SearchMode::Glob => "Glob",
+            SearchMode::Regex => "Regex",
+            SearchMode::Content => "Content",
+        }
+    }
+
+    pub fn from_index(index: usize) -> SearchMode {
+        Self::ALL[index % Self::ALL.len()]
+    }
+}
+
+/// A small broot-style pattern language read straight out of the search
+/// box, so typing doesn't require first cycling `SearchMode` with Tab: a
+/// bare term is a fuzzy/substring name match, `/pattern/` is a name regex,
+/// and `c/pattern/` searches file contents by regex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchPattern {
+    NameFuzzy(String),
+    NameRegex(String),
+    ContentRegex(String),
+}
+
+impl SearchPattern {
+    pub fn parse(input: &str) -> SearchPattern {
+        if let Some(rest) = input.strip_prefix("c/") {
+            if let Some(pattern) = rest.strip_suffix('/') {
+                if !pattern.is_empty() {
+                    return SearchPattern::ContentRegex(pattern.to_string());
+                }
+            }
+        }
+        if input.len() >= 2 && input.starts_with('/') && input.ends_with('/') {
+            let pattern = &input[1..input.len() - 1];
+            if !pattern.is_empty() {
+                return SearchPattern::NameRegex(pattern.to_string());
+            }
+        }
+        SearchPattern::NameFuzzy(input.to_string())
+    }
+
+    /// The `SearchMode` this pattern executes as, for status-line display.
+    pub fn mode(&self) -> SearchMode {
+        match self {
+            SearchPattern::NameFuzzy(_) => SearchMode::Substring,
+            SearchPattern::NameRegex(_) => SearchMode::Regex,
+            SearchPattern::ContentRegex(_) => SearchMode::Content,
+        }
+    }
+
+    pub fn term(&self) -> &str {
+        match self {
+            SearchPattern::NameFuzzy(s) | SearchPattern::NameRegex(s) | SearchPattern::ContentRegex(s) => s,
+        }
+    }
+}
+
+/// Run a search described by the pattern language in `input` (see
+/// `SearchPattern`), dispatching to name or content search as needed.
+pub fn execute_search_with_pattern(base_path: &Path, input: &str, max_results: usize) -> Vec<SearchResultItem> {
+    let max_results = max_results.min(MAX_RESULTS);
+    match SearchPattern::parse(input) {
+        SearchPattern::NameFuzzy(term) => search_names(base_path, &term, max_results, SearchMode::Substring),
+        SearchPattern::NameRegex(term) => search_names(base_path, &term, max_results, SearchMode::Regex),
+        SearchPattern::ContentRegex(term) => search_content_regex(base_path, &term, max_results),
+    }
+}
+
+/// One match surfaced by a search: either a name match (directory entry) or,
+/// for `SearchMode::Content`, a matching line inside a file.
+#[derive(Debug, Clone)]
+pub struct SearchResultItem {
+    pub name: String,
+    pub full_path: PathBuf,
+    pub relative_path: String,
+    pub is_directory: bool,
+    /// Set for content matches: the 1-based line number and a short snippet
+    /// of the matching line.
+    pub line_match: Option<(usize, String)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchResultState {
+    pub results: Vec<SearchResultItem>,
+    pub selected_index: usize,
+    pub scroll_offset: usize,
+    pub search_term: String,
+    pub base_path: PathBuf,
+    pub active: bool,
+}
+
+impl SearchResultState {
+    pub fn current_item(&self) -> Option<&SearchResultItem> {
+        self.results.get(self.selected_index)
+    }
+}
+
+/// Maximum number of matches collected, across every search mode.
+const MAX_RESULTS: usize = 1000;
+
+/// Files larger than this are skipped for content search - reading every
+/// line of a huge file would stall the search on the UI thread.
+const CONTENT_SEARCH_SIZE_LIMIT: u64 = 10 * 1024 * 1024;
+
+/// Bytes sniffed from the start of a file to decide whether it's binary.
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Characters kept verbatim around a content match; the rest of a long line
+/// is trimmed so results stay readable in a single row.
+const SNIPPET_CONTEXT: usize = 60;
+
+/// Recursive name search, kept for callers that don't care about search
+/// mode (defaults to a case-insensitive substring match, the original
+/// behavior of `execute_search`).
+pub fn execute_recursive_search(base_path: &Path, term: &str, max_results: usize) -> Vec<SearchResultItem> {
+    execute_search_with_mode(base_path, term, max_results, SearchMode::Substring)
+}
+
+pub fn execute_search_with_mode(
+    base_path: &Path,
+    term: &str,
+    max_results: usize,
+    mode: SearchMode,
+) -> Vec<SearchResultItem> {
+    let max_results = max_results.min(MAX_RESULTS);
+    match mode {
+        SearchMode::Content => search_content(base_path, term, max_results),
+        _ => search_names(base_path, term, max_results, mode),
+    }
+}
+
+fn search_names(base_path: &Path, term: &str, max_results: usize, mode: SearchMode) -> Vec<SearchResultItem> {
+    let matcher = match NameMatcher::new(term, mode) {
+        Some(matcher) => matcher,
+        None => return Vec::new(),
+    };
+
+    let mut results = Vec::new();
+    walk(base_path, &mut |entry, is_dir| {
+        if results.len() >= max_results {
+            return false;
+        }
+        let name = entry.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if matcher.matches(&name) {
+            results.push(make_item(base_path, entry, is_dir, None));
+        }
+        true
+    });
+    results
+}
+
+fn search_content(base_path: &Path, term: &str, max_results: usize) -> Vec<SearchResultItem> {
+    if term.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results = Vec::new();
+    walk(base_path, &mut |entry, is_dir| {
+        if results.len() >= max_results {
+            return false;
+        }
+        if is_dir {
+            return true;
+        }
+        if let Some(line_match) = search_file_content(entry, term) {
+            results.push(make_item(base_path, entry, false, Some(line_match)));
+        }
+        true
+    });
+    results
+}
+
+/// Search a single file's content for `term`, returning the first matching
+/// line and number. Skips files over the size threshold and files that
+/// look binary.
+fn search_file_content(path: &Path, term: &str) -> Option<(usize, String)> {
+    let term_lower = term.to_lowercase();
+    search_file_content_lines(path, |line| {
+        let byte_pos = line.to_lowercase().find(&term_lower)?;
+        Some(snippet(line, byte_pos))
+    })
+}
+
+/// Like `search_content`, but `pattern` is a regular expression matched
+/// against each line rather than a plain substring.
+fn search_content_regex(base_path: &Path, pattern: &str, max_results: usize) -> Vec<SearchResultItem> {
+    let Ok(re) = Regex::new(pattern) else {
+        return Vec::new();
+    };
+
+    let mut results = Vec::new();
+    walk(base_path, &mut |entry, is_dir| {
+        if results.len() >= max_results {
+            return false;
+        }
+        if is_dir {
+            return true;
+        }
+        let line_match = search_file_content_lines(entry, |line| {
+            re.find(line).map(|m| snippet(line, m.start()))
+        });
+        if let Some(line_match) = line_match {
+            results.push(make_item(base_path, entry, false, Some(line_match)));
+        }
+        true
+    });
+    results
+}
+
+/// Shared line-by-line content scan: skips files over the size threshold
+/// and files that look binary, then calls `try_match` on each line (with
+/// its trailing newline stripped) until one returns a snippet.
+fn search_file_content_lines(
+    path: &Path,
+    try_match: impl Fn(&str) -> Option<String>,
+) -> Option<(usize, String)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.len() > CONTENT_SEARCH_SIZE_LIMIT {
+        return None;
+    }
+
+    let file = File::open(path).ok()?;
+    let mut reader = BufReader::new(file);
+
+    let mut sniff = vec![0u8; BINARY_SNIFF_LEN.min(metadata.len() as usize)];
+    let read = reader.read(&mut sniff).ok()?;
+    if sniff[..read].contains(&0) {
+        return None;
+    }
+
+    let mut reader = BufReader::new(Cursor::new(sniff[..read].to_vec()).chain(reader));
+    let mut line = String::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).ok()?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        if let Some(snippet) = try_match(trimmed) {
+            return Some((line_number, snippet));
+        }
+    }
+
+    None
+}
+
+/// Trim a matching line down to a short, single-line snippet centered on
+/// the match (at `byte_pos`) so long lines don't blow out the result list's
+/// width.
+fn snippet(trimmed: &str, byte_pos: usize) -> String {
+    let match_start = trimmed[..byte_pos].chars().count();
+    let chars: Vec<char> = trimmed.chars().collect();
+    let start = match_start.saturating_sub(SNIPPET_CONTEXT);
+    let end = (match_start + SNIPPET_CONTEXT).min(chars.len());
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push_str("...");
+    }
+    out.extend(&chars[start..end]);
+    if end < chars.len() {
+        out.push_str("...");
+    }
+    out
+}
+
+enum NameMatcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl NameMatcher {
+    fn new(term: &str, mode: SearchMode) -> Option<NameMatcher> {
+        match mode {
+            SearchMode::Substring => Some(NameMatcher::Substring(term.to_lowercase())),
+            SearchMode::Glob => Regex::new(&glob_to_regex(term)).ok().map(NameMatcher::Regex),
+            SearchMode::Regex => Regex::new(term).ok().map(NameMatcher::Regex),
+            SearchMode::Content => None,
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            NameMatcher::Substring(term) => name.to_lowercase().contains(term),
+            NameMatcher::Regex(re) => re.is_match(name),
+        }
+    }
+}
+
+/// Translate a shell-style glob (`*`, `?`) into an anchored, case-insensitive
+/// regex. Other regex metacharacters in `pattern` are escaped so they match
+/// literally, matching the least-surprising reading of a glob term.
+fn glob_to_regex(pattern: &str) -> String {
+    let mut out = String::from("(?i)^");
+    for c in pattern.chars() {
+        match c {
+            '*' => out.push_str(".*"),
+            '?' => out.push('.'),
+            _ => out.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    out.push('$');
+    out
+}
+
+fn make_item(base_path: &Path, full_path: &Path, is_directory: bool, line_match: Option<(usize, String)>) -> SearchResultItem {
+    let name = full_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let relative_path = full_path
+        .strip_prefix(base_path)
+        .unwrap_or(full_path)
+        .display()
+        .to_string();
+
+    SearchResultItem {
+        name,
+        full_path: full_path.to_path_buf(),
+        relative_path,
+        is_directory,
+        line_match,
+    }
+}
+
+/// Walk `base_path` depth-first, calling `visit(entry_path, is_dir)` for
+/// every entry. `visit` returns `false` to stop the walk early (e.g. once
+/// the result cap is reached).
+pub(crate) fn walk(base_path: &Path, visit: &mut dyn FnMut(&Path, bool) -> bool) {
+    let mut stack = vec![base_path.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_dir = path.is_dir();
+
+            if !visit(&path, is_dir) {
+                return;
+            }
+
+            if is_dir {
+                stack.push(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn create_temp_dir() -> PathBuf {
+        let unique_id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let temp_dir = std::env::temp_dir().join(format!(
+            "cokacdir_search_test_{}_{}",
+            std::process::id(),
+            unique_id
+        ));
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+        temp_dir
+    }
+
+    fn cleanup_temp_dir(path: &Path) {
+        let _ = fs::remove_dir_all(path);
+    }
+
+    #[test]
+    fn test_substring_search_is_case_insensitive() {
+        let temp_dir = create_temp_dir();
+        fs::write(temp_dir.join("Report.TXT"), "").unwrap();
+        fs::write(temp_dir.join("other.log"), "").unwrap();
+
+        let results = execute_search_with_mode(&temp_dir, "report", 1000, SearchMode::Substring);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "Report.TXT");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_glob_search_matches_extension() {
+        let temp_dir = create_temp_dir();
+        fs::write(temp_dir.join("a.rs"), "").unwrap();
+        fs::write(temp_dir.join("b.txt"), "").unwrap();
+
+        let results = execute_search_with_mode(&temp_dir, "*.rs", 1000, SearchMode::Glob);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "a.rs");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_regex_search_matches_names() {
+        let temp_dir = create_temp_dir();
+        fs::write(temp_dir.join("log_2024.txt"), "").unwrap();
+        fs::write(temp_dir.join("notes.txt"), "").unwrap();
+
+        let results = execute_search_with_mode(&temp_dir, r"^log_\d+", 1000, SearchMode::Regex);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "log_2024.txt");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_content_search_finds_matching_line_and_number() {
+        let temp_dir = create_temp_dir();
+        fs::write(temp_dir.join("notes.txt"), "first line\nneedle here\nlast line").unwrap();
+
+        let results = execute_search_with_mode(&temp_dir, "needle", 1000, SearchMode::Content);
+        assert_eq!(results.len(), 1);
+        let (line_number, snippet) = results[0].line_match.clone().unwrap();
+        assert_eq!(line_number, 2);
+        assert!(snippet.contains("needle"));
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_content_search_skips_binary_files() {
+        let temp_dir = create_temp_dir();
+        fs::write(temp_dir.join("data.bin"), [b'n', b'e', 0u8, b'e', b'd', b'l', b'e']).unwrap();
+
+        let results = execute_search_with_mode(&temp_dir, "needle", 1000, SearchMode::Content);
+        assert!(results.is_empty());
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_result_cap_is_enforced() {
+        let temp_dir = create_temp_dir();
+        for i in 0..5 {
+            fs::write(temp_dir.join(format!("match_{i}.txt")), "").unwrap();
+        }
+
+        let results = execute_search_with_mode(&temp_dir, "match", 3, SearchMode::Substring);
+        assert_eq!(results.len(), 3);
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_search_pattern_parse() {
+        assert_eq!(SearchPattern::parse("report"), SearchPattern::NameFuzzy("report".to_string()));
+        assert_eq!(SearchPattern::parse("/^log_/"), SearchPattern::NameRegex("^log_".to_string()));
+        assert_eq!(SearchPattern::parse("c/needle/"), SearchPattern::ContentRegex("needle".to_string()));
+        // 빈 괄호는 패턴이 아니라 평범한 이름 퍼지 매치로 취급한다
+        assert_eq!(SearchPattern::parse("//"), SearchPattern::NameFuzzy("//".to_string()));
+        assert_eq!(SearchPattern::parse("c//"), SearchPattern::NameFuzzy("c//".to_string()));
+    }
+
+    #[test]
+    fn test_execute_search_with_pattern_content_regex() {
+        let temp_dir = create_temp_dir();
+        fs::write(temp_dir.join("notes.txt"), "first line\nneedle-42 here\nlast line").unwrap();
+
+        let results = execute_search_with_pattern(&temp_dir, r"c/needle-\d+/", 1000);
+        assert_eq!(results.len(), 1);
+        let (line_number, snippet) = results[0].line_match.clone().unwrap();
+        assert_eq!(line_number, 2);
+        assert!(snippet.contains("needle-42"));
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
+    #[test]
+    fn test_execute_search_with_pattern_name_regex() {
+        let temp_dir = create_temp_dir();
+        fs::write(temp_dir.join("log_2024.txt"), "").unwrap();
+        fs::write(temp_dir.join("notes.txt"), "").unwrap();
+
+        let results = execute_search_with_pattern(&temp_dir, r"/^log_\d+/", 1000);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "log_2024.txt");
+
+        cleanup_temp_dir(&temp_dir);
+    }
+}
@@ -1,5 +1,6 @@
 use ratatui::style::{Color, Modifier, Style};
 use std::path::Path;
+use std::sync::OnceLock;
 
 /// 토큰 유형
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,10 +45,15 @@ pub enum Language {
     Swift,
     Kotlin,
     Plain,
+    /// A language this binary doesn't ship, declared at runtime by a
+    /// `[[language]]` table in `~/.config/cokacdir/languages.toml` (see
+    /// `crate::services::custom_languages`). The index points into the
+    /// `CustomLanguages` registry the highlighter loaded alongside it.
+    Custom(usize),
 }
 
 impl Language {
-    /// 파일 확장자로 언어 감지
+    /// 파일 확장자로 언어 감지 (내장 언어만)
     pub fn from_extension(path: &Path) -> Self {
         let ext = path
             .extension()
@@ -55,7 +61,30 @@ impl Language {
             .map(|e| e.to_lowercase())
             .unwrap_or_default();
 
-        match ext.as_str() {
+        Self::from_extension_str(&ext)
+    }
+
+    /// Like `from_extension`, but also consults `custom` first so a
+    /// user-defined language from `languages.toml` can claim an extension
+    /// this binary doesn't otherwise recognize.
+    pub fn resolve_with_custom(
+        path: &Path,
+        custom: &crate::services::custom_languages::CustomLanguages,
+    ) -> Self {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+
+        if let Some(idx) = custom.resolve_extension(&ext) {
+            return Language::Custom(idx);
+        }
+        Self::from_extension_str(&ext)
+    }
+
+    fn from_extension_str(ext: &str) -> Self {
+        match ext {
             "rs" => Language::Rust,
             "py" | "pyw" | "pyi" => Language::Python,
             "js" | "mjs" | "cjs" | "jsx" => Language::JavaScript,
@@ -81,6 +110,37 @@ impl Language {
         }
     }
 
+    /// Map a Markdown fenced-code-block language tag (e.g. ```rust, ```js)
+    /// to a `Language`. Returns `None` for tags this highlighter doesn't
+    /// recognize, so callers can fall back to unhighlighted rendering
+    /// instead of silently guessing `Plain`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag.to_lowercase().as_str() {
+            "rust" | "rs" => Some(Language::Rust),
+            "python" | "py" => Some(Language::Python),
+            "javascript" | "js" | "jsx" | "mjs" | "cjs" => Some(Language::JavaScript),
+            "typescript" | "ts" | "tsx" => Some(Language::TypeScript),
+            "c" | "h" => Some(Language::C),
+            "cpp" | "c++" | "cc" | "cxx" | "hpp" => Some(Language::Cpp),
+            "java" => Some(Language::Java),
+            "go" | "golang" => Some(Language::Go),
+            "html" | "htm" => Some(Language::Html),
+            "css" | "scss" | "sass" => Some(Language::Css),
+            "json" | "jsonc" => Some(Language::Json),
+            "yaml" | "yml" => Some(Language::Yaml),
+            "toml" => Some(Language::Toml),
+            "markdown" | "md" => Some(Language::Markdown),
+            "bash" | "sh" | "shell" | "zsh" => Some(Language::Shell),
+            "sql" => Some(Language::Sql),
+            "xml" | "svg" => Some(Language::Xml),
+            "ruby" | "rb" => Some(Language::Ruby),
+            "php" => Some(Language::Php),
+            "swift" => Some(Language::Swift),
+            "kotlin" | "kt" => Some(Language::Kotlin),
+            _ => None,
+        }
+    }
+
     /// 언어 이름 반환
     pub fn name(&self) -> &'static str {
         match self {
@@ -106,6 +166,11 @@ impl Language {
             Language::Swift => "Swift",
             Language::Kotlin => "Kotlin",
             Language::Plain => "Plain",
+            // A real per-instance name would need owned storage here,
+            // which would cost `Language` its `Copy` impl; callers that
+            // need the declared name look it up in the registry instead
+            // (see `CustomLanguages::defs`).
+            Language::Custom(_) => "Custom",
         }
     }
 }
@@ -126,6 +191,9 @@ pub struct SyntaxColors {
     pub constant: Color,
     pub bracket: Color,
     pub normal: Color,
+    /// Rainbow-bracket palette, indexed by nesting depth modulo its length.
+    /// Falls back to `bracket` if ever empty.
+    pub bracket_palette: Vec<Color>,
 }
 
 impl Default for SyntaxColors {
@@ -145,6 +213,13 @@ impl Default for SyntaxColors {
             constant: Color::Rgb(189, 147, 249),   // Purple
             bracket: Color::Rgb(248, 248, 242),    // Foreground
             normal: Color::Rgb(248, 248, 242),     // Foreground
+            bracket_palette: vec![
+                Color::Rgb(255, 121, 198),  // Pink
+                Color::Rgb(139, 233, 253),  // Cyan
+                Color::Rgb(80, 250, 123),   // Green
+                Color::Rgb(241, 250, 140),  // Yellow
+                Color::Rgb(189, 147, 249),  // Purple
+            ],
         }
     }
 }
@@ -166,7 +241,25 @@ impl SyntaxColors {
             constant: Color::LightMagenta,
             bracket: Color::White,
             normal: Color::White,
+            bracket_palette: vec![
+                Color::Magenta,
+                Color::Cyan,
+                Color::Green,
+                Color::Yellow,
+                Color::LightMagenta,
+            ],
+        }
+    }
+
+    /// Rainbow-bracket color for nesting `depth` (0-based), wrapping around
+    /// the palette. Falls back to the flat `bracket` color if the palette
+    /// is empty.
+    pub fn style_for_bracket(&self, depth: i32) -> Style {
+        if self.bracket_palette.is_empty() {
+            return Style::default().fg(self.bracket);
         }
+        let idx = (depth.max(0) as usize) % self.bracket_palette.len();
+        Style::default().fg(self.bracket_palette[idx])
     }
 
     /// 토큰 타입에 따른 스타일 반환
@@ -203,75 +296,365 @@ impl SyntaxColors {
 pub struct Token {
     pub text: String,
     pub token_type: TokenType,
+    /// Start/end column (char offset, not byte offset -- this highlighter
+    /// indexes every line as `Vec<char>` throughout for unicode safety) of
+    /// this token within its line. Filled in by `tokenize_line` after the
+    /// language-specific tokenizer runs, so individual `tokenize_*` methods
+    /// never have to track it themselves.
+    pub start: usize,
+    pub end: usize,
+    /// Nesting depth for a `Bracket` token, used for rainbow-bracket
+    /// coloring (`SyntaxColors::style_for_bracket`); `None` for every other
+    /// token type. Also filled in by `tokenize_line`, carrying the running
+    /// depth across lines via `LexerState::bracket_depth`.
+    pub bracket_depth: Option<i32>,
 }
 
-/// 문법 강조기
-#[derive(Debug, Clone)]
-pub struct SyntaxHighlighter {
-    language: Language,
-    colors: SyntaxColors,
-    in_multiline_comment: bool,
-    in_multiline_string: bool,
+/// The lexer state carried from the end of one line to the start of the
+/// next: whether the cursor left off inside a block comment, a Rust-style
+/// raw string (with the hash count its closing delimiter needs to match), a
+/// Python triple-quoted string (with its quote character), a JS/TS template
+/// literal, a shell heredoc body, a YAML block scalar, or at some bracket
+/// nesting depth. Each `tokenize_*` method checks the field it cares about
+/// at the top of a line to resume an unterminated construct, and sets it
+/// again if the construct is still open at end-of-line; `bracket_depth` is
+/// instead maintained centrally by `tokenize_line`, since every tokenizer
+/// funnels its `Bracket` tokens through the same post-processing pass.
+/// Carrying a heredoc's delimiter as a `String` is why this can't be `Copy`
+/// like the rest of the lexer state.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LexerState {
+    pub in_block_comment: bool,
+    pub in_raw_string: Option<u8>,
+    pub in_triple_quoted_string: Option<char>,
+    pub in_template_literal: bool,
+    pub bracket_depth: i32,
+    pub in_heredoc: Option<HeredocState>,
+    /// Indentation (leading space count) of the key line that opened a
+    /// `|`/`>`/`|-`/`>-` block scalar, set by `tokenize_yaml`. Lines
+    /// indented deeper than this (or blank) are carried as the scalar's
+    /// body; the first line back at or above this indent ends it.
+    pub in_yaml_block_scalar: Option<usize>,
+    /// The previous line's trimmed text, set by `tokenize_markdown` whenever
+    /// that line fell through as plain paragraph text. Markdown's setext
+    /// headings put the heading marker on the line *below* the text (an
+    /// all-`=` or all-`-` underline), so recognizing the underline needs
+    /// this lookback; `tokenize_line` only ever moves forward, so the
+    /// paragraph line itself has already been returned as plain text by the
+    /// time its underline arrives and can't be repainted in hindsight.
+    pub md_prev_line: Option<String>,
 }
 
-impl SyntaxHighlighter {
-    pub fn new(language: Language) -> Self {
-        let truecolor = std::env::var("COLORTERM")
-            .map(|v| v == "truecolor" || v == "24bit")
-            .unwrap_or(false);
+/// The still-open heredoc/nowdoc a lexer state is resuming -- shell's
+/// `<<`/`<<-` (named after the `io_here` redirection POSIX shells parse it
+/// as), Ruby's `<<`/`<<-`/`<<~`, or PHP's `<<<`. `strip_tabs` covers both
+/// shell's `-` (strip leading tabs) and Ruby's `-`/`~` (allow an indented
+/// terminator); `quoted` means the body is literal text with no variable
+/// interpolation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeredocState {
+    pub delimiter: String,
+    pub strip_tabs: bool,
+    pub quoted: bool,
+}
+
+fn default_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn default_ident_continue(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
 
+/// Ruby identifiers may end in `?`/`!` (`empty?`, `save!`).
+fn ruby_ident_continue(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '?' || c == '!'
+}
+
+/// Declarative description of a language's lexical rules: keyword/type
+/// tables, comment delimiters, string/raw-string rules and identifier
+/// predicates, enough for `SyntaxHighlighter::tokenize_with` to drive
+/// scanning without a bespoke `tokenize_*` method. Built-in languages build
+/// one from `'static` tables (`LanguageDef::c_family`); languages declared
+/// at runtime in `languages.toml` build one that borrows from their own
+/// owned strings instead (see `crate::services::custom_languages`).
+pub struct LanguageDef<'a> {
+    pub keywords: &'a [&'a str],
+    pub types: &'a [&'a str],
+    pub line_comment: Option<&'a str>,
+    pub block_comment: Option<(&'a str, &'a str)>,
+    pub nested_block_comments: bool,
+    pub string_delimiters: &'a [char],
+    pub raw_string_prefix: Option<char>,
+    /// Trailing type suffixes a numeric literal may end in, longest match
+    /// wins -- Rust's `u32`/`f64`, Java's `L`/`f`/`d`, Ruby's `r`/`i`.
+    /// Passed straight through to `scan_number`.
+    pub numeric_suffixes: &'a [&'a str],
+    pub ident_start: fn(char) -> bool,
+    pub ident_continue: fn(char) -> bool,
+    pub support_attributes: bool,
+    /// Whether `'` can introduce a lifetime (`'a`, `'static`) rather than
+    /// always a char literal (`'a'`). Only Rust sets this -- without it,
+    /// `fn f<'a>(x: &'a str)` would read as an unterminated char literal
+    /// that swallows the rest of the line.
+    pub lifetimes: bool,
+    /// Sigils that introduce a variable reference when immediately
+    /// followed by an identifier character, emitted as `TokenType::Variable`
+    /// -- PHP's `$name`, Ruby's `@ivar`/`@@cvar`/`$global`. A doubled sigil
+    /// (`@@`) is swallowed as part of the same token.
+    pub variable_sigils: &'a [char],
+    /// Sigil that introduces a symbol literal when followed by an
+    /// identifier character, emitted as `TokenType::Constant` -- Ruby's
+    /// `:name`. A bare `:` (hash literal, ternary) still falls through to
+    /// the operator rule since there's no identifier after it.
+    pub symbol_sigil: Option<char>,
+    /// An embedded-script open/close marker pair emitted as a single
+    /// `TokenType::Keyword` token each, e.g. PHP's `<?php` / `?>`.
+    pub tag_markers: Option<(&'a str, &'a str)>,
+    /// A second line-comment prefix alongside `line_comment`, e.g. PHP
+    /// accepting both `//` and `#`.
+    pub extra_line_comment: Option<&'a str>,
+    /// Whether an identifier starting with an uppercase letter classifies
+    /// as `TokenType::Type` regardless of the `types` table -- Ruby's
+    /// convention that any constant-cased name (`String`, `MAX_SIZE`) is a
+    /// class/module/constant reference.
+    pub capitalized_is_type: bool,
+    /// A precomputed perfect-hash `keywords`/`types` lookup, checked before
+    /// falling back to the linear `keywords.contains()`/`types.contains()`
+    /// scans below. `None` for runtime-loaded custom languages, whose word
+    /// lists aren't known until the user's `languages.toml` is parsed and so
+    /// can't be baked into a `'static`-cached table.
+    pub keyword_table: Option<&'a KeywordTable>,
+    /// Whether a `"""` on its own opens a multiline string that continues
+    /// until a later line closes it -- Swift's triple-quoted literal.
+    /// Carried across lines the same way Python's (bespoke) triple-quoted
+    /// strings are, via `LexerState::in_triple_quoted_string`.
+    pub triple_quote: bool,
+    /// The heredoc/nowdoc redirect token that opens a multiline string
+    /// continuing until a line matching the identifier that follows it --
+    /// PHP's `<<<` and Ruby's `<<`. `None` for languages without one.
+    pub heredoc_prefix: Option<&'a str>,
+    /// Whether an unquoted (interpolating) heredoc body gets `$name`/
+    /// `${...}` highlighted inside it the way shell's heredocs already do
+    /// -- true for PHP, false for Ruby (whose heredoc bodies don't get the
+    /// `#{...}`-aware treatment `interp_hash_brace` gives double-quoted
+    /// strings).
+    pub heredoc_dollar_interpolation: bool,
+    /// Whether a double-quoted string interpolates `#{expr}` -- Ruby's
+    /// convention. The literal run before `#{` flushes as `String`, the
+    /// expression up to the matching `}` re-enters `tokenize_with` under
+    /// this same `LanguageDef` so it renders with real token types, then
+    /// the string resumes. Single-quoted strings never interpolate.
+    pub interp_hash_brace: bool,
+    /// Whether a double-quoted string interpolates PHP-style `$name` and
+    /// `{$expr}`. A bare `$name` emits as `TokenType::Variable`; a
+    /// `{$expr}` is handled the same way `interp_hash_brace` handles
+    /// `#{expr}`.
+    pub interp_dollar: bool,
+    /// Multi-character operators this language recognizes, tried
+    /// longest-first at an operator position via `scan_operator` --
+    /// Rust's `..=`, Ruby's `<=>`/`=~`, PHP's `??=`/`->`, Swift's `?.`/
+    /// `..<`. Empty for languages without a table, which fall back to the
+    /// older greedy same-symbol-class scan (bounded to 3 characters).
+    pub operators: &'a [&'a str],
+}
+
+impl<'a> LanguageDef<'a> {
+    /// The shape shared by every C-family language this highlighter ships
+    /// (Rust, C/C++, Java/Kotlin, Go, JS/TS, Swift): `//` line comments,
+    /// `/* */` block comments that don't nest, double/single-quoted
+    /// strings, no raw strings, no numeric suffixes. Callers that differ
+    /// from one of these (Rust's raw strings and nestable block comments)
+    /// override the relevant field with struct-update syntax; attribute
+    /// support (off for Go) is the `support_attributes` argument.
+    pub fn c_family(keywords: &'a [&'a str], types: &'a [&'a str], support_attributes: bool) -> Self {
         Self {
-            language,
-            colors: if truecolor {
-                SyntaxColors::default()
-            } else {
-                SyntaxColors::compatible()
-            },
-            in_multiline_comment: false,
-            in_multiline_string: false,
+            keywords,
+            types,
+            line_comment: Some("//"),
+            block_comment: Some(("/*", "*/")),
+            nested_block_comments: false,
+            string_delimiters: &['"', '\''],
+            raw_string_prefix: None,
+            numeric_suffixes: &[],
+            ident_start: default_ident_start,
+            ident_continue: default_ident_continue,
+            support_attributes,
+            lifetimes: false,
+            variable_sigils: &[],
+            symbol_sigil: None,
+            tag_markers: None,
+            extra_line_comment: None,
+            capitalized_is_type: false,
+            keyword_table: None,
+            triple_quote: false,
+            heredoc_prefix: None,
+            heredoc_dollar_interpolation: false,
+            interp_hash_brace: false,
+            interp_dollar: false,
+            operators: &[],
         }
     }
+}
 
-    /// 라인을 토큰화
-    pub fn tokenize_line(&mut self, line: &str) -> Vec<Token> {
-        match self.language {
-            Language::Rust => self.tokenize_rust(line),
-            Language::Python => self.tokenize_python(line),
-            Language::JavaScript | Language::TypeScript => self.tokenize_javascript(line),
-            Language::C | Language::Cpp => self.tokenize_c(line),
-            Language::Java | Language::Kotlin => self.tokenize_java(line),
-            Language::Go => self.tokenize_go(line),
-            Language::Html | Language::Xml => self.tokenize_html(line),
-            Language::Css => self.tokenize_css(line),
-            Language::Json => self.tokenize_json(line),
-            Language::Yaml | Language::Toml => self.tokenize_yaml(line),
-            Language::Shell => self.tokenize_shell(line),
-            Language::Sql => self.tokenize_sql(line),
-            Language::Ruby => self.tokenize_ruby(line),
-            Language::Php => self.tokenize_php(line),
-            Language::Swift => self.tokenize_swift(line),
-            Language::Markdown => self.tokenize_markdown(line),
-            Language::Plain => vec![Token {
-                text: line.to_string(),
-                token_type: TokenType::Normal,
-            }],
+/// Fixed-size, open-addressed keyword lookup in the spirit of a
+/// gperf-generated perfect hash (see rhai's `lookup_symbol_from_syntax`):
+/// a word hashes straight to a slot instead of being checked against every
+/// entry in a keyword list. Built once per language and cached behind a
+/// `OnceLock`, so classifying a word is a hash, a length/first-byte check,
+/// and (on a real match) one string compare -- never an O(n) scan.
+pub(crate) struct KeywordTable {
+    slots: Vec<Option<(&'static str, TokenType)>>,
+}
+
+impl KeywordTable {
+    fn build(entries: &[(&'static str, TokenType)]) -> Self {
+        let size = (entries.len() * 2).next_power_of_two().max(16);
+        let mut slots = vec![None; size];
+        for &(word, token_type) in entries {
+            let mut idx = Self::hash(word) as usize % size;
+            while slots[idx].is_some() {
+                idx = (idx + 1) % size;
+            }
+            slots[idx] = Some((word, token_type));
         }
+        Self { slots }
     }
 
-    /// 토큰에 대한 스타일 가져오기
-    pub fn style_for(&self, token_type: TokenType) -> Style {
-        self.colors.style_for(token_type)
+    /// Hashes on length plus the first/middle/last byte, the same kind of
+    /// cheap fixed-position sampling gperf's generated hash functions use
+    /// instead of hashing every byte of the word.
+    fn hash(word: &str) -> u32 {
+        let bytes = word.as_bytes();
+        let len = bytes.len() as u32;
+        let first = *bytes.first().unwrap_or(&0) as u32;
+        let mid = *bytes.get(bytes.len() / 2).unwrap_or(&0) as u32;
+        let last = *bytes.last().unwrap_or(&0) as u32;
+        len.wrapping_mul(31)
+            .wrapping_add(first.wrapping_mul(17))
+            .wrapping_add(mid.wrapping_mul(7))
+            .wrapping_add(last)
     }
 
-    /// 상태 리셋
-    pub fn reset(&mut self) {
-        self.in_multiline_comment = false;
-        self.in_multiline_string = false;
+    /// Looks up `word` exactly as given (case-sensitive; a case-insensitive
+    /// caller such as `tokenize_sql` lowercases it first), failing fast on a
+    /// length or first-byte mismatch before falling back to the full string
+    /// compare.
+    fn get(&self, word: &str) -> Option<TokenType> {
+        let size = self.slots.len();
+        let first_byte = word.as_bytes().first().copied();
+        let mut idx = Self::hash(word) as usize % size;
+        loop {
+            match self.slots[idx] {
+                None => return None,
+                Some((candidate, token_type)) => {
+                    if candidate.len() == word.len()
+                        && candidate.as_bytes().first().copied() == first_byte
+                        && candidate == word
+                    {
+                        return Some(token_type);
+                    }
+                    idx = (idx + 1) % size;
+                }
+            }
+        }
     }
+}
 
-    // Rust 토큰화
-    fn tokenize_rust(&mut self, line: &str) -> Vec<Token> {
-        let keywords = [
+/// SQL's canonical lowercase keyword/type/function table, built once and
+/// reused for every `tokenize_sql` call (SQL itself is case-insensitive,
+/// so the caller lowercases each scanned word before looking it up here).
+fn sql_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "select", "from", "where", "and", "or", "not", "in", "between",
+            "like", "is", "null", "true", "false", "as", "on", "join", "left",
+            "right", "inner", "outer", "full", "cross", "natural", "using",
+            "group", "by", "having", "order", "asc", "desc", "limit", "offset",
+            "insert", "into", "values", "update", "set", "delete", "create",
+            "table", "index", "view", "drop", "alter", "add", "column",
+            "primary", "key", "foreign", "references", "unique", "check",
+            "default", "constraint", "cascade", "restrict", "union", "all",
+            "except", "intersect", "exists", "case", "when", "then", "else",
+            "end", "if", "begin", "commit", "rollback", "transaction",
+            "declare", "cursor", "fetch", "close", "open", "for", "while",
+            "loop", "return", "function", "procedure", "trigger", "database",
+            "schema", "grant", "revoke", "with", "recursive", "distinct",
+        ];
+        const TYPES: &[&str] = &[
+            "int", "integer", "smallint", "bigint", "decimal", "numeric",
+            "float", "real", "double", "precision", "char", "varchar", "text",
+            "date", "time", "timestamp", "datetime", "boolean", "bool", "blob",
+            "clob", "binary", "varbinary", "uuid", "json", "jsonb", "array",
+            "serial", "bigserial", "money", "interval",
+        ];
+        const FUNCTIONS: &[&str] = &[
+            "count", "sum", "avg", "min", "max", "coalesce", "nullif",
+            "cast", "convert", "concat", "substring", "trim", "upper", "lower",
+            "length", "replace", "round", "floor", "ceil", "abs", "now",
+            "current_date", "current_time", "current_timestamp", "extract",
+            "date_part", "date_trunc", "row_number", "rank", "dense_rank",
+            "first_value", "last_value", "lag", "lead", "over", "partition",
+        ];
+        let entries: Vec<(&'static str, TokenType)> = KEYWORDS
+            .iter()
+            .map(|w| (*w, TokenType::Keyword))
+            .chain(TYPES.iter().map(|w| (*w, TokenType::Type)))
+            .chain(FUNCTIONS.iter().map(|w| (*w, TokenType::Function)))
+            .collect();
+        KeywordTable::build(&entries)
+    })
+}
+
+/// Bash's canonical keyword/builtin table (shell is case-sensitive, unlike
+/// SQL, so words are looked up as scanned with no lowercasing).
+fn shell_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "if", "then", "else", "elif", "fi", "case", "esac", "for", "while",
+            "until", "do", "done", "in", "function", "select", "time", "coproc",
+            "return", "exit", "break", "continue", "local", "declare", "typeset",
+            "export", "readonly", "unset", "shift", "source", "alias", "unalias",
+            "set", "shopt", "trap", "exec", "eval", "true", "false",
+        ];
+        const BUILTINS: &[&str] = &[
+            "echo", "printf", "read", "cd", "pwd", "pushd", "popd", "dirs",
+            "let", "test", "[", "[[", "]]", "]", "getopts", "hash", "type",
+            "umask", "ulimit", "wait", "jobs", "fg", "bg", "kill", "disown",
+            "suspend", "logout", "history", "fc", "bind", "help", "enable",
+            "builtin", "command", "compgen", "complete", "compopt", "mapfile",
+            "readarray", "coproc",
+        ];
+        let entries: Vec<(&'static str, TokenType)> = KEYWORDS
+            .iter()
+            .map(|w| (*w, TokenType::Keyword))
+            .chain(BUILTINS.iter().map(|w| (*w, TokenType::Function)))
+            .collect();
+        KeywordTable::build(&entries)
+    })
+}
+
+/// Builds a `KeywordTable` from a language's keyword/type lists for the
+/// table-driven `tokenize_with` engine, used by each `xxx_keyword_table()`
+/// below -- one `.chain()` away from the SQL/shell tables above, just
+/// without a third `Function` tier since `tokenize_with` already classifies
+/// a trailing-`(` identifier as a function dynamically.
+fn build_keyword_table(keywords: &[&'static str], types: &[&'static str]) -> KeywordTable {
+    let entries: Vec<(&'static str, TokenType)> = keywords
+        .iter()
+        .map(|w| (*w, TokenType::Keyword))
+        .chain(types.iter().map(|w| (*w, TokenType::Type)))
+        .collect();
+    KeywordTable::build(&entries)
+}
+
+/// Rust's keyword/type table for `tokenize_rust`.
+fn rust_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
             "as", "async", "await", "break", "const", "continue", "crate", "dyn",
             "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
             "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
@@ -280,7 +663,7 @@ impl SyntaxHighlighter {
             "final", "macro", "override", "priv", "typeof", "unsized", "virtual",
             "yield",
         ];
-        let types = [
+        const TYPES: &[&str] = &[
             "i8", "i16", "i32", "i64", "i128", "isize",
             "u8", "u16", "u32", "u64", "u128", "usize",
             "f32", "f64", "bool", "char", "str", "String",
@@ -288,62 +671,756 @@ impl SyntaxHighlighter {
             "HashMap", "HashSet", "BTreeMap", "BTreeSet",
             "Path", "PathBuf", "OsStr", "OsString",
         ];
+        build_keyword_table(KEYWORDS, TYPES)
+    })
+}
 
-        self.tokenize_c_like(line, &keywords, &types, "//", ("/*", "*/"), true)
-    }
+/// JavaScript/TypeScript's keyword/type table for `tokenize_javascript`.
+fn javascript_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "break", "case", "catch", "class", "const", "continue", "debugger",
+            "default", "delete", "do", "else", "export", "extends", "false",
+            "finally", "for", "function", "if", "import", "in", "instanceof",
+            "let", "new", "null", "return", "super", "switch", "this", "throw",
+            "true", "try", "typeof", "var", "void", "while", "with", "yield",
+            "async", "await", "of", "static", "get", "set", "from", "as",
+            "interface", "type", "enum", "implements", "private", "protected",
+            "public", "readonly", "abstract", "declare", "namespace", "module",
+        ];
+        const TYPES: &[&str] = &[
+            "string", "number", "boolean", "object", "any", "void", "never",
+            "unknown", "undefined", "null", "Array", "Map", "Set", "Promise",
+            "Date", "RegExp", "Error", "Function", "Object", "Symbol", "BigInt",
+        ];
+        build_keyword_table(KEYWORDS, TYPES)
+    })
+}
 
-    // Python 토큰화
-    fn tokenize_python(&mut self, line: &str) -> Vec<Token> {
-        let keywords = [
-            "and", "as", "assert", "async", "await", "break", "class", "continue",
-            "def", "del", "elif", "else", "except", "False", "finally", "for",
-            "from", "global", "if", "import", "in", "is", "lambda", "None",
-            "nonlocal", "not", "or", "pass", "raise", "return", "True", "try",
-            "while", "with", "yield",
+/// C/C++'s keyword/type table for `tokenize_c`.
+fn c_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "auto", "break", "case", "char", "const", "continue", "default",
+            "do", "double", "else", "enum", "extern", "float", "for", "goto",
+            "if", "inline", "int", "long", "register", "restrict", "return",
+            "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+            "union", "unsigned", "void", "volatile", "while", "_Bool", "_Complex",
+            "_Imaginary",
+            "alignas", "alignof", "and", "and_eq", "asm", "atomic_cancel",
+            "atomic_commit", "atomic_noexcept", "bitand", "bitor", "bool",
+            "catch", "char8_t", "char16_t", "char32_t", "class", "compl",
+            "concept", "consteval", "constexpr", "constinit", "const_cast",
+            "co_await", "co_return", "co_yield", "decltype", "delete",
+            "dynamic_cast", "explicit", "export", "false", "friend", "mutable",
+            "namespace", "new", "noexcept", "not", "not_eq", "nullptr",
+            "operator", "or", "or_eq", "private", "protected", "public",
+            "reflexpr", "reinterpret_cast", "requires", "static_assert",
+            "static_cast", "synchronized", "template", "this", "thread_local",
+            "throw", "true", "try", "typeid", "typename", "using", "virtual",
+            "wchar_t", "xor", "xor_eq",
         ];
-        let types = [
-            "int", "float", "str", "bool", "list", "dict", "tuple", "set",
-            "frozenset", "bytes", "bytearray", "object", "type", "None",
+        const TYPES: &[&str] = &[
+            "int8_t", "int16_t", "int32_t", "int64_t", "uint8_t", "uint16_t",
+            "uint32_t", "uint64_t", "size_t", "ssize_t", "ptrdiff_t", "intptr_t",
+            "uintptr_t", "FILE", "time_t", "clock_t", "wint_t", "errno_t",
+            "nullptr_t",
+            "string", "vector", "map", "set", "list", "deque", "array",
+            "unordered_map", "unordered_set", "pair", "tuple", "optional",
+            "variant", "any", "span", "string_view", "unique_ptr", "shared_ptr",
+            "weak_ptr",
         ];
+        build_keyword_table(KEYWORDS, TYPES)
+    })
+}
 
-        let mut tokens = Vec::new();
-        let chars: Vec<char> = line.chars().collect();
-        let mut i = 0;
+/// Java/Kotlin's keyword/type table for `tokenize_java`.
+fn java_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "abstract", "assert", "boolean", "break", "byte", "case", "catch",
+            "char", "class", "const", "continue", "default", "do", "double",
+            "else", "enum", "extends", "final", "finally", "float", "for",
+            "goto", "if", "implements", "import", "instanceof", "int",
+            "interface", "long", "native", "new", "package", "private",
+            "protected", "public", "return", "short", "static", "strictfp",
+            "super", "switch", "synchronized", "this", "throw", "throws",
+            "transient", "try", "void", "volatile", "while", "true", "false",
+            "null",
+            "fun", "val", "var", "when", "object", "companion", "data", "sealed",
+            "inline", "crossinline", "noinline", "reified", "suspend", "typealias",
+            "by", "init", "constructor", "where", "out", "in", "is", "as",
+            "internal", "open", "lateinit", "annotation", "actual", "expect",
+        ];
+        const TYPES: &[&str] = &[
+            "String", "Integer", "Long", "Double", "Float", "Boolean", "Byte",
+            "Short", "Character", "Object", "Class", "List", "Map", "Set",
+            "ArrayList", "HashMap", "HashSet", "LinkedList", "TreeMap", "TreeSet",
+            "Optional", "Stream", "Comparable", "Runnable", "Callable", "Future",
+            "Thread", "Exception", "RuntimeException", "Error", "Throwable",
+            "Int", "Any", "Unit", "Nothing", "Array", "Pair", "Triple",
+            "Sequence", "MutableList", "MutableMap", "MutableSet",
+        ];
+        build_keyword_table(KEYWORDS, TYPES)
+    })
+}
 
-        while i < chars.len() {
-            // 주석
-            if chars[i] == '#' {
-                tokens.push(Token {
-                    text: chars[i..].iter().collect(),
-                    token_type: TokenType::Comment,
-                });
-                break;
-            }
+/// Go's keyword/type table for `tokenize_go`.
+fn go_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "break", "case", "chan", "const", "continue", "default", "defer",
+            "else", "fallthrough", "for", "func", "go", "goto", "if", "import",
+            "interface", "map", "package", "range", "return", "select", "struct",
+            "switch", "type", "var", "true", "false", "nil", "iota",
+        ];
+        const TYPES: &[&str] = &[
+            "bool", "byte", "complex64", "complex128", "error", "float32",
+            "float64", "int", "int8", "int16", "int32", "int64", "rune",
+            "string", "uint", "uint8", "uint16", "uint32", "uint64", "uintptr",
+        ];
+        build_keyword_table(KEYWORDS, TYPES)
+    })
+}
 
-            // 문자열 (triple quotes)
-            if i + 2 < chars.len()
-                && ((chars[i] == '"' && chars[i+1] == '"' && chars[i+2] == '"')
-                    || (chars[i] == '\'' && chars[i+1] == '\'' && chars[i+2] == '\''))
-            {
-                let quote = chars[i];
-                let start = i;
-                i += 3;
-                while i + 2 < chars.len() {
-                    if chars[i] == quote && chars[i+1] == quote && chars[i+2] == quote {
-                        i += 3;
-                        break;
-                    }
-                    i += 1;
-                }
-                if i > chars.len() {
-                    i = chars.len();
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::String,
-                });
-                continue;
-            }
+/// Swift's keyword/type table for `tokenize_swift`.
+fn swift_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "associatedtype", "class", "deinit", "enum", "extension", "fileprivate",
+            "func", "import", "init", "inout", "internal", "let", "open",
+            "operator", "private", "protocol", "public", "rethrows", "static",
+            "struct", "subscript", "typealias", "var", "break", "case",
+            "continue", "default", "defer", "do", "else", "fallthrough", "for",
+            "guard", "if", "in", "repeat", "return", "switch", "where", "while",
+            "as", "Any", "catch", "false", "is", "nil", "super", "self", "Self",
+            "throw", "throws", "true", "try", "async", "await", "actor",
+        ];
+        const TYPES: &[&str] = &[
+            "Int", "Int8", "Int16", "Int32", "Int64", "UInt", "UInt8", "UInt16",
+            "UInt32", "UInt64", "Float", "Double", "Bool", "String", "Character",
+            "Array", "Dictionary", "Set", "Optional", "Result", "Void", "Never",
+            "AnyObject", "AnyClass", "Error", "Codable", "Hashable", "Equatable",
+            "Comparable", "Identifiable", "View", "ObservableObject",
+        ];
+        build_keyword_table(KEYWORDS, TYPES)
+    })
+}
+
+/// Ruby's keyword table for `tokenize_ruby` (no separate type list --
+/// `capitalized_is_type` covers class/module/constant references instead).
+fn ruby_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "BEGIN", "END", "alias", "and", "begin", "break", "case", "class",
+            "def", "defined?", "do", "else", "elsif", "end", "ensure", "false",
+            "for", "if", "in", "module", "next", "nil", "not", "or", "redo",
+            "rescue", "retry", "return", "self", "super", "then", "true",
+            "undef", "unless", "until", "when", "while", "yield", "__FILE__",
+            "__LINE__", "__ENCODING__", "attr_reader", "attr_writer",
+            "attr_accessor", "private", "protected", "public", "require",
+            "require_relative", "include", "extend", "prepend", "raise", "fail",
+            "catch", "throw", "lambda", "proc", "loop",
+        ];
+        build_keyword_table(KEYWORDS, &[])
+    })
+}
+
+/// PHP's keyword/type table for `tokenize_php`.
+fn php_keyword_table() -> &'static KeywordTable {
+    static TABLE: OnceLock<KeywordTable> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        const KEYWORDS: &[&str] = &[
+            "abstract", "and", "array", "as", "break", "callable", "case",
+            "catch", "class", "clone", "const", "continue", "declare", "default",
+            "die", "do", "echo", "else", "elseif", "empty", "enddeclare",
+            "endfor", "endforeach", "endif", "endswitch", "endwhile", "eval",
+            "exit", "extends", "final", "finally", "fn", "for", "foreach",
+            "function", "global", "goto", "if", "implements", "include",
+            "include_once", "instanceof", "insteadof", "interface", "isset",
+            "list", "match", "namespace", "new", "or", "print", "private",
+            "protected", "public", "readonly", "require", "require_once",
+            "return", "static", "switch", "throw", "trait", "try", "unset",
+            "use", "var", "while", "xor", "yield", "yield from",
+            "true", "false", "null", "TRUE", "FALSE", "NULL",
+            "__CLASS__", "__DIR__", "__FILE__", "__FUNCTION__", "__LINE__",
+            "__METHOD__", "__NAMESPACE__", "__TRAIT__",
+        ];
+        const TYPES: &[&str] = &[
+            "int", "float", "bool", "string", "array", "object", "callable",
+            "iterable", "void", "mixed", "never", "null", "self", "parent",
+        ];
+        build_keyword_table(KEYWORDS, TYPES)
+    })
+}
+
+/// Shared numeric-literal scanner for the table-driven `tokenize_with`
+/// (which passes each `LanguageDef`'s `numeric_suffixes`) and the
+/// standalone shell, YAML and SQL tokenizers, so they agree on what a
+/// number looks like instead of each reimplementing a partial, divergent
+/// digit-swallowing loop. Recognizes an optional leading sign, a `0x`/`0o`/
+/// `0b` base prefix, digit groups with `_` separators, a fractional part,
+/// an exponent (`e`/`E`/`p`/`P` with an optional sign), and a trailing type
+/// suffix drawn from `suffixes` (longest match wins, so `f64` beats `f`).
+/// This mirrors the floating-point literal handling (exponents, `f32`/`f64`
+/// suffixes) the historical rustc lexer added to `rustc_lexer`. Callers
+/// that already matched `chars[start]` as a digit/sign/dot call this in
+/// place of their own scan loop; it returns the index just past the
+/// literal.
+fn scan_number(chars: &[char], start: usize, suffixes: &[&str]) -> usize {
+    let mut i = start;
+    if i < chars.len() && (chars[i] == '+' || chars[i] == '-') {
+        i += 1;
+    }
+    if i + 1 < chars.len() && chars[i] == '0' && matches!(chars[i + 1].to_ascii_lowercase(), 'x' | 'o' | 'b') {
+        i += 2;
+        while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+            i += 1;
+        }
+        return scan_number_suffix(chars, i, suffixes);
+    }
+    while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '_') {
+        i += 1;
+    }
+    if chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()) {
+        i += 1;
+        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '_') {
+            i += 1;
+        }
+    }
+    if matches!(chars.get(i), Some('e' | 'E' | 'p' | 'P')) {
+        let mut j = i + 1;
+        if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+            j += 1;
+        }
+        if j < chars.len() && chars[j].is_ascii_digit() {
+            i = j;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+        }
+    }
+    scan_number_suffix(chars, i, suffixes)
+}
+
+/// Matches the longest entry of `suffixes` at `chars[i]` (case-insensitively)
+/// and returns the index past it, or `i` unchanged if none match.
+fn scan_number_suffix(chars: &[char], i: usize, suffixes: &[&str]) -> usize {
+    suffixes
+        .iter()
+        .filter(|s| {
+            s.chars()
+                .enumerate()
+                .all(|(k, c)| chars.get(i + k).is_some_and(|&ch| ch.eq_ignore_ascii_case(&c)))
+        })
+        .max_by_key(|s| s.len())
+        .map_or(i, |s| i + s.chars().count())
+}
+
+/// Parses a heredoc/nowdoc opener -- PHP's `<<<EOT`/`<<<"EOT"`/`<<<'EOT'` and
+/// Ruby's `<<EOT`/`<<-EOT`/`<<~EOT`/`<<'EOT'` -- starting at `chars[start]`,
+/// which must already be the first `<` of `prefix`. Ruby's `~`/`-` modifier
+/// and PHP's (optional) quoting both map onto the same `HeredocState` that
+/// `tokenize_shell` already carries across lines: a `~`/`-` sets
+/// `strip_tabs`, a `'`/`"` quote sets `quoted` (PHP's unquoted form expands
+/// interpolation same as `"`, so only `'` is `quoted` there). Returns the
+/// parsed state plus the index just past the opener, or `None` if no
+/// identifier follows.
+fn scan_heredoc_open(chars: &[char], start: usize, prefix: &str) -> Option<(HeredocState, usize)> {
+    let prefix_chars: Vec<char> = prefix.chars().collect();
+    if start + prefix_chars.len() > chars.len() || chars[start..start + prefix_chars.len()] != prefix_chars[..] {
+        return None;
+    }
+    let mut j = start + prefix_chars.len();
+    let strip_tabs = j < chars.len() && (chars[j] == '-' || chars[j] == '~');
+    if strip_tabs {
+        j += 1;
+    }
+    let quote = if j < chars.len() && (chars[j] == '"' || chars[j] == '\'') {
+        Some(chars[j])
+    } else {
+        None
+    };
+    let delim_start = if quote.is_some() { j + 1 } else { j };
+    let mut k = delim_start;
+    match quote {
+        Some(q) => while k < chars.len() && chars[k] != q {
+            k += 1;
+        },
+        None => while k < chars.len() && (chars[k].is_alphanumeric() || chars[k] == '_') {
+            k += 1;
+        },
+    }
+    let delimiter: String = chars[delim_start..k].iter().collect();
+    if delimiter.is_empty() {
+        return None;
+    }
+    let end = if quote.is_some() { (k + 1).min(chars.len()) } else { k };
+    Some((
+        HeredocState {
+            delimiter,
+            strip_tabs,
+            quoted: quote == Some('\''),
+        },
+        end,
+    ))
+}
+
+/// Longest-match operator scan: tries every entry of `operators` at
+/// `start` and returns the end index of whichever matches and is longest
+/// (so `**=` beats `**` beats `*`), or `None` if none do. Languages that
+/// supply a table use this in place of the older greedy scan that just
+/// grabbed runs of same-symbol-class characters -- which coalesced
+/// unrelated adjacent operators like `=` and `-` in `x=-1` into one bogus
+/// `=-` token since it never checked against real operators.
+fn scan_operator(chars: &[char], start: usize, operators: &[&str]) -> Option<usize> {
+    operators
+        .iter()
+        .filter(|op| {
+            let len = op.chars().count();
+            start + len <= chars.len() && chars[start..start + len].iter().copied().eq(op.chars())
+        })
+        .max_by_key(|op| op.chars().count())
+        .map(|op| start + op.chars().count())
+}
+
+/// Scans a Markdown `[label]` starting at `start` and, if immediately
+/// followed by `(destination)` or another `[label]` (GFM's reference-style
+/// links), consumes that too. Returns the index just past everything
+/// consumed and whether a destination/reference part was found, so the
+/// caller can tell an actual link from a bare `[not a link]` and color it
+/// accordingly. Called with `start` past the `!` for `![alt](url)` images,
+/// which are otherwise identical to a link.
+fn scan_markdown_link(chars: &[char], start: usize) -> (usize, bool) {
+    let mut i = start;
+    if i >= chars.len() || chars[i] != '[' {
+        return (i, false);
+    }
+    i += 1;
+    while i < chars.len() && chars[i] != ']' {
+        i += 1;
+    }
+    if i >= chars.len() {
+        return (i, false);
+    }
+    i += 1; // ']' 다음
+    if i < chars.len() && (chars[i] == '(' || chars[i] == '[') {
+        let close = if chars[i] == '(' { ')' } else { ']' };
+        while i < chars.len() && chars[i] != close {
+            i += 1;
+        }
+        if i < chars.len() {
+            i += 1;
+        }
+        return (i, true);
+    }
+    (i, false)
+}
+
+/// Recognizes a Markdown table row: the `|---|:--:|` delimiter row (every
+/// cell is just dashes with an optional leading/trailing `:` for alignment)
+/// or an ordinary `| a | b |` header/body row. `tokenize_markdown` has no
+/// lookahead, so it can't confirm a candidate header row is actually
+/// followed by a delimiter row -- any line shaped like `a | b` (at least
+/// two pipes, or a leading/trailing pipe) is treated as a table row, the
+/// same simplification most line-based Markdown highlighters make.
+fn tokenize_table_row(line: &str, trimmed: &str) -> Option<Vec<Token>> {
+    let pipe_count = trimmed.matches('|').count();
+    if pipe_count == 0 || (pipe_count == 1 && !trimmed.starts_with('|') && !trimmed.ends_with('|'))
+    {
+        return None;
+    }
+
+    let stripped = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let inner = stripped.strip_suffix('|').unwrap_or(stripped);
+    let is_delimiter_row = inner.split('|').all(|cell| {
+        let cell = cell.trim().trim_start_matches(':').trim_end_matches(':');
+        !cell.is_empty() && cell.chars().all(|c| c == '-')
+    });
+
+    let indent = line.len() - trimmed.len();
+    let mut tokens = Vec::new();
+    if indent > 0 {
+        tokens.push(Token {
+            text: line[..indent].to_string(),
+            token_type: TokenType::Normal,
+            start: 0,
+            end: 0,
+            bracket_depth: None,
+        });
+    }
+
+    if is_delimiter_row {
+        tokens.push(Token {
+            text: trimmed.to_string(),
+            token_type: TokenType::Attribute,
+            start: 0,
+            end: 0,
+            bracket_depth: None,
+        });
+        return Some(tokens);
+    }
+
+    let chars: Vec<char> = trimmed.chars().collect();
+    let mut cell_start = 0;
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '|' {
+            if i > cell_start {
+                tokens.push(Token {
+                    text: chars[cell_start..i].iter().collect(),
+                    token_type: TokenType::Normal,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+            }
+            tokens.push(Token {
+                text: "|".to_string(),
+                token_type: TokenType::Operator,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+            cell_start = i + 1;
+        }
+    }
+    if cell_start < chars.len() {
+        tokens.push(Token {
+            text: chars[cell_start..].iter().collect(),
+            token_type: TokenType::Normal,
+            start: 0,
+            end: 0,
+            bracket_depth: None,
+        });
+    }
+    Some(tokens)
+}
+
+/// Recognizes a Markdown link reference definition: `[label]: url "title"`.
+/// The `[label]:` part renders as `Keyword`, the destination URL as
+/// `Function` (matching how an inline link's destination is colored), and
+/// everything after it (the optional whitespace and quoted title) as one
+/// `String` token.
+fn tokenize_link_reference_definition(line: &str, trimmed: &str) -> Option<Vec<Token>> {
+    if !trimmed.starts_with('[') {
+        return None;
+    }
+    let marker = trimmed.find("]:")?;
+    let after_colon = marker + 2;
+    let rest = &trimmed[after_colon..];
+    let url_start = after_colon + (rest.len() - rest.trim_start().len());
+    let url_rest = &trimmed[url_start..];
+    if url_rest.is_empty() {
+        return None;
+    }
+    let url_len = url_rest.find(char::is_whitespace).unwrap_or(url_rest.len());
+    let url_end = url_start + url_len;
+
+    let indent = line.len() - trimmed.len();
+    let mut tokens = Vec::new();
+    if indent > 0 {
+        tokens.push(Token {
+            text: line[..indent].to_string(),
+            token_type: TokenType::Normal,
+            start: 0,
+            end: 0,
+            bracket_depth: None,
+        });
+    }
+    tokens.push(Token {
+        text: trimmed[..after_colon].to_string(),
+        token_type: TokenType::Keyword,
+        start: 0,
+        end: 0,
+        bracket_depth: None,
+    });
+    if url_start > after_colon {
+        tokens.push(Token {
+            text: trimmed[after_colon..url_start].to_string(),
+            token_type: TokenType::Normal,
+            start: 0,
+            end: 0,
+            bracket_depth: None,
+        });
+    }
+    tokens.push(Token {
+        text: trimmed[url_start..url_end].to_string(),
+        token_type: TokenType::Function,
+        start: 0,
+        end: 0,
+        bracket_depth: None,
+    });
+    if url_end < trimmed.len() {
+        tokens.push(Token {
+            text: trimmed[url_end..].to_string(),
+            token_type: TokenType::String,
+            start: 0,
+            end: 0,
+            bracket_depth: None,
+        });
+    }
+    Some(tokens)
+}
+
+/// 문법 강조기
+#[derive(Debug, Clone)]
+pub struct SyntaxHighlighter {
+    language: Language,
+    colors: SyntaxColors,
+    state: LexerState,
+    custom_languages: crate::services::custom_languages::CustomLanguages,
+}
+
+impl SyntaxHighlighter {
+    pub fn new(language: Language) -> Self {
+        let truecolor = std::env::var("COLORTERM")
+            .map(|v| v == "truecolor" || v == "24bit")
+            .unwrap_or(false);
+
+        Self {
+            language,
+            colors: if truecolor {
+                SyntaxColors::default()
+            } else {
+                SyntaxColors::compatible()
+            },
+            state: LexerState::default(),
+            custom_languages: crate::services::custom_languages::CustomLanguages::load(),
+        }
+    }
+
+    /// 라인을 토큰화
+    pub fn tokenize_line(&mut self, line: &str) -> Vec<Token> {
+        let mut tokens = match self.language {
+            Language::Rust => self.tokenize_rust(line),
+            Language::Python => self.tokenize_python(line),
+            Language::JavaScript | Language::TypeScript => self.tokenize_javascript(line),
+            Language::C | Language::Cpp => self.tokenize_c(line),
+            Language::Java | Language::Kotlin => self.tokenize_java(line),
+            Language::Go => self.tokenize_go(line),
+            Language::Html | Language::Xml => self.tokenize_html(line),
+            Language::Css => self.tokenize_css(line),
+            Language::Json => self.tokenize_json(line),
+            Language::Yaml | Language::Toml => self.tokenize_yaml(line),
+            Language::Shell => self.tokenize_shell(line),
+            Language::Sql => self.tokenize_sql(line),
+            Language::Ruby => self.tokenize_ruby(line),
+            Language::Php => self.tokenize_php(line),
+            Language::Swift => self.tokenize_swift(line),
+            Language::Markdown => self.tokenize_markdown(line),
+            Language::Plain => vec![Token {
+                text: line.to_string(),
+                token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            }],
+            Language::Custom(idx) => self.tokenize_custom(idx, line),
+        };
+
+        self.fill_spans_and_bracket_depth(&mut tokens);
+        tokens
+    }
+
+    /// Shared final pass every `tokenize_line` call goes through: fills in
+    /// each token's `start`/`end` column from its text length (tokens come
+    /// out of every `tokenize_*` method already in left-to-right, gapless
+    /// order, so this is just a running sum), and assigns `bracket_depth`
+    /// to `Bracket` tokens by replaying them against the nesting depth
+    /// carried over from the previous line in `LexerState`.
+    fn fill_spans_and_bracket_depth(&mut self, tokens: &mut [Token]) {
+        let mut col = 0;
+        let mut depth = self.state.bracket_depth;
+        for token in tokens.iter_mut() {
+            let len = token.text.chars().count();
+            token.start = col;
+            token.end = col + len;
+            col = token.end;
+
+            if token.token_type != TokenType::Bracket {
+                continue;
+            }
+            match token.text.as_str() {
+                "(" | "[" | "{" => {
+                    token.bracket_depth = Some(depth);
+                    depth += 1;
+                }
+                ")" | "]" | "}" => {
+                    depth = (depth - 1).max(0);
+                    token.bracket_depth = Some(depth);
+                }
+                _ => {}
+            }
+        }
+        self.state.bracket_depth = depth;
+    }
+
+    /// 토큰에 대한 스타일 가져오기
+    pub fn style_for(&self, token_type: TokenType) -> Style {
+        self.colors.style_for(token_type)
+    }
+
+    /// Style for a single token, rainbow-coloring `Bracket` tokens by
+    /// their nesting depth instead of painting them all the flat
+    /// `SyntaxColors::bracket` color.
+    pub fn style_for_token(&self, token: &Token) -> Style {
+        match (token.token_type, token.bracket_depth) {
+            (TokenType::Bracket, Some(depth)) => self.colors.style_for_bracket(depth),
+            _ => self.colors.style_for(token.token_type),
+        }
+    }
+
+    /// 상태 리셋
+    pub fn reset(&mut self) {
+        self.state = LexerState::default();
+    }
+
+    /// The carry-over state this highlighter leaves for whatever line
+    /// comes next (inside a block comment, raw string, triple-quoted
+    /// string or template literal, or not). Callers that cache tokens per
+    /// line use this to tell whether a downstream line can still trust its
+    /// cached tokens after an upstream edit.
+    pub fn lexer_state(&self) -> LexerState {
+        self.state.clone()
+    }
+
+    /// Resume tokenizing as if the previous line had left `state` behind,
+    /// instead of starting fresh via `reset`.
+    pub fn set_lexer_state(&mut self, state: LexerState) {
+        self.state = state;
+    }
+
+    // Rust 토큰화
+    fn tokenize_rust(&mut self, line: &str) -> Vec<Token> {
+        let keywords = [
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn",
+            "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+            "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+            "self", "Self", "static", "struct", "super", "trait", "true", "type",
+            "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+            "final", "macro", "override", "priv", "typeof", "unsized", "virtual",
+            "yield",
+        ];
+        let types = [
+            "i8", "i16", "i32", "i64", "i128", "isize",
+            "u8", "u16", "u32", "u64", "u128", "usize",
+            "f32", "f64", "bool", "char", "str", "String",
+            "Vec", "Option", "Result", "Box", "Rc", "Arc",
+            "HashMap", "HashSet", "BTreeMap", "BTreeSet",
+            "Path", "PathBuf", "OsStr", "OsString",
+        ];
+
+        let def = LanguageDef {
+            raw_string_prefix: Some('r'),
+            nested_block_comments: true,
+            lifetimes: true,
+            numeric_suffixes: &[
+                "i8", "i16", "i32", "i64", "i128", "isize",
+                "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64",
+            ],
+            keyword_table: Some(rust_keyword_table()),
+            operators: &[
+                "..=", "...", "..", "->", "=>", "::", "==", "!=", "<=",
+                ">=", "&&", "||", "+=", "-=", "*=", "/=", "%=", "&=",
+                "|=", "^=", "<<=", ">>=", "<<", ">>",
+            ],
+            ..LanguageDef::c_family(&keywords, &types, true)
+        };
+        self.tokenize_with(&def, line)
+    }
+
+    // Python 토큰화
+    fn tokenize_python(&mut self, line: &str) -> Vec<Token> {
+        let keywords = [
+            "and", "as", "assert", "async", "await", "break", "class", "continue",
+            "def", "del", "elif", "else", "except", "False", "finally", "for",
+            "from", "global", "if", "import", "in", "is", "lambda", "None",
+            "nonlocal", "not", "or", "pass", "raise", "return", "True", "try",
+            "while", "with", "yield",
+        ];
+        let types = [
+            "int", "float", "str", "bool", "list", "dict", "tuple", "set",
+            "frozenset", "bytes", "bytearray", "object", "type", "None",
+        ];
+
+        let mut tokens = Vec::new();
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+
+        // 이전 줄에서 이어지는 triple-quoted 문자열
+        if let Some(quote) = self.state.in_triple_quoted_string {
+            let mut found_end = false;
+            while i < chars.len() {
+                if i + 3 <= chars.len() && chars[i] == quote && chars[i + 1] == quote && chars[i + 2] == quote {
+                    i += 3;
+                    found_end = true;
+                    break;
+                }
+                i += 1;
+            }
+            tokens.push(Token {
+                text: chars[..i].iter().collect(),
+                token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+            if found_end {
+                self.state.in_triple_quoted_string = None;
+            } else {
+                return tokens;
+            }
+        }
+
+        while i < chars.len() {
+            // 주석
+            if chars[i] == '#' {
+                tokens.push(Token {
+                    text: chars[i..].iter().collect(),
+                    token_type: TokenType::Comment,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                break;
+            }
+
+            // 문자열 (triple quotes)
+            if i + 2 < chars.len()
+                && ((chars[i] == '"' && chars[i+1] == '"' && chars[i+2] == '"')
+                    || (chars[i] == '\'' && chars[i+1] == '\'' && chars[i+2] == '\''))
+            {
+                let quote = chars[i];
+                let start = i;
+                i += 3;
+                let mut found_end = false;
+                while i < chars.len() {
+                    if i + 3 <= chars.len() && chars[i] == quote && chars[i+1] == quote && chars[i+2] == quote {
+                        i += 3;
+                        found_end = true;
+                        break;
+                    }
+                    i += 1;
+                }
+                tokens.push(Token {
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                if !found_end {
+                    self.state.in_triple_quoted_string = Some(quote);
+                    return tokens;
+                }
+                continue;
+            }
 
             // 문자열 (single/double quotes)
             if chars[i] == '"' || chars[i] == '\'' {
@@ -362,12 +1439,58 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // f-string prefix
-            if (chars[i] == 'f' || chars[i] == 'r' || chars[i] == 'b')
+            // f-string: the prefix and quotes stay String tokens, but the
+            // body is re-entrant — `{expr}` switches into the normal
+            // tokenizer so the embedded expression renders with its own
+            // true token types instead of being swallowed into the literal.
+            if chars[i] == 'f'
+                && i + 1 < chars.len()
+                && (chars[i + 1] == '"' || chars[i + 1] == '\'')
+            {
+                let quote = chars[i + 1];
+                i += 2;
+                let content_start = i;
+                while i < chars.len() && chars[i] != quote {
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                let content_end = i;
+                let closed = i < chars.len();
+                if closed {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    text: format!("f{}", quote),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                tokens.extend(self.tokenize_fstring_body(&chars[content_start..content_end]));
+                if closed {
+                    tokens.push(Token {
+                        text: quote.to_string(),
+                        token_type: TokenType::String,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                }
+                continue;
+            }
+
+            // r/b-string prefix (raw/bytes): no interpolation, kept as a
+            // single literal token like before.
+            if (chars[i] == 'r' || chars[i] == 'b')
                 && i + 1 < chars.len()
                 && (chars[i + 1] == '"' || chars[i + 1] == '\'')
             {
@@ -387,6 +1510,9 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: format!("{}{}", prefix, chars[start+1..i].iter().collect::<String>()),
                     token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -400,6 +1526,9 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Number,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -425,6 +1554,9 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: word,
                     token_type,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -439,6 +1571,9 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Attribute,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -448,32 +1583,166 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[i].to_string(),
                     token_type: TokenType::Operator,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                i += 1;
+                continue;
+            }
+
+            // 괄호
+            if "()[]{}".contains(chars[i]) {
+                tokens.push(Token {
+                    text: chars[i].to_string(),
+                    token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
             }
 
-            // 괄호
-            if "()[]{}".contains(chars[i]) {
+            // 기타
+            tokens.push(Token {
+                text: chars[i].to_string(),
+                token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+            i += 1;
+        }
+
+        tokens
+    }
+
+    /// Tokenize the body of an f-string (the text between the opening and
+    /// closing quote, quotes excluded). Literal runs become `String`
+    /// tokens; an unescaped `{` opens an interpolation that's re-tokenized
+    /// with the normal Python tokenizer so the embedded expression gets its
+    /// real token types, while `{{`/`}}` stay literal braces.
+    fn tokenize_fstring_body(&mut self, chars: &[char]) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        let mut literal_start = 0;
+
+        while i < chars.len() {
+            if chars[i] == '{' {
+                if i + 1 < chars.len() && chars[i + 1] == '{' {
+                    i += 2;
+                    continue;
+                }
+                if i > literal_start {
+                    tokens.push(Token {
+                        text: chars[literal_start..i].iter().collect(),
+                        token_type: TokenType::String,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                }
                 tokens.push(Token {
-                    text: chars[i].to_string(),
+                    text: "{".to_string(),
                     token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
-                i += 1;
+                let expr_start = i + 1;
+                let end = Self::find_matching_brace(chars, expr_start);
+                let inner: String = chars[expr_start..end.min(chars.len())].iter().collect();
+                tokens.extend(self.tokenize_python(&inner));
+                if end < chars.len() {
+                    tokens.push(Token {
+                        text: "}".to_string(),
+                        token_type: TokenType::Bracket,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    i = end + 1;
+                } else {
+                    i = end;
+                }
+                literal_start = i;
+                continue;
+            }
+            if chars[i] == '}' && i + 1 < chars.len() && chars[i + 1] == '}' {
+                i += 2;
                 continue;
             }
+            i += 1;
+        }
 
-            // 기타
+        if i > literal_start {
             tokens.push(Token {
-                text: chars[i].to_string(),
-                token_type: TokenType::Normal,
+                text: chars[literal_start..i].iter().collect(),
+                token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
-            i += 1;
         }
 
         tokens
     }
 
+    /// Find the `}` that closes an interpolation whose expression starts at
+    /// `start` (one past the opening `{`/`${`), counting brace depth so a
+    /// nested `{`/`}` inside the expression (e.g. a dict literal) doesn't
+    /// end it early. Quoted substrings are skipped whole so a brace inside
+    /// a nested string literal doesn't affect the count either. Returns
+    /// `chars.len()` if the interpolation is left open at end of line.
+    fn find_matching_brace(chars: &[char], start: usize) -> usize {
+        let mut depth = 1;
+        let mut i = start;
+        while i < chars.len() {
+            match chars[i] {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return i;
+                    }
+                }
+                '\'' | '"' | '`' => {
+                    let quote = chars[i];
+                    i += 1;
+                    while i < chars.len() && chars[i] != quote {
+                        if chars[i] == '\\' && i + 1 < chars.len() {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        i
+    }
+
+    /// Find the unescaped backtick that closes a template literal body
+    /// starting at `start`, or `None` if it runs off the end of `chars` —
+    /// meaning the template literal continues on a later line and
+    /// `LexerState::in_template_literal` should be set.
+    fn find_template_literal_close(chars: &[char], start: usize) -> Option<usize> {
+        let mut i = start;
+        while i < chars.len() {
+            if chars[i] == '\\' && i + 1 < chars.len() {
+                i += 2;
+                continue;
+            }
+            if chars[i] == '`' {
+                return Some(i);
+            }
+            i += 1;
+        }
+        None
+    }
+
     // JavaScript/TypeScript 토큰화
     fn tokenize_javascript(&mut self, line: &str) -> Vec<Token> {
         let keywords = [
@@ -493,7 +1762,170 @@ impl SyntaxHighlighter {
             "Date", "RegExp", "Error", "Function", "Object", "Symbol", "BigInt",
         ];
 
-        self.tokenize_c_like(line, &keywords, &types, "//", ("/*", "*/"), true)
+        let def = LanguageDef {
+            numeric_suffixes: &["n"],
+            keyword_table: Some(javascript_keyword_table()),
+            operators: &[
+                "===", "!==", "**=", "??=", "?.", "...", "=>", "==", "!=",
+                "<=", ">=", "&&", "||", "??", "++", "--", "+=", "-=",
+                "*=", "/=", "%=", "&=", "|=", "^=", "<<=", ">>=", ">>>=",
+                "<<", ">>", ">>>", "**",
+            ],
+            ..LanguageDef::c_family(&keywords, &types, true)
+        };
+        let chars: Vec<char> = line.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        let mut segment_start;
+
+        if self.state.in_template_literal {
+            match Self::find_template_literal_close(&chars, 0) {
+                Some(end) => {
+                    tokens.extend(self.tokenize_template_literal_body(&chars[..end]));
+                    tokens.push(Token {
+                        text: "`".to_string(),
+                        token_type: TokenType::String,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    self.state.in_template_literal = false;
+                    i = end + 1;
+                }
+                None => {
+                    tokens.extend(self.tokenize_template_literal_body(&chars));
+                    return tokens;
+                }
+            }
+            segment_start = i;
+        } else {
+            if !line.contains('`') {
+                return self.tokenize_with(&def, line);
+            }
+            segment_start = 0;
+        }
+
+        while i < chars.len() {
+            if chars[i] != '`' {
+                i += 1;
+                continue;
+            }
+
+            if i > segment_start {
+                let segment: String = chars[segment_start..i].iter().collect();
+                tokens.extend(self.tokenize_with(&def, &segment));
+            }
+
+            i += 1;
+            let content_start = i;
+            while i < chars.len() && chars[i] != '`' {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                }
+                i += 1;
+            }
+            let content_end = i;
+            let closed = i < chars.len();
+            if closed {
+                i += 1;
+            } else {
+                self.state.in_template_literal = true;
+            }
+
+            tokens.push(Token {
+                text: "`".to_string(),
+                token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+            tokens.extend(self.tokenize_template_literal_body(&chars[content_start..content_end]));
+            if closed {
+                tokens.push(Token {
+                    text: "`".to_string(),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+            }
+
+            segment_start = i;
+        }
+
+        if segment_start < chars.len() {
+            let segment: String = chars[segment_start..].iter().collect();
+            tokens.extend(self.tokenize_with(&def, &segment));
+        }
+
+        tokens
+    }
+
+    /// Tokenize the body of a JS/TS template literal (the text between the
+    /// backticks, backticks excluded). Literal runs become `String` tokens;
+    /// an unescaped `${` opens an interpolation that's re-tokenized with
+    /// the normal JS/TS tokenizer (so a nested template literal inside it
+    /// is handled too), while `\${` stays a literal `${`.
+    fn tokenize_template_literal_body(&mut self, chars: &[char]) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        let mut literal_start = 0;
+
+        while i < chars.len() {
+            if chars[i] == '\\' && i + 1 < chars.len() {
+                i += 2;
+                continue;
+            }
+            if chars[i] == '$' && i + 1 < chars.len() && chars[i + 1] == '{' {
+                if i > literal_start {
+                    tokens.push(Token {
+                        text: chars[literal_start..i].iter().collect(),
+                        token_type: TokenType::String,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                }
+                tokens.push(Token {
+                    text: "${".to_string(),
+                    token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                let expr_start = i + 2;
+                let end = Self::find_matching_brace(chars, expr_start);
+                let inner: String = chars[expr_start..end.min(chars.len())].iter().collect();
+                tokens.extend(self.tokenize_javascript(&inner));
+                if end < chars.len() {
+                    tokens.push(Token {
+                        text: "}".to_string(),
+                        token_type: TokenType::Bracket,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    i = end + 1;
+                } else {
+                    i = end;
+                }
+                literal_start = i;
+                continue;
+            }
+            i += 1;
+        }
+
+        if i > literal_start {
+            tokens.push(Token {
+                text: chars[literal_start..i].iter().collect(),
+                token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+        }
+
+        tokens
     }
 
     // C/C++ 토큰화
@@ -531,7 +1963,17 @@ impl SyntaxHighlighter {
             "weak_ptr",
         ];
 
-        self.tokenize_c_like(line, &keywords, &types, "//", ("/*", "*/"), true)
+        let def = LanguageDef {
+            numeric_suffixes: &["ull", "llu", "ll", "ul", "lu", "u", "l", "f"],
+            keyword_table: Some(c_keyword_table()),
+            operators: &[
+                "->", "==", "!=", "<=", ">=", "&&", "||", "++", "--",
+                "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<=",
+                ">>=", "<<", ">>", "::",
+            ],
+            ..LanguageDef::c_family(&keywords, &types, true)
+        };
+        self.tokenize_with(&def, line)
     }
 
     // Java/Kotlin 토큰화
@@ -563,7 +2005,17 @@ impl SyntaxHighlighter {
             "Sequence", "MutableList", "MutableMap", "MutableSet",
         ];
 
-        self.tokenize_c_like(line, &keywords, &types, "//", ("/*", "*/"), true)
+        let def = LanguageDef {
+            numeric_suffixes: &["l", "f", "d"],
+            keyword_table: Some(java_keyword_table()),
+            operators: &[
+                "->", "==", "!=", "<=", ">=", "&&", "||", "++", "--",
+                "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<=",
+                ">>=", "<<", ">>", "::",
+            ],
+            ..LanguageDef::c_family(&keywords, &types, true)
+        };
+        self.tokenize_with(&def, line)
     }
 
     // Go 토큰화
@@ -580,7 +2032,16 @@ impl SyntaxHighlighter {
             "string", "uint", "uint8", "uint16", "uint32", "uint64", "uintptr",
         ];
 
-        self.tokenize_c_like(line, &keywords, &types, "//", ("/*", "*/"), false)
+        let def = LanguageDef {
+            keyword_table: Some(go_keyword_table()),
+            operators: &[
+                "<-", ":=", "&^=", "==", "!=", "<=", ">=", "&&", "||",
+                "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=",
+                "^=", "<<=", ">>=", "<<", ">>", "&^",
+            ],
+            ..LanguageDef::c_family(&keywords, &types, false)
+        };
+        self.tokenize_with(&def, line)
     }
 
     // HTML/XML 토큰화
@@ -607,6 +2068,9 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Comment,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -632,10 +2096,16 @@ impl SyntaxHighlighter {
                     tokens.push(Token {
                         text: chars[start..tag_start].iter().collect(),
                         token_type: TokenType::Bracket,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                     tokens.push(Token {
                         text: tag_name,
                         token_type: TokenType::Keyword,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                 }
 
@@ -650,6 +2120,9 @@ impl SyntaxHighlighter {
                         tokens.push(Token {
                             text: chars[ws_start..i].iter().collect(),
                             token_type: TokenType::Normal,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
                         });
                         continue;
                     }
@@ -663,378 +2136,203 @@ impl SyntaxHighlighter {
                         tokens.push(Token {
                             text: chars[attr_start..i].iter().collect(),
                             token_type: TokenType::Attribute,
-                        });
-                        continue;
-                    }
-
-                    // 등호
-                    if chars[i] == '=' {
-                        tokens.push(Token {
-                            text: "=".to_string(),
-                            token_type: TokenType::Operator,
-                        });
-                        i += 1;
-                        continue;
-                    }
-
-                    // 속성 값
-                    if chars[i] == '"' || chars[i] == '\'' {
-                        let quote = chars[i];
-                        let str_start = i;
-                        i += 1;
-                        while i < chars.len() && chars[i] != quote {
-                            i += 1;
-                        }
-                        if i < chars.len() {
-                            i += 1;
-                        }
-                        tokens.push(Token {
-                            text: chars[str_start..i].iter().collect(),
-                            token_type: TokenType::String,
-                        });
-                        continue;
-                    }
-
-                    // Self-closing slash
-                    if chars[i] == '/' {
-                        tokens.push(Token {
-                            text: "/".to_string(),
-                            token_type: TokenType::Bracket,
-                        });
-                        i += 1;
-                        continue;
-                    }
-
-                    i += 1;
-                }
-
-                // 닫는 괄호
-                if i < chars.len() && chars[i] == '>' {
-                    tokens.push(Token {
-                        text: ">".to_string(),
-                        token_type: TokenType::Bracket,
-                    });
-                    i += 1;
-                }
-                continue;
-            }
-
-            // 텍스트 콘텐츠
-            let start = i;
-            while i < chars.len() && chars[i] != '<' {
-                i += 1;
-            }
-            if i > start {
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Normal,
-                });
-            }
-        }
-
-        tokens
-    }
-
-    // CSS 토큰화
-    fn tokenize_css(&mut self, line: &str) -> Vec<Token> {
-        let mut tokens = Vec::new();
-        let chars: Vec<char> = line.chars().collect();
-        let mut i = 0;
-
-        while i < chars.len() {
-            // 주석
-            if i + 1 < chars.len() && chars[i] == '/' && chars[i + 1] == '*' {
-                let start = i;
-                i += 2;
-                while i + 1 < chars.len() {
-                    if chars[i] == '*' && chars[i + 1] == '/' {
-                        i += 2;
-                        break;
-                    }
-                    i += 1;
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Comment,
-                });
-                continue;
-            }
-
-            // 선택자 (. # 로 시작)
-            if chars[i] == '.' || chars[i] == '#' {
-                let start = i;
-                i += 1;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '-' || chars[i] == '_') {
-                    i += 1;
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Function,
-                });
-                continue;
-            }
-
-            // @ 규칙
-            if chars[i] == '@' {
-                let start = i;
-                i += 1;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '-') {
-                    i += 1;
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Keyword,
-                });
-                continue;
-            }
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        continue;
+                    }
 
-            // 속성
-            if chars[i].is_alphabetic() || chars[i] == '-' {
-                let start = i;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '-') {
-                    i += 1;
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Attribute,
-                });
-                continue;
-            }
+                    // 등호
+                    if chars[i] == '=' {
+                        tokens.push(Token {
+                            text: "=".to_string(),
+                            token_type: TokenType::Operator,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        i += 1;
+                        continue;
+                    }
 
-            // 문자열
-            if chars[i] == '"' || chars[i] == '\'' {
-                let quote = chars[i];
-                let start = i;
-                i += 1;
-                while i < chars.len() && chars[i] != quote {
-                    if chars[i] == '\\' && i + 1 < chars.len() {
+                    // 속성 값
+                    if chars[i] == '"' || chars[i] == '\'' {
+                        let quote = chars[i];
+                        let str_start = i;
                         i += 1;
+                        while i < chars.len() && chars[i] != quote {
+                            i += 1;
+                        }
+                        if i < chars.len() {
+                            i += 1;
+                        }
+                        tokens.push(Token {
+                            text: chars[str_start..i].iter().collect(),
+                            token_type: TokenType::String,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        continue;
                     }
+
+                    // Self-closing slash
+                    if chars[i] == '/' {
+                        tokens.push(Token {
+                            text: "/".to_string(),
+                            token_type: TokenType::Bracket,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        i += 1;
+                        continue;
+                    }
+
                     i += 1;
                 }
-                if i < chars.len() {
-                    i += 1;
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::String,
-                });
-                continue;
-            }
 
-            // 숫자
-            if chars[i].is_ascii_digit() || (chars[i] == '.' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit()) {
-                let start = i;
-                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '%' || chars[i] == '-') {
+                // 닫는 괄호
+                if i < chars.len() && chars[i] == '>' {
+                    tokens.push(Token {
+                        text: ">".to_string(),
+                        token_type: TokenType::Bracket,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
                     i += 1;
                 }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Number,
-                });
                 continue;
             }
 
-            // 괄호
-            if "{}()[]".contains(chars[i]) {
-                tokens.push(Token {
-                    text: chars[i].to_string(),
-                    token_type: TokenType::Bracket,
-                });
+            // 텍스트 콘텐츠
+            let start = i;
+            while i < chars.len() && chars[i] != '<' {
                 i += 1;
-                continue;
             }
-
-            // 연산자
-            if ":;,".contains(chars[i]) {
+            if i > start {
                 tokens.push(Token {
-                    text: chars[i].to_string(),
-                    token_type: TokenType::Operator,
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::Normal,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
-                i += 1;
-                continue;
             }
-
-            // 기타
-            tokens.push(Token {
-                text: chars[i].to_string(),
-                token_type: TokenType::Normal,
-            });
-            i += 1;
         }
 
         tokens
     }
 
-    // JSON 토큰화
-    fn tokenize_json(&mut self, line: &str) -> Vec<Token> {
+    // CSS 토큰화
+    fn tokenize_css(&mut self, line: &str) -> Vec<Token> {
         let mut tokens = Vec::new();
         let chars: Vec<char> = line.chars().collect();
         let mut i = 0;
 
-        while i < chars.len() {
-            // 공백
-            if chars[i].is_whitespace() {
-                let start = i;
-                while i < chars.len() && chars[i].is_whitespace() {
-                    i += 1;
+        // 이전 줄에서 이어지는 블록 주석
+        if self.state.in_block_comment {
+            match line.find("*/") {
+                Some(idx) => {
+                    tokens.push(Token {
+                        text: line[..idx + 2].to_string(),
+                        token_type: TokenType::Comment,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    self.state.in_block_comment = false;
+                    i = line[..idx + 2].chars().count();
+                }
+                None => {
+                    tokens.push(Token {
+                        text: line.to_string(),
+                        token_type: TokenType::Comment,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    return tokens;
                 }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Normal,
-                });
-                continue;
             }
+        }
 
-            // 문자열 (키 또는 값)
-            if chars[i] == '"' {
+        while i < chars.len() {
+            // 주석 (닫히지 않으면 다음 줄로 상태를 이어간다)
+            if i + 1 < chars.len() && chars[i] == '/' && chars[i + 1] == '*' {
                 let start = i;
-                i += 1;
-                while i < chars.len() && chars[i] != '"' {
-                    if chars[i] == '\\' && i + 1 < chars.len() {
-                        i += 1;
+                i += 2;
+                let mut found_end = false;
+                while i < chars.len() {
+                    if chars[i] == '*' && i + 1 < chars.len() && chars[i + 1] == '/' {
+                        i += 2;
+                        found_end = true;
+                        break;
                     }
                     i += 1;
                 }
-                if i < chars.len() {
-                    i += 1;
-                }
-
-                // 뒤에 콜론이 있으면 키
-                let mut is_key = false;
-                let mut j = i;
-                while j < chars.len() && chars[j].is_whitespace() {
-                    j += 1;
-                }
-                if j < chars.len() && chars[j] == ':' {
-                    is_key = true;
+                if !found_end {
+                    i = chars.len();
                 }
-
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
-                    token_type: if is_key { TokenType::Attribute } else { TokenType::String },
+                    token_type: TokenType::Comment,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
+                self.state.in_block_comment = !found_end;
                 continue;
             }
 
-            // 숫자
-            if chars[i].is_ascii_digit() || chars[i] == '-' || chars[i] == '+' {
+            // 선택자 (. # 로 시작)
+            if chars[i] == '.' || chars[i] == '#' {
                 let start = i;
-                if chars[i] == '-' || chars[i] == '+' {
-                    i += 1;
-                }
-                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == 'e' || chars[i] == 'E' || chars[i] == '-' || chars[i] == '+') {
+                i += 1;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '-' || chars[i] == '_') {
                     i += 1;
                 }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Number,
-                });
-                continue;
-            }
-
-            // 불린/null
-            let remaining: String = chars[i..].iter().collect();
-            if remaining.starts_with("true") {
-                tokens.push(Token {
-                    text: "true".to_string(),
-                    token_type: TokenType::Keyword,
-                });
-                i += 4;
-                continue;
-            }
-            if remaining.starts_with("false") {
-                tokens.push(Token {
-                    text: "false".to_string(),
-                    token_type: TokenType::Keyword,
-                });
-                i += 5;
-                continue;
-            }
-            if remaining.starts_with("null") {
-                tokens.push(Token {
-                    text: "null".to_string(),
-                    token_type: TokenType::Keyword,
-                });
-                i += 4;
-                continue;
-            }
-
-            // 괄호
-            if "{}[]".contains(chars[i]) {
-                tokens.push(Token {
-                    text: chars[i].to_string(),
-                    token_type: TokenType::Bracket,
-                });
-                i += 1;
-                continue;
-            }
-
-            // 콜론, 콤마
-            if ":,".contains(chars[i]) {
-                tokens.push(Token {
-                    text: chars[i].to_string(),
-                    token_type: TokenType::Operator,
+                    token_type: TokenType::Function,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
-                i += 1;
                 continue;
             }
 
-            // 기타
-            tokens.push(Token {
-                text: chars[i].to_string(),
-                token_type: TokenType::Normal,
-            });
-            i += 1;
-        }
-
-        tokens
-    }
-
-    // YAML/TOML 토큰화
-    fn tokenize_yaml(&mut self, line: &str) -> Vec<Token> {
-        let mut tokens = Vec::new();
-
-        // 주석
-        if let Some(comment_pos) = line.find('#') {
-            // # 이전 부분
-            if comment_pos > 0 {
-                let before = &line[..comment_pos];
-                tokens.extend(self.tokenize_yaml_content(before));
-            }
-            // 주석 부분
-            tokens.push(Token {
-                text: line[comment_pos..].to_string(),
-                token_type: TokenType::Comment,
-            });
-            return tokens;
-        }
-
-        self.tokenize_yaml_content(line)
-    }
-
-    fn tokenize_yaml_content(&self, line: &str) -> Vec<Token> {
-        let mut tokens = Vec::new();
-        let chars: Vec<char> = line.chars().collect();
-        let mut i = 0;
-
-        while i < chars.len() {
-            // 키: 값 형태
-            if chars[i].is_alphabetic() || chars[i] == '_' || chars[i] == '-' {
+            // @ 규칙
+            if chars[i] == '@' {
                 let start = i;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-' || chars[i] == '.') {
+                i += 1;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '-') {
                     i += 1;
                 }
+                tokens.push(Token {
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::Keyword,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                continue;
+            }
 
-                // 뒤에 콜론이 있으면 키
-                let mut is_key = false;
-                if i < chars.len() && chars[i] == ':' {
-                    is_key = true;
+            // 속성
+            if chars[i].is_alphabetic() || chars[i] == '-' {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '-') {
+                    i += 1;
                 }
-
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
-                    token_type: if is_key { TokenType::Attribute } else { TokenType::Variable },
+                    token_type: TokenType::Attribute,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -1056,63 +2354,50 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
             // 숫자
-            if chars[i].is_ascii_digit() || ((chars[i] == '-' || chars[i] == '+') && i + 1 < chars.len() && chars[i + 1].is_ascii_digit()) {
+            if chars[i].is_ascii_digit() || (chars[i] == '.' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit()) {
                 let start = i;
-                if chars[i] == '-' || chars[i] == '+' {
-                    i += 1;
-                }
-                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '%' || chars[i] == '-') {
                     i += 1;
                 }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Number,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // 불린
-            let remaining: String = chars[i..].iter().collect();
-            if remaining.starts_with("true") || remaining.starts_with("false") || remaining.starts_with("yes") || remaining.starts_with("no") || remaining.starts_with("null") {
-                let word_len = if remaining.starts_with("false") { 5 } else if remaining.starts_with("true") { 4 } else if remaining.starts_with("null") { 4 } else if remaining.starts_with("yes") { 3 } else { 2 };
-                tokens.push(Token {
-                    text: chars[i..i + word_len].iter().collect(),
-                    token_type: TokenType::Keyword,
-                });
-                i += word_len;
-                continue;
-            }
-
-            // 콜론
-            if chars[i] == ':' {
-                tokens.push(Token {
-                    text: ":".to_string(),
-                    token_type: TokenType::Operator,
-                });
-                i += 1;
-                continue;
-            }
-
-            // 대시 (리스트 항목)
-            if chars[i] == '-' {
+            // 괄호
+            if "{}()[]".contains(chars[i]) {
                 tokens.push(Token {
-                    text: "-".to_string(),
-                    token_type: TokenType::Operator,
+                    text: chars[i].to_string(),
+                    token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
             }
 
-            // 괄호
-            if "{}[]".contains(chars[i]) {
+            // 연산자
+            if ":;,".contains(chars[i]) {
                 tokens.push(Token {
                     text: chars[i].to_string(),
-                    token_type: TokenType::Bracket,
+                    token_type: TokenType::Operator,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
@@ -1122,6 +2407,9 @@ impl SyntaxHighlighter {
             tokens.push(Token {
                 text: chars[i].to_string(),
                 token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             i += 1;
         }
@@ -1129,45 +2417,35 @@ impl SyntaxHighlighter {
         tokens
     }
 
-    // Shell 토큰화
-    fn tokenize_shell(&mut self, line: &str) -> Vec<Token> {
-        let keywords = [
-            "if", "then", "else", "elif", "fi", "case", "esac", "for", "while",
-            "until", "do", "done", "in", "function", "select", "time", "coproc",
-            "return", "exit", "break", "continue", "local", "declare", "typeset",
-            "export", "readonly", "unset", "shift", "source", "alias", "unalias",
-            "set", "shopt", "trap", "exec", "eval", "true", "false",
-        ];
-        let builtins = [
-            "echo", "printf", "read", "cd", "pwd", "pushd", "popd", "dirs",
-            "let", "test", "[", "[[", "]]", "]", "getopts", "hash", "type",
-            "umask", "ulimit", "wait", "jobs", "fg", "bg", "kill", "disown",
-            "suspend", "logout", "history", "fc", "bind", "help", "enable",
-            "builtin", "command", "compgen", "complete", "compopt", "mapfile",
-            "readarray", "coproc",
-        ];
-
+    // JSON 토큰화
+    fn tokenize_json(&mut self, line: &str) -> Vec<Token> {
         let mut tokens = Vec::new();
         let chars: Vec<char> = line.chars().collect();
         let mut i = 0;
 
         while i < chars.len() {
-            // 주석
-            if chars[i] == '#' {
+            // 공백
+            if chars[i].is_whitespace() {
+                let start = i;
+                while i < chars.len() && chars[i].is_whitespace() {
+                    i += 1;
+                }
                 tokens.push(Token {
-                    text: chars[i..].iter().collect(),
-                    token_type: TokenType::Comment,
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::Normal,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
-                break;
+                continue;
             }
 
-            // 문자열
-            if chars[i] == '"' || chars[i] == '\'' {
-                let quote = chars[i];
+            // 문자열 (키 또는 값)
+            if chars[i] == '"' {
                 let start = i;
                 i += 1;
-                while i < chars.len() && chars[i] != quote {
-                    if chars[i] == '\\' && i + 1 < chars.len() && quote == '"' {
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' && i + 1 < chars.len() {
                         i += 1;
                     }
                     i += 1;
@@ -1175,97 +2453,103 @@ impl SyntaxHighlighter {
                 if i < chars.len() {
                     i += 1;
                 }
+
+                // 뒤에 콜론이 있으면 키
+                let mut is_key = false;
+                let mut j = i;
+                while j < chars.len() && chars[j].is_whitespace() {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j] == ':' {
+                    is_key = true;
+                }
+
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
-                    token_type: TokenType::String,
+                    token_type: if is_key { TokenType::Attribute } else { TokenType::String },
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // 변수
-            if chars[i] == '$' {
+            // 숫자
+            if chars[i].is_ascii_digit() || chars[i] == '-' || chars[i] == '+' {
                 let start = i;
-                i += 1;
-                if i < chars.len() && chars[i] == '{' {
+                if chars[i] == '-' || chars[i] == '+' {
                     i += 1;
-                    while i < chars.len() && chars[i] != '}' {
-                        i += 1;
-                    }
-                    if i < chars.len() {
-                        i += 1;
-                    }
-                } else if i < chars.len() && chars[i] == '(' {
-                    let mut depth = 1;
+                }
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == 'e' || chars[i] == 'E' || chars[i] == '-' || chars[i] == '+') {
                     i += 1;
-                    while i < chars.len() && depth > 0 {
-                        if chars[i] == '(' {
-                            depth += 1;
-                        } else if chars[i] == ')' {
-                            depth -= 1;
-                        }
-                        i += 1;
-                    }
-                } else {
-                    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
-                        i += 1;
-                    }
                 }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Variable,
+                    token_type: TokenType::Number,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // 숫자
-            if chars[i].is_ascii_digit() {
-                let start = i;
-                while i < chars.len() && chars[i].is_ascii_digit() {
-                    i += 1;
-                }
+            // 불린/null
+            let remaining: String = chars[i..].iter().collect();
+            if remaining.starts_with("true") {
                 tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Number,
+                    text: "true".to_string(),
+                    token_type: TokenType::Keyword,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
+                i += 4;
                 continue;
             }
-
-            // 식별자/키워드
-            if chars[i].is_alphabetic() || chars[i] == '_' {
-                let start = i;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-') {
-                    i += 1;
-                }
-                let word: String = chars[start..i].iter().collect();
-                let token_type = if keywords.contains(&word.as_str()) {
-                    TokenType::Keyword
-                } else if builtins.contains(&word.as_str()) {
-                    TokenType::Function
-                } else {
-                    TokenType::Normal
-                };
+            if remaining.starts_with("false") {
                 tokens.push(Token {
-                    text: word,
-                    token_type,
+                    text: "false".to_string(),
+                    token_type: TokenType::Keyword,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                i += 5;
+                continue;
+            }
+            if remaining.starts_with("null") {
+                tokens.push(Token {
+                    text: "null".to_string(),
+                    token_type: TokenType::Keyword,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
+                i += 4;
                 continue;
             }
 
-            // 연산자
-            if "=|&;<>!+-*/%".contains(chars[i]) {
+            // 괄호
+            if "{}[]".contains(chars[i]) {
                 tokens.push(Token {
                     text: chars[i].to_string(),
-                    token_type: TokenType::Operator,
+                    token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
             }
 
-            // 괄호
-            if "()[]{}".contains(chars[i]) {
+            // 콜론, 콤마
+            if ":,".contains(chars[i]) {
                 tokens.push(Token {
                     text: chars[i].to_string(),
-                    token_type: TokenType::Bracket,
+                    token_type: TokenType::Operator,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
@@ -1275,6 +2559,9 @@ impl SyntaxHighlighter {
             tokens.push(Token {
                 text: chars[i].to_string(),
                 token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             i += 1;
         }
@@ -1282,150 +2569,235 @@ impl SyntaxHighlighter {
         tokens
     }
 
-    // SQL 토큰화
-    fn tokenize_sql(&mut self, line: &str) -> Vec<Token> {
-        let keywords = [
-            "SELECT", "FROM", "WHERE", "AND", "OR", "NOT", "IN", "BETWEEN",
-            "LIKE", "IS", "NULL", "TRUE", "FALSE", "AS", "ON", "JOIN", "LEFT",
-            "RIGHT", "INNER", "OUTER", "FULL", "CROSS", "NATURAL", "USING",
-            "GROUP", "BY", "HAVING", "ORDER", "ASC", "DESC", "LIMIT", "OFFSET",
-            "INSERT", "INTO", "VALUES", "UPDATE", "SET", "DELETE", "CREATE",
-            "TABLE", "INDEX", "VIEW", "DROP", "ALTER", "ADD", "COLUMN",
-            "PRIMARY", "KEY", "FOREIGN", "REFERENCES", "UNIQUE", "CHECK",
-            "DEFAULT", "CONSTRAINT", "CASCADE", "RESTRICT", "UNION", "ALL",
-            "EXCEPT", "INTERSECT", "EXISTS", "CASE", "WHEN", "THEN", "ELSE",
-            "END", "IF", "BEGIN", "COMMIT", "ROLLBACK", "TRANSACTION",
-            "DECLARE", "CURSOR", "FETCH", "CLOSE", "OPEN", "FOR", "WHILE",
-            "LOOP", "RETURN", "FUNCTION", "PROCEDURE", "TRIGGER", "DATABASE",
-            "SCHEMA", "GRANT", "REVOKE", "WITH", "RECURSIVE", "DISTINCT",
-            "select", "from", "where", "and", "or", "not", "in", "between",
-            "like", "is", "null", "true", "false", "as", "on", "join", "left",
-            "right", "inner", "outer", "full", "cross", "natural", "using",
-            "group", "by", "having", "order", "asc", "desc", "limit", "offset",
-            "insert", "into", "values", "update", "set", "delete", "create",
-            "table", "index", "view", "drop", "alter", "add", "column",
-            "primary", "key", "foreign", "references", "unique", "check",
-            "default", "constraint", "cascade", "restrict", "union", "all",
-            "except", "intersect", "exists", "case", "when", "then", "else",
-            "end", "if", "begin", "commit", "rollback", "transaction",
-        ];
-        let types = [
-            "INT", "INTEGER", "SMALLINT", "BIGINT", "DECIMAL", "NUMERIC",
-            "FLOAT", "REAL", "DOUBLE", "PRECISION", "CHAR", "VARCHAR", "TEXT",
-            "DATE", "TIME", "TIMESTAMP", "DATETIME", "BOOLEAN", "BOOL", "BLOB",
-            "CLOB", "BINARY", "VARBINARY", "UUID", "JSON", "JSONB", "ARRAY",
-            "SERIAL", "BIGSERIAL", "MONEY", "INTERVAL",
-            "int", "integer", "smallint", "bigint", "decimal", "numeric",
-            "float", "real", "double", "precision", "char", "varchar", "text",
-            "date", "time", "timestamp", "datetime", "boolean", "bool",
-        ];
-        let functions = [
-            "COUNT", "SUM", "AVG", "MIN", "MAX", "COALESCE", "NULLIF",
-            "CAST", "CONVERT", "CONCAT", "SUBSTRING", "TRIM", "UPPER", "LOWER",
-            "LENGTH", "REPLACE", "ROUND", "FLOOR", "CEIL", "ABS", "NOW",
-            "CURRENT_DATE", "CURRENT_TIME", "CURRENT_TIMESTAMP", "EXTRACT",
-            "DATE_PART", "DATE_TRUNC", "ROW_NUMBER", "RANK", "DENSE_RANK",
-            "FIRST_VALUE", "LAST_VALUE", "LAG", "LEAD", "OVER", "PARTITION",
-            "count", "sum", "avg", "min", "max", "coalesce", "nullif",
-            "cast", "convert", "concat", "substring", "trim", "upper", "lower",
-        ];
+    // YAML/TOML 토큰화
+    fn tokenize_yaml(&mut self, line: &str) -> Vec<Token> {
+        let indent = line.chars().take_while(|&c| c == ' ').count();
+
+        // 이전 줄에서 이어지는 블록 스칼라 (|, >, |-, >- 들여쓰기 본문)
+        if let Some(block_indent) = self.state.in_yaml_block_scalar {
+            if line.trim().is_empty() || indent > block_indent {
+                return vec![Token {
+                    text: line.to_string(),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                }];
+            }
+            self.state.in_yaml_block_scalar = None;
+        }
+
+        let mut tokens = Vec::new();
+
+        // 주석
+        if let Some(comment_pos) = line.find('#') {
+            // # 이전 부분
+            if comment_pos > 0 {
+                let before = &line[..comment_pos];
+                tokens.extend(self.tokenize_yaml_content(before));
+            }
+            // 주석 부분
+            tokens.push(Token {
+                text: line[comment_pos..].to_string(),
+                token_type: TokenType::Comment,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+        } else {
+            tokens.extend(self.tokenize_yaml_content(line));
+        }
+
+        if Self::yaml_starts_block_scalar(line) {
+            self.state.in_yaml_block_scalar = Some(indent);
+        }
+
+        tokens
+    }
 
+    /// Whether `line`'s value is exactly a block scalar indicator (`|`,
+    /// `>`, `|-`, or `>-`), in which case the following more-indented
+    /// lines are the scalar's body rather than ordinary keys/values.
+    fn yaml_starts_block_scalar(line: &str) -> bool {
+        match line.find(':') {
+            Some(pos) => {
+                let value = line[pos + 1..].split('#').next().unwrap_or("").trim();
+                matches!(value, "|" | ">" | "|-" | ">-")
+            }
+            None => false,
+        }
+    }
+
+    fn tokenize_yaml_content(&self, line: &str) -> Vec<Token> {
         let mut tokens = Vec::new();
         let chars: Vec<char> = line.chars().collect();
         let mut i = 0;
 
         while i < chars.len() {
-            // 단일 줄 주석 (--)
-            if i + 1 < chars.len() && chars[i] == '-' && chars[i + 1] == '-' {
+            // 키: 값 형태
+            if chars[i].is_alphabetic() || chars[i] == '_' || chars[i] == '-' {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-' || chars[i] == '.') {
+                    i += 1;
+                }
+
+                // 뒤에 콜론이 있으면 키
+                let mut is_key = false;
+                if i < chars.len() && chars[i] == ':' {
+                    is_key = true;
+                }
+
                 tokens.push(Token {
-                    text: chars[i..].iter().collect(),
-                    token_type: TokenType::Comment,
+                    text: chars[start..i].iter().collect(),
+                    token_type: if is_key { TokenType::Attribute } else { TokenType::Variable },
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
-                break;
+                continue;
             }
 
-            // 문자열
-            if chars[i] == '\'' {
+            // 앵커 (&name)
+            if chars[i] == '&' {
                 let start = i;
                 i += 1;
-                while i < chars.len() {
-                    if chars[i] == '\'' {
-                        if i + 1 < chars.len() && chars[i + 1] == '\'' {
-                            i += 2;
-                            continue;
-                        }
-                        i += 1;
-                        break;
-                    }
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-') {
                     i += 1;
                 }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
-                    token_type: TokenType::String,
+                    token_type: TokenType::Constant,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // 숫자
-            if chars[i].is_ascii_digit() || (chars[i] == '.' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit()) {
+            // 별칭 (*name)
+            if chars[i] == '*' {
                 let start = i;
-                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-') {
                     i += 1;
                 }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Number,
+                    token_type: TokenType::Macro,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // 식별자/키워드
-            if chars[i].is_alphabetic() || chars[i] == '_' {
+            // 태그 (!tag, !!tag)
+            if chars[i] == '!' {
                 let start = i;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+                if i < chars.len() && chars[i] == '!' {
+                    i += 1;
+                }
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-' || chars[i] == '/') {
                     i += 1;
                 }
-                let word: String = chars[start..i].iter().collect();
-                let token_type = if keywords.contains(&word.as_str()) {
-                    TokenType::Keyword
-                } else if types.contains(&word.as_str()) {
-                    TokenType::Type
-                } else if functions.contains(&word.as_str()) {
-                    TokenType::Function
-                } else {
-                    TokenType::Variable
-                };
                 tokens.push(Token {
-                    text: word,
-                    token_type,
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::Type,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // 연산자
-            if "=<>!+-*/%".contains(chars[i]) {
+            // 문자열
+            if chars[i] == '"' || chars[i] == '\'' {
+                let quote = chars[i];
                 let start = i;
-                while i < chars.len() && "=<>!".contains(chars[i]) {
-                    i += 1;
-                    if i - start >= 2 {
-                        break;
+                i += 1;
+                while i < chars.len() && chars[i] != quote {
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        i += 1;
                     }
+                    i += 1;
                 }
-                if i == start {
+                if i < chars.len() {
                     i += 1;
                 }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                continue;
+            }
+
+            // 숫자
+            if chars[i].is_ascii_digit() || ((chars[i] == '-' || chars[i] == '+') && i + 1 < chars.len() && chars[i + 1].is_ascii_digit()) {
+                let start = i;
+                i = scan_number(chars, i, &[]);
+                tokens.push(Token {
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::Number,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                continue;
+            }
+
+            // 불린
+            let remaining: String = chars[i..].iter().collect();
+            if remaining.starts_with("true") || remaining.starts_with("false") || remaining.starts_with("yes") || remaining.starts_with("no") || remaining.starts_with("null") {
+                let word_len = if remaining.starts_with("false") { 5 } else if remaining.starts_with("true") { 4 } else if remaining.starts_with("null") { 4 } else if remaining.starts_with("yes") { 3 } else { 2 };
+                tokens.push(Token {
+                    text: chars[i..i + word_len].iter().collect(),
+                    token_type: TokenType::Keyword,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                i += word_len;
+                continue;
+            }
+
+            // 콜론
+            if chars[i] == ':' {
+                tokens.push(Token {
+                    text: ":".to_string(),
+                    token_type: TokenType::Operator,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                i += 1;
+                continue;
+            }
+
+            // 대시 (리스트 항목)
+            if chars[i] == '-' {
+                tokens.push(Token {
+                    text: "-".to_string(),
                     token_type: TokenType::Operator,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
+                i += 1;
                 continue;
             }
 
             // 괄호
-            if "()".contains(chars[i]) {
+            if "{}[]".contains(chars[i]) {
                 tokens.push(Token {
                     text: chars[i].to_string(),
                     token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
@@ -1435,6 +2807,9 @@ impl SyntaxHighlighter {
             tokens.push(Token {
                 text: chars[i].to_string(),
                 token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             i += 1;
         }
@@ -1442,23 +2817,36 @@ impl SyntaxHighlighter {
         tokens
     }
 
-    // Ruby 토큰화
-    fn tokenize_ruby(&mut self, line: &str) -> Vec<Token> {
-        let keywords = [
-            "BEGIN", "END", "alias", "and", "begin", "break", "case", "class",
-            "def", "defined?", "do", "else", "elsif", "end", "ensure", "false",
-            "for", "if", "in", "module", "next", "nil", "not", "or", "redo",
-            "rescue", "retry", "return", "self", "super", "then", "true",
-            "undef", "unless", "until", "when", "while", "yield", "__FILE__",
-            "__LINE__", "__ENCODING__", "attr_reader", "attr_writer",
-            "attr_accessor", "private", "protected", "public", "require",
-            "require_relative", "include", "extend", "prepend", "raise", "fail",
-            "catch", "throw", "lambda", "proc", "loop",
-        ];
+    // Shell 토큰화
+    fn tokenize_shell(&mut self, line: &str) -> Vec<Token> {
+        // 이전 줄에서 이어지는 히어독 본문
+        if let Some(heredoc) = self.state.in_heredoc.clone() {
+            let terminator_candidate = if heredoc.strip_tabs {
+                line.trim_start_matches('\t')
+            } else {
+                line
+            };
+            if terminator_candidate == heredoc.delimiter {
+                self.state.in_heredoc = None;
+                // 종료 구분자 줄 자체는 본문이 아니므로 아래에서 평소처럼 토큰화한다
+            } else if heredoc.quoted {
+                // 따옴표로 감싼 구분자는 변수 확장을 하지 않는다
+                return vec![Token {
+                    text: line.to_string(),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                }];
+            } else {
+                return Self::tokenize_heredoc_body_line(line);
+            }
+        }
 
         let mut tokens = Vec::new();
         let chars: Vec<char> = line.chars().collect();
         let mut i = 0;
+        let mut pending_heredoc: Option<HeredocState> = None;
 
         while i < chars.len() {
             // 주석
@@ -1466,17 +2854,67 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[i..].iter().collect(),
                     token_type: TokenType::Comment,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 break;
             }
 
+            // 히어독 리다이렉션 (<<, <<-) -- 구분자를 기록해 다음 줄부터
+            // 종료 구분자를 만날 때까지 본문을 문자열로 취급한다
+            if chars[i] == '<' && i + 1 < chars.len() && chars[i + 1] == '<' {
+                let start = i;
+                let mut j = i + 2;
+                let strip_tabs = j < chars.len() && chars[j] == '-';
+                if strip_tabs {
+                    j += 1;
+                }
+                while j < chars.len() && chars[j] == ' ' {
+                    j += 1;
+                }
+                let quote = if j < chars.len() && (chars[j] == '"' || chars[j] == '\'') {
+                    Some(chars[j])
+                } else {
+                    None
+                };
+                let delim_start = if quote.is_some() { j + 1 } else { j };
+                let mut k = delim_start;
+                match quote {
+                    Some(q) => while k < chars.len() && chars[k] != q {
+                        k += 1;
+                    },
+                    None => while k < chars.len() && (chars[k].is_alphanumeric() || chars[k] == '_') {
+                        k += 1;
+                    },
+                }
+                let delimiter: String = chars[delim_start..k].iter().collect();
+                if !delimiter.is_empty() {
+                    let token_end = if quote.is_some() { (k + 1).min(chars.len()) } else { k };
+                    tokens.push(Token {
+                        text: chars[start..token_end].iter().collect(),
+                        token_type: TokenType::Operator,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    pending_heredoc = Some(HeredocState {
+                        delimiter,
+                        strip_tabs,
+                        quoted: quote.is_some(),
+                    });
+                    i = token_end;
+                    continue;
+                }
+            }
+
             // 문자열
             if chars[i] == '"' || chars[i] == '\'' {
                 let quote = chars[i];
                 let start = i;
                 i += 1;
                 while i < chars.len() && chars[i] != quote {
-                    if chars[i] == '\\' && i + 1 < chars.len() {
+                    if chars[i] == '\\' && i + 1 < chars.len() && quote == '"' {
                         i += 1;
                     }
                     i += 1;
@@ -1487,51 +2925,47 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // 심볼
-            if chars[i] == ':' && i + 1 < chars.len() && (chars[i + 1].is_alphabetic() || chars[i + 1] == '_') {
-                let start = i;
-                i += 1;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '?' || chars[i] == '!') {
-                    i += 1;
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Constant,
-                });
-                continue;
-            }
-
-            // 인스턴스 변수
-            if chars[i] == '@' {
-                let start = i;
-                i += 1;
-                if i < chars.len() && chars[i] == '@' {
-                    i += 1;
-                }
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
-                    i += 1;
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Variable,
-                });
-                continue;
-            }
-
-            // 글로벌 변수
+            // 변수
             if chars[i] == '$' {
                 let start = i;
                 i += 1;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                if i < chars.len() && chars[i] == '{' {
+                    i += 1;
+                    while i < chars.len() && chars[i] != '}' {
+                        i += 1;
+                    }
+                    if i < chars.len() {
+                        i += 1;
+                    }
+                } else if i < chars.len() && chars[i] == '(' {
+                    let mut depth = 1;
                     i += 1;
+                    while i < chars.len() && depth > 0 {
+                        if chars[i] == '(' {
+                            depth += 1;
+                        } else if chars[i] == ')' {
+                            depth -= 1;
+                        }
+                        i += 1;
+                    }
+                } else {
+                    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                        i += 1;
+                    }
                 }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Variable,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -1539,12 +2973,13 @@ impl SyntaxHighlighter {
             // 숫자
             if chars[i].is_ascii_digit() {
                 let start = i;
-                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
-                    i += 1;
-                }
+                i = scan_number(chars, i, &[]);
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Number,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -1552,31 +2987,31 @@ impl SyntaxHighlighter {
             // 식별자/키워드
             if chars[i].is_alphabetic() || chars[i] == '_' {
                 let start = i;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '?' || chars[i] == '!') {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-') {
                     i += 1;
                 }
                 let word: String = chars[start..i].iter().collect();
-                let token_type = if keywords.contains(&word.as_str()) {
-                    TokenType::Keyword
-                } else if word.chars().next().map(|c| c.is_uppercase()).unwrap_or(false) {
-                    TokenType::Type
-                } else if i < chars.len() && chars[i] == '(' {
-                    TokenType::Function
-                } else {
-                    TokenType::Variable
-                };
+                let token_type = shell_keyword_table()
+                    .get(&word)
+                    .unwrap_or(TokenType::Normal);
                 tokens.push(Token {
                     text: word,
                     token_type,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
             // 연산자
-            if "=<>!+-*/%&|^~".contains(chars[i]) {
+            if "=|&;<>!+-*/%".contains(chars[i]) {
                 tokens.push(Token {
                     text: chars[i].to_string(),
                     token_type: TokenType::Operator,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
@@ -1587,131 +3022,200 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[i].to_string(),
                     token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
             }
-
-            // 기타
+
+            // 기타
+            tokens.push(Token {
+                text: chars[i].to_string(),
+                token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+            i += 1;
+        }
+
+        self.state.in_heredoc = pending_heredoc;
+        tokens
+    }
+
+    /// One line of an unquoted heredoc's body: `$VAR`/`${...}` substrings
+    /// highlight as `TokenType::Variable`, everything else is plain
+    /// `TokenType::String`, matching how a real shell still expands
+    /// variables inside an unquoted `<<EOF` but not inside `<<'EOF'`.
+    fn tokenize_heredoc_body_line(line: &str) -> Vec<Token> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        let mut start = 0;
+
+        while i < chars.len() {
+            let starts_var = chars[i] == '$'
+                && i + 1 < chars.len()
+                && (chars[i + 1] == '{' || chars[i + 1].is_alphanumeric() || chars[i + 1] == '_');
+            if !starts_var {
+                i += 1;
+                continue;
+            }
+            if start < i {
+                tokens.push(Token {
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+            }
+            let var_start = i;
+            i += 1;
+            if i < chars.len() && chars[i] == '{' {
+                i += 1;
+                while i < chars.len() && chars[i] != '}' {
+                    i += 1;
+                }
+                if i < chars.len() {
+                    i += 1;
+                }
+            } else {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+            }
             tokens.push(Token {
-                text: chars[i].to_string(),
-                token_type: TokenType::Normal,
+                text: chars[var_start..i].iter().collect(),
+                token_type: TokenType::Variable,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+            start = i;
+        }
+        if start < chars.len() {
+            tokens.push(Token {
+                text: chars[start..].iter().collect(),
+                token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
-            i += 1;
         }
-
         tokens
     }
 
-    // PHP 토큰화
-    fn tokenize_php(&mut self, line: &str) -> Vec<Token> {
-        let keywords = [
-            "abstract", "and", "array", "as", "break", "callable", "case",
-            "catch", "class", "clone", "const", "continue", "declare", "default",
-            "die", "do", "echo", "else", "elseif", "empty", "enddeclare",
-            "endfor", "endforeach", "endif", "endswitch", "endwhile", "eval",
-            "exit", "extends", "final", "finally", "fn", "for", "foreach",
-            "function", "global", "goto", "if", "implements", "include",
-            "include_once", "instanceof", "insteadof", "interface", "isset",
-            "list", "match", "namespace", "new", "or", "print", "private",
-            "protected", "public", "readonly", "require", "require_once",
-            "return", "static", "switch", "throw", "trait", "try", "unset",
-            "use", "var", "while", "xor", "yield", "yield from",
-            "true", "false", "null", "TRUE", "FALSE", "NULL",
-            "__CLASS__", "__DIR__", "__FILE__", "__FUNCTION__", "__LINE__",
-            "__METHOD__", "__NAMESPACE__", "__TRAIT__",
-        ];
-        let types = [
-            "int", "float", "bool", "string", "array", "object", "callable",
-            "iterable", "void", "mixed", "never", "null", "self", "parent",
-        ];
-
+    // SQL 토큰화
+    fn tokenize_sql(&mut self, line: &str) -> Vec<Token> {
         let mut tokens = Vec::new();
         let chars: Vec<char> = line.chars().collect();
         let mut i = 0;
 
-        while i < chars.len() {
-            // PHP 태그
-            if i + 4 < chars.len() {
-                let slice: String = chars[i..i+5].iter().collect();
-                if slice == "<?php" {
+        // 이전 줄에서 이어지는 블록 주석
+        if self.state.in_block_comment {
+            match line.find("*/") {
+                Some(idx) => {
                     tokens.push(Token {
-                        text: "<?php".to_string(),
-                        token_type: TokenType::Keyword,
+                        text: line[..idx + 2].to_string(),
+                        token_type: TokenType::Comment,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
-                    i += 5;
-                    continue;
+                    self.state.in_block_comment = false;
+                    i = line[..idx + 2].chars().count();
+                }
+                None => {
+                    tokens.push(Token {
+                        text: line.to_string(),
+                        token_type: TokenType::Comment,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    return tokens;
                 }
             }
-            if i + 1 < chars.len() && chars[i] == '?' && chars[i + 1] == '>' {
-                tokens.push(Token {
-                    text: "?>".to_string(),
-                    token_type: TokenType::Keyword,
-                });
-                i += 2;
-                continue;
-            }
+        }
 
-            // 주석
-            if i + 1 < chars.len() && chars[i] == '/' && chars[i + 1] == '/' {
+        while i < chars.len() {
+            // 블록 주석 (/* ... */, 닫히지 않으면 다음 줄로 상태를 이어간다)
+            if i + 1 < chars.len() && chars[i] == '/' && chars[i + 1] == '*' {
+                let start = i;
+                i += 2;
+                let mut found_end = false;
+                while i < chars.len() {
+                    if chars[i] == '*' && i + 1 < chars.len() && chars[i + 1] == '/' {
+                        i += 2;
+                        found_end = true;
+                        break;
+                    }
+                    i += 1;
+                }
+                if !found_end {
+                    i = chars.len();
+                }
                 tokens.push(Token {
-                    text: chars[i..].iter().collect(),
+                    text: chars[start..i].iter().collect(),
                     token_type: TokenType::Comment,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
-                break;
+                self.state.in_block_comment = !found_end;
+                continue;
             }
-            if chars[i] == '#' {
+
+            // 단일 줄 주석 (--)
+            if i + 1 < chars.len() && chars[i] == '-' && chars[i + 1] == '-' {
                 tokens.push(Token {
                     text: chars[i..].iter().collect(),
                     token_type: TokenType::Comment,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 break;
             }
 
             // 문자열
-            if chars[i] == '"' || chars[i] == '\'' {
-                let quote = chars[i];
+            if chars[i] == '\'' {
                 let start = i;
                 i += 1;
-                while i < chars.len() && chars[i] != quote {
-                    if chars[i] == '\\' && i + 1 < chars.len() {
+                while i < chars.len() {
+                    if chars[i] == '\'' {
+                        if i + 1 < chars.len() && chars[i + 1] == '\'' {
+                            i += 2;
+                            continue;
+                        }
                         i += 1;
+                        break;
                     }
                     i += 1;
                 }
-                if i < chars.len() {
-                    i += 1;
-                }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::String,
-                });
-                continue;
-            }
-
-            // 변수
-            if chars[i] == '$' {
-                let start = i;
-                i += 1;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
-                    i += 1;
-                }
-                tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Variable,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
             // 숫자
-            if chars[i].is_ascii_digit() {
+            if chars[i].is_ascii_digit() || (chars[i] == '.' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit()) {
                 let start = i;
-                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
-                    i += 1;
-                }
+                i = scan_number(chars, i, &[]);
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Number,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -1723,37 +3227,45 @@ impl SyntaxHighlighter {
                     i += 1;
                 }
                 let word: String = chars[start..i].iter().collect();
-                let token_type = if keywords.contains(&word.as_str()) {
-                    TokenType::Keyword
-                } else if types.contains(&word.as_str()) {
-                    TokenType::Type
-                } else if i < chars.len() && chars[i] == '(' {
-                    TokenType::Function
-                } else {
-                    TokenType::Variable
-                };
+                let token_type = sql_keyword_table()
+                    .get(&word.to_lowercase())
+                    .unwrap_or(TokenType::Variable);
                 tokens.push(Token {
                     text: word,
                     token_type,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
             // 연산자
-            if "=<>!+-*/%&|^~.".contains(chars[i]) {
+            if "=<>!+-*/%".contains(chars[i]) {
+                const SQL_OPERATORS: &[&str] = &["<>", "!=", "<=", ">=", "||", "::"];
+                let start = i;
+                match scan_operator(chars, i, SQL_OPERATORS) {
+                    Some(end) => i = end,
+                    None => i += 1,
+                }
                 tokens.push(Token {
-                    text: chars[i].to_string(),
+                    text: chars[start..i].iter().collect(),
                     token_type: TokenType::Operator,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
-                i += 1;
                 continue;
             }
 
             // 괄호
-            if "()[]{}".contains(chars[i]) {
+            if "()".contains(chars[i]) {
                 tokens.push(Token {
                     text: chars[i].to_string(),
                     token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
@@ -1763,6 +3275,9 @@ impl SyntaxHighlighter {
             tokens.push(Token {
                 text: chars[i].to_string(),
                 token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             i += 1;
         }
@@ -1770,6 +3285,114 @@ impl SyntaxHighlighter {
         tokens
     }
 
+    // Ruby 토큰화
+    fn tokenize_ruby(&mut self, line: &str) -> Vec<Token> {
+        const KEYWORDS: &[&str] = &[
+            "BEGIN", "END", "alias", "and", "begin", "break", "case", "class",
+            "def", "defined?", "do", "else", "elsif", "end", "ensure", "false",
+            "for", "if", "in", "module", "next", "nil", "not", "or", "redo",
+            "rescue", "retry", "return", "self", "super", "then", "true",
+            "undef", "unless", "until", "when", "while", "yield", "__FILE__",
+            "__LINE__", "__ENCODING__", "attr_reader", "attr_writer",
+            "attr_accessor", "private", "protected", "public", "require",
+            "require_relative", "include", "extend", "prepend", "raise", "fail",
+            "catch", "throw", "lambda", "proc", "loop",
+        ];
+
+        self.tokenize_with(
+            &LanguageDef {
+                keywords: KEYWORDS,
+                types: &[],
+                line_comment: Some("#"),
+                block_comment: None,
+                nested_block_comments: false,
+                string_delimiters: &['"', '\''],
+                raw_string_prefix: None,
+                numeric_suffixes: &["r", "i"],
+                ident_start: default_ident_start,
+                ident_continue: ruby_ident_continue,
+                support_attributes: false,
+                lifetimes: false,
+                variable_sigils: &['@', '$'],
+                symbol_sigil: Some(':'),
+                tag_markers: None,
+                extra_line_comment: None,
+                capitalized_is_type: true,
+                keyword_table: Some(ruby_keyword_table()),
+                triple_quote: false,
+                heredoc_prefix: Some("<<"),
+                heredoc_dollar_interpolation: false,
+                interp_hash_brace: true,
+                interp_dollar: false,
+                operators: &[
+                    "<=>", "===", "=~", "!~", "**=", "||=", "&&=", "==",
+                    "!=", "<=", ">=", "&&", "||", "..", "...", "->", "=>",
+                    "+=", "-=", "*=", "/=", "%=", "**", "<<", ">>",
+                ],
+            },
+            line,
+        )
+    }
+
+    // PHP 토큰화
+    fn tokenize_php(&mut self, line: &str) -> Vec<Token> {
+        const KEYWORDS: &[&str] = &[
+            "abstract", "and", "array", "as", "break", "callable", "case",
+            "catch", "class", "clone", "const", "continue", "declare", "default",
+            "die", "do", "echo", "else", "elseif", "empty", "enddeclare",
+            "endfor", "endforeach", "endif", "endswitch", "endwhile", "eval",
+            "exit", "extends", "final", "finally", "fn", "for", "foreach",
+            "function", "global", "goto", "if", "implements", "include",
+            "include_once", "instanceof", "insteadof", "interface", "isset",
+            "list", "match", "namespace", "new", "or", "print", "private",
+            "protected", "public", "readonly", "require", "require_once",
+            "return", "static", "switch", "throw", "trait", "try", "unset",
+            "use", "var", "while", "xor", "yield", "yield from",
+            "true", "false", "null", "TRUE", "FALSE", "NULL",
+            "__CLASS__", "__DIR__", "__FILE__", "__FUNCTION__", "__LINE__",
+            "__METHOD__", "__NAMESPACE__", "__TRAIT__",
+        ];
+        const TYPES: &[&str] = &[
+            "int", "float", "bool", "string", "array", "object", "callable",
+            "iterable", "void", "mixed", "never", "null", "self", "parent",
+        ];
+
+        self.tokenize_with(
+            &LanguageDef {
+                keywords: KEYWORDS,
+                types: TYPES,
+                line_comment: Some("//"),
+                block_comment: None,
+                nested_block_comments: false,
+                string_delimiters: &['"', '\''],
+                raw_string_prefix: None,
+                numeric_suffixes: &[],
+                ident_start: default_ident_start,
+                ident_continue: default_ident_continue,
+                support_attributes: false,
+                lifetimes: false,
+                variable_sigils: &['$'],
+                symbol_sigil: None,
+                tag_markers: Some(("<?php", "?>")),
+                extra_line_comment: Some("#"),
+                capitalized_is_type: false,
+                keyword_table: Some(php_keyword_table()),
+                triple_quote: false,
+                heredoc_prefix: Some("<<<"),
+                heredoc_dollar_interpolation: true,
+                interp_hash_brace: false,
+                interp_dollar: true,
+                operators: &[
+                    "<=>", "===", "!==", "??=", "**=", "...", "??", "->",
+                    "=>", "::", "==", "!=", "<=", ">=", "&&", "||", "++",
+                    "--", "+=", "-=", "*=", "/=", "%=", "**", ".=", "<<",
+                    ">>",
+                ],
+            },
+            line,
+        )
+    }
+
     // Swift 토큰화
     fn tokenize_swift(&mut self, line: &str) -> Vec<Token> {
         let keywords = [
@@ -1790,7 +3413,17 @@ impl SyntaxHighlighter {
             "Comparable", "Identifiable", "View", "ObservableObject",
         ];
 
-        self.tokenize_c_like(line, &keywords, &types, "//", ("/*", "*/"), true)
+        let def = LanguageDef {
+            keyword_table: Some(swift_keyword_table()),
+            triple_quote: true,
+            operators: &[
+                "...", "..<", "?.", "??", "->", "==", "!=", "<=", ">=",
+                "&&", "||", "++", "--", "+=", "-=", "*=", "/=", "%=",
+                "&=", "|=", "^=", "<<=", ">>=", "<<", ">>",
+            ],
+            ..LanguageDef::c_family(&keywords, &types, true)
+        };
+        self.tokenize_with(&def, line)
     }
 
     // Markdown 토큰화
@@ -1798,6 +3431,25 @@ impl SyntaxHighlighter {
         let mut tokens = Vec::new();
         let chars: Vec<char> = line.chars().collect();
         let trimmed = line.trim_start();
+        let prev_line = self.state.md_prev_line.take();
+
+        // Setext 제목 밑줄: 바로 윗 줄이 평범한 문단이었을 때만 전체 줄을
+        // 제목 밑줄로 인식한다 (앞 줄은 이미 Normal 텍스트로 반환된 뒤라
+        // 되돌려 칠할 수 없으니, 밑줄 자신만 제목처럼 강조한다).
+        if !trimmed.is_empty() && prev_line.as_deref().is_some_and(|p| !p.is_empty()) {
+            let is_setext_underline =
+                trimmed.chars().all(|c| c == '=') || trimmed.chars().all(|c| c == '-');
+            if is_setext_underline {
+                tokens.push(Token {
+                    text: line.to_string(),
+                    token_type: TokenType::Keyword,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                return tokens;
+            }
+        }
 
         // 헤더
         if trimmed.starts_with('#') {
@@ -1806,6 +3458,9 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: line.to_string(),
                     token_type: TokenType::Keyword,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 return tokens;
             }
@@ -1816,6 +3471,9 @@ impl SyntaxHighlighter {
             tokens.push(Token {
                 text: line.to_string(),
                 token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             return tokens;
         }
@@ -1825,6 +3483,9 @@ impl SyntaxHighlighter {
             tokens.push(Token {
                 text: line.to_string(),
                 token_type: TokenType::Comment,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             return tokens;
         }
@@ -1836,16 +3497,52 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: line[..indent].to_string(),
                     token_type: TokenType::Normal,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
             }
             tokens.push(Token {
                 text: trimmed[..2].to_string(),
                 token_type: TokenType::Operator,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
-            tokens.push(Token {
-                text: trimmed[2..].to_string(),
-                token_type: TokenType::Normal,
-            });
+            let rest = &trimmed[2..];
+            // 작업 목록 체크박스: - [ ] 또는 - [x]
+            let checkbox_len =
+                if rest.starts_with("[ ]") || rest.starts_with("[x]") || rest.starts_with("[X]") {
+                    Some(3)
+                } else {
+                    None
+                };
+            if let Some(checkbox_len) = checkbox_len {
+                tokens.push(Token {
+                    text: rest[..checkbox_len].to_string(),
+                    token_type: TokenType::Attribute,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                if checkbox_len < rest.len() {
+                    tokens.push(Token {
+                        text: rest[checkbox_len..].to_string(),
+                        token_type: TokenType::Normal,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                }
+            } else {
+                tokens.push(Token {
+                    text: rest.to_string(),
+                    token_type: TokenType::Normal,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+            }
             return tokens;
         }
 
@@ -1860,16 +3557,25 @@ impl SyntaxHighlighter {
                     tokens.push(Token {
                         text: line[..indent].to_string(),
                         token_type: TokenType::Normal,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                 }
                 tokens.push(Token {
                     text: trimmed[..num_end + 1].to_string(),
                     token_type: TokenType::Number,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 if num_end + 1 < trimmed.len() {
                     tokens.push(Token {
                         text: trimmed[num_end + 1..].to_string(),
                         token_type: TokenType::Normal,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                 }
                 return tokens;
@@ -1883,10 +3589,25 @@ impl SyntaxHighlighter {
             tokens.push(Token {
                 text: line.to_string(),
                 token_type: TokenType::Comment,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             return tokens;
         }
 
+        // GFM 파이프 테이블 (헤더 행과 구분 행 모두 파이프 존재 여부만으로 판단)
+        if trimmed.contains('|') {
+            if let Some(row_tokens) = tokenize_table_row(line, trimmed) {
+                return row_tokens;
+            }
+        }
+
+        // 링크 참조 정의: [label]: url "title"
+        if let Some(def_tokens) = tokenize_link_reference_definition(line, trimmed) {
+            return def_tokens;
+        }
+
         // 인라인 요소 처리
         let mut i = 0;
         while i < chars.len() {
@@ -1911,6 +3632,9 @@ impl SyntaxHighlighter {
                             tokens.push(Token {
                                 text: chars[start..i].iter().collect(),
                                 token_type: TokenType::Attribute,
+                                start: 0,
+                                end: 0,
+                                bracket_depth: None,
                             });
                             found_end = true;
                             break;
@@ -1923,6 +3647,9 @@ impl SyntaxHighlighter {
                     tokens.push(Token {
                         text: chars[start..].iter().collect(),
                         token_type: TokenType::Normal,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                     break;
                 }
@@ -1942,127 +3669,582 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
-            // 링크
-            if chars[i] == '[' {
+            // 이미지: ![alt](url) -- 링크와 동일한 모양이라 scan_markdown_link를 재사용한다
+            if chars[i] == '!' && i + 1 < chars.len() && chars[i + 1] == '[' {
                 let start = i;
+                let (end, matched) = scan_markdown_link(&chars, i + 1);
+                if matched {
+                    tokens.push(Token {
+                        text: chars[start..end].iter().collect(),
+                        token_type: TokenType::Constant,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    i = end;
+                    continue;
+                }
+                tokens.push(Token {
+                    text: "!".to_string(),
+                    token_type: TokenType::Normal,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
                 i += 1;
-                while i < chars.len() && chars[i] != ']' {
-                    i += 1;
+                continue;
+            }
+
+            // 링크: [text](url) 또는 참조 링크 [text][label]
+            if chars[i] == '[' {
+                let start = i;
+                let (end, matched) = scan_markdown_link(&chars, i);
+                tokens.push(Token {
+                    text: chars[start..end].iter().collect(),
+                    token_type: if matched {
+                        TokenType::Function
+                    } else {
+                        TokenType::Normal
+                    },
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                i = end;
+                continue;
+            }
+
+            // 일반 텍스트
+            tokens.push(Token {
+                text: chars[i].to_string(),
+                token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+            i += 1;
+        }
+
+        // 다음 줄이 setext 밑줄일 수 있으니, 평범한 문단으로 떨어진 줄만 기억해 둔다.
+        if !trimmed.is_empty() {
+            self.state.md_prev_line = Some(trimmed.to_string());
+        }
+        tokens
+    }
+
+    /// Tokenize a line belonging to a custom language declared in
+    /// `languages.toml` (see `crate::services::custom_languages`). Looks up
+    /// `idx` in the highlighter's loaded registry and drives `tokenize_with`
+    /// off it, the same way a built-in `tokenize_*` method does off a
+    /// `'static` table.
+    fn tokenize_custom(&mut self, idx: usize, line: &str) -> Vec<Token> {
+        let Some(def) = self.custom_languages.defs.get(idx).cloned() else {
+            return vec![Token {
+                text: line.to_string(),
+                token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            }];
+        };
+
+        let keywords: Vec<&str> = def.keywords.iter().map(String::as_str).collect();
+        let types: Vec<&str> = def.types.iter().map(String::as_str).collect();
+        let lang_def = LanguageDef {
+            keywords: &keywords,
+            types: &types,
+            line_comment: def.line_comment.as_deref(),
+            block_comment: def
+                .block_comment
+                .as_ref()
+                .map(|(open, close)| (open.as_str(), close.as_str())),
+            nested_block_comments: def.nested_block_comments,
+            string_delimiters: &def.string_delimiters,
+            raw_string_prefix: None,
+            numeric_suffixes: &[],
+            ident_start: default_ident_start,
+            ident_continue: default_ident_continue,
+            support_attributes: false,
+            lifetimes: false,
+            variable_sigils: &[],
+            symbol_sigil: None,
+            tag_markers: None,
+            extra_line_comment: None,
+            capitalized_is_type: false,
+            keyword_table: None,
+            triple_quote: false,
+            heredoc_prefix: None,
+            heredoc_dollar_interpolation: false,
+            interp_hash_brace: false,
+            interp_dollar: false,
+            operators: &[],
+        };
+
+        self.tokenize_with(&lang_def, line)
+    }
+
+    /// Tokenizes a double-quoted string that interpolates embedded
+    /// expressions -- Ruby's `#{expr}` and PHP's `{$expr}`/bare `$name` --
+    /// instead of treating the whole quoted span as opaque text, the same
+    /// way `tokenize_fstring_body` re-enters the Python tokenizer for an
+    /// f-string's `{expr}`. `chars[start]` must be the opening `"`. A
+    /// literal run flushes as `String`; a braced expression's contents
+    /// (up to the matching `}`, tracking nesting via `find_matching_brace`)
+    /// re-enter `tokenize_with` under this same `def` so it renders with
+    /// real token types, then the string resumes. Returns the tokens plus
+    /// the index just past the string (or end of line if left unclosed).
+    fn tokenize_interpolated_string(
+        &mut self,
+        def: &LanguageDef<'_>,
+        chars: &[char],
+        start: usize,
+    ) -> (Vec<Token>, usize) {
+        let quote = chars[start];
+        let mut tokens = vec![Token {
+            text: quote.to_string(),
+            token_type: TokenType::String,
+            start: 0,
+            end: 0,
+            bracket_depth: None,
+        }];
+        let mut i = start + 1;
+        let mut literal_start = i;
+
+        while i < chars.len() && chars[i] != quote {
+            if chars[i] == '\\' && i + 1 < chars.len() {
+                i += 2;
+                continue;
+            }
+
+            let hash_brace = def.interp_hash_brace && chars[i] == '#' && chars.get(i + 1) == Some(&'{');
+            let dollar_brace = def.interp_dollar && chars[i] == '{' && chars.get(i + 1) == Some(&'$');
+            if hash_brace || dollar_brace {
+                if i > literal_start {
+                    tokens.push(Token {
+                        text: chars[literal_start..i].iter().collect(),
+                        token_type: TokenType::String,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                }
+                let open_len = if hash_brace { 2 } else { 1 };
+                tokens.push(Token {
+                    text: chars[i..i + open_len].iter().collect(),
+                    token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                let expr_start = i + open_len;
+                let end = Self::find_matching_brace(chars, expr_start);
+                let inner: String = chars[expr_start..end.min(chars.len())].iter().collect();
+                tokens.extend(self.tokenize_with(def, &inner));
+                if end < chars.len() {
+                    tokens.push(Token {
+                        text: "}".to_string(),
+                        token_type: TokenType::Bracket,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    i = end + 1;
+                } else {
+                    i = end;
+                }
+                literal_start = i;
+                continue;
+            }
+
+            if def.interp_dollar
+                && chars[i] == '$'
+                && i + 1 < chars.len()
+                && (chars[i + 1].is_alphabetic() || chars[i + 1] == '_')
+            {
+                if i > literal_start {
+                    tokens.push(Token {
+                        text: chars[literal_start..i].iter().collect(),
+                        token_type: TokenType::String,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
                 }
-                if i < chars.len() {
+                let var_start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
                     i += 1;
-                    if i < chars.len() && chars[i] == '(' {
-                        while i < chars.len() && chars[i] != ')' {
-                            i += 1;
-                        }
-                        if i < chars.len() {
-                            i += 1;
-                        }
-                        tokens.push(Token {
-                            text: chars[start..i].iter().collect(),
-                            token_type: TokenType::Function,
-                        });
-                        continue;
-                    }
                 }
                 tokens.push(Token {
-                    text: chars[start..i].iter().collect(),
-                    token_type: TokenType::Normal,
+                    text: chars[var_start..i].iter().collect(),
+                    token_type: TokenType::Variable,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
+                literal_start = i;
                 continue;
             }
 
-            // 일반 텍스트
+            i += 1;
+        }
+
+        if i > literal_start {
             tokens.push(Token {
-                text: chars[i].to_string(),
-                token_type: TokenType::Normal,
+                text: chars[literal_start..i].iter().collect(),
+                token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+        }
+        if i < chars.len() {
+            tokens.push(Token {
+                text: quote.to_string(),
+                token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             i += 1;
         }
 
-        tokens
+        (tokens, i)
     }
 
-    // C-like 언어 공통 토큰화
-    fn tokenize_c_like(
-        &mut self,
-        line: &str,
-        keywords: &[&str],
-        types: &[&str],
-        line_comment: &str,
-        block_comment: (&str, &str),
-        support_attributes: bool,
-    ) -> Vec<Token> {
+    /// The generic lexer that the table-driven `tokenize_*` methods (Rust,
+    /// JS/TS, C/C++, Java/Kotlin, Go, Ruby, PHP, Swift and custom languages)
+    /// drive through a `LanguageDef` table instead of re-implementing their
+    /// own scanning: line/block comments, quoted strings, an optional
+    /// raw-string prefix, numbers, keyword/type/identifier words, optional
+    /// Rust/Java-style attributes, operators and brackets. `LexerState`'s
+    /// `in_block_comment` carries a block comment across lines, and
+    /// `in_raw_string` does the same for a Rust raw string left open at
+    /// end-of-line (matching its hash count on the way back out).
+    /// `def.triple_quote` and `def.heredoc_prefix` opt into the same
+    /// treatment for a `"""` block (`in_triple_quoted_string`) or a
+    /// heredoc/nowdoc body (`in_heredoc`), mirroring `tokenize_shell`'s
+    /// bespoke heredoc handling and Python's bespoke triple-quote handling.
+    fn tokenize_with(&mut self, def: &LanguageDef<'_>, line: &str) -> Vec<Token> {
         let mut tokens = Vec::new();
         let chars: Vec<char> = line.chars().collect();
         let mut i = 0;
 
         // 멀티라인 주석 계속
-        if self.in_multiline_comment {
-            let end_idx = line.find(block_comment.1);
-            if let Some(idx) = end_idx {
-                tokens.push(Token {
-                    text: line[..idx + block_comment.1.len()].to_string(),
-                    token_type: TokenType::Comment,
-                });
-                self.in_multiline_comment = false;
-                i = idx + block_comment.1.len();
+        if self.state.in_block_comment {
+            match def.block_comment {
+                Some(block_comment) => match line.find(block_comment.1) {
+                    Some(idx) => {
+                        tokens.push(Token {
+                            text: line[..idx + block_comment.1.len()].to_string(),
+                            token_type: TokenType::Comment,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        self.state.in_block_comment = false;
+                        i = idx + block_comment.1.len();
+                    }
+                    None => {
+                        tokens.push(Token {
+                            text: line.to_string(),
+                            token_type: TokenType::Comment,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        return tokens;
+                    }
+                },
+                None => self.state.in_block_comment = false,
+            }
+        }
+
+        // 이전 줄에서 이어지는 raw 문자열 (해시 개수가 일치해야 닫힘)
+        if let Some(hash_count) = self.state.in_raw_string {
+            let mut found_end = false;
+            while i < chars.len() {
+                if chars[i] == '"' {
+                    let mut closing_hashes: u8 = 0;
+                    let mut j = i + 1;
+                    while j < chars.len() && chars[j] == '#' && closing_hashes < hash_count {
+                        closing_hashes += 1;
+                        j += 1;
+                    }
+                    if closing_hashes == hash_count {
+                        i = j;
+                        found_end = true;
+                        break;
+                    }
+                }
+                i += 1;
+            }
+            tokens.push(Token {
+                text: chars[..i].iter().collect(),
+                token_type: TokenType::String,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
+            });
+            if found_end {
+                self.state.in_raw_string = None;
             } else {
+                return tokens;
+            }
+        }
+
+        // 이전 줄에서 이어지는 triple-quoted 문자열 (Swift의 """)
+        if def.triple_quote {
+            if let Some(quote) = self.state.in_triple_quoted_string {
+                let mut found_end = false;
+                while i < chars.len() {
+                    if i + 3 <= chars.len() && chars[i] == quote && chars[i + 1] == quote && chars[i + 2] == quote {
+                        i += 3;
+                        found_end = true;
+                        break;
+                    }
+                    i += 1;
+                }
                 tokens.push(Token {
-                    text: line.to_string(),
-                    token_type: TokenType::Comment,
+                    text: chars[..i].iter().collect(),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
-                return tokens;
+                if found_end {
+                    self.state.in_triple_quoted_string = None;
+                } else {
+                    return tokens;
+                }
+            }
+        }
+
+        // 이전 줄에서 이어지는 히어독 본문 (PHP의 <<<, Ruby의 <<)
+        if def.heredoc_prefix.is_some() {
+            if let Some(heredoc) = self.state.in_heredoc.clone() {
+                let terminator_candidate = if heredoc.strip_tabs {
+                    line.trim_start_matches(|c: char| c == '\t' || c == ' ')
+                } else {
+                    line
+                };
+                if terminator_candidate == heredoc.delimiter {
+                    self.state.in_heredoc = None;
+                    // 종료 구분자 줄 자체는 본문이 아니므로 아래에서 평소처럼 토큰화한다
+                } else if heredoc.quoted {
+                    return vec![Token {
+                        text: line.to_string(),
+                        token_type: TokenType::String,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    }];
+                } else if def.heredoc_dollar_interpolation {
+                    return Self::tokenize_heredoc_body_line(line);
+                } else {
+                    return vec![Token {
+                        text: line.to_string(),
+                        token_type: TokenType::String,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    }];
+                }
             }
         }
 
+        let mut pending_heredoc: Option<HeredocState> = None;
+
         while i < chars.len() {
-            // 라인 주석
-            if i + line_comment.len() <= chars.len() {
-                let slice: String = chars[i..i + line_comment.len()].iter().collect();
-                if slice == line_comment {
+            // 임베디드 스크립트 태그 (PHP의 <?php, ?>)
+            if let Some((open, close)) = def.tag_markers {
+                let matched = [open, close].into_iter().find(|marker| {
+                    i + marker.len() <= chars.len()
+                        && chars[i..i + marker.len()].iter().collect::<String>() == *marker
+                });
+                if let Some(marker) = matched {
                     tokens.push(Token {
-                        text: chars[i..].iter().collect(),
-                        token_type: TokenType::Comment,
+                        text: marker.to_string(),
+                        token_type: TokenType::Keyword,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
-                    break;
+                    i += marker.len();
+                    continue;
                 }
             }
 
-            // 블록 주석 시작
-            if i + block_comment.0.len() <= chars.len() {
-                let slice: String = chars[i..i + block_comment.0.len()].iter().collect();
-                if slice == block_comment.0 {
-                    let start = i;
-                    i += block_comment.0.len();
-                    let mut found_end = false;
-                    while i + block_comment.1.len() <= chars.len() {
-                        let end_slice: String = chars[i..i + block_comment.1.len()].iter().collect();
-                        if end_slice == block_comment.1 {
-                            i += block_comment.1.len();
-                            found_end = true;
-                            break;
+            // 라인 주석
+            if let Some(line_comment) = def.line_comment {
+                if i + line_comment.len() <= chars.len() {
+                    let slice: String = chars[i..i + line_comment.len()].iter().collect();
+                    if slice == line_comment {
+                        tokens.push(Token {
+                            text: chars[i..].iter().collect(),
+                            token_type: TokenType::Comment,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        break;
+                    }
+                }
+            }
+            if let Some(line_comment) = def.extra_line_comment {
+                if i + line_comment.len() <= chars.len() {
+                    let slice: String = chars[i..i + line_comment.len()].iter().collect();
+                    if slice == line_comment {
+                        tokens.push(Token {
+                            text: chars[i..].iter().collect(),
+                            token_type: TokenType::Comment,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        break;
+                    }
+                }
+            }
+
+            // 블록 주석 시작 (nested_block_comments가 true면 중첩 깊이를 추적)
+            if let Some(block_comment) = def.block_comment {
+                if i + block_comment.0.len() <= chars.len() {
+                    let slice: String = chars[i..i + block_comment.0.len()].iter().collect();
+                    if slice == block_comment.0 {
+                        let start = i;
+                        i += block_comment.0.len();
+                        let mut depth = 1;
+                        let mut found_end = false;
+                        while i < chars.len() {
+                            if def.nested_block_comments
+                                && i + block_comment.0.len() <= chars.len()
+                                && chars[i..i + block_comment.0.len()].iter().collect::<String>() == block_comment.0
+                            {
+                                depth += 1;
+                                i += block_comment.0.len();
+                                continue;
+                            }
+                            if i + block_comment.1.len() <= chars.len()
+                                && chars[i..i + block_comment.1.len()].iter().collect::<String>() == block_comment.1
+                            {
+                                depth -= 1;
+                                i += block_comment.1.len();
+                                if depth == 0 {
+                                    found_end = true;
+                                    break;
+                                }
+                                continue;
+                            }
+                            i += 1;
                         }
-                        i += 1;
+                        if !found_end {
+                            self.state.in_block_comment = true;
+                            i = chars.len();
+                        }
+                        tokens.push(Token {
+                            text: chars[start..i].iter().collect(),
+                            token_type: TokenType::Comment,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        continue;
                     }
-                    if !found_end {
-                        self.in_multiline_comment = true;
-                        i = chars.len();
+                }
+            }
+
+            // Rust 라이프타임 ('a, 'static) vs 문자 리터럴 ('a') 구분:
+            // 여는 따옴표 다음이 식별자이고 그 뒤에 곧바로 닫는 따옴표가 오지
+            // 않으면(단일 문자 리터럴이 아니면) 라이프타임으로 취급한다.
+            if def.lifetimes && chars[i] == '\'' {
+                let next_is_ident = i + 1 < chars.len() && (def.ident_start)(chars[i + 1]);
+                let is_char_literal = next_is_ident
+                    && i + 2 < chars.len()
+                    && chars[i + 2] == '\'';
+                if next_is_ident && !is_char_literal {
+                    let start = i;
+                    i += 1;
+                    while i < chars.len() && (def.ident_continue)(chars[i]) {
+                        i += 1;
                     }
                     tokens.push(Token {
                         text: chars[start..i].iter().collect(),
-                        token_type: TokenType::Comment,
+                        token_type: TokenType::Type,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                     continue;
                 }
             }
 
+            // Triple-quoted 문자열 시작 (Swift의 """)
+            if def.triple_quote
+                && i + 2 < chars.len()
+                && chars[i] == '"' && chars[i + 1] == '"' && chars[i + 2] == '"'
+            {
+                let start = i;
+                i += 3;
+                let mut found_end = false;
+                while i < chars.len() {
+                    if i + 3 <= chars.len() && chars[i] == '"' && chars[i + 1] == '"' && chars[i + 2] == '"' {
+                        i += 3;
+                        found_end = true;
+                        break;
+                    }
+                    i += 1;
+                }
+                tokens.push(Token {
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                if !found_end {
+                    self.state.in_triple_quoted_string = Some('"');
+                    return tokens;
+                }
+                continue;
+            }
+
+            // 히어독 리다이렉션 (PHP의 <<<, Ruby의 <<) -- 구분자를 기록해
+            // 다음 줄부터 종료 구분자를 만날 때까지 본문을 문자열로 취급한다
+            if let Some(prefix) = def.heredoc_prefix {
+                if chars[i] == '<' && i + 1 < chars.len() && chars[i + 1] == '<' {
+                    if let Some((heredoc, end)) = scan_heredoc_open(chars, i, prefix) {
+                        tokens.push(Token {
+                            text: chars[i..end].iter().collect(),
+                            token_type: TokenType::Operator,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        pending_heredoc = Some(heredoc);
+                        i = end;
+                        continue;
+                    }
+                }
+            }
+
+            // 문자열 (보간이 있는 큰따옴표 문자열은 별도 처리)
+            if chars[i] == '"' && (def.interp_hash_brace || def.interp_dollar) {
+                let (interp_tokens, end) = self.tokenize_interpolated_string(def, chars, i);
+                tokens.extend(interp_tokens);
+                i = end;
+                continue;
+            }
+
             // 문자열
-            if chars[i] == '"' || chars[i] == '\'' {
+            if def.string_delimiters.contains(&chars[i]) {
                 let quote = chars[i];
                 let start = i;
                 i += 1;
@@ -2078,74 +4260,83 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::String,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
             // Raw 문자열 (Rust의 r#"..."#)
-            if chars[i] == 'r' && i + 1 < chars.len() && (chars[i + 1] == '"' || chars[i + 1] == '#') {
-                let start = i;
-                i += 1;
-                let mut hash_count = 0;
-                while i < chars.len() && chars[i] == '#' {
-                    hash_count += 1;
-                    i += 1;
-                }
-                if i < chars.len() && chars[i] == '"' {
+            if let Some(raw_prefix) = def.raw_string_prefix {
+                if chars[i] == raw_prefix
+                    && i + 1 < chars.len()
+                    && (chars[i + 1] == '"' || chars[i + 1] == '#')
+                {
+                    let start = i;
                     i += 1;
-                    loop {
-                        while i < chars.len() && chars[i] != '"' {
-                            i += 1;
-                        }
-                        if i >= chars.len() {
-                            break;
-                        }
+                    let mut hash_count: u8 = 0;
+                    while i < chars.len() && chars[i] == '#' {
+                        hash_count += 1;
+                        i += 1;
+                    }
+                    if i < chars.len() && chars[i] == '"' {
                         i += 1;
-                        let mut closing_hashes = 0;
-                        while i < chars.len() && chars[i] == '#' && closing_hashes < hash_count {
-                            closing_hashes += 1;
+                        let mut found_end = false;
+                        loop {
+                            while i < chars.len() && chars[i] != '"' {
+                                i += 1;
+                            }
+                            if i >= chars.len() {
+                                break;
+                            }
                             i += 1;
+                            let mut closing_hashes: u8 = 0;
+                            while i < chars.len() && chars[i] == '#' && closing_hashes < hash_count {
+                                closing_hashes += 1;
+                                i += 1;
+                            }
+                            if closing_hashes == hash_count {
+                                found_end = true;
+                                break;
+                            }
                         }
-                        if closing_hashes == hash_count {
-                            break;
+                        tokens.push(Token {
+                            text: chars[start..i].iter().collect(),
+                            token_type: TokenType::String,
+                            start: 0,
+                            end: 0,
+                            bracket_depth: None,
+                        });
+                        if !found_end {
+                            self.state.in_raw_string = Some(hash_count);
+                            return tokens;
                         }
+                        continue;
                     }
-                    tokens.push(Token {
-                        text: chars[start..i].iter().collect(),
-                        token_type: TokenType::String,
-                    });
-                    continue;
                 }
             }
 
-            // 숫자
+            // 숫자 (16진수/8진수/2진수, 지수, 자리 구분자, 접미사는 scan_number가 처리)
             if chars[i].is_ascii_digit()
                 || (chars[i] == '.' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit())
             {
                 let start = i;
-                // 16진수, 8진수, 2진수
-                if chars[i] == '0' && i + 1 < chars.len() {
-                    let next = chars[i + 1].to_ascii_lowercase();
-                    if next == 'x' || next == 'o' || next == 'b' {
-                        i += 2;
-                    }
-                }
-                while i < chars.len()
-                    && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_')
-                {
-                    i += 1;
-                }
+                i = scan_number(chars, i, def.numeric_suffixes);
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Number,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
             // 식별자/키워드
-            if chars[i].is_alphabetic() || chars[i] == '_' {
+            if (def.ident_start)(chars[i]) {
                 let start = i;
-                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                while i < chars.len() && (def.ident_continue)(chars[i]) {
                     i += 1;
                 }
                 let word: String = chars[start..i].iter().collect();
@@ -2157,13 +4348,20 @@ impl SyntaxHighlighter {
                     tokens.push(Token {
                         text: format!("{}!", word),
                         token_type: TokenType::Macro,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                     continue;
                 }
 
-                let token_type = if keywords.contains(&word.as_str()) {
+                let token_type = if let Some(tt) = def.keyword_table.and_then(|t| t.get(&word)) {
+                    tt
+                } else if def.keywords.contains(&word.as_str()) {
                     TokenType::Keyword
-                } else if types.contains(&word.as_str()) {
+                } else if def.types.contains(&word.as_str()) {
+                    TokenType::Type
+                } else if def.capitalized_is_type && word.starts_with(|c: char| c.is_uppercase()) {
                     TokenType::Type
                 } else if word.chars().all(|c| c.is_uppercase() || c == '_') && word.len() > 1 {
                     TokenType::Constant
@@ -2175,12 +4373,58 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: word,
                     token_type,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
+                });
+                continue;
+            }
+
+            // 심볼 (Ruby의 :name)
+            if let Some(sigil) = def.symbol_sigil {
+                if chars[i] == sigil
+                    && i + 1 < chars.len()
+                    && ((def.ident_start)(chars[i + 1]))
+                {
+                    let start = i;
+                    i += 1;
+                    while i < chars.len() && (def.ident_continue)(chars[i]) {
+                        i += 1;
+                    }
+                    tokens.push(Token {
+                        text: chars[start..i].iter().collect(),
+                        token_type: TokenType::Constant,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
+                    });
+                    continue;
+                }
+            }
+
+            // 변수 시길 (PHP의 $name, Ruby의 @ivar/@@cvar/$global)
+            if def.variable_sigils.contains(&chars[i]) {
+                let start = i;
+                let sigil = chars[i];
+                i += 1;
+                if i < chars.len() && chars[i] == sigil {
+                    i += 1;
+                }
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    text: chars[start..i].iter().collect(),
+                    token_type: TokenType::Variable,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
 
             // 속성 (Rust의 #[...], Java의 @...)
-            if support_attributes {
+            if def.support_attributes {
                 if chars[i] == '#' && i + 1 < chars.len() && chars[i + 1] == '[' {
                     let start = i;
                     i += 2;
@@ -2196,6 +4440,9 @@ impl SyntaxHighlighter {
                     tokens.push(Token {
                         text: chars[start..i].iter().collect(),
                         token_type: TokenType::Attribute,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                     continue;
                 }
@@ -2208,24 +4455,40 @@ impl SyntaxHighlighter {
                     tokens.push(Token {
                         text: chars[start..i].iter().collect(),
                         token_type: TokenType::Attribute,
+                        start: 0,
+                        end: 0,
+                        bracket_depth: None,
                     });
                     continue;
                 }
             }
 
             // 연산자
-            if "+-*/%=<>!&|^~?:".contains(chars[i]) {
+            if "+-*/%=<>!&|^~?:".contains(chars[i])
+                || def.operators.iter().any(|op| op.starts_with(chars[i]))
+            {
                 let start = i;
-                // 복합 연산자
-                while i < chars.len() && "+-*/%=<>!&|^~?:".contains(chars[i]) {
-                    i += 1;
-                    if i - start >= 3 {
-                        break;
+                match scan_operator(chars, i, def.operators) {
+                    Some(end) => i = end,
+                    None => {
+                        // 테이블에 없으면 예전처럼 같은 기호 집합을 최대 3글자까지 탐욕적으로 묶는다
+                        while i < chars.len() && "+-*/%=<>!&|^~?:".contains(chars[i]) {
+                            i += 1;
+                            if i - start >= 3 {
+                                break;
+                            }
+                        }
+                        if i == start {
+                            i += 1;
+                        }
                     }
                 }
                 tokens.push(Token {
                     text: chars[start..i].iter().collect(),
                     token_type: TokenType::Operator,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 continue;
             }
@@ -2235,6 +4498,9 @@ impl SyntaxHighlighter {
                 tokens.push(Token {
                     text: chars[i].to_string(),
                     token_type: TokenType::Bracket,
+                    start: 0,
+                    end: 0,
+                    bracket_depth: None,
                 });
                 i += 1;
                 continue;
@@ -2244,10 +4510,17 @@ impl SyntaxHighlighter {
             tokens.push(Token {
                 text: chars[i].to_string(),
                 token_type: TokenType::Normal,
+                start: 0,
+                end: 0,
+                bracket_depth: None,
             });
             i += 1;
         }
 
+        if def.heredoc_prefix.is_some() {
+            self.state.in_heredoc = pending_heredoc;
+        }
+
         tokens
     }
 }
@@ -2274,6 +4547,31 @@ mod tests {
         assert!(tokens.iter().any(|t| t.text == "main" && t.token_type == TokenType::Function));
     }
 
+    #[test]
+    fn test_rust_nested_block_comments_raw_strings_and_lifetimes() {
+        let mut highlighter = SyntaxHighlighter::new(Language::Rust);
+
+        // Nested block comment: only closes once the outer /* does.
+        let tokens = highlighter.tokenize_line("/* outer /* inner */ still comment */ fn");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Comment
+            && t.text == "/* outer /* inner */ still comment */"));
+        assert!(tokens.iter().any(|t| t.text == "fn" && t.token_type == TokenType::Keyword));
+
+        // Raw string with a hash count that must match on the way out.
+        let tokens = highlighter.tokenize_line("let s = r#\"has \" inside\"#;");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::String
+            && t.text == "r#\"has \" inside\"#"));
+
+        // A lifetime is not a char literal.
+        let tokens = highlighter.tokenize_line("fn f<'a>(x: &'a str) -> &'static str {");
+        assert!(tokens.iter().any(|t| t.text == "'a" && t.token_type == TokenType::Type));
+        assert!(tokens.iter().any(|t| t.text == "'static" && t.token_type == TokenType::Type));
+
+        // But 'a' (quote, one char, quote) still is.
+        let tokens = highlighter.tokenize_line("let c = 'a';");
+        assert!(tokens.iter().any(|t| t.text == "'a'" && t.token_type == TokenType::String));
+    }
+
     #[test]
     fn test_python_tokenization() {
         let mut highlighter = SyntaxHighlighter::new(Language::Python);
@@ -2281,4 +4579,156 @@ mod tests {
         assert!(tokens.iter().any(|t| t.text == "def" && t.token_type == TokenType::Keyword));
         assert!(tokens.iter().any(|t| t.text == "hello" && t.token_type == TokenType::Function));
     }
+
+    #[test]
+    fn test_python_fstring_interpolation() {
+        let mut highlighter = SyntaxHighlighter::new(Language::Python);
+        let tokens = highlighter.tokenize_line("f\"count: {total + 1}\"");
+        assert!(tokens.iter().any(|t| t.text == "total" && t.token_type == TokenType::Variable));
+        assert!(tokens.iter().any(|t| t.text == "1" && t.token_type == TokenType::Number));
+        assert!(tokens.iter().any(|t| t.text == "+" && t.token_type == TokenType::Operator));
+        // {{ }} are literal braces, not an interpolation
+        let tokens = highlighter.tokenize_line("f\"{{literal}}\"");
+        assert!(tokens.iter().all(|t| t.token_type != TokenType::Bracket));
+    }
+
+    #[test]
+    fn test_javascript_template_literal_interpolation() {
+        let mut highlighter = SyntaxHighlighter::new(Language::JavaScript);
+        let tokens = highlighter.tokenize_line("`total: ${count + 1}`");
+        assert!(tokens.iter().any(|t| t.text == "count" && t.token_type == TokenType::Variable));
+        assert!(tokens.iter().any(|t| t.text == "1" && t.token_type == TokenType::Number));
+        assert!(tokens.iter().any(|t| t.text == "${" && t.token_type == TokenType::Bracket));
+        // \${ is an escaped, literal ${
+        let tokens = highlighter.tokenize_line("`price: \\${amount}`");
+        assert!(tokens.iter().all(|t| t.token_type != TokenType::Bracket));
+    }
+
+    #[test]
+    fn test_lexer_state_carries_multiline_constructs() {
+        // Python triple-quoted string spanning lines
+        let mut highlighter = SyntaxHighlighter::new(Language::Python);
+        let tokens = highlighter.tokenize_line("s = \"\"\"start");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::String));
+        let state = highlighter.lexer_state();
+        assert_eq!(state.in_triple_quoted_string, Some('"'));
+        let tokens = highlighter.tokenize_line("end\"\"\"");
+        assert!(tokens.iter().all(|t| t.token_type == TokenType::String));
+        assert_eq!(highlighter.lexer_state(), LexerState::default());
+
+        // Rust raw string spanning lines, hash count must match on close
+        let mut highlighter = SyntaxHighlighter::new(Language::Rust);
+        let tokens = highlighter.tokenize_line("let s = r#\"start");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::String));
+        assert_eq!(highlighter.lexer_state().in_raw_string, Some(1));
+        let tokens = highlighter.tokenize_line("still \" not closed");
+        assert!(tokens.iter().all(|t| t.token_type == TokenType::String));
+        assert_eq!(highlighter.lexer_state().in_raw_string, Some(1));
+        let tokens = highlighter.tokenize_line("end\"#;");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::String));
+        assert_eq!(highlighter.lexer_state(), LexerState::default());
+
+        // JS/TS template literal spanning lines
+        let mut highlighter = SyntaxHighlighter::new(Language::JavaScript);
+        let tokens = highlighter.tokenize_line("const s = `start");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::String));
+        assert!(highlighter.lexer_state().in_template_literal);
+        let tokens = highlighter.tokenize_line("end`;");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::String));
+        assert_eq!(highlighter.lexer_state(), LexerState::default());
+
+        // CSS block comment spanning lines
+        let mut highlighter = SyntaxHighlighter::new(Language::Css);
+        let tokens = highlighter.tokenize_line("/* start");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Comment));
+        assert!(highlighter.lexer_state().in_block_comment);
+        let tokens = highlighter.tokenize_line("still comment");
+        assert!(tokens.iter().all(|t| t.token_type == TokenType::Comment));
+        assert!(highlighter.lexer_state().in_block_comment);
+        let tokens = highlighter.tokenize_line("end */ .foo {");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Comment));
+        assert_eq!(highlighter.lexer_state(), LexerState::default());
+
+        // SQL block comment spanning lines
+        let mut highlighter = SyntaxHighlighter::new(Language::Sql);
+        let tokens = highlighter.tokenize_line("/* start");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Comment));
+        assert!(highlighter.lexer_state().in_block_comment);
+        let tokens = highlighter.tokenize_line("end */ SELECT 1;");
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Comment));
+        assert!(tokens.iter().any(|t| t.text == "SELECT" && t.token_type == TokenType::Keyword));
+        assert_eq!(highlighter.lexer_state(), LexerState::default());
+    }
+
+    #[test]
+    fn test_shell_heredoc() {
+        // Unquoted heredoc: body is a string, but $VAR still expands
+        let mut highlighter = SyntaxHighlighter::new(Language::Shell);
+        let tokens = highlighter.tokenize_line("cat <<EOF");
+        assert!(tokens.iter().any(|t| t.text == "<<EOF" && t.token_type == TokenType::Operator));
+        let state = highlighter.lexer_state();
+        assert_eq!(state.in_heredoc.as_ref().map(|h| h.delimiter.as_str()), Some("EOF"));
+        assert!(!state.in_heredoc.as_ref().unwrap().quoted);
+
+        let tokens = highlighter.tokenize_line("hello $NAME, welcome to ${PLACE}");
+        assert!(tokens.iter().all(|t| t.token_type == TokenType::String || t.token_type == TokenType::Variable));
+        assert!(tokens.iter().any(|t| t.text == "$NAME" && t.token_type == TokenType::Variable));
+        assert!(tokens.iter().any(|t| t.text == "${PLACE}" && t.token_type == TokenType::Variable));
+
+        let tokens = highlighter.tokenize_line("EOF");
+        assert!(highlighter.lexer_state().in_heredoc.is_none());
+        assert!(tokens.iter().any(|t| t.text == "EOF"));
+
+        // Quoted delimiter (<<'EOF') suppresses variable expansion
+        let mut highlighter = SyntaxHighlighter::new(Language::Shell);
+        highlighter.tokenize_line("cat <<'EOF'");
+        assert!(highlighter.lexer_state().in_heredoc.as_ref().unwrap().quoted);
+        let tokens = highlighter.tokenize_line("literal $NAME stays as-is");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].token_type, TokenType::String);
+
+        // <<- strips leading tabs when matching the terminator
+        let mut highlighter = SyntaxHighlighter::new(Language::Shell);
+        highlighter.tokenize_line("cat <<-EOF");
+        highlighter.tokenize_line("\tbody line");
+        assert!(highlighter.lexer_state().in_heredoc.is_some());
+        highlighter.tokenize_line("\tEOF");
+        assert!(highlighter.lexer_state().in_heredoc.is_none());
+    }
+
+    #[test]
+    fn test_custom_language_def_table_driven() {
+        let keywords = ["let", "return"];
+        let types = ["Int"];
+        let def = LanguageDef::c_family(&keywords, &types, false);
+        let mut highlighter = SyntaxHighlighter::new(Language::Plain);
+        let tokens = highlighter.tokenize_with(&def, "let x: Int = 1; // note");
+        assert!(tokens.iter().any(|t| t.text == "let" && t.token_type == TokenType::Keyword));
+        assert!(tokens.iter().any(|t| t.text == "Int" && t.token_type == TokenType::Type));
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Comment));
+    }
+
+    #[test]
+    fn test_token_spans_and_bracket_depth() {
+        let mut highlighter = SyntaxHighlighter::new(Language::Rust);
+        let tokens = highlighter.tokenize_line("foo(bar[1])");
+        let paren_open = tokens.iter().find(|t| t.text == "(").unwrap();
+        assert_eq!((paren_open.start, paren_open.end), (3, 4));
+        assert_eq!(paren_open.bracket_depth, Some(0));
+
+        let bracket_open = tokens.iter().find(|t| t.text == "[").unwrap();
+        assert_eq!(bracket_open.bracket_depth, Some(1));
+        let bracket_close = tokens.iter().find(|t| t.text == "]").unwrap();
+        assert_eq!(bracket_close.bracket_depth, Some(1));
+
+        let paren_close = tokens.iter().find(|t| t.text == ")").unwrap();
+        assert_eq!(paren_close.bracket_depth, Some(0));
+
+        // Brackets inside a string are `String` tokens, not `Bracket`, so
+        // they never perturb the running depth.
+        let mut highlighter = SyntaxHighlighter::new(Language::Rust);
+        let tokens = highlighter.tokenize_line("let s = \"(\"; baz(1)");
+        let paren_open = tokens.iter().find(|t| t.text == "(" && t.token_type == TokenType::Bracket).unwrap();
+        assert_eq!(paren_open.bracket_depth, Some(0));
+    }
 }
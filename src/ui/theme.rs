@@ -1,5 +1,69 @@
 use ratatui::style::{Color, Modifier, Style};
 
+use super::ls_colors::LsColors;
+use crate::utils::format::SizeUnit;
+
+/// Terminal color capability, from richest to none. Mirrors the detection
+/// order used by the `supports-color` ecosystem: an explicit opt-out wins,
+/// then an explicit `--color` flag, then TTY-ness, then environment hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    None,
+}
+
+impl ColorSupport {
+    /// Detect the terminal's color capability. `color_flag` is the value of
+    /// an explicit `--color=always|auto|never` CLI flag, if the caller
+    /// parsed one; `None` behaves like `auto`.
+    pub fn detect(color_flag: Option<&str>) -> Self {
+        if std::env::var_os("NO_COLOR").is_some() {
+            return ColorSupport::None;
+        }
+
+        match color_flag {
+            Some("never") => return ColorSupport::None,
+            Some("always") => {}
+            _ => {
+                if !Self::stdout_is_tty() {
+                    return ColorSupport::None;
+                }
+            }
+        }
+
+        Self::detect_level()
+    }
+
+    #[cfg(unix)]
+    fn stdout_is_tty() -> bool {
+        unsafe { libc::isatty(libc::STDOUT_FILENO) != 0 }
+    }
+
+    #[cfg(not(unix))]
+    fn stdout_is_tty() -> bool {
+        true
+    }
+
+    fn detect_level() -> Self {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorSupport::TrueColor;
+        }
+
+        let term = std::env::var("TERM").unwrap_or_default();
+        if term.contains("256color") {
+            return ColorSupport::Ansi256;
+        }
+        if term.is_empty() || term == "dumb" {
+            return ColorSupport::None;
+        }
+
+        ColorSupport::Ansi16
+    }
+}
+
 /// Theme characters for file/folder icons
 #[derive(Clone, Copy)]
 #[allow(dead_code)]
@@ -45,6 +109,18 @@ pub struct Theme {
     pub info: Color,
 
     pub chars: ThemeChars,
+
+    /// Detected terminal capability. The `*_style()` helpers consult this to
+    /// drop `fg`/`bg` in favor of `Modifier`-only styling when it's `None`.
+    pub support: ColorSupport,
+
+    /// `LS_COLORS`/per-extension palette for file-name coloring in
+    /// `create_file_line`. `None` disables it entirely, which is how the
+    /// no-color theme opts out along with everything else.
+    pub ls_colors: Option<LsColors>,
+
+    /// IEC binary (`KiB`) vs SI decimal (`kB`) units for `format_size_with_unit`.
+    pub size_unit: SizeUnit,
 }
 
 impl Default for Theme {
@@ -80,20 +156,27 @@ impl Theme {
             info: Color::Cyan,
 
             chars: ThemeChars::default(),
+            support: ColorSupport::TrueColor,
+            ls_colors: Some(LsColors::from_env()),
+            size_unit: SizeUnit::Binary,
         }
     }
 
-    /// Dracula theme (default) - uses 256 color palette for compatibility
+    /// Dracula theme (default), auto-detecting terminal color capability
+    /// from the environment.
     pub fn dracula() -> Self {
-        // Check if terminal supports true color
-        let truecolor = std::env::var("COLORTERM")
-            .map(|v| v == "truecolor" || v == "24bit")
-            .unwrap_or(false);
+        Self::new(ColorSupport::detect(None))
+    }
 
-        if truecolor {
-            Self::dracula_rgb()
-        } else {
-            Self::dracula_256()
+    /// Build the Dracula theme at a specific, already-detected color level.
+    /// Use this when the caller has its own `--color` flag to honor instead
+    /// of re-running environment detection.
+    pub fn new(support: ColorSupport) -> Self {
+        match support {
+            ColorSupport::TrueColor => Self::dracula_rgb(),
+            ColorSupport::Ansi256 => Self::dracula_256(),
+            ColorSupport::Ansi16 => Self::dracula_16(),
+            ColorSupport::None => Self::dracula_no_color(),
         }
     }
 
@@ -122,6 +205,9 @@ impl Theme {
             info: Color::Rgb(139, 233, 253),
 
             chars: ThemeChars::default(),
+            support: ColorSupport::TrueColor,
+            ls_colors: Some(LsColors::from_env()),
+            size_unit: SizeUnit::Binary,
         }
     }
 
@@ -150,37 +236,136 @@ impl Theme {
             info: Color::Indexed(87),          // cyan
 
             chars: ThemeChars::default(),
+            support: ColorSupport::Ansi256,
+            ls_colors: Some(LsColors::from_env()),
+            size_unit: SizeUnit::Binary,
+        }
+    }
+
+    /// Dracula-flavored palette restricted to the 16 standard ANSI colors,
+    /// for terminals that only advertise basic color support.
+    fn dracula_16() -> Self {
+        Self {
+            bg: Color::Black,
+            bg_panel: Color::Black,
+            bg_selected: Color::DarkGray,
+            bg_header: Color::Black,
+            bg_status_bar: Color::DarkGray,
+
+            text: Color::White,
+            text_dim: Color::DarkGray,
+            text_bold: Color::White,
+            text_selected: Color::White,
+            text_header: Color::Magenta,
+            text_directory: Color::Cyan,
+
+            border: Color::DarkGray,
+            border_active: Color::Magenta,
+
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            info: Color::Cyan,
+
+            chars: ThemeChars::default(),
+            support: ColorSupport::Ansi16,
+            ls_colors: Some(LsColors::from_env()),
+            size_unit: SizeUnit::Binary,
+        }
+    }
+
+    /// No-color palette for `NO_COLOR`/non-TTY/dumb terminals. The color
+    /// fields are unused by the `*_style()` helpers at this level - they're
+    /// only kept populated so the struct stays uniform - and styling leans
+    /// on `Modifier` (bold/underline/reversed) instead.
+    fn dracula_no_color() -> Self {
+        Self {
+            bg: Color::Reset,
+            bg_panel: Color::Reset,
+            bg_selected: Color::Reset,
+            bg_header: Color::Reset,
+            bg_status_bar: Color::Reset,
+
+            text: Color::Reset,
+            text_dim: Color::Reset,
+            text_bold: Color::Reset,
+            text_selected: Color::Reset,
+            text_header: Color::Reset,
+            text_directory: Color::Reset,
+
+            border: Color::Reset,
+            border_active: Color::Reset,
+
+            success: Color::Reset,
+            warning: Color::Reset,
+            error: Color::Reset,
+            info: Color::Reset,
+
+            chars: ThemeChars::default(),
+            support: ColorSupport::None,
+            ls_colors: None,
+            size_unit: SizeUnit::Binary,
         }
     }
 
+    fn no_color(&self) -> bool {
+        self.support == ColorSupport::None
+    }
+
     pub fn normal_style(&self) -> Style {
-        Style::default().fg(self.text)
+        if self.no_color() {
+            Style::default()
+        } else {
+            Style::default().fg(self.text)
+        }
     }
 
     pub fn dim_style(&self) -> Style {
-        Style::default().fg(self.text_dim)
+        if self.no_color() {
+            Style::default()
+        } else {
+            Style::default().fg(self.text_dim)
+        }
     }
 
     pub fn selected_style(&self) -> Style {
-        Style::default()
-            .fg(self.text_selected)
-            .bg(self.bg_selected)
+        if self.no_color() {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+                .fg(self.text_selected)
+                .bg(self.bg_selected)
+        }
     }
 
     pub fn directory_style(&self) -> Style {
-        Style::default()
-            .fg(self.text_directory)
-            .add_modifier(Modifier::BOLD)
+        if self.no_color() {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(self.text_directory)
+                .add_modifier(Modifier::BOLD)
+        }
     }
 
     pub fn header_style(&self) -> Style {
-        Style::default()
-            .fg(self.text_header)
-            .add_modifier(Modifier::BOLD)
+        if self.no_color() {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(self.text_header)
+                .add_modifier(Modifier::BOLD)
+        }
     }
 
     pub fn border_style(&self, active: bool) -> Style {
-        if active {
+        if self.no_color() {
+            if active {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            }
+        } else if active {
             Style::default().fg(self.border_active)
         } else {
             Style::default().fg(self.border)
@@ -188,31 +373,55 @@ impl Theme {
     }
 
     pub fn warning_style(&self) -> Style {
-        Style::default()
-            .fg(self.warning)
-            .add_modifier(Modifier::BOLD)
+        if self.no_color() {
+            Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default()
+                .fg(self.warning)
+                .add_modifier(Modifier::BOLD)
+        }
     }
 
     pub fn error_style(&self) -> Style {
-        Style::default().fg(self.error)
+        if self.no_color() {
+            Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(self.error)
+        }
     }
 
     pub fn success_style(&self) -> Style {
-        Style::default().fg(self.success)
+        if self.no_color() {
+            Style::default()
+        } else {
+            Style::default().fg(self.success)
+        }
     }
 
     pub fn marked_style(&self) -> Style {
-        Style::default()
-            .fg(self.warning)
+        if self.no_color() {
+            Style::default().add_modifier(Modifier::UNDERLINED)
+        } else {
+            Style::default()
+                .fg(self.warning)
+        }
     }
 
     pub fn status_bar_style(&self) -> Style {
-        Style::default()
-            .fg(self.text_header)
-            .bg(self.bg_status_bar)
+        if self.no_color() {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+                .fg(self.text_header)
+                .bg(self.bg_status_bar)
+        }
     }
 
     pub fn info_style(&self) -> Style {
-        Style::default().fg(self.info)
+        if self.no_color() {
+            Style::default()
+        } else {
+            Style::default().fg(self.info)
+        }
     }
 }
@@ -0,0 +1,149 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+use super::{app::App, theme::Theme};
+
+pub fn draw(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border_style(true));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if inner.height < 5 {
+        return;
+    }
+
+    let header = Line::from(vec![
+        Span::styled(" Trash ", theme.header_style()),
+        Span::styled(
+            format!(" [{} item(s)]", app.trash_entries.len()),
+            theme.dim_style(),
+        ),
+    ]);
+    frame.render_widget(
+        Paragraph::new(header),
+        Rect::new(inner.x, inner.y, inner.width, 1),
+    );
+
+    if app.trash_entries.is_empty() {
+        frame.render_widget(
+            Paragraph::new(" Trash is empty").style(theme.dim_style()),
+            Rect::new(inner.x, inner.y + 2, inner.width, 1),
+        );
+        return;
+    }
+
+    let deleted_width = 20;
+
+    let col_header = Line::from(vec![
+        Span::styled(format!("{:width$}", "DELETED", width = deleted_width), theme.header_style()),
+        Span::styled("ORIGINAL PATH", theme.header_style()),
+    ]);
+    frame.render_widget(
+        Paragraph::new(col_header),
+        Rect::new(inner.x, inner.y + 1, inner.width, 1),
+    );
+
+    let list_height = (inner.height - 3) as usize;
+    let start_index = app.trash_selected_index.saturating_sub(list_height / 2);
+    let start_index = start_index.min(app.trash_entries.len().saturating_sub(list_height));
+
+    for (i, entry) in app.trash_entries.iter().skip(start_index).take(list_height).enumerate() {
+        let actual_index = start_index + i;
+        let is_cursor = actual_index == app.trash_selected_index;
+
+        let style = if is_cursor {
+            theme.selected_style()
+        } else {
+            theme.normal_style()
+        };
+
+        let line = Line::from(vec![
+            Span::styled(
+                format!("{:width$}", entry.deleted_at.format("%Y-%m-%d %H:%M:%S"), width = deleted_width),
+                style,
+            ),
+            Span::styled(entry.original_path.display().to_string(), style),
+        ]);
+
+        frame.render_widget(
+            Paragraph::new(line),
+            Rect::new(inner.x, inner.y + 2 + i as u16, inner.width, 1),
+        );
+    }
+
+    let total_entries = app.trash_entries.len();
+    if total_entries > list_height {
+        let scrollbar = Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("▲"))
+            .end_symbol(Some("▼"));
+
+        let mut scrollbar_state = ScrollbarState::new(total_entries)
+            .position(app.trash_selected_index);
+
+        let scrollbar_area = Rect::new(
+            inner.x + inner.width - 1,
+            inner.y + 2,
+            1,
+            list_height as u16,
+        );
+
+        frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+    }
+
+    let footer_spans = vec![
+        Span::styled("Enter", theme.header_style()),
+        Span::styled(" restore  ", theme.dim_style()),
+        Span::styled("E", theme.header_style()),
+        Span::styled("mpty all  ", theme.dim_style()),
+        Span::styled("q", theme.header_style()),
+        Span::styled("uit", theme.dim_style()),
+    ];
+    frame.render_widget(
+        Paragraph::new(Line::from(footer_spans)),
+        Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1),
+    );
+}
+
+pub fn handle_input(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.current_screen = super::app::Screen::DualPanel;
+        }
+        KeyCode::Up => {
+            if app.trash_selected_index > 0 {
+                app.trash_selected_index -= 1;
+            }
+        }
+        KeyCode::Down => {
+            if app.trash_selected_index < app.trash_entries.len().saturating_sub(1) {
+                app.trash_selected_index += 1;
+            }
+        }
+        KeyCode::Home => {
+            app.trash_selected_index = 0;
+        }
+        KeyCode::End => {
+            app.trash_selected_index = app.trash_entries.len().saturating_sub(1);
+        }
+        KeyCode::Enter => {
+            app.restore_selected_trash_entry();
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            app.empty_trash_now();
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            app.trash_entries = crate::services::trash::list_trash();
+            app.show_message("Refreshed");
+        }
+        _ => {}
+    }
+}
@@ -15,6 +15,64 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+/// Unit convention for [`format_size_with_unit`]: IEC binary units (1024,
+/// `KiB/MiB/GiB`) or SI decimal units (1000, `kB/MB/GB`), the way uu_ls's
+/// `NumberPrefix` distinguishes them. Selectable from `Theme`/config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeUnit {
+    Binary,
+    Decimal,
+}
+
+impl Default for SizeUnit {
+    fn default() -> Self {
+        SizeUnit::Binary
+    }
+}
+
+/// Format `bytes` under the given [`SizeUnit`] convention, stepping through
+/// progressively larger units and adaptively dropping to 0 decimals once the
+/// value reaches 100 of its unit (so a column of sizes stays aligned),
+/// otherwise showing 1. Unlike `format_size`, this doesn't hardcode binary
+/// units or a fixed decimal count.
+pub fn format_size_with_unit(bytes: u64, unit: SizeUnit) -> String {
+    let (base, suffixes): (f64, &[&str]) = match unit {
+        SizeUnit::Binary => (1024.0, &["B", "KiB", "MiB", "GiB", "TiB", "PiB"]),
+        SizeUnit::Decimal => (1000.0, &["B", "kB", "MB", "GB", "TB", "PB"]),
+    };
+
+    let mut value = bytes as f64;
+    let mut suffix_index = 0;
+    while value >= base && suffix_index < suffixes.len() - 1 {
+        value /= base;
+        suffix_index += 1;
+    }
+
+    if suffix_index == 0 {
+        format!("{} {}", bytes, suffixes[suffix_index])
+    } else if value >= 100.0 {
+        format!("{:.0} {}", value, suffixes[suffix_index])
+    } else {
+        format!("{:.1} {}", value, suffixes[suffix_index])
+    }
+}
+
+/// Render `bytes` as a plain decimal with thousands separators, e.g.
+/// `1,503,238,553`, for the exact-byte-count tooltip in the info dialog.
+pub fn format_bytes_exact(bytes: u64) -> String {
+    let digits = bytes.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+
+    out
+}
+
 /// Format file permissions in short format (rwxrwxrwx)
 #[cfg(unix)]
 pub fn format_permissions_short(mode: u32) -> String {
@@ -78,6 +136,25 @@ mod tests {
         assert_eq!(format_size(1073741824), "1.0 GB");
     }
 
+    #[test]
+    fn test_format_size_with_unit() {
+        assert_eq!(format_size_with_unit(0, SizeUnit::Binary), "0 B");
+        assert_eq!(format_size_with_unit(1536, SizeUnit::Binary), "1.5 KiB");
+        assert_eq!(format_size_with_unit(1_503_238_553, SizeUnit::Binary), "1.4 GiB");
+        assert_eq!(format_size_with_unit(150 * 1024 * 1024, SizeUnit::Binary), "150 MiB");
+
+        assert_eq!(format_size_with_unit(1500, SizeUnit::Decimal), "1.5 kB");
+        assert_eq!(format_size_with_unit(1_503_238_553, SizeUnit::Decimal), "1.5 GB");
+    }
+
+    #[test]
+    fn test_format_bytes_exact() {
+        assert_eq!(format_bytes_exact(0), "0");
+        assert_eq!(format_bytes_exact(999), "999");
+        assert_eq!(format_bytes_exact(1000), "1,000");
+        assert_eq!(format_bytes_exact(1_503_238_553), "1,503,238,553");
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_format_permissions_short() {
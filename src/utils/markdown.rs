@@ -2,265 +2,707 @@ use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
+use std::collections::HashMap;
 use unicode_width::UnicodeWidthStr;
 
+/// A single block-level element of the document tree. Container blocks
+/// (`BlockQuote`, `List`) hold their own nested `Block`s rather than flat
+/// lines, so a fenced code block inside a list item or a list inside a
+/// blockquote parses (and renders) as a real nesting rather than losing
+/// its container context after the first line.
+#[derive(Debug, Clone)]
+enum Block {
+    Blank,
+    ThematicBreak,
+    Heading { level: usize, text: String },
+    CodeBlock { lang: Option<String>, lines: Vec<String> },
+    Table(Vec<String>),
+    Paragraph(String),
+    BlockQuote(Vec<Block>),
+    List { ordered: bool, items: Vec<ListItem> },
+}
+
+/// One item of a `Block::List`. `checked` is `Some` for GFM task-list
+/// items (`- [ ]` / `- [x]`); the item's own content is parsed as a
+/// nested `Vec<Block>` so continuation paragraphs, nested lists, and
+/// fenced code blocks inside the item all parse correctly.
+#[derive(Debug, Clone)]
+struct ListItem {
+    checked: Option<bool>,
+    blocks: Vec<Block>,
+}
+
 /// Parse Markdown text and return styled lines for ratatui
 pub fn render_markdown(text: &str, theme_colors: MarkdownTheme) -> Vec<Line<'static>> {
-    let mut lines: Vec<Line<'static>> = Vec::new();
-    let mut in_code_block = false;
-    let mut _code_block_lang: Option<String> = None;
-    let mut code_block_lines: Vec<String> = Vec::new();
-
-    let text_lines: Vec<&str> = text.lines().collect();
-    let mut i = 0;
+    render_markdown_lines(text, &theme_colors)
+        .into_iter()
+        .map(|(line, _)| line)
+        .collect()
+}
 
-    while i < text_lines.len() {
-        let line = text_lines[i];
+/// Like [`render_markdown`], but reflows every line that isn't exempt from
+/// wrapping (see [`render_block`]) to fit within `width` columns, using
+/// [`UnicodeWidthStr`] to measure spans and breaking at word boundaries.
+/// Each continuation line repeats the original line's leading indent,
+/// blockquote bar(s), or list-marker width as a hanging indent (with the
+/// bullet/ordinal itself blanked out, so it isn't repeated). Code-block and
+/// table lines are left intact since their layout is significant.
+pub fn render_markdown_wrapped(
+    text: &str,
+    theme_colors: MarkdownTheme,
+    width: usize,
+) -> Vec<Line<'static>> {
+    render_markdown_lines(text, &theme_colors)
+        .into_iter()
+        .flat_map(|(line, exempt)| {
+            if exempt || width == 0 {
+                vec![line]
+            } else {
+                wrap_styled_line(&line, width)
+            }
+        })
+        .collect()
+}
 
-        // Handle code block
-        if line.trim().starts_with("```") {
-            if in_code_block {
-                // End code block
-                for code_line in &code_block_lines {
-                    lines.push(Line::from(vec![
-                        Span::styled("  ", Style::default()),
+/// Shared implementation for [`render_markdown`] and
+/// [`render_markdown_wrapped`]: parses the block tree, renders it, and
+/// appends the footnotes section, pairing each line with whether it's
+/// exempt from word-wrapping (code blocks and tables).
+fn render_markdown_lines(text: &str, theme_colors: &MarkdownTheme) -> Vec<(Line<'static>, bool)> {
+    let raw_lines: Vec<&str> = text.lines().collect();
+    let (link_refs, footnote_defs, content_lines) = collect_references(&raw_lines);
+    let owned_lines: Vec<String> = content_lines.iter().map(|s| s.to_string()).collect();
+    let blocks = parse_blocks(&owned_lines);
+
+    let mut referenced_footnotes: Vec<String> = Vec::new();
+    let mut lines: Vec<(Line<'static>, bool)> = {
+        let mut ctx = RenderCtx {
+            theme: theme_colors,
+            link_refs: &link_refs,
+            footnote_defs: &footnote_defs,
+            referenced_footnotes: &mut referenced_footnotes,
+        };
+        blocks
+            .iter()
+            .flat_map(|block| render_block(block, &mut ctx, 0))
+            .collect()
+    };
+
+    // Append a footnotes section for every `[^id]` reference actually used,
+    // in first-reference order, matching the superscript markers above.
+    if !referenced_footnotes.is_empty() {
+        lines.push((Line::from(""), false));
+        lines.push((
+            Line::from(Span::styled("─".repeat(20), Style::default().fg(theme_colors.dim))),
+            false,
+        ));
+        for (idx, id) in referenced_footnotes.iter().enumerate() {
+            if let Some(footnote_text) = footnote_defs.get(id) {
+                lines.push((
+                    Line::from(vec![
                         Span::styled(
-                            code_line.clone(),
-                            Style::default().fg(theme_colors.code),
+                            format!("[{}] ", idx + 1),
+                            Style::default().fg(theme_colors.link),
                         ),
-                    ]));
-                }
-                code_block_lines.clear();
-                in_code_block = false;
-                _code_block_lang = None;
-            } else {
-                // Start code block
-                in_code_block = true;
-                let lang = line.trim().trim_start_matches("```").trim();
-                if !lang.is_empty() {
-                    _code_block_lang = Some(lang.to_string());
-                    lines.push(Line::from(Span::styled(
-                        format!("  [{}]", lang),
-                        Style::default().fg(theme_colors.dim),
-                    )));
+                        Span::styled(footnote_text.clone(), Style::default().fg(theme_colors.dim)),
+                    ]),
+                    false,
+                ));
+            }
+        }
+    }
+
+    lines
+}
+
+/// One heading extracted by [`extract_outline`].
+#[derive(Debug, Clone)]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+    pub line_index: usize,
+}
+
+/// Extract a document outline (table of contents) from `text`, reusing the
+/// same heading detection and block tree as `render_markdown`. `line_index`
+/// points at the corresponding entry in the `Vec<Line>` that `render_markdown`
+/// produces for the same input, so a TUI can jump straight to that heading's
+/// rendered line. Headers inside fenced code blocks are ignored, since they
+/// parse as `Block::CodeBlock` content rather than `Block::Heading`.
+pub fn extract_outline(text: &str) -> Vec<Heading> {
+    let raw_lines: Vec<&str> = text.lines().collect();
+    let (_, _, content_lines) = collect_references(&raw_lines);
+    let owned_lines: Vec<String> = content_lines.iter().map(|s| s.to_string()).collect();
+    let blocks = parse_blocks(&owned_lines);
+
+    let theme = MarkdownTheme::default();
+    let mut headings = Vec::new();
+    let mut line_index = 0;
+    collect_outline(&blocks, &theme, &mut line_index, &mut headings);
+    headings
+}
+
+/// Walk the block tree in the same preorder that `render_block`/
+/// `render_list_item` flatten it into lines, recording a `Heading` (with its
+/// flattened, formatting-stripped title) at each `Block::Heading` and
+/// otherwise advancing `line_index` by however many lines that block renders
+/// to, so indices stay in sync with `render_markdown`'s output.
+fn collect_outline(blocks: &[Block], theme: &MarkdownTheme, line_index: &mut usize, out: &mut Vec<Heading>) {
+    for block in blocks {
+        match block {
+            Block::Heading { level, text } => {
+                out.push(Heading {
+                    level: *level as u8,
+                    text: flatten_inline_text(text),
+                    line_index: *line_index,
+                });
+                *line_index += 1;
+            }
+            Block::BlockQuote(inner) => collect_outline(inner, theme, line_index, out),
+            Block::List { items, .. } => {
+                for item in items {
+                    collect_outline(&item.blocks, theme, line_index, out);
                 }
             }
-            i += 1;
-            continue;
+            _ => *line_index += block_line_count(block, theme),
         }
+    }
+}
 
-        if in_code_block {
-            code_block_lines.push(line.to_string());
-            i += 1;
-            continue;
+/// Number of lines `block` renders to via `render_block`, without actually
+/// rendering it (no inline spans / styling needed for a line count).
+fn block_line_count(block: &Block, theme: &MarkdownTheme) -> usize {
+    match block {
+        Block::Blank | Block::ThematicBreak | Block::Heading { .. } => 1,
+        Block::CodeBlock { lang, lines } => {
+            (if lang.is_some() { 1 } else { 0 })
+                + render_code_block_lines(lines, lang.as_deref(), theme).len()
         }
+        Block::Table(table_lines) => {
+            let refs: Vec<&str> = table_lines.iter().map(|s| s.as_str()).collect();
+            render_table(&refs, theme).len()
+        }
+        Block::Paragraph(_) => 1,
+        Block::BlockQuote(inner) => inner.iter().map(|b| block_line_count(b, theme)).sum(),
+        Block::List { items, .. } => items
+            .iter()
+            .map(|item| item.blocks.iter().map(|b| block_line_count(b, theme)).sum::<usize>())
+            .sum(),
+    }
+}
 
-        // Handle table (lines starting with |)
-        if line.trim().starts_with('|') && line.trim().ends_with('|') {
-            // Collect all table lines
-            let mut table_lines: Vec<&str> = vec![line];
-            let mut j = i + 1;
-            while j < text_lines.len() {
-                let next_line = text_lines[j];
-                if next_line.trim().starts_with('|') && next_line.trim().ends_with('|') {
-                    table_lines.push(next_line);
-                    j += 1;
-                } else {
-                    break;
+/// Flatten inline Markdown formatting (bold, italic, code, strikethrough,
+/// links) down to plain text, for contexts like [`extract_outline`] that
+/// want a clean title rather than styled spans. Scans by byte offset with
+/// the same marker-jump helpers `parse_inline_markdown` uses.
+fn flatten_inline_text(text: &str) -> String {
+    let mut out = String::new();
+    let mut pos = 0;
+    let len = text.len();
+
+    while pos < len {
+        let marker_pos = match find_next_marker(text, pos) {
+            Some(p) => p,
+            None => {
+                out.push_str(&text[pos..]);
+                break;
+            }
+        };
+        out.push_str(&text[pos..marker_pos]);
+        pos = marker_pos;
+
+        if text[pos..].starts_with("***") {
+            if let Some(end) = find_closing_marker(text, pos + 3, "***") {
+                out.push_str(&text[pos + 3..end]);
+                pos = end + 3;
+                continue;
+            }
+        }
+        if text[pos..].starts_with("**") {
+            if let Some(end) = find_closing_marker(text, pos + 2, "**") {
+                out.push_str(&text[pos + 2..end]);
+                pos = end + 2;
+                continue;
+            }
+        }
+        if text[pos..].starts_with('`') {
+            if let Some(end) = find_closing_char(text, pos + 1, b'`') {
+                out.push_str(&text[pos + 1..end]);
+                pos = end + 1;
+                continue;
+            }
+        }
+        if text[pos..].starts_with('*') || text[pos..].starts_with('_') {
+            let marker = text.as_bytes()[pos];
+            if let Some(end) = find_closing_char(text, pos + 1, marker) {
+                out.push_str(&text[pos + 1..end]);
+                pos = end + 1;
+                continue;
+            }
+        }
+        if text[pos..].starts_with("~~") {
+            if let Some(end) = find_closing_marker(text, pos + 2, "~~") {
+                out.push_str(&text[pos + 2..end]);
+                pos = end + 2;
+                continue;
+            }
+        }
+        if text[pos..].starts_with('[') {
+            if let Some((link_text, _url, end_pos)) = parse_link(text, pos) {
+                out.push_str(&link_text);
+                pos = end_pos;
+                continue;
+            }
+            if let Some(close) = find_closing_char(text, pos + 1, b']') {
+                out.push_str(&text[pos + 1..close]);
+                pos = close + 1;
+                if text.as_bytes().get(pos) == Some(&b'[') {
+                    if let Some(close2) = find_closing_char(text, pos + 1, b']') {
+                        pos = close2 + 1;
+                    }
                 }
+                continue;
             }
+        }
 
-            // Parse and render table
-            let table_rendered = render_table(&table_lines, &theme_colors);
-            lines.extend(table_rendered);
+        let next = pos + 1;
+        out.push_str(&text[pos..next]);
+        pos = next;
+    }
 
-            i = j;
-            continue;
-        }
+    out
+}
 
-        // Handle headers
-        if line.starts_with("#### ") {
-            let content = line.trim_start_matches("#### ");
-            lines.push(Line::from(Span::styled(
-                format!("    {}", content),
-                Style::default()
-                    .fg(theme_colors.dim)
-                    .add_modifier(Modifier::ITALIC),
-            )));
+/// Mutable context threaded through `render_block`: the theme, the
+/// reference maps collected up front, and the accumulator of footnote
+/// ids seen so far (in first-reference order, for numbering).
+struct RenderCtx<'a> {
+    theme: &'a MarkdownTheme,
+    link_refs: &'a HashMap<String, String>,
+    footnote_defs: &'a HashMap<String, String>,
+    referenced_footnotes: &'a mut Vec<String>,
+}
+
+impl RenderCtx<'_> {
+    fn inline(&mut self, text: &str) -> Vec<Span<'static>> {
+        parse_inline_markdown(
+            text,
+            self.theme,
+            self.link_refs,
+            self.footnote_defs,
+            self.referenced_footnotes,
+        )
+    }
+}
+
+/// Parse a flat run of lines into the block tree. Recurses on the
+/// dedented inner lines of blockquotes and list items, so container
+/// nesting falls out of the recursion rather than needing explicit
+/// depth tracking here.
+fn parse_blocks(lines: &[String]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i].as_str();
+
+        if line.trim().is_empty() {
+            blocks.push(Block::Blank);
             i += 1;
             continue;
         }
-        if line.starts_with("### ") {
-            let content = line.trim_start_matches("### ");
-            lines.push(Line::from(Span::styled(
-                format!("   {}", content),
-                Style::default()
-                    .fg(theme_colors.text)
-                    .add_modifier(Modifier::BOLD),
-            )));
+
+        if line.trim().starts_with("```") {
+            let lang_tag = line.trim().trim_start_matches("```").trim();
+            let lang = if lang_tag.is_empty() { None } else { Some(lang_tag.to_string()) };
+            let mut code_lines = Vec::new();
             i += 1;
+            while i < lines.len() && !lines[i].trim().starts_with("```") {
+                code_lines.push(lines[i].clone());
+                i += 1;
+            }
+            if i < lines.len() {
+                i += 1; // consume closing fence
+            }
+            blocks.push(Block::CodeBlock { lang, lines: code_lines });
             continue;
         }
-        if line.starts_with("## ") {
-            let content = line.trim_start_matches("## ");
-            lines.push(Line::from(Span::styled(
-                format!("  {}", content),
-                Style::default()
-                    .fg(theme_colors.header)
-                    .add_modifier(Modifier::BOLD),
-            )));
+
+        if let Some(level) = heading_level(line) {
+            let text = line.trim_start().trim_start_matches('#').trim_start().to_string();
+            blocks.push(Block::Heading { level, text });
             i += 1;
             continue;
         }
-        if line.starts_with("# ") {
-            let content = line.trim_start_matches("# ");
-            lines.push(Line::from(Span::styled(
-                format!(" {}", content),
-                Style::default()
-                    .fg(theme_colors.header)
-                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
-            )));
+
+        if line.trim().chars().all(|c| c == '-' || c == '*' || c == '_') && line.trim().len() >= 3 {
+            blocks.push(Block::ThematicBreak);
             i += 1;
             continue;
         }
 
-        // Handle horizontal rule
-        if line.trim().chars().all(|c| c == '-' || c == '*' || c == '_')
-            && line.trim().len() >= 3
-        {
-            lines.push(Line::from(Span::styled(
-                "─".repeat(40),
-                Style::default().fg(theme_colors.dim),
-            )));
-            i += 1;
+        if line.trim().starts_with('|') && line.trim().ends_with('|') {
+            let mut table_lines = vec![line.to_string()];
+            let mut j = i + 1;
+            while j < lines.len() {
+                let next = lines[j].trim();
+                if next.starts_with('|') && next.ends_with('|') {
+                    table_lines.push(lines[j].clone());
+                    j += 1;
+                } else {
+                    break;
+                }
+            }
+            blocks.push(Block::Table(table_lines));
+            i = j;
             continue;
         }
 
-        // Handle blockquote (> text or >> nested)
         if line.starts_with('>') {
-            let mut depth = 0;
-            let mut remaining = line;
-            while remaining.starts_with('>') {
-                depth += 1;
-                remaining = remaining[1..].trim_start();
-            }
-            let indent = "│ ".repeat(depth);
-            let spans = parse_inline_markdown(remaining, &theme_colors);
-            let mut result = vec![Span::styled(
-                indent,
-                Style::default().fg(theme_colors.blockquote),
-            )];
-            result.extend(spans.into_iter().map(|mut s| {
-                s.style = s.style.add_modifier(Modifier::ITALIC);
-                s
-            }));
-            lines.push(Line::from(result));
-            i += 1;
+            let mut quote_lines = Vec::new();
+            let mut j = i;
+            while j < lines.len() && lines[j].starts_with('>') {
+                let stripped = lines[j].strip_prefix('>').unwrap_or(&lines[j]);
+                let stripped = stripped.strip_prefix(' ').unwrap_or(stripped);
+                quote_lines.push(stripped.to_string());
+                j += 1;
+            }
+            blocks.push(Block::BlockQuote(parse_blocks(&quote_lines)));
+            i = j;
             continue;
         }
 
-        // Handle checkbox list (- [ ] or - [x])
-        if let Some(rest) = line.strip_prefix("- [ ] ").or_else(|| line.strip_prefix("* [ ] ")) {
-            let spans = parse_inline_markdown(rest, &theme_colors);
-            let mut result = vec![
-                Span::styled("  ", Style::default()),
-                Span::styled("☐ ", Style::default().fg(theme_colors.dim)),
-            ];
-            result.extend(spans);
-            lines.push(Line::from(result));
-            i += 1;
-            continue;
-        }
-        if let Some(rest) = line.strip_prefix("- [x] ")
-            .or_else(|| line.strip_prefix("* [x] ")
-            .or_else(|| line.strip_prefix("- [X] ")
-            .or_else(|| line.strip_prefix("* [X] "))))
-        {
-            let spans = parse_inline_markdown(rest, &theme_colors);
-            let mut result = vec![
-                Span::styled("  ", Style::default()),
-                Span::styled("☑ ", Style::default().fg(theme_colors.success)),
-            ];
-            result.extend(spans.into_iter().map(|mut s| {
-                s.style = s.style.add_modifier(Modifier::CROSSED_OUT);
-                s
-            }));
-            lines.push(Line::from(result));
-            i += 1;
+        if list_item_marker_width(line).is_some() {
+            let ordered = is_ordered_list_marker(line);
+            let (items, next_i) = parse_list_region(lines, i);
+            blocks.push(Block::List { ordered, items });
+            i = next_i;
             continue;
         }
 
-        // Handle nested unordered list (with indentation)
-        if let Some((indent_level, content)) = parse_nested_list(line, &['-', '*', '+']) {
-            let indent = "  ".repeat(indent_level);
-            let bullet = if indent_level == 0 { "• " } else if indent_level == 1 { "◦ " } else { "▪ " };
-            let spans = parse_inline_markdown(content, &theme_colors);
-            let mut result = vec![
-                Span::styled(format!("{}{}", indent, bullet), Style::default().fg(theme_colors.text)),
-            ];
-            result.extend(spans);
-            lines.push(Line::from(result));
+        blocks.push(Block::Paragraph(line.to_string()));
+        i += 1;
+    }
+
+    blocks
+}
+
+/// Recognize `#`..`####` headings, returning the level (1-4).
+fn heading_level(line: &str) -> Option<usize> {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    if (1..=4).contains(&hashes) && line.as_bytes().get(hashes) == Some(&b' ') {
+        Some(hashes)
+    } else {
+        None
+    }
+}
+
+/// Width of an unordered (`- `, `* `, `+ `) or ordered (`1. `) list marker
+/// prefix, if `line` starts with one.
+fn list_item_marker_width(line: &str) -> Option<usize> {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return Some(2);
+    }
+    let digit_count = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digit_count > 0 && line[digit_count..].starts_with(". ") {
+        return Some(digit_count + 2);
+    }
+    None
+}
+
+fn is_ordered_list_marker(line: &str) -> bool {
+    let digit_count = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    digit_count > 0 && line[digit_count..].starts_with(". ")
+}
+
+/// Strip a GFM task-list checkbox (`[ ] ` / `[x] ` / `[X] `) from an
+/// item's first line of content, if present.
+fn strip_checkbox(text: &str) -> (Option<bool>, String) {
+    if let Some(rest) = text.strip_prefix("[ ] ") {
+        (Some(false), rest.to_string())
+    } else if let Some(rest) = text.strip_prefix("[x] ").or_else(|| text.strip_prefix("[X] ")) {
+        (Some(true), rest.to_string())
+    } else {
+        (None, text.to_string())
+    }
+}
+
+/// Consume a maximal run of list items (and their indented continuation
+/// lines) starting at `start`, returning the parsed items and the index
+/// just past the list region.
+fn parse_list_region(lines: &[String], start: usize) -> (Vec<ListItem>, usize) {
+    let mut items: Vec<(Option<bool>, Vec<String>)> = Vec::new();
+    let mut i = start;
+
+    while i < lines.len() {
+        let line = lines[i].as_str();
+
+        if let Some(width) = list_item_marker_width(line) {
+            let (checked, content) = strip_checkbox(&line[width..]);
+            items.push((checked, vec![content]));
             i += 1;
             continue;
         }
 
-        // Handle unordered list
-        if let Some(content) = line.strip_prefix("- ")
-            .or_else(|| line.strip_prefix("* ")
-            .or_else(|| line.strip_prefix("+ ")))
-        {
-            let spans = parse_inline_markdown(content, &theme_colors);
-            let mut result = vec![Span::styled("  • ", Style::default().fg(theme_colors.text))];
-            result.extend(spans);
-            lines.push(Line::from(result));
-            i += 1;
+        if line.trim().is_empty() {
+            // A run of blank lines only continues the list if a marker or
+            // an indented continuation line follows; otherwise it ends the
+            // region here without being consumed as part of the last item.
+            let mut j = i;
+            while j < lines.len() && lines[j].trim().is_empty() {
+                j += 1;
+            }
+            let continues = j < lines.len()
+                && (list_item_marker_width(&lines[j]).is_some() || lines[j].starts_with("  "));
+            if !continues || items.is_empty() {
+                break;
+            }
+            if let Some((_, raw_lines)) = items.last_mut() {
+                for _ in i..j {
+                    raw_lines.push(String::new());
+                }
+            }
+            i = j;
             continue;
         }
 
-        // Handle ordered list
-        if let Some(pos) = line.find(". ") {
-            let prefix = &line[..pos];
-            if prefix.chars().all(|c| c.is_ascii_digit()) {
-                let content = &line[pos + 2..];
-                let spans = parse_inline_markdown(content, &theme_colors);
-                let mut result = vec![Span::styled(
-                    format!("  {}. ", prefix),
-                    Style::default().fg(theme_colors.text),
-                )];
-                result.extend(spans);
-                lines.push(Line::from(result));
+        if let Some(rest) = line.strip_prefix("  ") {
+            if let Some((_, raw_lines)) = items.last_mut() {
+                raw_lines.push(rest.to_string());
                 i += 1;
                 continue;
             }
         }
 
-        // Handle empty line
-        if line.trim().is_empty() {
-            lines.push(Line::from(""));
-            i += 1;
-            continue;
+        break;
+    }
+
+    let items = items
+        .into_iter()
+        .map(|(checked, raw_lines)| ListItem {
+            checked,
+            blocks: parse_blocks(&raw_lines),
+        })
+        .collect();
+
+    (items, i)
+}
+
+/// Render a single block (and, for containers, everything nested inside
+/// it) at the given list-nesting depth. Each returned line is paired with
+/// whether it's exempt from word-wrapping in [`render_markdown_wrapped`] —
+/// true for code-block and table lines, whose layout is significant.
+fn render_block(block: &Block, ctx: &mut RenderCtx, list_depth: usize) -> Vec<(Line<'static>, bool)> {
+    match block {
+        Block::Blank => vec![(Line::from(""), false)],
+        Block::ThematicBreak => vec![(
+            Line::from(Span::styled("─".repeat(40), Style::default().fg(ctx.theme.dim))),
+            false,
+        )],
+        Block::Heading { level, text } => {
+            // Headings render as plain styled text (no inline-span parsing),
+            // matching their historical single-style look.
+            let (indent, style) = match level {
+                1 => (" ", Style::default().fg(ctx.theme.header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+                2 => ("  ", Style::default().fg(ctx.theme.header).add_modifier(Modifier::BOLD)),
+                3 => ("   ", Style::default().fg(ctx.theme.text).add_modifier(Modifier::BOLD)),
+                _ => ("    ", Style::default().fg(ctx.theme.dim).add_modifier(Modifier::ITALIC)),
+            };
+            vec![(Line::from(Span::styled(format!("{}{}", indent, text), style)), false)]
+        }
+        Block::CodeBlock { lang, lines } => {
+            let mut out = Vec::new();
+            if let Some(tag) = lang {
+                out.push(Line::from(Span::styled(
+                    format!("  [{}]", tag),
+                    Style::default().fg(ctx.theme.dim),
+                )));
+            }
+            out.extend(render_code_block_lines(lines, lang.as_deref(), ctx.theme));
+            out.into_iter().map(|line| (line, true)).collect()
+        }
+        Block::Table(table_lines) => {
+            let refs: Vec<&str> = table_lines.iter().map(|s| s.as_str()).collect();
+            render_table(&refs, ctx.theme).into_iter().map(|line| (line, true)).collect()
+        }
+        Block::Paragraph(text) => vec![(Line::from(ctx.inline(text)), false)],
+        Block::BlockQuote(inner) => {
+            let rendered: Vec<(Line<'static>, bool)> = inner
+                .iter()
+                .flat_map(|b| render_block(b, ctx, list_depth))
+                .collect();
+            rendered
+                .into_iter()
+                .map(|(line, exempt)| {
+                    let mut spans = vec![Span::styled(
+                        "│ ",
+                        Style::default().fg(ctx.theme.blockquote),
+                    )];
+                    spans.extend(line.spans.into_iter().map(|mut s| {
+                        s.style = s.style.add_modifier(Modifier::ITALIC);
+                        s
+                    }));
+                    (Line::from(spans), exempt)
+                })
+                .collect()
         }
+        Block::List { ordered, items } => items
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, item)| render_list_item(*ordered, idx, item, ctx, list_depth))
+            .collect(),
+    }
+}
 
-        // Regular text with inline formatting
-        let spans = parse_inline_markdown(line, &theme_colors);
-        lines.push(Line::from(spans));
-        i += 1;
+/// Render one list item: its marker (bullet, ordinal, or checkbox) on the
+/// first rendered line, with subsequent lines (from nested blocks inside
+/// the item) indented to align under it.
+fn render_list_item(
+    ordered: bool,
+    idx: usize,
+    item: &ListItem,
+    ctx: &mut RenderCtx,
+    list_depth: usize,
+) -> Vec<(Line<'static>, bool)> {
+    let indent = "  ".repeat(list_depth + 1);
+    let (marker, marker_style, crossed_out) = if let Some(checked) = item.checked {
+        if checked {
+            ("☑ ".to_string(), Style::default().fg(ctx.theme.success), true)
+        } else {
+            ("☐ ".to_string(), Style::default().fg(ctx.theme.dim), false)
+        }
+    } else if ordered {
+        (format!("{}. ", idx + 1), Style::default().fg(ctx.theme.text), false)
+    } else {
+        let bullet = match list_depth {
+            0 => "• ",
+            1 => "◦ ",
+            _ => "▪ ",
+        };
+        (bullet.to_string(), Style::default().fg(ctx.theme.text), false)
+    };
+
+    let marker_width = UnicodeWidthStr::width(indent.as_str()) + UnicodeWidthStr::width(marker.as_str());
+    let continuation_indent = " ".repeat(marker_width);
+
+    let rendered: Vec<(Line<'static>, bool)> = item
+        .blocks
+        .iter()
+        .flat_map(|b| render_block(b, ctx, list_depth + 1))
+        .collect();
+
+    rendered
+        .into_iter()
+        .enumerate()
+        .map(|(line_idx, (line, exempt))| {
+            let mut spans = if line_idx == 0 {
+                vec![Span::styled(format!("{}{}", indent, marker), marker_style)]
+            } else {
+                vec![Span::styled(continuation_indent.clone(), Style::default())]
+            };
+            if crossed_out {
+                spans.extend(line.spans.into_iter().map(|mut s| {
+                    s.style = s.style.add_modifier(Modifier::CROSSED_OUT);
+                    s
+                }));
+            } else {
+                spans.extend(line.spans);
+            }
+            (Line::from(spans), exempt)
+        })
+        .collect()
+}
+
+/// Normalize a link/footnote label for case-insensitive, whitespace-
+/// collapsed matching, as CommonMark requires for reference definitions.
+fn normalize_label(label: &str) -> String {
+    label.trim().to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse a link reference definition line (`[label]: url "title"`). The
+/// optional title is recognized but not stored.
+fn parse_link_reference_def(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_prefix('[')?;
+    if rest.starts_with('^') {
+        return None;
     }
+    let close = rest.find(']')?;
+    let label = &rest[..close];
+    let after_colon = rest[close + 1..].strip_prefix(':')?;
+    let url = after_colon.trim().split_whitespace().next()?;
+    Some((normalize_label(label), url.to_string()))
+}
+
+/// Parse a footnote definition line (`[^id]: text`).
+fn parse_footnote_def(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_prefix("[^")?;
+    let close = rest.find(']')?;
+    let id = &rest[..close];
+    let text = rest[close + 1..].strip_prefix(':')?.trim();
+    Some((normalize_label(id), text.to_string()))
+}
 
-    // Handle unclosed code block
-    if in_code_block {
-        for code_line in &code_block_lines {
-            lines.push(Line::from(vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(
-                    code_line.clone(),
-                    Style::default().fg(theme_colors.code),
-                ),
-            ]));
+/// First pass over the raw lines: pull out link reference and footnote
+/// definitions into lookup maps, returning the remaining content lines
+/// with those definition lines removed.
+fn collect_references<'a>(
+    raw_lines: &[&'a str],
+) -> (HashMap<String, String>, HashMap<String, String>, Vec<&'a str>) {
+    let mut link_refs = HashMap::new();
+    let mut footnote_defs = HashMap::new();
+    let mut content_lines = Vec::with_capacity(raw_lines.len());
+
+    for &line in raw_lines {
+        if let Some((id, text)) = parse_footnote_def(line) {
+            footnote_defs.insert(id, text);
+            continue;
+        }
+        if let Some((label, url)) = parse_link_reference_def(line) {
+            link_refs.insert(label, url);
+            continue;
         }
+        content_lines.push(line);
     }
 
-    lines
+    (link_refs, footnote_defs, content_lines)
+}
+
+/// A table column's alignment, taken from the colon markers on its
+/// separator-row cell (e.g. `:---`, `---:`, `:---:`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Alignment {
+    Left,
+    Right,
+    Center,
+    Default,
+}
+
+impl Alignment {
+    fn from_separator_cell(cell: &str) -> Self {
+        let left = cell.starts_with(':');
+        let right = cell.ends_with(':');
+        match (left, right) {
+            (true, true) => Alignment::Center,
+            (true, false) => Alignment::Left,
+            (false, true) => Alignment::Right,
+            (false, false) => Alignment::Default,
+        }
+    }
+
+    /// Split `padding` spaces (column width minus the cell's width) into
+    /// `(left, right)` padding amounts, per this alignment.
+    fn pad(&self, padding: usize) -> (usize, usize) {
+        match self {
+            Alignment::Left | Alignment::Default => (0, padding),
+            Alignment::Right => (padding, 0),
+            Alignment::Center => {
+                let left = padding / 2;
+                (left, padding - left)
+            }
+        }
+    }
 }
 
 /// Render a markdown table
@@ -297,6 +739,20 @@ fn render_table(table_lines: &[&str], theme: &MarkdownTheme) -> Vec<Line<'static
 
     // Calculate column widths using unicode width (for CJK characters)
     let num_cols = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+
+    let col_alignments: Vec<Alignment> = separator_idx
+        .map(|idx| {
+            (0..num_cols)
+                .map(|col_idx| {
+                    rows[idx]
+                        .get(col_idx)
+                        .map(|cell| Alignment::from_separator_cell(cell))
+                        .unwrap_or(Alignment::Default)
+                })
+                .collect()
+        })
+        .unwrap_or_else(|| vec![Alignment::Default; num_cols]);
+
     let mut col_widths: Vec<usize> = vec![0; num_cols];
     for row in &rows {
         for (col_idx, cell) in row.iter().enumerate() {
@@ -347,7 +803,17 @@ fn render_table(table_lines: &[&str], theme: &MarkdownTheme) -> Vec<Line<'static
             // Calculate padding using unicode width
             let cell_width = UnicodeWidthStr::width(cell_content);
             let padding = width.saturating_sub(cell_width);
-            let padded = format!(" {}{} ", cell_content, " ".repeat(padding));
+            let (left_pad, right_pad) = col_alignments
+                .get(col_idx)
+                .copied()
+                .unwrap_or(Alignment::Default)
+                .pad(padding);
+            let padded = format!(
+                " {}{}{} ",
+                " ".repeat(left_pad),
+                cell_content,
+                " ".repeat(right_pad)
+            );
 
             // Header row (before separator) gets bold style
             let is_header = separator_idx.map(|idx| row_idx < idx).unwrap_or(false);
@@ -378,96 +844,115 @@ fn render_table(table_lines: &[&str], theme: &MarkdownTheme) -> Vec<Line<'static
     result
 }
 
-/// Parse inline Markdown (bold, italic, code, links)
-fn parse_inline_markdown(text: &str, theme: &MarkdownTheme) -> Vec<Span<'static>> {
+/// Parse inline Markdown (bold, italic, code, links).
+///
+/// Scans `text` by byte offset rather than collecting it into a `Vec<char>`:
+/// `find_next_marker` uses `memchr` to jump straight to the next candidate
+/// marker byte (`*`, `_`, `` ` ``, `~`, `[`) instead of inspecting every
+/// char, and span content is produced by slicing `&text[a..b]` directly.
+/// This matters because the renderer re-tokenizes the same streamed AI
+/// response on every redraw. All of the markers above are single-byte ASCII,
+/// so any offset derived from one (or from `0`/`text.len()`) always lands on
+/// a `char` boundary, even when the surrounding content is multi-byte UTF-8.
+fn parse_inline_markdown(
+    text: &str,
+    theme: &MarkdownTheme,
+    link_refs: &HashMap<String, String>,
+    footnote_defs: &HashMap<String, String>,
+    referenced_footnotes: &mut Vec<String>,
+) -> Vec<Span<'static>> {
     let mut spans: Vec<Span<'static>> = Vec::new();
-    let mut current_pos = 0;
-    let chars: Vec<char> = text.chars().collect();
-    let len = chars.len();
+    let mut pos = 0;
+    let len = text.len();
+
+    while pos < len {
+        let marker_pos = match find_next_marker(text, pos) {
+            Some(p) => p,
+            None => {
+                spans.push(Span::styled(text[pos..].to_string(), Style::default().fg(theme.text)));
+                break;
+            }
+        };
+        if marker_pos > pos {
+            spans.push(Span::styled(
+                text[pos..marker_pos].to_string(),
+                Style::default().fg(theme.text),
+            ));
+        }
+        pos = marker_pos;
 
-    while current_pos < len {
         // Check for bold+italic (***text***)
-        if current_pos + 2 < len
-            && chars[current_pos] == '*'
-            && chars[current_pos + 1] == '*'
-            && chars[current_pos + 2] == '*'
-        {
-            if let Some(end) = find_closing_marker(&chars, current_pos + 3, "***") {
-                let content: String = chars[current_pos + 3..end].iter().collect();
+        if text[pos..].starts_with("***") {
+            if let Some(end) = find_closing_marker(text, pos + 3, "***") {
                 spans.push(Span::styled(
-                    content,
+                    text[pos + 3..end].to_string(),
                     Style::default()
                         .fg(theme.text)
                         .add_modifier(Modifier::BOLD | Modifier::ITALIC),
                 ));
-                current_pos = end + 3;
+                pos = end + 3;
                 continue;
             }
         }
 
         // Check for bold (**text**)
-        if current_pos + 1 < len && chars[current_pos] == '*' && chars[current_pos + 1] == '*' {
-            if let Some(end) = find_closing_marker(&chars, current_pos + 2, "**") {
-                let content: String = chars[current_pos + 2..end].iter().collect();
+        if text[pos..].starts_with("**") {
+            if let Some(end) = find_closing_marker(text, pos + 2, "**") {
                 spans.push(Span::styled(
-                    content,
+                    text[pos + 2..end].to_string(),
                     Style::default()
                         .fg(theme.text)
                         .add_modifier(Modifier::BOLD),
                 ));
-                current_pos = end + 2;
+                pos = end + 2;
                 continue;
             }
         }
 
         // Check for inline code (`code`)
-        if chars[current_pos] == '`' {
-            if let Some(end) = find_closing_char(&chars, current_pos + 1, '`') {
-                let content: String = chars[current_pos + 1..end].iter().collect();
+        if text[pos..].starts_with('`') {
+            if let Some(end) = find_closing_char(text, pos + 1, b'`') {
                 spans.push(Span::styled(
-                    content,
+                    text[pos + 1..end].to_string(),
                     Style::default().fg(theme.code),
                 ));
-                current_pos = end + 1;
+                pos = end + 1;
                 continue;
             }
         }
 
         // Check for italic (*text* or _text_)
-        if chars[current_pos] == '*' || chars[current_pos] == '_' {
-            let marker = chars[current_pos];
-            if let Some(end) = find_closing_char(&chars, current_pos + 1, marker) {
-                // Make sure it's not part of a word (for underscores)
-                let content: String = chars[current_pos + 1..end].iter().collect();
+        if text[pos..].starts_with('*') || text[pos..].starts_with('_') {
+            let marker = text.as_bytes()[pos];
+            if let Some(end) = find_closing_char(text, pos + 1, marker) {
                 spans.push(Span::styled(
-                    content,
+                    text[pos + 1..end].to_string(),
                     Style::default()
                         .fg(theme.text)
                         .add_modifier(Modifier::ITALIC),
                 ));
-                current_pos = end + 1;
+                pos = end + 1;
                 continue;
             }
         }
 
         // Check for strikethrough (~~text~~)
-        if current_pos + 1 < len && chars[current_pos] == '~' && chars[current_pos + 1] == '~' {
-            if let Some(end) = find_closing_marker(&chars, current_pos + 2, "~~") {
-                let content: String = chars[current_pos + 2..end].iter().collect();
+        if text[pos..].starts_with("~~") {
+            if let Some(end) = find_closing_marker(text, pos + 2, "~~") {
                 spans.push(Span::styled(
-                    content,
+                    text[pos + 2..end].to_string(),
                     Style::default()
                         .fg(theme.dim)
                         .add_modifier(Modifier::CROSSED_OUT),
                 ));
-                current_pos = end + 2;
+                pos = end + 2;
                 continue;
             }
         }
 
         // Check for link [text](url)
-        if chars[current_pos] == '[' {
-            if let Some((link_text, url, end_pos)) = parse_link(&chars, current_pos) {
+        if text[pos..].starts_with('[') {
+            if let Some((link_text, url, end_pos)) = parse_link(text, pos) {
                 spans.push(Span::styled(
                     link_text,
                     Style::default().fg(theme.link).add_modifier(Modifier::UNDERLINED),
@@ -476,111 +961,146 @@ fn parse_inline_markdown(text: &str, theme: &MarkdownTheme) -> Vec<Span<'static>
                     format!(" ({})", url),
                     Style::default().fg(theme.dim),
                 ));
-                current_pos = end_pos;
+                pos = end_pos;
+                continue;
+            }
+
+            // Reference-style link ([text][label], [label][], [label]) or
+            // footnote reference ([^id]), resolved against the maps
+            // collected by `collect_references`.
+            if let Some((rendered, end_pos)) = parse_reference(
+                text,
+                pos,
+                theme,
+                link_refs,
+                footnote_defs,
+                referenced_footnotes,
+            ) {
+                spans.extend(rendered);
+                pos = end_pos;
                 continue;
             }
         }
 
-        // Regular character
+        // Regular character (the marker byte didn't open any recognized
+        // construct, or its closing marker was never found)
+        let next = pos + 1;
         spans.push(Span::styled(
-            chars[current_pos].to_string(),
+            text[pos..next].to_string(),
             Style::default().fg(theme.text),
         ));
-        current_pos += 1;
+        pos = next;
     }
 
     // Merge consecutive spans with same style
     merge_spans(spans)
 }
 
-fn find_closing_char(chars: &[char], start: usize, marker: char) -> Option<usize> {
-    for i in start..chars.len() {
-        if chars[i] == marker {
-            return Some(i);
-        }
-    }
-    None
+/// Byte offset of the next character that can open an inline construct
+/// (`*`, `_`, `` ` ``, `~`, `[`) at or after `from`, found via `memchr`
+/// instead of a char-by-char scan.
+fn find_next_marker(text: &str, from: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    [b'*', b'_', b'`', b'~', b'[']
+        .into_iter()
+        .filter_map(|m| memchr::memchr(m, &bytes[from..]))
+        .map(|rel| rel + from)
+        .min()
 }
 
-fn find_closing_marker(chars: &[char], start: usize, marker: &str) -> Option<usize> {
-    let marker_chars: Vec<char> = marker.chars().collect();
-    let marker_len = marker_chars.len();
+fn find_closing_char(text: &str, start: usize, marker: u8) -> Option<usize> {
+    memchr::memchr(marker, &text.as_bytes()[start..]).map(|rel| rel + start)
+}
 
-    for i in start..=chars.len().saturating_sub(marker_len) {
-        let mut matches = true;
-        for (j, mc) in marker_chars.iter().enumerate() {
-            if chars.get(i + j) != Some(mc) {
-                matches = false;
-                break;
-            }
-        }
-        if matches {
-            return Some(i);
+fn find_closing_marker(text: &str, start: usize, marker: &str) -> Option<usize> {
+    let marker_first = marker.as_bytes()[0];
+    let bytes = text.as_bytes();
+    let mut i = start;
+    loop {
+        let rel = memchr::memchr(marker_first, &bytes[i..])?;
+        let candidate = i + rel;
+        if text[candidate..].starts_with(marker) {
+            return Some(candidate);
         }
+        i = candidate + 1;
     }
-    None
 }
 
-fn parse_link(chars: &[char], start: usize) -> Option<(String, String, usize)> {
-    // Find closing bracket
-    let mut bracket_end = None;
-    for i in start + 1..chars.len() {
-        if chars[i] == ']' {
-            bracket_end = Some(i);
-            break;
-        }
-    }
-    let bracket_end = bracket_end?;
+fn parse_link(text: &str, start: usize) -> Option<(String, String, usize)> {
+    let bracket_end = find_closing_char(text, start + 1, b']')?;
 
     // Check for opening parenthesis
-    if bracket_end + 1 >= chars.len() || chars[bracket_end + 1] != '(' {
+    if text.as_bytes().get(bracket_end + 1) != Some(&b'(') {
         return None;
     }
 
-    // Find closing parenthesis
-    let mut paren_end = None;
-    for i in bracket_end + 2..chars.len() {
-        if chars[i] == ')' {
-            paren_end = Some(i);
-            break;
-        }
-    }
-    let paren_end = paren_end?;
+    let paren_end = find_closing_char(text, bracket_end + 2, b')')?;
 
-    let link_text: String = chars[start + 1..bracket_end].iter().collect();
-    let url: String = chars[bracket_end + 2..paren_end].iter().collect();
+    let link_text = text[start + 1..bracket_end].to_string();
+    let url = text[bracket_end + 2..paren_end].to_string();
 
     Some((link_text, url, paren_end + 1))
 }
 
-/// Parse nested list item and return (indent_level, content)
-fn parse_nested_list<'a>(line: &'a str, markers: &[char]) -> Option<(usize, &'a str)> {
-    let mut indent = 0;
-    let mut chars = line.chars().peekable();
-
-    // Count leading spaces (2 spaces = 1 indent level)
-    while chars.peek() == Some(&' ') {
-        chars.next();
-        indent += 1;
+/// Parse a footnote reference (`[^id]`) or a reference-style link
+/// (`[text][label]`, collapsed `[text][]`, or shortcut `[label]`) starting
+/// at `text[start..] == "["...`, resolving it against the maps collected by
+/// `collect_references`. Returns the rendered spans and the position just
+/// past the consumed text, or `None` if it doesn't resolve to anything
+/// (the caller then falls through to treating `[` as a literal character).
+fn parse_reference(
+    text: &str,
+    start: usize,
+    theme: &MarkdownTheme,
+    link_refs: &HashMap<String, String>,
+    footnote_defs: &HashMap<String, String>,
+    referenced_footnotes: &mut Vec<String>,
+) -> Option<(Vec<Span<'static>>, usize)> {
+    if text.as_bytes().get(start + 1) == Some(&b'^') {
+        let close = find_closing_char(text, start + 2, b']')?;
+        let label = normalize_label(&text[start + 2..close]);
+        if !footnote_defs.contains_key(&label) {
+            return None;
+        }
+        if !referenced_footnotes.contains(&label) {
+            referenced_footnotes.push(label.clone());
+        }
+        let number = referenced_footnotes.iter().position(|f| *f == label)? + 1;
+        return Some((
+            vec![Span::styled(
+                format!("[{}]", number),
+                Style::default().fg(theme.link).add_modifier(Modifier::ITALIC),
+            )],
+            close + 1,
+        ));
     }
 
-    let indent_level = indent / 2;
+    let text_close = find_closing_char(text, start + 1, b']')?;
+    let link_text = text[start + 1..text_close].to_string();
 
-    // Only consider it a nested list if there's actual indentation
-    if indent_level == 0 {
-        return None;
-    }
+    let mut end = text_close + 1;
+    let mut label = normalize_label(&link_text);
 
-    // Check for list marker
-    let rest = &line[indent..];
-    for marker in markers {
-        let prefix = format!("{} ", marker);
-        if let Some(content) = rest.strip_prefix(&prefix) {
-            return Some((indent_level, content));
+    if text.as_bytes().get(end) == Some(&b'[') {
+        let label_close = find_closing_char(text, end + 1, b']')?;
+        let explicit_label = &text[end + 1..label_close];
+        if !explicit_label.trim().is_empty() {
+            label = normalize_label(explicit_label);
         }
+        end = label_close + 1;
     }
 
-    None
+    let url = link_refs.get(&label)?;
+    Some((
+        vec![
+            Span::styled(
+                link_text,
+                Style::default().fg(theme.link).add_modifier(Modifier::UNDERLINED),
+            ),
+            Span::styled(format!(" ({})", url), Style::default().fg(theme.dim)),
+        ],
+        end,
+    ))
 }
 
 fn merge_spans(spans: Vec<Span<'static>>) -> Vec<Span<'static>> {
@@ -611,6 +1131,147 @@ fn merge_spans(spans: Vec<Span<'static>>) -> Vec<Span<'static>> {
     result
 }
 
+/// Width, in the line's leading characters, of its indent/blockquote-bar/
+/// list-marker prefix: a run of spaces and `│` blockquote bars, optionally
+/// followed by a bullet (`•`/`◦`/`▪`/`☑`/`☐`) or an ordinal (`N. `) marker.
+/// Used by [`wrap_styled_line`] to build the hanging indent for
+/// continuation lines.
+fn detect_prefix_len(chars: &[char]) -> usize {
+    let mut i = 0;
+    while i < chars.len() && (chars[i] == ' ' || chars[i] == '│') {
+        i += 1;
+    }
+
+    const BULLETS: &[char] = &['•', '◦', '▪', '☑', '☐'];
+    if i < chars.len() && BULLETS.contains(&chars[i]) {
+        i += 1;
+        if i < chars.len() && chars[i] == ' ' {
+            i += 1;
+        }
+        return i;
+    }
+
+    let digit_start = i;
+    let mut j = i;
+    while j < chars.len() && chars[j].is_ascii_digit() {
+        j += 1;
+    }
+    if j > digit_start && j + 1 < chars.len() && chars[j] == '.' && chars[j + 1] == ' ' {
+        i = j + 2;
+    }
+    i
+}
+
+fn line_chars(line: &Line<'static>) -> Vec<(char, Style)> {
+    line.spans
+        .iter()
+        .flat_map(|span| span.content.chars().map(move |c| (c, span.style)))
+        .collect()
+}
+
+fn chars_to_spans(chars: &[(char, Style)]) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_style: Option<Style> = None;
+
+    for &(c, style) in chars {
+        match current_style {
+            Some(s) if s == style => current.push(c),
+            _ => {
+                if let Some(s) = current_style.take() {
+                    spans.push(Span::styled(std::mem::take(&mut current), s));
+                }
+                current.push(c);
+                current_style = Some(style);
+            }
+        }
+    }
+    if let Some(s) = current_style {
+        spans.push(Span::styled(current, s));
+    }
+    spans
+}
+
+fn finish_wrapped_line(prefix: &[(char, Style)], body: Vec<(char, Style)>) -> Line<'static> {
+    let mut chars = prefix.to_vec();
+    chars.extend(body);
+    Line::from(chars_to_spans(&chars))
+}
+
+/// Reflow a single rendered line to `width` columns, breaking at word
+/// boundaries and measuring with [`UnicodeWidthStr`]. Continuation lines
+/// repeat the original line's leading indent and blockquote bar(s) as a
+/// hanging indent, with any bullet/ordinal marker blanked out so it isn't
+/// repeated on every wrapped line. Each char keeps the `Style` of the span
+/// it came from.
+fn wrap_styled_line(line: &Line<'static>, width: usize) -> Vec<Line<'static>> {
+    let chars = line_chars(line);
+    let plain: String = chars.iter().map(|&(c, _)| c).collect();
+    if UnicodeWidthStr::width(plain.as_str()) <= width {
+        return vec![line.clone()];
+    }
+
+    let just_chars: Vec<char> = chars.iter().map(|&(c, _)| c).collect();
+    let prefix_len = detect_prefix_len(&just_chars);
+    let literal_prefix = &chars[..prefix_len];
+    let hanging_prefix: Vec<(char, Style)> = literal_prefix
+        .iter()
+        .map(|&(c, style)| if c == '│' || c == ' ' { (c, style) } else { (' ', style) })
+        .collect();
+    let prefix_width =
+        UnicodeWidthStr::width(just_chars[..prefix_len].iter().collect::<String>().as_str());
+    let body_width = width.saturating_sub(prefix_width).max(1);
+
+    // Split the body into whitespace-delimited words, each a run of
+    // (char, Style) pairs, so a word never splits mid-style or mid-word.
+    let mut words: Vec<Vec<(char, Style)>> = Vec::new();
+    let mut current_word: Vec<(char, Style)> = Vec::new();
+    for &(c, style) in &chars[prefix_len..] {
+        if c == ' ' {
+            if !current_word.is_empty() {
+                words.push(std::mem::take(&mut current_word));
+            }
+        } else {
+            current_word.push((c, style));
+        }
+    }
+    if !current_word.is_empty() {
+        words.push(current_word);
+    }
+
+    let mut out_lines: Vec<Line<'static>> = Vec::new();
+    let mut current: Vec<(char, Style)> = Vec::new();
+    let mut current_width = 0usize;
+
+    for word in words {
+        let word_text: String = word.iter().map(|&(c, _)| c).collect();
+        let word_width = UnicodeWidthStr::width(word_text.as_str());
+        let needed = if current.is_empty() { word_width } else { current_width + 1 + word_width };
+        if !current.is_empty() && needed > body_width {
+            out_lines.push(finish_wrapped_line(
+                if out_lines.is_empty() { literal_prefix } else { &hanging_prefix },
+                std::mem::take(&mut current),
+            ));
+            current_width = 0;
+        }
+        if !current.is_empty() {
+            let joiner_style = word.first().map(|&(_, s)| s).unwrap_or_default();
+            current.push((' ', joiner_style));
+            current_width += 1;
+        }
+        current_width += word_width;
+        current.extend(word);
+    }
+    if !current.is_empty() || out_lines.is_empty() {
+        out_lines.push(finish_wrapped_line(
+            if out_lines.is_empty() { literal_prefix } else { &hanging_prefix },
+            current,
+        ));
+    }
+
+    out_lines
+}
+
 /// Theme colors for Markdown rendering
 #[derive(Clone, Copy)]
 pub struct MarkdownTheme {
@@ -621,6 +1282,13 @@ pub struct MarkdownTheme {
     pub link: Color,
     pub blockquote: Color,
     pub success: Color,
+    /// Colors used when a fenced code block's language tag is recognized
+    /// by `crate::ui::syntax`; fall back to `code` for token kinds not
+    /// listed here and for unrecognized/absent language tags.
+    pub code_keyword: Color,
+    pub code_string: Color,
+    pub code_comment: Color,
+    pub code_number: Color,
 }
 
 impl Default for MarkdownTheme {
@@ -633,6 +1301,10 @@ impl Default for MarkdownTheme {
             link: Color::Cyan,
             blockquote: Color::Magenta,
             success: Color::Green,
+            code_keyword: Color::Magenta,
+            code_string: Color::Green,
+            code_comment: Color::Gray,
+            code_number: Color::Cyan,
         }
     }
 }
@@ -647,10 +1319,63 @@ impl MarkdownTheme {
             link: theme.info,
             blockquote: theme.text_header,
             success: theme.success,
+            code_keyword: theme.error,
+            code_string: theme.success,
+            code_comment: theme.text_dim,
+            code_number: theme.info,
         }
     }
 }
 
+/// Style a single highlighted code token for the given `MarkdownTheme`,
+/// mapping the token kinds the theme has dedicated colors for and falling
+/// back to the plain `code` color for the rest.
+fn style_for_code_token(theme: &MarkdownTheme, token_type: crate::ui::syntax::TokenType) -> Style {
+    use crate::ui::syntax::TokenType;
+
+    match token_type {
+        TokenType::Keyword => Style::default()
+            .fg(theme.code_keyword)
+            .add_modifier(Modifier::BOLD),
+        TokenType::String => Style::default().fg(theme.code_string),
+        TokenType::Comment => Style::default()
+            .fg(theme.code_comment)
+            .add_modifier(Modifier::ITALIC),
+        TokenType::Number => Style::default().fg(theme.code_number),
+        _ => Style::default().fg(theme.code),
+    }
+}
+
+/// Render a fenced code block's collected lines, token-highlighting them
+/// when `lang` maps to a known `crate::ui::syntax::Language` and falling
+/// back to the single-color rendering otherwise.
+fn render_code_block_lines(code_block_lines: &[String], lang: Option<&str>, theme: &MarkdownTheme) -> Vec<Line<'static>> {
+    use crate::ui::syntax::{Language, SyntaxHighlighter};
+
+    if let Some(mut highlighter) = lang.and_then(Language::from_tag).map(SyntaxHighlighter::new) {
+        code_block_lines
+            .iter()
+            .map(|code_line| {
+                let mut spans = vec![Span::styled("  ", Style::default())];
+                spans.extend(highlighter.tokenize_line(code_line).into_iter().map(|token| {
+                    Span::styled(token.text, style_for_code_token(theme, token.token_type))
+                }));
+                Line::from(spans)
+            })
+            .collect()
+    } else {
+        code_block_lines
+            .iter()
+            .map(|code_line| {
+                Line::from(vec![
+                    Span::styled("  ", Style::default()),
+                    Span::styled(code_line.clone(), Style::default().fg(theme.code)),
+                ])
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -670,6 +1395,48 @@ mod tests {
         assert!(lines.len() >= 2);
     }
 
+    #[test]
+    fn test_render_code_block_highlights_known_language() {
+        let theme = MarkdownTheme::default();
+        let text = "```rust\nlet x = 1; // comment\n```";
+        let lines = render_markdown(text, theme);
+        let code_line = &lines[1];
+        assert!(code_line.spans.len() > 2);
+    }
+
+    #[test]
+    fn test_render_code_block_falls_back_for_unknown_language() {
+        let theme = MarkdownTheme::default();
+        let text = "```totallymadeup\nsome text\n```";
+        let lines = render_markdown(text, theme);
+        let code_line = &lines[1];
+        assert_eq!(code_line.spans.len(), 2);
+    }
+
+    #[test]
+    fn test_render_reference_style_link() {
+        let theme = MarkdownTheme::default();
+        let text = "See [my site][Home].\n\n[home]: https://example.com";
+        let lines = render_markdown(text, theme);
+        let rendered: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(rendered.contains("https://example.com"));
+    }
+
+    #[test]
+    fn test_render_footnote_reference_and_section() {
+        let theme = MarkdownTheme::default();
+        let text = "Note here.[^1]\n\n[^1]: An explanation.";
+        let lines = render_markdown(text, theme);
+        let rendered: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(rendered.contains("[1]"));
+        let footnotes_rendered: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(footnotes_rendered.contains("An explanation."));
+    }
+
     #[test]
     fn test_render_list() {
         let theme = MarkdownTheme::default();
@@ -686,6 +1453,16 @@ mod tests {
         assert!(lines.len() >= 4);
     }
 
+    #[test]
+    fn test_render_table_honors_column_alignment() {
+        let theme = MarkdownTheme::default();
+        let text = "| AAA | BBB | C |\n|:---|---:|:--:|\n| 1 | 22 | 3 |";
+        let lines = render_markdown(text, theme);
+        let data_row: String = lines[3].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(data_row.contains("1  "));
+        assert!(data_row.contains("  22"));
+    }
+
     #[test]
     fn test_render_blockquote() {
         let theme = MarkdownTheme::default();
@@ -699,4 +1476,34 @@ mod tests {
         let lines = render_markdown("- [ ] Todo\n- [x] Done", theme);
         assert_eq!(lines.len(), 2);
     }
+
+    #[test]
+    fn test_render_code_block_nested_in_list_item() {
+        let theme = MarkdownTheme::default();
+        let text = "- Item\n  ```rust\n  let x = 1;\n  ```";
+        let lines = render_markdown(text, theme);
+        let rendered: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|s| s.content.as_ref())
+            .collect::<Vec<_>>()
+            .join("");
+        assert!(rendered.contains("let x = 1;"));
+    }
+
+    #[test]
+    fn test_render_list_nested_in_blockquote() {
+        let theme = MarkdownTheme::default();
+        let text = "> - First\n> - Second";
+        let lines = render_markdown(text, theme);
+        let rendered: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|s| s.content.as_ref())
+            .collect::<Vec<_>>()
+            .join("");
+        assert!(rendered.contains('│'));
+        assert!(rendered.contains("First"));
+        assert!(rendered.contains("Second"));
+    }
 }
@@ -0,0 +1,327 @@
+use std::ops::Range;
+
+#[cfg(not(test))]
+const CHUNK_CHARS: usize = 1024;
+// Tiny in tests so a handful of chars already spans several chunks,
+// exercising the split/merge/cross-chunk paths without huge fixtures.
+#[cfg(test)]
+const CHUNK_CHARS: usize = 4;
+
+/// One leaf of the rope: a run of text plus its char/newline counts, cached
+/// so the rope doesn't have to re-scan a chunk's text to answer "how many
+/// chars/lines does this hold".
+#[derive(Debug, Clone)]
+struct Chunk {
+    text: String,
+    chars: usize,
+    newlines: usize,
+}
+
+impl Chunk {
+    fn new(text: String) -> Self {
+        let chars = text.chars().count();
+        let newlines = text.matches('\n').count();
+        Self { text, chars, newlines }
+    }
+
+    fn recount(&mut self) {
+        self.chars = self.text.chars().count();
+        self.newlines = self.text.matches('\n').count();
+    }
+}
+
+/// A UTF-8 text buffer indexed by char offset rather than by `(line, col)`,
+/// so edits and undo/redo don't need separate "insert a char" / "split a
+/// line" / "merge two lines" cases: every edit is just `insert`/`remove`
+/// over a single char-offset space, and a newline is just another char in
+/// it.
+///
+/// Backed by a flat list of fixed-size chunks rather than one `ropey`-style
+/// balanced tree (ropey itself isn't available — this tree has no
+/// `Cargo.toml` to add it to), so locating a chunk is still an O(chunks)
+/// scan rather than an O(log n) tree descent. But every chunk caches its
+/// own char/newline count, so `char_to_line`/`line_to_char` skip whole
+/// chunks using those counts instead of walking every char in the
+/// document, and `insert`/`remove` only touch the one or two chunks an
+/// edit actually lands in (splitting a chunk in two past `CHUNK_CHARS * 2`
+/// chars) instead of shifting the whole buffer. For a document of `n`
+/// chars chunked at `CHUNK_CHARS`, that's roughly `O(n / CHUNK_CHARS)` per
+/// edit rather than `O(n)` — real editing costs stop scaling with total
+/// file size and start scaling with chunk count. Revisit with a true
+/// balanced tree if profiling ever shows that scan matters.
+#[derive(Debug, Clone)]
+pub struct Rope {
+    chunks: Vec<Chunk>,
+}
+
+impl Rope {
+    pub fn from_str(s: &str) -> Self {
+        if s.is_empty() {
+            return Self { chunks: vec![Chunk::new(String::new())] };
+        }
+        let chars: Vec<char> = s.chars().collect();
+        let chunks = chars
+            .chunks(CHUNK_CHARS)
+            .map(|c| Chunk::new(c.iter().collect()))
+            .collect();
+        Self { chunks }
+    }
+
+    /// Materialize the whole document as one owned `String`.
+    pub fn as_str(&self) -> String {
+        self.chunks.iter().map(|c| c.text.as_str()).collect()
+    }
+
+    /// Total number of chars in the document.
+    pub fn len_chars(&self) -> usize {
+        self.chunks.iter().map(|c| c.chars).sum()
+    }
+
+    /// Number of lines, counting a trailing unterminated line (a document
+    /// with no `\n` at all is one line; `"a\nb"` is two lines).
+    pub fn len_lines(&self) -> usize {
+        self.chunks.iter().map(|c| c.newlines).sum::<usize>() + 1
+    }
+
+    /// The (0-based) line containing `char_idx`.
+    pub fn char_to_line(&self, char_idx: usize) -> usize {
+        let mut seen_chars = 0;
+        let mut seen_lines = 0;
+        for chunk in &self.chunks {
+            if char_idx <= seen_chars + chunk.chars {
+                let local_idx = char_idx - seen_chars;
+                return seen_lines + chunk.text.chars().take(local_idx).filter(|&c| c == '\n').count();
+            }
+            seen_chars += chunk.chars;
+            seen_lines += chunk.newlines;
+        }
+        seen_lines
+    }
+
+    /// The char offset of the first char of `line_idx`.
+    pub fn line_to_char(&self, line_idx: usize) -> usize {
+        let mut seen_chars = 0;
+        let mut seen_lines = 0;
+        for chunk in &self.chunks {
+            if line_idx <= seen_lines + chunk.newlines {
+                let mut local_line = seen_lines;
+                for (i, c) in chunk.text.chars().enumerate() {
+                    if local_line == line_idx {
+                        return seen_chars + i;
+                    }
+                    if c == '\n' {
+                        local_line += 1;
+                    }
+                }
+            }
+            seen_chars += chunk.chars;
+            seen_lines += chunk.newlines;
+        }
+        self.len_chars()
+    }
+
+    /// The content of `line_idx` (no trailing `\n`), or an empty string if
+    /// out of range.
+    pub fn line(&self, line_idx: usize) -> String {
+        if line_idx >= self.len_lines() {
+            return String::new();
+        }
+        let start = self.line_to_char(line_idx);
+        let (chunk_idx, local_char) = self.locate(start);
+        self.chars_from(chunk_idx, local_char).take_while(|&c| c != '\n').collect()
+    }
+
+    /// Char length of `line_idx`.
+    pub fn line_len_chars(&self, line_idx: usize) -> usize {
+        self.line(line_idx).chars().count()
+    }
+
+    /// Every line in the document, split on `\n` (no trailing `\n` on any
+    /// entry). Used for whole-document operations (save, reload, regex
+    /// search/replace) that already materialize the full text, so this
+    /// doesn't try to avoid the `O(n)` split those call sites pay anyway.
+    pub fn lines(&self) -> Vec<String> {
+        self.as_str().split('\n').map(String::from).collect()
+    }
+
+    /// The char offset of `(line, col)`.
+    pub fn line_col_to_char(&self, line: usize, col: usize) -> usize {
+        self.line_to_char(line) + col
+    }
+
+    /// Find the chunk holding `char_idx`, returning `(chunk_index, char
+    /// offset within that chunk)`.
+    fn locate(&self, char_idx: usize) -> (usize, usize) {
+        let mut seen = 0;
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if char_idx <= seen + chunk.chars || i == self.chunks.len() - 1 {
+                return (i, char_idx - seen);
+            }
+            seen += chunk.chars;
+        }
+        (0, 0)
+    }
+
+    /// Same as `locate`, but in bytes within the chunk's `String` rather
+    /// than chars, for splicing into it directly.
+    fn locate_byte(&self, char_idx: usize) -> (usize, usize) {
+        let (chunk_idx, local_char) = self.locate(char_idx);
+        let byte = self.chunks[chunk_idx]
+            .text
+            .char_indices()
+            .nth(local_char)
+            .map(|(b, _)| b)
+            .unwrap_or(self.chunks[chunk_idx].text.len());
+        (chunk_idx, byte)
+    }
+
+    /// Chars from `(chunk_idx, local_char)` to the end of the document,
+    /// without materializing anything before or after that span.
+    fn chars_from(&self, chunk_idx: usize, local_char: usize) -> impl Iterator<Item = char> + '_ {
+        self.chunks[chunk_idx..]
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, chunk)| chunk.text.chars().skip(if i == 0 { local_char } else { 0 }))
+    }
+
+    /// Text between two char offsets, without removing it.
+    pub fn slice(&self, range: Range<usize>) -> String {
+        if range.start >= range.end {
+            return String::new();
+        }
+        let (chunk_idx, local_char) = self.locate(range.start);
+        self.chars_from(chunk_idx, local_char).take(range.end - range.start).collect()
+    }
+
+    /// Insert `text` at `char_idx`.
+    pub fn insert(&mut self, char_idx: usize, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let (chunk_idx, byte_idx) = self.locate_byte(char_idx);
+        let chunk = &mut self.chunks[chunk_idx];
+        chunk.text.insert_str(byte_idx, text);
+        chunk.recount();
+
+        if chunk.chars > CHUNK_CHARS * 2 {
+            self.split_chunk(chunk_idx);
+        }
+    }
+
+    /// Split an overgrown chunk in half at a char boundary.
+    fn split_chunk(&mut self, chunk_idx: usize) {
+        let chunk = &self.chunks[chunk_idx];
+        let mid_char = chunk.chars / 2;
+        let mid_byte = chunk.text.char_indices().nth(mid_char).map(|(b, _)| b).unwrap_or(chunk.text.len());
+        let tail = chunk.text[mid_byte..].to_string();
+
+        let chunk = &mut self.chunks[chunk_idx];
+        chunk.text.truncate(mid_byte);
+        chunk.recount();
+        self.chunks.insert(chunk_idx + 1, Chunk::new(tail));
+    }
+
+    /// Remove the chars in `range`, returning the removed text.
+    pub fn remove(&mut self, range: Range<usize>) -> String {
+        if range.start >= range.end {
+            return String::new();
+        }
+        let removed = self.slice(range.clone());
+
+        let (start_chunk, start_byte) = self.locate_byte(range.start);
+        let (end_chunk, end_byte) = self.locate_byte(range.end);
+
+        if start_chunk == end_chunk {
+            let chunk = &mut self.chunks[start_chunk];
+            chunk.text.replace_range(start_byte..end_byte, "");
+            chunk.recount();
+        } else {
+            let tail = self.chunks[end_chunk].text[end_byte..].to_string();
+            let chunk = &mut self.chunks[start_chunk];
+            chunk.text.truncate(start_byte);
+            chunk.text.push_str(&tail);
+            chunk.recount();
+            self.chunks.drain(start_chunk + 1..=end_chunk);
+        }
+
+        if self.chunks.is_empty() {
+            self.chunks.push(Chunk::new(String::new()));
+        }
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_to_char_and_back() {
+        let rope = Rope::from_str("abc\nde\nfghi");
+        assert_eq!(rope.len_lines(), 3);
+        assert_eq!(rope.line_to_char(0), 0);
+        assert_eq!(rope.line_to_char(1), 4);
+        assert_eq!(rope.line_to_char(2), 7);
+        assert_eq!(rope.char_to_line(0), 0);
+        assert_eq!(rope.char_to_line(4), 1);
+        assert_eq!(rope.char_to_line(7), 2);
+        assert_eq!(rope.line(1), "de");
+    }
+
+    #[test]
+    fn test_insert_and_remove_roundtrip() {
+        let mut rope = Rope::from_str("hello world");
+        rope.insert(5, ",");
+        assert_eq!(rope.as_str(), "hello, world");
+        let removed = rope.remove(5..6);
+        assert_eq!(removed, ",");
+        assert_eq!(rope.as_str(), "hello world");
+    }
+
+    #[test]
+    fn test_insert_newline_updates_line_count() {
+        let mut rope = Rope::from_str("abc");
+        rope.insert(1, "\n");
+        assert_eq!(rope.as_str(), "a\nbc");
+        assert_eq!(rope.len_lines(), 2);
+        assert_eq!(rope.line(0), "a");
+        assert_eq!(rope.line(1), "bc");
+    }
+
+    // CHUNK_CHARS is 4 under #[cfg(test)], so this string spans several
+    // chunks and every op below has to cross chunk boundaries correctly.
+    #[test]
+    fn test_multi_chunk_roundtrip() {
+        let text = "the quick brown fox\njumps over\nthe lazy dog";
+        let rope = Rope::from_str(text);
+        assert!(rope.chunks.len() > 1, "fixture should span multiple chunks");
+        assert_eq!(rope.as_str(), text);
+        assert_eq!(rope.len_chars(), text.chars().count());
+        assert_eq!(rope.len_lines(), 3);
+
+        for (idx, expected) in text.split('\n').enumerate() {
+            assert_eq!(rope.line(idx), expected);
+        }
+        assert_eq!(rope.slice(4..19), "quick brown fox");
+    }
+
+    #[test]
+    fn test_insert_across_chunk_boundary_splits_and_grows() {
+        let mut rope = Rope::from_str("0123456789");
+        let before = rope.chunks.len();
+        rope.insert(5, "abcdefgh");
+        assert_eq!(rope.as_str(), "01234abcdefgh56789");
+        assert_eq!(rope.len_chars(), 18);
+        assert!(rope.chunks.len() >= before, "a big insert should never shrink the chunk count");
+    }
+
+    #[test]
+    fn test_remove_spanning_multiple_chunks() {
+        let mut rope = Rope::from_str("0123456789abcdef");
+        let removed = rope.remove(3..13);
+        assert_eq!(removed, "3456789abc");
+        assert_eq!(rope.as_str(), "012def");
+        assert_eq!(rope.len_chars(), 6);
+    }
+}